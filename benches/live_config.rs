@@ -0,0 +1,58 @@
+//! Measures `LiveConfig::load_guard` throughput across threads while the value is reloaded.
+//!
+//! Run with `cargo bench --bench live_config`.
+
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+use cdumay_config::LiveConfig;
+
+const READS_PER_THREAD: u64 = 5_000_000;
+
+fn main() {
+    let live = Arc::new(LiveConfig::new(|| Ok(vec![0u8; 64])).unwrap());
+    let threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+    let stop = Arc::new(AtomicBool::new(false));
+
+    let writer = {
+        let live = live.clone();
+        let stop = stop.clone();
+        std::thread::spawn(move || {
+            while !stop.load(Ordering::Relaxed) {
+                live.reload().unwrap();
+                std::thread::sleep(Duration::from_millis(1));
+            }
+        })
+    };
+
+    let start = Instant::now();
+    let readers: Vec<_> = (0..threads)
+        .map(|_| {
+            let live = live.clone();
+            std::thread::spawn(move || {
+                let mut total = 0usize;
+                for _ in 0..READS_PER_THREAD {
+                    total += live.load_guard().len();
+                }
+                total
+            })
+        })
+        .collect();
+    for reader in readers {
+        std::hint::black_box(reader.join().unwrap());
+    }
+    let elapsed = start.elapsed();
+    stop.store(true, Ordering::Relaxed);
+    writer.join().unwrap();
+
+    let reads = READS_PER_THREAD * threads as u64;
+    println!(
+        "{} threads, {} reads in {:?}: {:.1}M reads/sec ({} reloads)",
+        threads,
+        reads,
+        elapsed,
+        reads as f64 / elapsed.as_secs_f64() / 1_000_000.0,
+        live.generation()
+    );
+}
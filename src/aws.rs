@@ -0,0 +1,172 @@
+//! Credentials and request signing shared by the AWS backends.
+//!
+//! Requests to AWS services are signed with AWS Signature Version 4, computed here rather
+//! than with the AWS SDK to keep the dependency tree small.
+
+/// Credentials of an AWS identity.
+#[derive(Clone, PartialEq)]
+pub struct AwsCredentials {
+    /// Access key identifier.
+    access_key_id: String,
+    /// Secret access key.
+    secret_access_key: String,
+    /// Session token of temporary credentials.
+    pub(crate) session_token: Option<String>,
+}
+
+impl AwsCredentials {
+    /// Creates long-term credentials.
+    pub fn new(access_key_id: &str, secret_access_key: &str) -> Self {
+        Self {
+            access_key_id: access_key_id.to_string(),
+            secret_access_key: secret_access_key.to_string(),
+            session_token: None,
+        }
+    }
+
+    /// Sets the session token of temporary credentials.
+    pub fn with_session_token(mut self, session_token: &str) -> Self {
+        self.session_token = Some(session_token.to_string());
+        self
+    }
+
+    /// Reads the credentials from the `AWS_ACCESS_KEY_ID`, `AWS_SECRET_ACCESS_KEY` and
+    /// optional `AWS_SESSION_TOKEN` environment variables.
+    ///
+    /// # Returns
+    /// The credentials, or `None` if the key identifier or the secret key is not set.
+    pub fn from_env() -> Option<Self> {
+        let credentials = Self::new(&std::env::var("AWS_ACCESS_KEY_ID").ok()?, &std::env::var("AWS_SECRET_ACCESS_KEY").ok()?);
+        Some(match std::env::var("AWS_SESSION_TOKEN") {
+            Ok(token) => credentials.with_session_token(&token),
+            Err(_) => credentials,
+        })
+    }
+}
+
+impl std::fmt::Debug for AwsCredentials {
+    /// Formats the credentials without their secret parts.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AwsCredentials")
+            .field("access_key_id", &self.access_key_id)
+            .field("secret_access_key", &"***")
+            .field("session_token", &self.session_token.as_ref().map(|_| "***"))
+            .finish()
+    }
+}
+
+/// Returns the region of the environment, read from `AWS_REGION` or `AWS_DEFAULT_REGION`.
+pub(crate) fn region_from_env() -> Option<String> {
+    std::env::var("AWS_REGION").or_else(|_| std::env::var("AWS_DEFAULT_REGION")).ok()
+}
+
+/// Percent-encodes a value as required by AWS Signature Version 4, keeping the unreserved
+/// characters (and `/` in paths).
+#[cfg(feature = "s3")]
+pub(crate) fn uri_encode(value: &str, path: bool) -> String {
+    value
+        .bytes()
+        .map(|byte| match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => (byte as char).to_string(),
+            b'/' if path => "/".to_string(),
+            _ => format!("%{:02X}", byte),
+        })
+        .collect()
+}
+
+/// Formats a time as an AWS timestamp (e.g. `20150830T123600Z`), in UTC.
+pub(crate) fn amz_date(time: std::time::SystemTime) -> String {
+    let seconds = time.duration_since(std::time::UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    // Civil date from days since the epoch, see http://howardhinnant.github.io/date_algorithms.html.
+    let days = seconds / 86400 + 719468;
+    let (era, day_of_era) = (days / 146097, days % 146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = era * 400 + year_of_era + u64::from(month <= 2);
+    let time_of_day = seconds % 86400;
+    format!(
+        "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+        year,
+        month,
+        day,
+        time_of_day / 3600,
+        time_of_day / 60 % 60,
+        time_of_day % 60
+    )
+}
+
+/// Builds the canonical request of AWS Signature Version 4, returning it along with the
+/// list of signed headers.
+///
+/// `headers` are the signed headers, lowercased and sorted by name.
+pub(crate) fn canonical_request(method: &str, path: &str, query: &str, headers: &[(&str, String)], body: &str) -> (String, String) {
+    use sha2::Digest;
+
+    let signed_headers = headers.iter().map(|(name, _)| *name).collect::<Vec<_>>().join(";");
+    let canonical_headers: String = headers.iter().map(|(name, value)| format!("{}:{}\n", name, value.trim())).collect();
+    let request = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        method,
+        path,
+        query,
+        canonical_headers,
+        signed_headers,
+        hex::encode(sha2::Sha256::digest(body.as_bytes()))
+    );
+    (request, signed_headers)
+}
+
+/// Computes the `Authorization` header of a canonical request signed with AWS Signature
+/// Version 4.
+pub(crate) fn authorization(
+    credentials: &AwsCredentials,
+    region: &str,
+    service: &str,
+    amz_date: &str,
+    (canonical_request, signed_headers): (String, String),
+) -> String {
+    use sha2::Digest;
+
+    let scope = format!("{}/{}/{}/aws4_request", &amz_date[..8], region, service);
+    let string_to_sign = format!(
+        "AWS4-HMAC-SHA256\n{}\n{}\n{}",
+        amz_date,
+        scope,
+        hex::encode(sha2::Sha256::digest(canonical_request.as_bytes()))
+    );
+    let key = [&amz_date[..8], region, service, "aws4_request"]
+        .iter()
+        .fold(format!("AWS4{}", credentials.secret_access_key).into_bytes(), |key, data| {
+            hmac_sha256(&key, data.as_bytes())
+        });
+    format!(
+        "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}",
+        credentials.access_key_id,
+        scope,
+        signed_headers,
+        hex::encode(hmac_sha256(&key, string_to_sign.as_bytes()))
+    )
+}
+
+/// Computes the HMAC-SHA256 of data (RFC 2104).
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    use sha2::Digest;
+
+    let mut block = [0u8; 64];
+    match key.len() > 64 {
+        true => block[..32].copy_from_slice(&sha2::Sha256::digest(key)),
+        false => block[..key.len()].copy_from_slice(key),
+    }
+    let inner = sha2::Sha256::new()
+        .chain_update(block.map(|byte| byte ^ 0x36))
+        .chain_update(data)
+        .finalize();
+    sha2::Sha256::new()
+        .chain_update(block.map(|byte| byte ^ 0x5c))
+        .chain_update(inner)
+        .finalize()
+        .to_vec()
+}
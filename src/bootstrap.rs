@@ -0,0 +1,140 @@
+//! Startup loading of a remote configuration, falling back to a local copy.
+//!
+//! Services must be able to start while their configuration service is down.
+//! [`bootstrap_config`] fetches the configuration with a deadline, keeps a local copy of
+//! every configuration successfully fetched and falls back to that copy when the remote
+//! source fails or is too slow.
+
+/// Where a bootstrapped configuration comes from.
+#[derive(Debug, Clone)]
+pub enum BootstrapSource {
+    /// The remote source answered in time.
+    Remote,
+    /// The local copy was used because the remote source failed or timed out.
+    Cache {
+        /// The remote failure, or the timeout.
+        remote_error: cdumay_core::Error,
+    },
+}
+
+/// A configuration loaded by [`bootstrap_config`], along with its provenance.
+#[derive(Debug, Clone)]
+pub struct Bootstrapped<C> {
+    /// The configuration.
+    pub config: C,
+    /// Where the configuration comes from.
+    pub source: BootstrapSource,
+}
+
+impl<C> Bootstrapped<C> {
+    /// Returns `true` if the configuration was read from the local copy.
+    pub fn is_fallback(&self) -> bool {
+        matches!(self.source, BootstrapSource::Cache { .. })
+    }
+}
+
+/// Fetches a configuration from a remote source with a deadline, falling back to a local copy.
+///
+/// The fetch runs on a dedicated thread. When it succeeds within `deadline`, the
+/// configuration is saved to `cache_path` for later fallbacks (a failure to save it is only
+/// logged) and returned. Otherwise the configuration is read from `cache_path`. A fetch
+/// still running at the deadline is not cancelled: its thread is detached and its result
+/// discarded.
+///
+/// # Type Parameters
+/// - `C`: The configuration type.
+/// - `F`: The remote fetch.
+///
+/// # Parameters
+/// - `fetch`: Loads the configuration from the remote source.
+/// - `deadline`: Maximum time to wait for the remote source.
+/// - `cache_path`: Path of the local copy. Tilde `~` expansion is supported.
+/// - `format`: Format of the local copy. Defaults to `JSON` if not provided.
+/// - `context`: A context used for error details.
+///
+/// # Returns
+/// The configuration and its source, or an error if neither the remote source nor the
+/// local copy could provide it. The error then holds the remote failure in its `remote`
+/// detail.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Serialize, serde::Deserialize)]
+/// struct Settings {
+///     endpoint: String,
+/// }
+///
+/// fn fetch_from_config_service() -> cdumay_core::Result<Settings> {
+///     Ok(Settings { endpoint: "https://api.example.com".to_string() })
+/// }
+///
+/// fn start() -> cdumay_core::Result<Settings> {
+///     let context = std::collections::BTreeMap::new();
+///     let loaded = cdumay_config::bootstrap_config(
+///         fetch_from_config_service,
+///         std::time::Duration::from_secs(5),
+///         "~/.cache/app/settings.json",
+///         None,
+///         &context,
+///     )?;
+///     if loaded.is_fallback() {
+///         log::warn!("Started with the cached configuration");
+///     }
+///     Ok(loaded.config)
+/// }
+/// ```
+pub fn bootstrap_config<C, F>(
+    fetch: F,
+    deadline: std::time::Duration,
+    cache_path: &str,
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<Bootstrapped<C>>
+where
+    C: serde::Serialize + serde::de::DeserializeOwned + Send + 'static,
+    F: FnOnce() -> cdumay_core::Result<C> + Send + 'static,
+{
+    let (sender, receiver) = std::sync::mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = sender.send(fetch());
+    });
+    let remote_error = match receiver.recv_timeout(deadline) {
+        Ok(Ok(config)) => {
+            if let Err(err) = crate::write_config(cache_path, format, &config, context) {
+                log::warn!(
+                    "Failed to save the local copy of the configuration to '{}': {}",
+                    cache_path,
+                    err.message()
+                );
+            }
+            return Ok(Bootstrapped {
+                config,
+                source: BootstrapSource::Remote,
+            });
+        }
+        Ok(Err(err)) => err,
+        Err(_) => crate::ConfigurationFileError::new()
+            .with_message(format!("Remote configuration not received within {:?}", deadline))
+            .with_details(crate::context::bounded(context.clone()))
+            .into(),
+    };
+    log::warn!(
+        "Remote configuration unavailable ({}), using the local copy '{}'",
+        remote_error.message(),
+        cache_path
+    );
+    match crate::read_config(cache_path, format, context) {
+        Ok(config) => Ok(Bootstrapped {
+            config,
+            source: BootstrapSource::Cache { remote_error },
+        }),
+        Err(err) => Err(crate::ConfigurationFileError::new()
+            .with_message(format!("Remote configuration unavailable and local copy unreadable: {}", err.message()))
+            .with_details(crate::context::bounded({
+                let mut ctx = err.details();
+                ctx.insert("remote".to_string(), serde_value::Value::String(remote_error.message().to_string()));
+                ctx
+            }))
+            .into()),
+    }
+}
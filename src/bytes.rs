@@ -0,0 +1,69 @@
+//! Serde helpers encoding binary fields as text, consistently across formats.
+//!
+//! Binary values such as certificates or keys have no native representation in JSON,
+//! YAML or TOML. These modules encode them as base64 or hexadecimal strings and are
+//! meant to be used with `#[serde(with = "...")]` on `Vec<u8>` and `[u8; N]` fields:
+//!
+//! ```rust
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Tls {
+//!     #[serde(with = "cdumay_config::bytes::base64")]
+//!     certificate: Vec<u8>,
+//!     #[serde(with = "cdumay_config::bytes::hex")]
+//!     fingerprint: [u8; 4],
+//! }
+//! ```
+//!
+//! Decoding failures and length mismatches are reported as deserialization errors, so
+//! they carry the location information of the underlying format.
+
+/// Decodes a string into a byte container, checking its length for fixed-size arrays.
+fn decode<'de, D, T>(deserializer: D, encoding: &str, decoder: impl Fn(&str) -> Result<Vec<u8>, String>) -> Result<T, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: TryFrom<Vec<u8>>,
+{
+    let encoded = <String as serde::Deserialize>::deserialize(deserializer)?;
+    let decoded = decoder(encoded.trim()).map_err(|err| serde::de::Error::custom(format!("invalid {} data: {}", encoding, err)))?;
+    let length = decoded.len();
+    T::try_from(decoded).map_err(|_| {
+        serde::de::Error::custom(format!(
+            "invalid {} data: unexpected length of {} bytes ({} expected)",
+            encoding,
+            length,
+            std::any::type_name::<T>()
+        ))
+    })
+}
+
+/// Encodes bytes as standard, padded base64 strings.
+pub mod base64 {
+    use base64::Engine;
+
+    /// Serializes bytes as a base64 string.
+    pub fn serialize<S: serde::Serializer, T: AsRef<[u8]>>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&base64::engine::general_purpose::STANDARD.encode(value.as_ref()))
+    }
+
+    /// Deserializes bytes from a base64 string.
+    pub fn deserialize<'de, D: serde::Deserializer<'de>, T: TryFrom<Vec<u8>>>(deserializer: D) -> Result<T, D::Error> {
+        super::decode(deserializer, "base64", |encoded| {
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded)
+                .map_err(|err| err.to_string())
+        })
+    }
+}
+
+/// Encodes bytes as lowercase hexadecimal strings.
+pub mod hex {
+    /// Serializes bytes as a hexadecimal string.
+    pub fn serialize<S: serde::Serializer, T: AsRef<[u8]>>(value: &T, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(value.as_ref()))
+    }
+
+    /// Deserializes bytes from a hexadecimal string (either case).
+    pub fn deserialize<'de, D: serde::Deserializer<'de>, T: TryFrom<Vec<u8>>>(deserializer: D) -> Result<T, D::Error> {
+        super::decode(deserializer, "hex", |encoded| hex::decode(encoded).map_err(|err| err.to_string()))
+    }
+}
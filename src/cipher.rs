@@ -0,0 +1,64 @@
+//! Encryption of configuration files at rest.
+//!
+//! A [`Cipher`] set in [`WriteOptions::cipher`](crate::WriteOptions::cipher) encrypts the
+//! serialized content before it is written, and the same cipher set in
+//! [`ReadOptions::cipher`](crate::ReadOptions::cipher) decrypts the content before it is
+//! decoded, so that configurations holding secrets can be committed or shared safely. The
+//! format of a file is the one of its plaintext: an encrypted `app.json.age` or
+//! `app.json.gpg` is read as JSON, and a `FormatRegistry` infers it from the extension
+//! preceding the `.age`, `.gpg`, `.pgp`, `.asc` or `.enc` one.
+
+/// Encrypts and decrypts the content of configuration files.
+pub trait Cipher: std::fmt::Debug + Send + Sync {
+    /// Encrypts serialized content before it is written.
+    ///
+    /// # Parameters
+    /// - `plaintext`: The serialized configuration.
+    ///
+    /// # Returns
+    /// The content to write, or an error if it cannot be encrypted.
+    fn encrypt(&self, plaintext: &[u8]) -> std::io::Result<Vec<u8>>;
+
+    /// Decrypts read content before it is decoded.
+    ///
+    /// # Parameters
+    /// - `ciphertext`: The content read.
+    ///
+    /// # Returns
+    /// The serialized configuration, or an error if it cannot be decrypted.
+    fn decrypt(&self, ciphertext: &[u8]) -> std::io::Result<Vec<u8>>;
+}
+
+/// Runs a cipher operation, turning its failure into a configuration error holding the
+/// `path` of the file and the `origin` of the failure.
+pub(crate) fn apply(
+    operation: &str,
+    result: std::io::Result<Vec<u8>>,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<Vec<u8>> {
+    result.map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to {} '{}': {}", operation, path.display(), err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, path);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+            .into()
+    })
+}
+
+/// Extensions of encrypted files, which are not the one of their format.
+const ENCRYPTED_EXTENSIONS: [&str; 5] = ["age", "asc", "enc", "gpg", "pgp"];
+
+/// Returns the lowercased extension of the plaintext of a file, skipping the extension of
+/// encrypted files (e.g. `json` for `app.json.gpg`).
+pub(crate) fn plaintext_extension(path: &std::path::Path) -> String {
+    let extension = |path: &std::path::Path| path.extension().map(|ext| ext.to_string_lossy().to_ascii_lowercase()).unwrap_or_default();
+    let outer = extension(path);
+    match ENCRYPTED_EXTENSIONS.contains(&outer.as_str()) {
+        true => extension(std::path::Path::new(path.file_stem().unwrap_or_default())),
+        false => outer,
+    }
+}
@@ -0,0 +1,154 @@
+//! Passphrase encryption of configuration files with AES-256-GCM.
+//!
+//! With the `aes-gcm` feature, an [`AesGcmCipher`] encrypts configurations with a key derived
+//! from a passphrase by Argon2id, for deployments without age or OpenPGP keys. Each file gets
+//! a random salt and nonce, stored with the Argon2 parameters in a single line of text:
+//!
+//! ```text
+//! $aes-256-gcm$argon2id$m=19456,t=2,p=1$<salt>$<nonce>$<ciphertext>
+//! ```
+//!
+//! where the salt, nonce and ciphertext are base64 encoded, so that the parameters may be
+//! tuned without breaking existing files. Decryption refuses parameters costing more than
+//! 4 times the ones of the cipher, so that a crafted file cannot exhaust memory or CPU.
+//!
+//! ```rust
+//! fn load(passphrase: &str) -> cdumay_core::Result<serde_json::Value> {
+//!     let options = cdumay_config::ReadOptions {
+//!         cipher: Some(std::sync::Arc::new(cdumay_config::AesGcmCipher::new(passphrase))),
+//!         ..Default::default()
+//!     };
+//!     cdumay_config::read_config_with("secrets.json.enc", None, &std::collections::BTreeMap::new(), &options)
+//! }
+//! ```
+
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, KeyInit};
+use base64::Engine;
+
+/// Prefix of the content encrypted by an [`AesGcmCipher`].
+const PREFIX: &str = "$aes-256-gcm$argon2id$";
+/// Length of the random salt of the key derivation, in bytes.
+const SALT_LENGTH: usize = 16;
+/// Length of the random nonce of AES-GCM, in bytes.
+const NONCE_LENGTH: usize = 12;
+/// Factor by which each Argon2 parameter of decrypted content may exceed the one of the cipher.
+const MAX_COST_FACTOR: u32 = 4;
+
+/// A [`Cipher`](crate::Cipher) encrypting with AES-256-GCM and a key derived from a passphrase.
+///
+/// The passphrase and the derived keys are zeroized when dropped, and the passphrase is
+/// masked when the cipher is formatted.
+#[derive(Clone, Debug)]
+pub struct AesGcmCipher {
+    /// The passphrase the key is derived from.
+    passphrase: crate::SecretString,
+    /// Memory cost of the key derivation of encryption, in KiB.
+    memory: u32,
+    /// Number of iterations of the key derivation of encryption.
+    iterations: u32,
+    /// Degree of parallelism of the key derivation of encryption.
+    parallelism: u32,
+}
+
+impl AesGcmCipher {
+    /// Creates a cipher deriving its key from a passphrase with the default Argon2id
+    /// parameters (19 MiB of memory, 2 iterations, no parallelism).
+    pub fn new(passphrase: &str) -> Self {
+        Self {
+            passphrase: crate::SecretString::from(passphrase),
+            memory: argon2::Params::DEFAULT_M_COST,
+            iterations: argon2::Params::DEFAULT_T_COST,
+            parallelism: argon2::Params::DEFAULT_P_COST,
+        }
+    }
+
+    /// Sets the Argon2id parameters of encryption. Decryption uses the parameters stored in
+    /// the content, as long as none of them exceeds 4 times the one set here.
+    ///
+    /// # Parameters
+    /// - `memory`: The memory cost, in KiB.
+    /// - `iterations`: The number of iterations.
+    /// - `parallelism`: The degree of parallelism.
+    pub fn with_params(mut self, memory: u32, iterations: u32, parallelism: u32) -> Self {
+        self.memory = memory;
+        self.iterations = iterations;
+        self.parallelism = parallelism;
+        self
+    }
+
+    /// Derives the AES-256 key of a salt with the given Argon2id parameters.
+    fn key(&self, salt: &[u8], memory: u32, iterations: u32, parallelism: u32) -> std::io::Result<aes_gcm::Aes256Gcm> {
+        let invalid = |err: argon2::Error| std::io::Error::new(std::io::ErrorKind::InvalidInput, format!("invalid argon2 parameters: {}", err));
+        let params = argon2::Params::new(memory, iterations, parallelism, Some(32)).map_err(invalid)?;
+        let mut key = zeroize::Zeroizing::new([0u8; 32]);
+        argon2::Argon2::new(argon2::Algorithm::Argon2id, argon2::Version::V0x13, params)
+            .hash_password_into(self.passphrase.expose().as_bytes(), salt, key.as_mut_slice())
+            .map_err(invalid)?;
+        aes_gcm::Aes256Gcm::new_from_slice(key.as_slice()).map_err(|err| std::io::Error::other(err.to_string()))
+    }
+}
+
+impl crate::Cipher for AesGcmCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        aes_gcm::aead::OsRng.fill_bytes(&mut salt);
+        aes_gcm::aead::OsRng.fill_bytes(&mut nonce);
+        let ciphertext = self
+            .key(&salt, self.memory, self.iterations, self.parallelism)?
+            .encrypt(aes_gcm::Nonce::from_slice(&nonce), plaintext)
+            .map_err(|_| std::io::Error::other("AES-GCM encryption failed"))?;
+        let engine = base64::engine::general_purpose::STANDARD;
+        Ok(format!(
+            "{}m={},t={},p={}${}${}${}\n",
+            PREFIX,
+            self.memory,
+            self.iterations,
+            self.parallelism,
+            engine.encode(salt),
+            engine.encode(nonce),
+            engine.encode(ciphertext)
+        )
+        .into_bytes())
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> std::io::Result<Vec<u8>> {
+        let invalid = |message: &str| std::io::Error::new(std::io::ErrorKind::InvalidData, message.to_string());
+        let content = std::str::from_utf8(ciphertext)
+            .ok()
+            .and_then(|content| content.trim().strip_prefix(PREFIX))
+            .ok_or_else(|| invalid("content is not encrypted with AES-256-GCM"))?;
+        let [params, salt, nonce, ciphertext] = content.split('$').collect::<Vec<_>>()[..] else {
+            return Err(invalid("malformed AES-256-GCM content"));
+        };
+        let mut costs = [None; 3];
+        for param in params.split(',') {
+            let (index, value) = match param.split_once('=') {
+                Some(("m", value)) => (0, value),
+                Some(("t", value)) => (1, value),
+                Some(("p", value)) => (2, value),
+                _ => return Err(invalid("malformed argon2 parameters")),
+            };
+            costs[index] = Some(value.parse::<u32>().map_err(|_| invalid("malformed argon2 parameters"))?);
+        }
+        let [Some(memory), Some(iterations), Some(parallelism)] = costs else {
+            return Err(invalid("missing argon2 parameters"));
+        };
+        if memory > self.memory.saturating_mul(MAX_COST_FACTOR)
+            || iterations > self.iterations.saturating_mul(MAX_COST_FACTOR)
+            || parallelism > self.parallelism.saturating_mul(MAX_COST_FACTOR)
+        {
+            return Err(invalid("argon2 parameters exceed the allowed cost"));
+        }
+        let engine = base64::engine::general_purpose::STANDARD;
+        let decode = |value: &str| engine.decode(value).map_err(|_| invalid("malformed AES-256-GCM content"));
+        let (salt, nonce, ciphertext) = (decode(salt)?, decode(nonce)?, decode(ciphertext)?);
+        if nonce.len() != NONCE_LENGTH {
+            return Err(invalid("malformed AES-256-GCM content"));
+        }
+        self.key(&salt, memory, iterations, parallelism)?
+            .decrypt(aes_gcm::Nonce::from_slice(&nonce), ciphertext.as_slice())
+            .map_err(|_| invalid("wrong passphrase or corrupted content"))
+    }
+}
@@ -0,0 +1,144 @@
+//! [age](https://age-encryption.org) encryption of configuration files.
+//!
+//! With the `age` feature, an [`AgeCipher`] encrypts configurations to one or more X25519
+//! recipients (`age1...` public keys) and decrypts them with identities (`AGE-SECRET-KEY-1...`
+//! private keys) read from identity files, such as those generated by `age-keygen`. Files
+//! remain compatible with the `age` command line tool.
+//!
+//! ```rust
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Secrets {
+//!     db_password: String,
+//! }
+//!
+//! fn roundtrip(secrets: Secrets) -> cdumay_core::Result<Secrets> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let cipher: std::sync::Arc<dyn cdumay_config::Cipher> = std::sync::Arc::new(
+//!         cdumay_config::AgeCipher::new()
+//!             .with_recipient("age1ql3z7hjy54pw3hyww5ayyfg7zqgvc7w3j2elw8zmrj2kg5sfn9aqmcac8p")
+//!             .with_identity_file("~/.config/age/keys.txt"),
+//!     );
+//!     let write = cdumay_config::WriteOptions { cipher: Some(cipher.clone()), ..Default::default() };
+//!     cdumay_config::write_config_with("secrets.json.age", Some(cdumay_config::ContentFormat::JSON), &secrets, &context, &write)?;
+//!     let read = cdumay_config::ReadOptions { cipher: Some(cipher), ..Default::default() };
+//!     cdumay_config::read_config_with("secrets.json.age", Some(cdumay_config::ContentFormat::JSON), &context, &read)
+//! }
+//! ```
+
+/// A [`Cipher`](crate::Cipher) encrypting with age.
+#[derive(Clone, Default, PartialEq)]
+pub struct AgeCipher {
+    /// Public keys of the recipients the content is encrypted to.
+    recipients: Vec<String>,
+    /// Paths of the identity files decrypting the content.
+    identity_files: Vec<String>,
+    /// Private keys decrypting the content.
+    identities: Vec<String>,
+    /// Whether the encrypted content is written in binary instead of ASCII armor.
+    binary: bool,
+}
+
+impl std::fmt::Debug for AgeCipher {
+    /// Formats the cipher without its private keys.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AgeCipher")
+            .field("recipients", &self.recipients)
+            .field("identity_files", &self.identity_files)
+            .field("identities", &self.identities.iter().map(|_| "***").collect::<Vec<_>>())
+            .field("binary", &self.binary)
+            .finish()
+    }
+}
+
+impl AgeCipher {
+    /// Creates a cipher without recipients nor identities.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a recipient the content is encrypted to.
+    ///
+    /// # Parameters
+    /// - `recipient`: The public key of the recipient (`age1...`).
+    pub fn with_recipient(mut self, recipient: &str) -> Self {
+        self.recipients.push(recipient.trim().to_string());
+        self
+    }
+
+    /// Adds an identity file decrypting the content, read on each decryption.
+    ///
+    /// # Parameters
+    /// - `path`: Path of the identity file. Tilde `~` expansion is supported.
+    pub fn with_identity_file(mut self, path: &str) -> Self {
+        self.identity_files.push(shellexpand::tilde(path).to_string());
+        self
+    }
+
+    /// Adds an identity decrypting the content (e.g. read from a secret store).
+    ///
+    /// # Parameters
+    /// - `identity`: The private key (`AGE-SECRET-KEY-1...`), or the content of an identity file.
+    pub fn with_identity(mut self, identity: &str) -> Self {
+        self.identities.push(identity.to_string());
+        self
+    }
+
+    /// Writes the encrypted content in binary instead of the default ASCII armor, which is
+    /// friendlier to version control.
+    pub fn with_binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+}
+
+impl crate::Cipher for AgeCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> std::io::Result<Vec<u8>> {
+        let recipients = self
+            .recipients
+            .iter()
+            .map(|recipient| {
+                recipient.parse::<::age::x25519::Recipient>().map_err(|err| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        format!("invalid age recipient '{}': {}", recipient, err),
+                    )
+                })
+            })
+            .collect::<std::io::Result<Vec<_>>>()?;
+        let encryptor = ::age::Encryptor::with_recipients(recipients.iter().map(|recipient| recipient as &dyn ::age::Recipient))
+            .map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidInput, err.to_string()))?;
+        let format = match self.binary {
+            true => ::age::armor::Format::Binary,
+            false => ::age::armor::Format::AsciiArmor,
+        };
+        let mut output = Vec::new();
+        let armored = ::age::armor::ArmoredWriter::wrap_output(&mut output, format)?;
+        let mut writer = encryptor.wrap_output(armored)?;
+        std::io::Write::write_all(&mut writer, plaintext)?;
+        writer.finish()?.finish()?;
+        Ok(output)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> std::io::Result<Vec<u8>> {
+        let invalid = |err: String| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+        let mut identities = Vec::new();
+        for path in &self.identity_files {
+            let file = ::age::IdentityFile::from_file(path.clone())?;
+            identities.extend(
+                file.into_identities()
+                    .map_err(|err| invalid(format!("invalid age identity file '{}': {}", path, err)))?,
+            );
+        }
+        for identity in &self.identities {
+            let file = ::age::IdentityFile::from_buffer(identity.as_bytes())?;
+            identities.extend(file.into_identities().map_err(|err| invalid(format!("invalid age identity: {}", err)))?);
+        }
+        let decryptor = ::age::Decryptor::new(::age::armor::ArmoredReader::new(ciphertext)).map_err(|err| invalid(err.to_string()))?;
+        let mut reader = decryptor
+            .decrypt(identities.iter().map(|identity| identity.as_ref() as &dyn ::age::Identity))
+            .map_err(|err| invalid(err.to_string()))?;
+        let mut plaintext = Vec::new();
+        std::io::Read::read_to_end(&mut reader, &mut plaintext)?;
+        Ok(plaintext)
+    }
+}
@@ -0,0 +1,198 @@
+//! [OpenPGP](https://www.openpgp.org) encryption of configuration files with GnuPG.
+//!
+//! With the `gpg` feature, a [`GpgCipher`] encrypts and decrypts configurations, such as
+//! `app.json.gpg`, by invoking the `gpg` command line tool, so that keys are looked up in the
+//! user keyring and passphrases of secret keys may be asked by the gpg agent. Content is
+//! encrypted to the public keys of recipients, or symmetrically with a passphrase.
+//!
+//! ```rust
+//! fn load() -> cdumay_core::Result<serde_json::Value> {
+//!     let cipher = cdumay_config::GpgCipher::new().with_secret_key("ops@example.com");
+//!     let options = cdumay_config::ReadOptions {
+//!         cipher: Some(std::sync::Arc::new(cipher)),
+//!         ..Default::default()
+//!     };
+//!     cdumay_config::read_config_with("app.json.gpg", None, &std::collections::BTreeMap::new(), &options)
+//! }
+//! ```
+
+/// A [`Cipher`](crate::Cipher) encrypting with OpenPGP through the `gpg` program.
+#[derive(Clone, PartialEq)]
+pub struct GpgCipher {
+    /// The gpg program to run.
+    program: String,
+    /// The home directory of gpg, instead of `$GNUPGHOME` or `~/.gnupg`.
+    home: Option<String>,
+    /// User IDs or fingerprints of the recipients the content is encrypted to.
+    recipients: Vec<String>,
+    /// User IDs or fingerprints of the secret keys tried for decryption.
+    secret_keys: Vec<String>,
+    /// Passphrase of symmetric encryption, or of the secret keys.
+    passphrase: Option<String>,
+    /// Whether the encrypted content is written in binary instead of ASCII armor.
+    binary: bool,
+}
+
+impl Default for GpgCipher {
+    fn default() -> Self {
+        Self {
+            program: "gpg".to_string(),
+            home: None,
+            recipients: Vec::new(),
+            secret_keys: Vec::new(),
+            passphrase: None,
+            binary: false,
+        }
+    }
+}
+
+impl std::fmt::Debug for GpgCipher {
+    /// Formats the cipher without its passphrase.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GpgCipher")
+            .field("program", &self.program)
+            .field("home", &self.home)
+            .field("recipients", &self.recipients)
+            .field("secret_keys", &self.secret_keys)
+            .field("passphrase", &self.passphrase.as_ref().map(|_| "***"))
+            .field("binary", &self.binary)
+            .finish()
+    }
+}
+
+impl GpgCipher {
+    /// Creates a cipher running `gpg` with its default keyring.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the gpg program to run (e.g. `gpg2` or an absolute path).
+    pub fn with_program(mut self, program: &str) -> Self {
+        self.program = program.to_string();
+        self
+    }
+
+    /// Sets the home directory holding the keyring, instead of `$GNUPGHOME` or `~/.gnupg`.
+    ///
+    /// # Parameters
+    /// - `home`: Path of the directory. Tilde `~` expansion is supported.
+    pub fn with_home(mut self, home: &str) -> Self {
+        self.home = Some(shellexpand::tilde(home).to_string());
+        self
+    }
+
+    /// Adds a recipient the content is encrypted to.
+    ///
+    /// # Parameters
+    /// - `recipient`: The user ID, key ID or fingerprint of a public key of the keyring.
+    pub fn with_recipient(mut self, recipient: &str) -> Self {
+        self.recipients.push(recipient.trim().to_string());
+        self
+    }
+
+    /// Adds a secret key tried for decryption, e.g. when the content is encrypted to hidden
+    /// recipients (`--throw-keyids`) and gpg cannot tell which key to use.
+    ///
+    /// # Parameters
+    /// - `secret_key`: The user ID, key ID or fingerprint of a secret key of the keyring.
+    pub fn with_secret_key(mut self, secret_key: &str) -> Self {
+        self.secret_keys.push(secret_key.trim().to_string());
+        self
+    }
+
+    /// Sets the passphrase given to gpg instead of asking the gpg agent.
+    ///
+    /// Without recipients, the content is encrypted symmetrically with the passphrase;
+    /// with recipients, it can be decrypted by either. On decryption, the passphrase
+    /// unlocks symmetrically encrypted content or the selected secret keys.
+    pub fn with_passphrase(mut self, passphrase: &str) -> Self {
+        self.passphrase = Some(passphrase.to_string());
+        self
+    }
+
+    /// Writes the encrypted content in binary instead of the default ASCII armor, which is
+    /// friendlier to version control.
+    pub fn with_binary(mut self, binary: bool) -> Self {
+        self.binary = binary;
+        self
+    }
+
+    /// Runs gpg with the given arguments, feeding it the input on its standard input.
+    fn run(&self, arguments: &[&str], input: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut command = std::process::Command::new(&self.program);
+        command.args(["--batch", "--yes", "--quiet", "--no-tty"]);
+        if let Some(home) = &self.home {
+            command.arg("--homedir").arg(home);
+        }
+        let mut stdin = Vec::with_capacity(input.len());
+        if let Some(passphrase) = &self.passphrase {
+            command.args(["--pinentry-mode", "loopback", "--passphrase-fd", "0"]);
+            stdin.extend_from_slice(passphrase.as_bytes());
+            stdin.push(b'\n');
+        }
+        stdin.extend_from_slice(input);
+        let mut child = command
+            .args(arguments)
+            .stdin(std::process::Stdio::piped())
+            .stdout(std::process::Stdio::piped())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .map_err(|err| std::io::Error::new(err.kind(), format!("failed to run '{}': {}", self.program, err)))?;
+        let mut pipe = child
+            .stdin
+            .take()
+            .ok_or_else(|| std::io::Error::other("gpg standard input is unavailable"))?;
+        // Written from another thread so that gpg never blocks on a full output pipe.
+        let writer = std::thread::spawn(move || std::io::Write::write_all(&mut pipe, &stdin));
+        let output = child.wait_with_output()?;
+        // gpg may exit before reading all its input, e.g. on a bad passphrase.
+        let _ = writer.join();
+        match output.status.success() {
+            true => Ok(output.stdout),
+            false => {
+                let stderr = String::from_utf8_lossy(&output.stderr);
+                let message = stderr.lines().map(|line| line.trim_start_matches("gpg: ")).collect::<Vec<_>>().join("; ");
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    match message.is_empty() {
+                        true => format!("gpg failed with {}", output.status),
+                        false => message,
+                    },
+                ))
+            }
+        }
+    }
+}
+
+impl crate::Cipher for GpgCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> std::io::Result<Vec<u8>> {
+        if self.recipients.is_empty() && self.passphrase.is_none() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "no gpg recipient nor passphrase to encrypt to",
+            ));
+        }
+        let mut arguments = vec!["--trust-model", "always"];
+        if !self.binary {
+            arguments.push("--armor");
+        }
+        if self.passphrase.is_some() {
+            arguments.push("--symmetric");
+        }
+        if !self.recipients.is_empty() {
+            arguments.push("--encrypt");
+        }
+        for recipient in &self.recipients {
+            arguments.extend(["--recipient", recipient]);
+        }
+        self.run(&arguments, plaintext)
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> std::io::Result<Vec<u8>> {
+        let mut arguments = vec!["--decrypt"];
+        for secret_key in &self.secret_keys {
+            arguments.extend(["--try-secret-key", secret_key]);
+        }
+        self.run(&arguments, ciphertext)
+    }
+}
@@ -0,0 +1,154 @@
+//! Recovery of lock and temporary files left behind by crashed processes.
+//!
+//! [`FileLock`](crate::FileLock) sidecars are kept on disk once released, and a process
+//! crashing while writing a configuration leaves its temporary file behind. Long-lived daemons
+//! can call [`cleanup_stale_files`] periodically to remove the ones that have not been used
+//! for a while.
+
+/// Outcome of a [`cleanup_stale_files`] run.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CleanupReport {
+    /// Stale files which were removed.
+    pub removed: Vec<std::path::PathBuf>,
+    /// Lock files old enough to be removed but still held by a live process.
+    pub held: Vec<std::path::PathBuf>,
+}
+
+/// Removes stale [`FileLock`](crate::FileLock) sidecars and orphaned temporary files from a
+/// directory.
+///
+/// Only `<file>.lock` files next to an existing `<file>` are considered, so that lock files
+/// of other tools (e.g. `Cargo.lock` or `yarn.lock`) are left alone. Temporary files are the
+/// `<file>.<pid>.<sequence>.tmp` files written before being renamed over `<file>`. A file is
+/// stale when it has not been modified for at least `max_age`, which must thus exceed the
+/// duration of a write. Sidecars are refreshed on every
+/// acquisition and are only removed when no process holds them; the
+/// removal itself happens under an exclusive lock, so concurrent lockers never end up on
+/// distinct files. The directory is not scanned recursively, and files which cannot be
+/// removed are logged and skipped.
+///
+/// # Parameters
+/// - `dir`: The directory holding the configuration files. Tilde `~` expansion is supported.
+/// - `max_age`: Minimum time since the last modification of a file to consider it stale.
+/// - `context`: A context used for error details if the operation fails.
+///
+/// # Returns
+/// The removed files and the stale-looking lock files still in use, or an error if the
+/// directory cannot be listed.
+///
+/// # Example
+/// ```rust
+/// fn sweep() -> cdumay_core::Result<usize> {
+///     let context = std::collections::BTreeMap::new();
+///     let report = cdumay_config::cleanup_stale_files("/etc/app", std::time::Duration::from_secs(3600), &context)?;
+///     Ok(report.removed.len())
+/// }
+/// ```
+pub fn cleanup_stale_files(
+    dir: &str,
+    max_age: std::time::Duration,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<CleanupReport> {
+    cleanup_stale_files_with(dir, max_age, context, &crate::WriteOptions::default())
+}
+
+/// Removes stale [`FileLock`](crate::FileLock) sidecars and orphaned temporary files from a
+/// directory, mapping it with the options' [`PathResolver`](crate::PathResolver) first.
+///
+/// See [`cleanup_stale_files`]. Only the resolver of the options is used.
+///
+/// # Parameters
+/// - `dir`: The directory holding the configuration files.
+/// - `max_age`: Minimum time since the last modification of a file to consider it stale.
+/// - `context`: A context used for error details if the operation fails.
+/// - `options`: The write options whose resolver maps the directory.
+///
+/// # Returns
+/// The removed files and the stale-looking lock files still in use, or an error if the
+/// directory cannot be resolved or listed.
+pub fn cleanup_stale_files_with(
+    dir: &str,
+    max_age: std::time::Duration,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<CleanupReport> {
+    let resolved = crate::resolver::resolve(options.resolver.as_ref(), std::path::Path::new(dir), context)?;
+    let entries = std::fs::read_dir(&resolved).map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to list directory: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_resolved_path(context, std::path::Path::new(dir), &resolved);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    })?;
+    let mut report = CleanupReport::default();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if is_temporary(&path) && is_stale(&path, max_age) {
+            match std::fs::remove_file(&path) {
+                Ok(()) => {
+                    log::warn!("Removed orphaned temporary file '{}'", path.display());
+                    report.removed.push(path);
+                }
+                Err(err) => log::warn!("Failed to remove orphaned temporary file '{}': {}", path.display(), err),
+            }
+            continue;
+        }
+        if !is_sidecar(&path) || !is_stale(&path, max_age) {
+            continue;
+        }
+        let Some(removed) = remove_unheld_lock(&path, max_age) else {
+            log::debug!("Keeping stale-looking lock file '{}': still held", path.display());
+            report.held.push(path);
+            continue;
+        };
+        match removed {
+            Ok(()) => {
+                log::warn!("Removed stale file '{}'", path.display());
+                report.removed.push(path);
+            }
+            Err(err) => log::warn!("Failed to remove stale file '{}': {}", path.display(), err),
+        }
+    }
+    Ok(report)
+}
+
+/// Tells whether a file is the [`FileLock`](crate::FileLock) sidecar of an existing file.
+fn is_sidecar(path: &std::path::Path) -> bool {
+    path.to_str()
+        .and_then(|path| path.strip_suffix(".lock"))
+        .is_some_and(|target| std::path::Path::new(target).is_file() && crate::FileLock::lock_path(target) == path)
+}
+
+/// Tells whether a file is a temporary file written by a configuration manager.
+fn is_temporary(path: &std::path::Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(crate::formats::is_temporary_name)
+        && path.is_file()
+}
+
+/// Tells whether a file was last modified at least `max_age` ago.
+fn is_stale(path: &std::path::Path, max_age: std::time::Duration) -> bool {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age >= max_age)
+}
+
+/// Removes a lock file while holding it exclusively.
+///
+/// Returns `None` if another process holds the lock or acquired it since the age check.
+fn remove_unheld_lock(path: &std::path::Path, max_age: std::time::Duration) -> Option<std::io::Result<()>> {
+    let file = match std::fs::OpenOptions::new().read(true).write(true).open(path) {
+        Ok(file) => file,
+        Err(err) => return Some(Err(err)),
+    };
+    match file.try_lock() {
+        Ok(()) if is_stale(path, max_age) => Some(std::fs::remove_file(path)),
+        Ok(()) | Err(std::fs::TryLockError::WouldBlock) => None,
+        Err(std::fs::TryLockError::Error(err)) => Some(Err(err)),
+    }
+}
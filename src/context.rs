@@ -0,0 +1,121 @@
+//! Lazily built error details.
+//!
+//! Error contexts are only cloned and enriched once an error actually occurs, so that
+//! successful reads and writes do not pay for building them. Their secrets are then masked
+//! according to the [error redaction](crate::set_error_redaction) and they are bounded to
+//! the [error detail limit](crate::set_error_detail_limit) before being attached to errors.
+
+/// Returns a copy of `context` holding the path of the file involved in an error.
+pub(crate) fn with_path(
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    path: &std::path::Path,
+) -> std::collections::BTreeMap<String, serde_value::Value> {
+    let mut ctx = context.clone();
+    ctx.insert("path".to_string(), serde_value::Value::String(path.display().to_string()));
+    ctx
+}
+
+/// Returns a copy of `context` holding the path of a file involved in an error, along with
+/// the `resolved_path` actually accessed when the [resolver](crate::PathResolver) mapped it
+/// elsewhere.
+pub(crate) fn with_resolved_path(
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    path: &std::path::Path,
+    resolved: &std::path::Path,
+) -> std::collections::BTreeMap<String, serde_value::Value> {
+    let mut ctx = with_path(context, path);
+    if resolved != path {
+        ctx.insert("resolved_path".to_string(), serde_value::Value::String(resolved.display().to_string()));
+    }
+    ctx
+}
+
+/// Adds the position of a parse failure to an error context, as 1-based `line` and `column`
+/// details, so that tools can jump to the offending location.
+pub(crate) fn with_location(
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    line: usize,
+    column: usize,
+) -> std::collections::BTreeMap<String, serde_value::Value> {
+    let mut ctx = context.clone();
+    ctx.insert("line".to_string(), serde_value::Value::U64(line as u64));
+    ctx.insert("column".to_string(), serde_value::Value::U64(column as u64));
+    ctx
+}
+
+/// Adds the URL of a remote request involved in an error to an error context, along with
+/// the HTTP `status` of its response and the `origin` of the failure if known.
+#[cfg(any(feature = "aws", feature = "azure", feature = "etcd", feature = "gcp", feature = "http", feature = "redis", feature = "s3", feature = "vault-hashicorp"))]
+pub(crate) fn with_request(
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    url: &str,
+    status: Option<u16>,
+    origin: Option<String>,
+) -> std::collections::BTreeMap<String, serde_value::Value> {
+    let mut ctx = context.clone();
+    ctx.insert("url".to_string(), serde_value::Value::String(url.to_string()));
+    if let Some(status) = status {
+        ctx.insert("status".to_string(), serde_value::Value::U16(status));
+    }
+    if let Some(origin) = origin {
+        ctx.insert("origin".to_string(), serde_value::Value::String(origin));
+    }
+    ctx
+}
+
+/// Computes the 1-based line and column, counted in characters, of a byte offset in a text.
+pub(crate) fn location(content: &str, offset: usize) -> (usize, usize) {
+    let before = &content[..content.floor_char_boundary(offset.min(content.len()))];
+    let line_start = before.rfind('\n').map_or(0, |index| index + 1);
+    (before.matches('\n').count() + 1, before[line_start..].chars().count() + 1)
+}
+
+/// Masks the secrets of error details, then truncates their strings and bytes longer than
+/// the error detail limit.
+pub(crate) fn bounded(mut details: std::collections::BTreeMap<String, serde_value::Value>) -> std::collections::BTreeMap<String, serde_value::Value> {
+    if let Some(redaction) = crate::error_redaction() {
+        details = redaction.redact_details(details);
+    }
+    let limit = crate::error_detail_limit();
+    for value in details.values_mut() {
+        bound(value, limit);
+    }
+    details
+}
+
+/// Truncates the strings and bytes of a value tree longer than `limit` bytes.
+fn bound(value: &mut serde_value::Value, limit: usize) {
+    match value {
+        serde_value::Value::String(text) if text.len() > limit => {
+            let mut end = limit;
+            while !text.is_char_boundary(end) {
+                end -= 1;
+            }
+            let truncated = text.len() - end;
+            text.truncate(end);
+            text.push_str(&format!("{} ({} bytes truncated)", crate::TRUNCATION_MARKER, truncated));
+        }
+        serde_value::Value::Bytes(bytes) if bytes.len() > limit => bytes.truncate(limit),
+        serde_value::Value::Seq(items) => items.iter_mut().for_each(|item| bound(item, limit)),
+        serde_value::Value::Map(entries) => entries.values_mut().for_each(|entry| bound(entry, limit)),
+        serde_value::Value::Option(Some(inner)) | serde_value::Value::Newtype(inner) => bound(inner, limit),
+        _ => {}
+    }
+}
+
+/// Adds the path of the file involved to the details of an error.
+pub(crate) fn add_path(err: cdumay_core::Error, path: &std::path::Path) -> cdumay_core::Error {
+    let details = bounded(with_path(err.details_ref(), path));
+    cdumay_core::Error::new(err.code(), err.class().to_string(), err.message().to_string(), details)
+}
+
+/// Prefixes the message of an error with the path of the key which failed to deserialize
+/// (e.g. `server.listeners[2].port`), also added to its `key_path` detail.
+pub(crate) fn add_key_path(err: cdumay_core::Error, key: &str) -> cdumay_core::Error {
+    let mut details = err.details();
+    details.extend(bounded(std::collections::BTreeMap::from([(
+        "key_path".to_string(),
+        serde_value::Value::String(key.to_string()),
+    )])));
+    cdumay_core::Error::new(err.code(), err.class().to_string(), format!("{}: {}", key, err.message()), details)
+}
@@ -0,0 +1,107 @@
+//! Machine-readable representation of errors, for services returning them over HTTP.
+//!
+//! The `Serialize` implementation of `cdumay_core::Error` skips its code and keeps the
+//! details as `serde_value` values, some of which (bytes, maps with non-string keys) have no
+//! JSON equivalent. [`ErrorEnvelope`] holds every part of the error as plain JSON.
+
+/// JSON envelope of an error, following the cdumay error model.
+///
+/// ```json
+/// {
+///   "code": 400,
+///   "kind": "InvalidConfiguration",
+///   "class": "Client::InvalidConfiguration::ConfigurationFileError",
+///   "message": "Invalid configuration content: ...",
+///   "details": {"path": "/etc/app.json", "key_path": "server.port"}
+/// }
+/// ```
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ErrorEnvelope {
+    /// Numerical code of the error, an HTTP status code.
+    pub code: u16,
+    /// Name of the error kind (e.g. `InvalidConfiguration`).
+    pub kind: String,
+    /// Full class of the error (e.g. `Client::InvalidConfiguration::ConfigurationFileError`).
+    pub class: String,
+    /// Human-readable message.
+    pub message: String,
+    /// Details of the error. Bytes are encoded in standard base64 and map keys which are not
+    /// strings are formatted as JSON.
+    pub details: serde_json::Map<String, serde_json::Value>,
+}
+
+impl ErrorEnvelope {
+    /// Returns the envelope as a JSON value.
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::json!({
+            "code": self.code,
+            "kind": self.kind,
+            "class": self.class,
+            "message": self.message,
+            "details": self.details,
+        })
+    }
+}
+
+impl From<&cdumay_core::Error> for ErrorEnvelope {
+    fn from(err: &cdumay_core::Error) -> Self {
+        let class = err.class().to_string();
+        Self {
+            code: err.code(),
+            kind: class.split("::").nth(1).unwrap_or(&class).to_string(),
+            message: err.message().to_string(),
+            details: err.details_ref().iter().map(|(key, value)| (key.clone(), to_json(value))).collect(),
+            class,
+        }
+    }
+}
+
+impl From<cdumay_core::Error> for ErrorEnvelope {
+    fn from(err: cdumay_core::Error) -> Self {
+        Self::from(&err)
+    }
+}
+
+/// Exports an error as a JSON value holding its kind, code, class, message and details.
+///
+/// # Parameters
+/// - `err`: The error to export.
+///
+/// # Returns
+/// The JSON value of the [`ErrorEnvelope`] of the error.
+///
+/// # Example
+/// ```rust
+/// fn body(path: &str) -> Option<serde_json::Value> {
+///     let context = std::collections::BTreeMap::new();
+///     let result: cdumay_core::Result<serde_json::Value> = cdumay_config::read_config(path, None, &context);
+///     result.err().map(|err| cdumay_config::error_to_json(&err))
+/// }
+/// ```
+pub fn error_to_json(err: &cdumay_core::Error) -> serde_json::Value {
+    ErrorEnvelope::from(err).to_json()
+}
+
+/// Converts a detail value to JSON, encoding the values which have no JSON equivalent.
+fn to_json(value: &serde_value::Value) -> serde_json::Value {
+    use base64::Engine;
+
+    match value {
+        serde_value::Value::Bytes(bytes) => serde_json::Value::String(base64::engine::general_purpose::STANDARD.encode(bytes)),
+        serde_value::Value::Option(value) => value.as_deref().map_or(serde_json::Value::Null, to_json),
+        serde_value::Value::Newtype(value) => to_json(value),
+        serde_value::Value::Seq(values) => serde_json::Value::Array(values.iter().map(to_json).collect()),
+        serde_value::Value::Map(map) => serde_json::Value::Object(
+            map.iter()
+                .map(|(key, value)| {
+                    let key = match to_json(key) {
+                        serde_json::Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, to_json(value))
+                })
+                .collect(),
+        ),
+        value => serde_json::to_value(value).unwrap_or(serde_json::Value::Null),
+    }
+}
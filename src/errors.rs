@@ -1,8 +1,88 @@
-use cdumay_core::define_errors;
-use cdumay_error::InvalidConfiguration;
+use cdumay_core::{define_errors, define_kinds};
+use cdumay_error::{InvalidConfiguration, IoError};
+
+define_kinds! {
+    EmptyConfiguration = (400, "Empty configuration file"),
+    HookFailure = (500, "Post-write hook failed"),
+    SecretExpired = (410, "Secret expired"),
+    UnsupportedSchemaVersion = (400, "Unsupported configuration schema version"),
+}
+
+/// Error returned by every fallible function of the crate.
+///
+/// All the error types defined below convert into it, so that managers of any format
+/// can be used from the same generic code.
+pub use cdumay_core::Error;
+
+/// Result returned by every fallible function of the crate, an alias of `cdumay_core::Result`.
+pub type Result<T> = cdumay_core::Result<T>;
+
+/// Default maximum size, in bytes, of a string attached to error details.
+pub const DEFAULT_ERROR_DETAIL_LIMIT: usize = 4096;
+
+/// Marker ending the strings of error details which were truncated.
+pub const TRUNCATION_MARKER: &str = "…";
+
+/// Maximum size, in bytes, of a string attached to error details.
+static ERROR_DETAIL_LIMIT: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(DEFAULT_ERROR_DETAIL_LIMIT);
+
+/// Sets the maximum size, in bytes, of each string attached to the details of the errors
+/// built by the crate, for the whole process.
+///
+/// Longer strings (such as an origin message quoting a whole file, or a huge context value)
+/// are cut at this size and end with [`TRUNCATION_MARKER`] followed by the number of bytes
+/// removed; byte values are cut at this size. Use `usize::MAX` to disable truncation.
+///
+/// # Parameters
+/// - `max_bytes`: The maximum size of a string, [`DEFAULT_ERROR_DETAIL_LIMIT`] by default.
+pub fn set_error_detail_limit(max_bytes: usize) {
+    ERROR_DETAIL_LIMIT.store(max_bytes, std::sync::atomic::Ordering::Relaxed);
+}
+
+/// Returns the maximum size, in bytes, of a string attached to error details.
+pub fn error_detail_limit() -> usize {
+    ERROR_DETAIL_LIMIT.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Rules masking the secrets of error details, [`Redaction::default`](crate::Redaction::default)
+/// patterns with a `***` placeholder unless changed.
+static ERROR_REDACTION: std::sync::LazyLock<std::sync::RwLock<Option<crate::Redaction>>> =
+    std::sync::LazyLock::new(|| std::sync::RwLock::new(Some(crate::Redaction::default().with_placeholder("***"))));
+
+/// Sets the rules masking secret values in the details of the errors built by the crate, for
+/// the whole process.
+///
+/// Error details hold a copy of the context given by the caller, which may contain
+/// credentials. Each detail whose key matches the rules (e.g. `db_password` or
+/// `auth.token`) is replaced by the placeholder of the rules before being attached to the
+/// error, nested maps included. By default the patterns of
+/// [`Redaction::default`](crate::Redaction::default) are masked with `***`.
+///
+/// # Parameters
+/// - `redaction`: The rules selecting the details to mask, `None` to attach them unmodified.
+///
+/// # Example
+/// ```rust
+/// let redaction = cdumay_config::Redaction::default().with_key("dsn").with_placeholder("***");
+/// cdumay_config::set_error_redaction(Some(redaction));
+/// ```
+pub fn set_error_redaction(redaction: Option<crate::Redaction>) {
+    *ERROR_REDACTION.write().unwrap_or_else(std::sync::PoisonError::into_inner) = redaction;
+}
+
+/// Returns the rules masking secret values in error details, if any.
+pub fn error_redaction() -> Option<crate::Redaction> {
+    ERROR_REDACTION.read().unwrap_or_else(std::sync::PoisonError::into_inner).clone()
+}
 
 define_errors! {
     ConfigurationFileError = InvalidConfiguration,
+    EmptyFileError = EmptyConfiguration,
+    ExpiredSecretError = SecretExpired,
+    FileLockError = IoError,
+    SchemaVersionError = UnsupportedSchemaVersion,
+    VaultSecretError = InvalidConfiguration,
+    WriteHookError = HookFailure,
 }
 
 impl From<ConfigurationFileError> for std::io::Error {
@@ -10,3 +90,9 @@ impl From<ConfigurationFileError> for std::io::Error {
         std::io::Error::new(std::io::ErrorKind::InvalidData, e)
     }
 }
+
+impl From<VaultSecretError> for std::io::Error {
+    fn from(e: VaultSecretError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
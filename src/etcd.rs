@@ -0,0 +1,290 @@
+//! Configuration stored as keys of an etcd cluster.
+//!
+//! With the `etcd` feature, an [`EtcdSource`] reads every key under a prefix through the
+//! JSON gateway of the etcd v3 API, and nests them by splitting their names on `/`: under the
+//! prefix `/app/`, the keys `/app/db/host` and `/app/db/port` become
+//! `{"db": {"host": ..., "port": ...}}`. Each value is parsed by the manager of the leaf
+//! format, JSON by default, so that `8080` is read as a number; values which are not valid in
+//! this format (e.g. `localhost` in JSON) are kept as strings.
+//!
+//! ```rust
+//! #[derive(serde::Deserialize)]
+//! struct Database {
+//!     host: String,
+//!     port: u16,
+//! }
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     db: Database,
+//! }
+//!
+//! fn load() -> cdumay_core::Result<Config> {
+//!     cdumay_config::EtcdSource::new("http://etcd.internal:2379", "/app/")
+//!         .read_config(&std::collections::BTreeMap::new())
+//! }
+//! ```
+
+/// A configuration read from the keys of an etcd cluster under a prefix.
+#[derive(Clone, PartialEq)]
+pub struct EtcdSource {
+    /// Address of an etcd member (e.g. `http://etcd.internal:2379`).
+    endpoint: String,
+    /// Prefix of the keys to read, removed from their names.
+    prefix: String,
+    /// Format of the values.
+    leaf_format: crate::ContentFormat,
+    /// User name and password of an etcd user, if authentication is enabled.
+    credentials: Option<(String, String)>,
+    /// Maximum duration of each request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+}
+
+impl std::fmt::Debug for EtcdSource {
+    /// Formats the source without the password of its user.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EtcdSource")
+            .field("endpoint", &self.endpoint)
+            .field("prefix", &self.prefix)
+            .field("leaf_format", &self.leaf_format)
+            .field("user", &self.credentials.as_ref().map(|(user, _)| user))
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl EtcdSource {
+    /// Creates a source reading the keys under `prefix` from the etcd member at `endpoint`.
+    ///
+    /// # Parameters
+    /// - `endpoint`: Address of an etcd member (e.g. `http://etcd.internal:2379`).
+    /// - `prefix`: Prefix of the keys to read (e.g. `/app/`).
+    pub fn new(endpoint: &str, prefix: &str) -> Self {
+        Self {
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            prefix: prefix.to_string(),
+            leaf_format: crate::ContentFormat::JSON,
+            credentials: None,
+            timeout: None,
+        }
+    }
+
+    /// Sets the format of the values, JSON by default.
+    pub fn with_leaf_format(mut self, format: crate::ContentFormat) -> Self {
+        self.leaf_format = format;
+        self
+    }
+
+    /// Authenticates as an etcd user.
+    pub fn with_credentials(mut self, user: &str, password: &str) -> Self {
+        self.credentials = Some((user.to_string(), password.to_string()));
+        self
+    }
+
+    /// Sets the maximum duration of each request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Reads the keys under the prefix as a nested value tree.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// The nested keys, or an error holding the `url` (and `status`) of the failed request in
+    /// its details, or the conflicting `key` when a key is both a value and a parent of
+    /// other keys.
+    pub fn read_value(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<serde_value::Value> {
+        use base64::Engine;
+
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let token = match &self.credentials {
+            Some((user, password)) => {
+                let url = format!("{}/v3/auth/authenticate", self.endpoint);
+                let body = serde_json::json!({"name": user, "password": password});
+                match call(&agent, &url, None, body, context)?.get("token") {
+                    Some(serde_json::Value::String(token)) => Some(token.clone()),
+                    _ => {
+                        return Err(error(
+                            "Invalid etcd authentication response: no token".to_string(),
+                            &url,
+                            None,
+                            None,
+                            context,
+                        ));
+                    }
+                }
+            }
+            None => None,
+        };
+        let url = format!("{}/v3/kv/range", self.endpoint);
+        let engine = base64::engine::general_purpose::STANDARD;
+        let body = serde_json::json!({
+            "key": engine.encode(&self.prefix),
+            "range_end": engine.encode(range_end(self.prefix.as_bytes())),
+        });
+        let response = call(&agent, &url, token.as_deref(), body, context)?;
+        let mut root = std::collections::BTreeMap::new();
+        let kvs = response.get("kvs").and_then(serde_json::Value::as_array).map_or(&[][..], Vec::as_slice);
+        for kv in kvs {
+            let decode = |field: &str| -> Option<String> {
+                match kv.get(field) {
+                    Some(serde_json::Value::String(encoded)) => String::from_utf8(engine.decode(encoded).ok()?).ok(),
+                    // etcd omits empty values.
+                    _ => Some(String::new()),
+                }
+            };
+            let (Some(key), Some(value)) = (decode("key"), decode("value")) else {
+                return Err(error(
+                    "Invalid etcd response: keys and values must be UTF-8".to_string(),
+                    &url,
+                    None,
+                    None,
+                    context,
+                ));
+            };
+            let leaf = match self.leaf_format.read_str::<serde_value::Value>(&value, context) {
+                Ok(leaf) => leaf,
+                Err(_) => serde_value::Value::String(value),
+            };
+            let segments: Vec<&str> = key
+                .strip_prefix(&self.prefix)
+                .unwrap_or(&key)
+                .split('/')
+                .filter(|segment| !segment.is_empty())
+                .collect();
+            insert(&mut root, &segments, leaf).map_err(|()| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("etcd key '{}' conflicts with the prefix or another key", key))
+                    .with_details(crate::context::bounded({
+                        let mut ctx = crate::context::with_request(context, &url, None, None);
+                        ctx.insert("key".to_string(), serde_value::Value::String(key.clone()));
+                        ctx
+                    }))
+            })?;
+        }
+        Ok(serde_value::Value::Map(root))
+    }
+
+    /// Reads the keys under the prefix and deserializes them into a configuration.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type to deserialize the configuration into.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// The deserialized configuration, or an error if reading or deserializing fails.
+    pub fn read_config<C: serde::de::DeserializeOwned>(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut context = context.clone();
+        context.insert("url".to_string(), serde_value::Value::String(self.endpoint.clone()));
+        context.insert("prefix".to_string(), serde_value::Value::String(self.prefix.clone()));
+        crate::formats::from_value(self.read_value(&context)?, &context)
+    }
+}
+
+/// Returns the end of the range of the keys starting with `prefix`, the prefix with its last
+/// byte incremented (`\0`, meaning every key, for an empty prefix).
+fn range_end(prefix: &[u8]) -> Vec<u8> {
+    let mut end = prefix.to_vec();
+    while let Some(last) = end.pop() {
+        if last < 0xff {
+            end.push(last + 1);
+            return end;
+        }
+    }
+    vec![0]
+}
+
+/// Inserts a value at the path of its segments, failing if a value and a map share a path.
+fn insert(
+    map: &mut std::collections::BTreeMap<serde_value::Value, serde_value::Value>,
+    segments: &[&str],
+    value: serde_value::Value,
+) -> Result<(), ()> {
+    let Some((first, rest)) = segments.split_first() else {
+        return Err(());
+    };
+    let key = serde_value::Value::String(first.to_string());
+    if rest.is_empty() {
+        return match map.insert(key, value) {
+            Some(serde_value::Value::Map(_)) => Err(()),
+            _ => Ok(()),
+        };
+    }
+    match map
+        .entry(key)
+        .or_insert_with(|| serde_value::Value::Map(std::collections::BTreeMap::new()))
+    {
+        serde_value::Value::Map(child) => insert(child, rest, value),
+        _ => Err(()),
+    }
+}
+
+/// Sends a request of the JSON gateway, returning the JSON body of a successful response.
+fn call(
+    agent: &ureq::Agent,
+    url: &str,
+    token: Option<&str>,
+    body: serde_json::Value,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<serde_json::Value> {
+    let request = agent.post(url).header("Content-Type", "application/json");
+    let request = match token {
+        Some(token) => request.header("Authorization", token),
+        None => request,
+    };
+    let mut response = request.send(body.to_string()).map_err(|err| {
+        error(
+            format!("Failed to reach etcd at '{}': {}", url, err),
+            url,
+            None,
+            Some(err.to_string()),
+            context,
+        )
+    })?;
+    let status = response.status().as_u16();
+    let content = response.body_mut().read_to_string().unwrap_or_default();
+    if !response.status().is_success() {
+        return Err(error(
+            format!("etcd request '{}' failed with HTTP status {}", url, status),
+            url,
+            Some(status),
+            Some(content),
+            context,
+        ));
+    }
+    serde_json::from_str(&content).map_err(|err| {
+        error(
+            format!("Invalid etcd response from '{}': {}", url, err),
+            url,
+            Some(status),
+            Some(err.to_string()),
+            context,
+        )
+    })
+}
+
+/// Builds the error of a failed etcd request.
+fn error(
+    message: String,
+    url: &str,
+    status: Option<u16>,
+    origin: Option<String>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Error {
+    crate::ConfigurationFileError::new()
+        .with_message(message)
+        .with_details(crate::context::bounded(crate::context::with_request(context, url, status, origin)))
+        .into()
+}
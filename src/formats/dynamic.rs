@@ -0,0 +1,177 @@
+/// An object-safe counterpart of the [`Manager`](crate::Manager) trait.
+///
+/// The generic methods of `Manager` prevent holding a `Box<dyn Manager>`. `DynManager`
+/// exposes the same operations on [`serde_value::Value`] trees and raw bytes instead, so
+/// that the backend can be selected at runtime, e.g. from a format name read in a
+/// configuration file. It is implemented for every thread-safe `Manager`; use
+/// [`ContentFormat::manager`](crate::ContentFormat::manager) or
+/// [`FormatRegistry::manager`](crate::FormatRegistry::manager) to build one.
+///
+/// Values can be converted from and to typed configurations with
+/// [`serde_value::Value::deserialize_into`] and [`serde_value::to_value`].
+pub trait DynManager: Send + Sync {
+    /// Returns the file path associated with the manager.
+    fn config_path(&self) -> &std::path::Path;
+
+    /// Reads the managed file into a value tree, applying the given read options.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details and templating.
+    /// - `options`: The read options to apply.
+    ///
+    /// # Returns
+    /// The parsed value tree or an error.
+    fn read_value_with(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<serde_value::Value>;
+
+    /// Writes a value tree to the managed file, applying the given write options.
+    ///
+    /// # Parameters
+    /// - `value`: The value tree to write.
+    /// - `context`: A context used for error details and templating.
+    /// - `options`: The write options to apply.
+    ///
+    /// # Returns
+    /// The path to the file where the configuration was written.
+    fn write_value_with(
+        &self,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::WriteOptions,
+    ) -> cdumay_core::Result<std::path::PathBuf>;
+
+    /// Decodes in-memory content with the manager format.
+    ///
+    /// # Parameters
+    /// - `content`: The serialized configuration.
+    /// - `context`: A context used for error reporting.
+    ///
+    /// # Returns
+    /// The parsed value tree or an error.
+    fn decode_value(
+        &self,
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_value::Value>;
+
+    /// Encodes a value tree with the manager format.
+    ///
+    /// # Parameters
+    /// - `value`: The value tree to serialize.
+    /// - `context`: A context used for error reporting.
+    ///
+    /// # Returns
+    /// The serialized bytes or an error.
+    fn encode_value(
+        &self,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<u8>>;
+
+    /// Reads a value tree from a stream with the manager format.
+    ///
+    /// Any source can be used through a `&mut dyn Read`, such as a flash partition or a
+    /// serial link on targets without a file system; `embedded-io` sources can be wrapped
+    /// with the `ToStd` adapter of the `embedded-io-adapters` crate.
+    ///
+    /// # Parameters
+    /// - `reader`: The stream to read from.
+    /// - `context`: A context used for error reporting.
+    ///
+    /// # Returns
+    /// The parsed value tree or an error.
+    fn read_value_from(
+        &self,
+        reader: &mut dyn std::io::Read,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_value::Value>;
+
+    /// Writes a value tree to a stream with the manager format.
+    ///
+    /// # Parameters
+    /// - `writer`: The stream to write to.
+    /// - `value`: The value tree to serialize.
+    /// - `context`: A context used for error reporting.
+    ///
+    /// # Returns
+    /// An empty result on success or an error on failure.
+    fn write_value_to(
+        &self,
+        writer: &mut dyn std::io::Write,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()>;
+
+    /// Reads the managed file into a value tree.
+    fn read_value(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<serde_value::Value> {
+        self.read_value_with(context, &crate::ReadOptions::default())
+    }
+
+    /// Writes a value tree to the managed file.
+    fn write_value(
+        &self,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        self.write_value_with(value, context, &crate::WriteOptions::default())
+    }
+}
+
+impl<M: crate::Manager + Send + Sync> DynManager for M {
+    fn config_path(&self) -> &std::path::Path {
+        self.file_path()
+    }
+
+    fn read_value_with(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<serde_value::Value> {
+        self.read_config_with(context, options)
+    }
+
+    fn write_value_with(
+        &self,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::WriteOptions,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        self.write_config_with(value, context, options)
+    }
+
+    fn decode_value(
+        &self,
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_value::Value> {
+        M::read_bytes(content, context)
+    }
+
+    fn encode_value(
+        &self,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<u8>> {
+        self.write_bytes(value, context)
+    }
+
+    fn read_value_from(
+        &self,
+        reader: &mut dyn std::io::Read,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_value::Value> {
+        self.read(reader, context)
+    }
+
+    fn write_value_to(
+        &self,
+        writer: &mut dyn std::io::Write,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        self.write(writer, value, context)
+    }
+}
@@ -5,7 +5,7 @@ use cdumay_core::ErrorConverter;
 /// and integrates with the error and context handling system.
 pub struct JsonManager {
     /// Path to the JSON configuration file.
-    path: String,
+    path: std::path::PathBuf,
 }
 
 impl crate::Manager for JsonManager {
@@ -16,13 +16,13 @@ impl crate::Manager for JsonManager {
     ///
     /// # Returns
     /// A new instance of `JsonManager`.
-    fn new(path: String) -> JsonManager {
-        JsonManager { path }
+    fn new<P: AsRef<std::path::Path>>(path: P) -> JsonManager {
+        JsonManager { path: path.as_ref().to_path_buf() }
     }
 
     /// Returns the path to the JSON configuration file.
-    fn path(&self) -> String {
-        self.path.clone()
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
     }
 
     /// Reads and deserializes JSON content from a `Read` stream.
@@ -42,9 +42,7 @@ impl crate::Manager for JsonManager {
         reader: R,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        let mut ctx = context.clone();
-        ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-        cdumay_json::convert_json_result!(serde_json::from_reader(reader), ctx)
+        deserialize(serde_json::de::IoRead::new(reader), Some(&self.path), context)
     }
 
     /// Serializes and writes data as pretty-printed JSON to a `Write` stream.
@@ -66,26 +64,90 @@ impl crate::Manager for JsonManager {
         data: D,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<()> {
-        let mut ctx = context.clone();
-        ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-        cdumay_json::convert_json_result!(serde_json::to_writer_pretty(writer, &data), ctx)
+        cdumay_json::convert_json_result!(
+            serde_json::to_writer_pretty(writer, &data),
+            crate::context::bounded(crate::context::with_path(context, &self.path))
+        )
     }
 
-    /// Deserializes JSON content from a string slice.
+    /// Serializes and writes data as pretty-printed or compact JSON to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `style`: The layout of the output; pretty JSON is indented with two spaces by default.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write_styled<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        style: &crate::WriteStyle,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let result = match style.sort_keys {
+            true => to_writer(writer, &crate::formats::sorted_value(data, &self.path, context)?, style),
+            false => to_writer(writer, &data, style),
+        };
+        cdumay_json::convert_json_result!(result, crate::context::bounded(crate::context::with_path(context, &self.path)))
+    }
+
+    /// Deserializes JSON content from raw bytes.
     ///
     /// # Type Parameters
     /// - `C`: The type into which the content will be deserialized.
     ///
     /// # Parameters
-    /// - `content`: The JSON string to parse.
+    /// - `content`: The JSON content to parse.
     /// - `context`: Context used for error reporting.
     ///
     /// # Returns
     /// The deserialized object or an error if the content is invalid.
-    fn read_str<C: serde::de::DeserializeOwned>(
-        content: &str,
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        cdumay_json::convert_json_result!(serde_json::from_str(content), context.clone())
+        deserialize(serde_json::de::SliceRead::new(content), None, context)
+    }
+}
+
+/// Serializes data as JSON with the given layout.
+pub(crate) fn to_writer<D: serde::Serialize, W: std::io::Write>(writer: W, data: &D, style: &crate::WriteStyle) -> serde_json::Result<()> {
+    match style.pretty {
+        true => {
+            let indent = " ".repeat(style.indent.unwrap_or(2));
+            let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+            data.serialize(&mut serde_json::Serializer::with_formatter(writer, formatter))
+        }
+        false => serde_json::to_writer(writer, data),
     }
 }
+
+/// Deserializes JSON content, recording the path of the key which fails to deserialize.
+fn deserialize<'de, R: serde_json::de::Read<'de>, C: serde::de::DeserializeOwned>(
+    read: R,
+    path: Option<&std::path::Path>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<C> {
+    let mut deserializer = serde_json::Deserializer::new(read);
+    crate::formats::deserialize_tracked(&mut deserializer)
+        .and_then(|config| Ok(deserializer.end().map(|_| config)?))
+        .map_err(|err| {
+            err.convert(|err| {
+                let mut ctx = match path {
+                    Some(path) => crate::context::with_path(context, path),
+                    None => context.clone(),
+                };
+                if err.line() > 0 {
+                    ctx = crate::context::with_location(&ctx, err.line(), err.column());
+                }
+                cdumay_json::JsonErrorConverter::convert_error(&err, None, crate::context::bounded(ctx))
+            })
+        })
+}
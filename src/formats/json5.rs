@@ -0,0 +1,142 @@
+use cdumay_core::ErrorConverter;
+/// JSON5 configuration file manager implementing the `Manager` trait.
+///
+/// This struct reads hand-edited JSON files which may contain comments, trailing
+/// commas and the other JSON5 extensions, using the `json5` crate. Output is always
+/// written as strict, pretty-printed JSON so that any JSON parser can read it back.
+pub struct Json5Manager {
+    /// Path to the JSON5 configuration file.
+    path: std::path::PathBuf,
+}
+
+impl crate::Manager for Json5Manager {
+    /// Creates a new `Json5Manager` with the specified file path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the JSON5 configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `Json5Manager`.
+    fn new<P: AsRef<std::path::Path>>(path: P) -> Json5Manager {
+        Json5Manager { path: path.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path to the JSON5 configuration file.
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Reads JSON5 content from a `Read` stream, deserializing it into the specified type.
+    ///
+    /// The entire stream is first read into a `String`, then parsed as JSON5.
+    ///
+    /// # Type Parameters
+    /// - `R`: A type implementing `Read`.
+    /// - `C`: The type into which the data will be deserialized.
+    ///
+    /// # Parameters
+    /// - `reader`: A readable stream containing JSON5 data.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read<R: std::io::Read, C: serde::de::DeserializeOwned>(
+        &self,
+        mut reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut buffer = String::new();
+        reader.read_to_string(&mut buffer).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read JSON5 file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?;
+        Self::read_str(&buffer, context).map_err(|err| crate::context::add_path(err, &self.path))
+    }
+
+    /// Serializes and writes data as strict pretty-printed JSON to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        cdumay_json::convert_json_result!(
+            serde_json::to_writer_pretty(writer, &data),
+            crate::context::bounded(crate::context::with_path(context, &self.path))
+        )
+    }
+
+    /// Serializes and writes data as strict pretty-printed or compact JSON to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `style`: The layout of the output; pretty JSON is indented with two spaces by default.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write_styled<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        style: &crate::WriteStyle,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let result = match style.sort_keys {
+            true => crate::formats::json::to_writer(writer, &crate::formats::sorted_value(data, &self.path, context)?, style),
+            false => crate::formats::json::to_writer(writer, &data, style),
+        };
+        cdumay_json::convert_json_result!(result, crate::context::bounded(crate::context::with_path(context, &self.path)))
+    }
+
+    /// Deserializes JSON5 content from raw bytes, which must be valid UTF-8.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the content will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The JSON5 content to parse.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized object or an error if the content is invalid.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let content = crate::formats::utf8(content, context)?;
+        json5::Deserializer::from_str(content)
+            .map_err(crate::formats::KeyError::from)
+            .and_then(|mut deserializer| crate::formats::deserialize_tracked(&mut deserializer))
+            .map_err(|err| {
+                err.convert(|err| {
+                    crate::ConfigurationFileError::new()
+                        .with_message(format!("Invalid JSON5 content: {}", err))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = context.clone();
+                            ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                            ctx
+                        }))
+                        .into()
+                })
+            })
+    }
+}
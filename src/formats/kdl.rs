@@ -0,0 +1,136 @@
+/// KDL configuration file manager implementing the `Manager` trait.
+///
+/// This struct handles reading from and writing to KDL document
+/// configuration files, using the `kdl` crate for serialization and deserialization.
+///
+/// KDL documents are mapped to maps keyed by node name: a node with a single argument is
+/// a scalar, a node with several arguments or `-` children is a sequence, and a node with
+/// properties or children is a nested map. Repeated node names are collected into a sequence.
+pub struct KdlManager {
+    /// Path to the KDL configuration file.
+    path: std::path::PathBuf,
+}
+
+impl crate::Manager for KdlManager {
+    /// Creates a new `KdlManager` with the specified file path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the KDL configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `KdlManager`.
+    fn new<P: AsRef<std::path::Path>>(path: P) -> KdlManager {
+        KdlManager { path: path.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path to the KDL configuration file.
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Reads KDL content from a `Read` stream, deserializing it into the specified type.
+    ///
+    /// The entire stream is first read into a `String`, then parsed as KDL.
+    ///
+    /// # Type Parameters
+    /// - `R`: A type implementing `Read`.
+    /// - `C`: The type into which the data will be deserialized.
+    ///
+    /// # Parameters
+    /// - `reader`: A readable stream containing KDL data.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read<R: std::io::Read, C: serde::de::DeserializeOwned>(
+        &self,
+        mut reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut buffer = String::new();
+        reader.read_to_string(&mut buffer).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read KDL file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?;
+        Self::read_str(&buffer, context).map_err(|err| crate::context::add_path(err, &self.path))
+    }
+
+    /// Serializes and writes data as a KDL document to a `Write` stream.
+    ///
+    /// KDL documents are lists of nodes: data whose root is a scalar is rejected, as it could
+    /// not be read back.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        mut writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        if let Ok(value) = serde_value::to_value(&data)
+            && crate::formats::root_kind(&value) == Some("scalar")
+        {
+            return Err(crate::formats::root_error("KDL", "scalar", &self.path, context));
+        }
+        let content = kdl::se::to_string(&data).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to serialize KDL content: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, &self.path);
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        Ok(writer.write_all(content.as_bytes()).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to write KDL file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?)
+    }
+
+    /// Deserializes KDL content from raw bytes, which must be valid UTF-8.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the content will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The KDL content to parse.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized object or an error if the content is invalid.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let content = crate::formats::utf8(content, context)?;
+        kdl::de::from_str(content).map_err(|err| {
+            crate::formats::locate_key::<C, _>(kdl::de::from_str(content).ok(), err).convert(|err| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Invalid KDL content: {}", err))
+                    .with_details(crate::context::bounded({
+                        let mut ctx = context.clone();
+                        ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                        ctx
+                    }))
+                    .into()
+            })
+        })
+    }
+
+    /// Renders text as `//` line comments.
+    fn comment(text: &str) -> Option<String> {
+        Some(crate::formats::line_comments(text, "//"))
+    }
+}
@@ -1,27 +1,72 @@
+mod dynamic;
+pub use dynamic::DynManager;
 mod json;
 pub use json::JsonManager;
+mod ndjson;
+pub use ndjson::NdjsonManager;
 
+#[cfg(feature = "json5")]
+mod json5;
+#[cfg(feature = "json5")]
+pub use json5::Json5Manager;
+
+#[cfg(feature = "msgpack")]
+mod msgpack;
+#[cfg(feature = "msgpack")]
+pub use msgpack::MsgpackManager;
 #[cfg(feature = "xml")]
 mod xml;
 #[cfg(feature = "xml")]
-pub use xml::XmlManager;
+pub use xml::{XmlManager, XmlOptions};
+#[cfg(feature = "xml")]
+mod xml_tree;
 #[cfg(feature = "yaml")]
 mod yaml;
 #[cfg(feature = "yaml")]
-pub use yaml::YamlManager;
+pub use yaml::{YamlLimits, YamlManager};
 #[cfg(feature = "toml")]
 mod toml;
 #[cfg(feature = "toml")]
 pub use toml::TomlManager;
+#[cfg(feature = "toml")]
+mod toml_document;
+#[cfg(feature = "toml")]
+pub use toml_document::TomlDocument;
+#[cfg(feature = "ron")]
+mod ron;
+#[cfg(feature = "ron")]
+pub use ron::RonManager;
+#[cfg(feature = "kdl")]
+mod kdl;
+#[cfg(feature = "kdl")]
+pub use kdl::KdlManager;
+#[cfg(feature = "plist")]
+mod plist;
+#[cfg(feature = "plist")]
+pub use plist::PlistManager;
 
 /// Enum representing the supported content formats for configuration files.
 ///
 /// Each variant corresponds to a specific data serialization format.
-/// Additional formats (YAML, XML, TOML) are enabled via Cargo features.
+/// Additional formats (JSON5, YAML, XML, TOML, RON, KDL, MessagePack, plist) are enabled via Cargo features.
+///
+/// Formats are displayed, parsed and (de)serialized by their lowercase name (e.g. `"yaml"`),
+/// so they can be used in CLI flags or stored in configuration files. Parsing also accepts
+/// common aliases such as `yml` or `jsonl`, and fails for formats whose feature is disabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ContentFormat {
     /// JSON format (always available).
     JSON,
 
+    /// JSON Lines (ND-JSON) format, one element of a sequence per line (always available).
+    NDJSON,
+
+    /// JSON5 format, accepting comments and trailing commas (available only if the `json5` feature is enabled).
+    ///
+    /// Content is always written back as strict JSON.
+    #[cfg(feature = "json5")]
+    JSON5,
+
     /// YAML format (available only if the `yaml` feature is enabled).
     #[cfg(feature = "yaml")]
     YAML,
@@ -33,7 +78,26 @@ pub enum ContentFormat {
     /// TOML format (available only if the `toml` feature is enabled).
     #[cfg(feature = "toml")]
     TOML,
+
+    /// RON format (available only if the `ron` feature is enabled).
+    #[cfg(feature = "ron")]
+    RON,
+
+    /// KDL document format (available only if the `kdl` feature is enabled).
+    #[cfg(feature = "kdl")]
+    KDL,
+
+    /// MessagePack binary format (available only if the `msgpack` feature is enabled).
+    #[cfg(feature = "msgpack")]
+    MSGPACK,
+
+    /// Apple property list format (available only if the `plist` feature is enabled).
+    ///
+    /// Both XML and binary property lists are read; content is written as XML.
+    #[cfg(feature = "plist")]
+    PLIST,
 }
+
 impl Default for ContentFormat {
     /// Provides the default format used when none is explicitly specified.
     ///
@@ -42,14 +106,220 @@ impl Default for ContentFormat {
         ContentFormat::JSON
     }
 }
+impl ContentFormat {
+    /// Returns the lowercase name of the format (e.g. `json`).
+    pub(crate) fn name(&self) -> &'static str {
+        match self {
+            ContentFormat::JSON => "json",
+            ContentFormat::NDJSON => "ndjson",
+            #[cfg(feature = "json5")]
+            ContentFormat::JSON5 => "json5",
+            #[cfg(feature = "yaml")]
+            ContentFormat::YAML => "yaml",
+            #[cfg(feature = "xml")]
+            ContentFormat::XML => "xml",
+            #[cfg(feature = "toml")]
+            ContentFormat::TOML => "toml",
+            #[cfg(feature = "ron")]
+            ContentFormat::RON => "ron",
+            #[cfg(feature = "kdl")]
+            ContentFormat::KDL => "kdl",
+            #[cfg(feature = "msgpack")]
+            ContentFormat::MSGPACK => "msgpack",
+            #[cfg(feature = "plist")]
+            ContentFormat::PLIST => "plist",
+        }
+    }
+
+    /// Parses a format from its name, if the corresponding feature is enabled.
+    pub(crate) fn from_name(name: &str) -> Option<ContentFormat> {
+        match name.to_ascii_lowercase().as_str() {
+            "json" => Some(ContentFormat::JSON),
+            "ndjson" | "jsonl" => Some(ContentFormat::NDJSON),
+            #[cfg(feature = "json5")]
+            "json5" | "jsonc" => Some(ContentFormat::JSON5),
+            #[cfg(feature = "yaml")]
+            "yaml" | "yml" => Some(ContentFormat::YAML),
+            #[cfg(feature = "xml")]
+            "xml" => Some(ContentFormat::XML),
+            #[cfg(feature = "toml")]
+            "toml" => Some(ContentFormat::TOML),
+            #[cfg(feature = "ron")]
+            "ron" => Some(ContentFormat::RON),
+            #[cfg(feature = "kdl")]
+            "kdl" => Some(ContentFormat::KDL),
+            #[cfg(feature = "msgpack")]
+            "msgpack" | "mpk" => Some(ContentFormat::MSGPACK),
+            #[cfg(feature = "plist")]
+            "plist" => Some(ContentFormat::PLIST),
+            _ => None,
+        }
+    }
+
+    /// Infers a format from the extension of the last segment of a URI path (e.g. `yaml`
+    /// for `s3://bucket/app/prod.yaml`), ignoring its query and fragment.
+    pub(crate) fn from_uri(uri: &str) -> Option<ContentFormat> {
+        let (_, rest) = uri.split_once("://")?;
+        let (_, path) = rest.split(['?', '#']).next()?.split_once('/')?;
+        ContentFormat::from_name(std::path::Path::new(path).extension()?.to_str()?)
+    }
+
+    /// Infers a format from a media type (e.g. `application/yaml; charset=utf-8`), using its
+    /// structured syntax suffix if any (`application/ld+json`) and ignoring the `x-` prefix
+    /// of unregistered subtypes (`application/x-yaml`).
+    #[cfg(feature = "http")]
+    pub(crate) fn from_media_type(media_type: &str) -> Option<ContentFormat> {
+        let (_, subtype) = media_type.split(';').next()?.trim().split_once('/')?;
+        let subtype = subtype.rsplit('+').next()?;
+        ContentFormat::from_name(subtype.strip_prefix("x-").unwrap_or(subtype))
+    }
+
+    /// Builds a type-erased manager of this format for the given file.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the configuration file.
+    ///
+    /// # Returns
+    /// The manager, behind a [`DynManager`] trait object.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn load(format: &str) -> cdumay_core::Result<serde_value::Value> {
+    ///     let format: cdumay_config::ContentFormat = format.parse()?;
+    ///     format.manager("/etc/app/config").read_value(&std::collections::BTreeMap::new())
+    /// }
+    /// ```
+    pub fn manager<P: AsRef<std::path::Path>>(&self, path: P) -> Box<dyn DynManager> {
+        match self {
+            ContentFormat::JSON => Box::new(JsonManager::new(path)),
+            ContentFormat::NDJSON => Box::new(NdjsonManager::new(path)),
+            #[cfg(feature = "json5")]
+            ContentFormat::JSON5 => Box::new(Json5Manager::new(path)),
+            #[cfg(feature = "yaml")]
+            ContentFormat::YAML => Box::new(YamlManager::new(path)),
+            #[cfg(feature = "xml")]
+            ContentFormat::XML => Box::new(XmlManager::new(path)),
+            #[cfg(feature = "toml")]
+            ContentFormat::TOML => Box::new(TomlManager::new(path)),
+            #[cfg(feature = "ron")]
+            ContentFormat::RON => Box::new(RonManager::new(path)),
+            #[cfg(feature = "kdl")]
+            ContentFormat::KDL => Box::new(KdlManager::new(path)),
+            #[cfg(feature = "msgpack")]
+            ContentFormat::MSGPACK => Box::new(MsgpackManager::new(path)),
+            #[cfg(feature = "plist")]
+            ContentFormat::PLIST => Box::new(PlistManager::new(path)),
+        }
+    }
+
+    /// Deserializes content obtained for a path using the manager of this format, applying
+    /// the given read options.
+    pub(crate) fn read_content_with<C: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        content: Vec<u8>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        match self {
+            ContentFormat::JSON => JsonManager::new(path).read_content_with(content, context, options),
+            ContentFormat::NDJSON => NdjsonManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "json5")]
+            ContentFormat::JSON5 => Json5Manager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "yaml")]
+            ContentFormat::YAML => YamlManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "xml")]
+            ContentFormat::XML => XmlManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "toml")]
+            ContentFormat::TOML => TomlManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "ron")]
+            ContentFormat::RON => RonManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "kdl")]
+            ContentFormat::KDL => KdlManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "msgpack")]
+            ContentFormat::MSGPACK => MsgpackManager::new(path).read_content_with(content, context, options),
+            #[cfg(feature = "plist")]
+            ContentFormat::PLIST => PlistManager::new(path).read_content_with(content, context, options),
+        }
+    }
+
+    /// Deserializes in-memory content using the manager of this format.
+    pub(crate) fn read_str<C: serde::de::DeserializeOwned>(
+        &self,
+        content: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        match self {
+            ContentFormat::JSON => JsonManager::read_str(content, context),
+            ContentFormat::NDJSON => NdjsonManager::read_str(content, context),
+            #[cfg(feature = "json5")]
+            ContentFormat::JSON5 => Json5Manager::read_str(content, context),
+            #[cfg(feature = "yaml")]
+            ContentFormat::YAML => YamlManager::read_str(content, context),
+            #[cfg(feature = "xml")]
+            ContentFormat::XML => XmlManager::read_str(content, context),
+            #[cfg(feature = "toml")]
+            ContentFormat::TOML => TomlManager::read_str(content, context),
+            #[cfg(feature = "ron")]
+            ContentFormat::RON => RonManager::read_str(content, context),
+            #[cfg(feature = "kdl")]
+            ContentFormat::KDL => KdlManager::read_str(content, context),
+            #[cfg(feature = "msgpack")]
+            ContentFormat::MSGPACK => MsgpackManager::read_str(content, context),
+            #[cfg(feature = "plist")]
+            ContentFormat::PLIST => PlistManager::read_str(content, context),
+        }
+    }
+}
+
+impl std::fmt::Display for ContentFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl std::str::FromStr for ContentFormat {
+    type Err = cdumay_core::Error;
+
+    /// Parses a format from its name or one of its aliases, case-insensitively.
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        ContentFormat::from_name(name).ok_or_else(|| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Unknown configuration format '{}'", name))
+                .with_details(crate::context::bounded(std::collections::BTreeMap::from([(
+                    "format".to_string(),
+                    serde_value::Value::String(name.to_string()),
+                )])))
+                .into()
+        })
+    }
+}
+
+impl serde::Serialize for ContentFormat {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.name())
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for ContentFormat {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let name = <std::borrow::Cow<'de, str>>::deserialize(deserializer)?;
+        ContentFormat::from_name(&name).ok_or_else(|| serde::de::Error::custom(format!("unknown configuration format '{}'", name)))
+    }
+}
+
 /// Reads a configuration file and deserializes its content into a strongly typed Rust value.
 ///
 /// # Type Parameters
 /// - `C`: The type to deserialize the configuration into. Must implement `DeserializeOwned`.
 ///
 /// # Parameters
-/// - `path`: Path to the configuration file. Tilde `~` expansion is supported.
-/// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+/// - `path`: Path to the configuration file. Tilde `~` expansion is supported. URIs such
+///   as `env://APP_CONFIG` are fetched by the source of their scheme (see [`SchemeRegistry`](crate::SchemeRegistry)).
+/// - `format`: Optional format specifier. When not provided, a file extension registered in
+///   [`FormatRegistry::global`](crate::FormatRegistry::global) selects its format, and `JSON` is used otherwise.
+///   URIs are decoded in the format of their HTTP `Content-Type`, else of their extension
+///   (e.g. `s3://bucket/app/prod.yaml`), and in `JSON` otherwise.
 /// - `context`: A templating context used to resolve variables inside the configuration.
 ///
 /// # Returns
@@ -67,16 +337,76 @@ pub fn read_config<C: serde::de::DeserializeOwned>(
     format: Option<ContentFormat>,
     context: &std::collections::BTreeMap<String, serde_value::Value>,
 ) -> cdumay_core::Result<C> {
-    let path = shellexpand::tilde(path);
-    log::info!("Reading config file '{}'", path.as_ref());
+    read_config_with(path, format, context, &crate::ReadOptions::default())
+}
+
+/// Reads a configuration file like [`read_config`], applying the given [`ReadOptions`](crate::ReadOptions).
+///
+/// # Parameters
+/// - `path`: Path to the configuration file. Tilde `~` expansion is supported. URIs such
+///   as `env://APP_CONFIG` are fetched by the source of their scheme (see [`SchemeRegistry`](crate::SchemeRegistry)).
+/// - `format`: Optional format specifier. When not provided, a file extension registered in
+///   [`FormatRegistry::global`](crate::FormatRegistry::global) selects its format, and `JSON` is used otherwise.
+///   URIs are decoded in the format of their HTTP `Content-Type`, else of their extension
+///   (e.g. `s3://bucket/app/prod.yaml`), and in `JSON` otherwise.
+/// - `context`: A templating context used to resolve variables inside the configuration.
+/// - `options`: Options such as the advisory lock to take while reading.
+///
+/// # Returns
+/// The deserialized configuration of type `C`, or an error if reading or parsing fails.
+///
+/// # Example
+/// ```rust
+/// fn load() -> cdumay_core::Result<String> {
+///     let context = std::collections::BTreeMap::new();
+///     let options = cdumay_config::ReadOptions {
+///         lock: Some(cdumay_config::LockMode::Timeout(std::time::Duration::from_secs(5))),
+///         ..Default::default()
+///     };
+///     cdumay_config::read_config_with("~/.config/app.json", None, &context, &options)
+/// }
+/// ```
+pub fn read_config_with<C: serde::de::DeserializeOwned>(
+    path: &str,
+    format: Option<ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<C> {
+    let path = match crate::scheme::scheme(path) {
+        Some(scheme) if scheme.eq_ignore_ascii_case("file") => &path[7..],
+        Some(_) => {
+            log::info!("Fetching config '{}'", path);
+            let (content, served) = crate::SchemeRegistry::global().fetch_typed(path, context, options)?;
+            let mut context = context.clone();
+            context.insert("url".to_string(), serde_value::Value::String(path.to_string()));
+            let format = format.or(served).or_else(|| ContentFormat::from_uri(path)).unwrap_or_default();
+            return format.read_content_with(path, content, &context, options);
+        }
+        None => path,
+    };
+    if format.is_none() && crate::FormatRegistry::global().handles(path) {
+        return crate::FormatRegistry::global().read_config_with(path, None, context, options);
+    }
+    log::info!("Reading config file '{}'", path);
     match format.unwrap_or(ContentFormat::JSON) {
-        ContentFormat::JSON => JsonManager::new(path.to_string()).read_config(context),
+        ContentFormat::JSON => JsonManager::new(path).read_config_with(context, options),
+        ContentFormat::NDJSON => NdjsonManager::new(path).read_config_with(context, options),
+        #[cfg(feature = "json5")]
+        ContentFormat::JSON5 => Json5Manager::new(path).read_config_with(context, options),
         #[cfg(feature = "yaml")]
-        ContentFormat::YAML => YamlManager::new(path.to_string()).read_config(context),
+        ContentFormat::YAML => YamlManager::new(path).read_config_with(context, options),
         #[cfg(feature = "xml")]
-        ContentFormat::XML => XmlManager::new(path.to_string()).read_config(context),
+        ContentFormat::XML => XmlManager::new(path).read_config_with(context, options),
         #[cfg(feature = "toml")]
-        ContentFormat::TOML => TomlManager::new(path.to_string()).read_config(context),
+        ContentFormat::TOML => TomlManager::new(path).read_config_with(context, options),
+        #[cfg(feature = "ron")]
+        ContentFormat::RON => RonManager::new(path).read_config_with(context, options),
+        #[cfg(feature = "kdl")]
+        ContentFormat::KDL => KdlManager::new(path).read_config_with(context, options),
+        #[cfg(feature = "msgpack")]
+        ContentFormat::MSGPACK => MsgpackManager::new(path).read_config_with(context, options),
+        #[cfg(feature = "plist")]
+        ContentFormat::PLIST => PlistManager::new(path).read_config_with(context, options),
     }
 }
 
@@ -87,7 +417,8 @@ pub fn read_config<C: serde::de::DeserializeOwned>(
 ///
 /// # Parameters
 /// - `path`: The file path to write to. Tilde `~` expansion is supported.
-/// - `format`: Optional output format. Defaults to `JSON` if not provided.
+/// - `format`: Optional output format. When not provided, a file extension registered in
+///   [`FormatRegistry::global`](crate::FormatRegistry::global) selects its format, and `JSON` is used otherwise.
 /// - `data`: The data to serialize and write to the file.
 /// - `context`: Templating context for value substitution, if applicable.
 ///
@@ -107,19 +438,419 @@ pub fn write_config<C: serde::Serialize>(
     data: C,
     context: &std::collections::BTreeMap<String, serde_value::Value>,
 ) -> cdumay_core::Result<std::path::PathBuf> {
-    let path = shellexpand::tilde(path);
-    log::info!("Saving config file '{}'", path.as_ref());
+    write_config_with(path, format, data, context, &crate::WriteOptions::default())
+}
+
+/// Writes a configuration file like [`write_config`], applying the given [`WriteOptions`](crate::WriteOptions).
+///
+/// # Parameters
+/// - `path`: The file path to write to. Tilde `~` expansion is supported.
+/// - `format`: Optional output format. When not provided, a file extension registered in
+///   [`FormatRegistry::global`](crate::FormatRegistry::global) selects its format, and `JSON` is used otherwise.
+/// - `data`: The data to serialize and write to the file.
+/// - `context`: Templating context for value substitution, if applicable.
+/// - `options`: Options such as the advisory lock to take while writing.
+///
+/// # Returns
+/// The path to the written file if successful, or an error otherwise.
+pub fn write_config_with<C: serde::Serialize>(
+    path: &str,
+    format: Option<ContentFormat>,
+    data: C,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    if format.is_none() && crate::FormatRegistry::global().handles(path) {
+        return crate::FormatRegistry::global().write_config_with(path, None, data, context, options);
+    }
+    log::info!("Saving config file '{}'", path);
     match format.unwrap_or(ContentFormat::JSON) {
-        ContentFormat::JSON => JsonManager::new(path.to_string()).write_config(&data, context),
+        ContentFormat::JSON => JsonManager::new(path).write_config_with(&data, context, options),
+        ContentFormat::NDJSON => NdjsonManager::new(path).write_config_with(&data, context, options),
+        #[cfg(feature = "json5")]
+        ContentFormat::JSON5 => Json5Manager::new(path).write_config_with(&data, context, options),
         #[cfg(feature = "yaml")]
-        ContentFormat::YAML => YamlManager::new(path.to_string()).write_config(&data, context),
+        ContentFormat::YAML => YamlManager::new(path).write_config_with(&data, context, options),
         #[cfg(feature = "xml")]
-        ContentFormat::XML => XmlManager::new(path.to_string()).write_config(&data, context),
+        ContentFormat::XML => XmlManager::new(path).write_config_with(&data, context, options),
         #[cfg(feature = "toml")]
-        ContentFormat::TOML => TomlManager::new(path.to_string()).write_config(&data, context),
+        ContentFormat::TOML => TomlManager::new(path).write_config_with(&data, context, options),
+        #[cfg(feature = "ron")]
+        ContentFormat::RON => RonManager::new(path).write_config_with(&data, context, options),
+        #[cfg(feature = "kdl")]
+        ContentFormat::KDL => KdlManager::new(path).write_config_with(&data, context, options),
+        #[cfg(feature = "msgpack")]
+        ContentFormat::MSGPACK => MsgpackManager::new(path).write_config_with(&data, context, options),
+        #[cfg(feature = "plist")]
+        ContentFormat::PLIST => PlistManager::new(path).write_config_with(&data, context, options),
     }
 }
 
+/// Deserializes an intermediate value tree into a strongly typed configuration.
+pub(crate) fn from_value<C: serde::de::DeserializeOwned>(
+    value: serde_value::Value,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<C> {
+    deserialize_tracked(serde_value::ValueDeserializer::<serde_value::DeserializerError>::new(value)).map_err(|err| {
+        err.convert(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Invalid configuration content: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+                .into()
+        })
+    })
+}
+
+/// Deserializes the value tree of a configuration once its references are resolved, checking
+/// its schema version and unknown keys according to the read options.
+fn decode_tree<C: serde::de::DeserializeOwned>(
+    value: serde_value::Value,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<C> {
+    if let Some(schema_version) = &options.schema_version {
+        schema_version.check(&value, context).map_err(|err| crate::context::add_path(err, path))?;
+    }
+    if options.unknown_keys == crate::UnknownKeyPolicy::Ignore {
+        return from_value(value, context);
+    }
+    let tracked = crate::tracking::from_value_tracked(value, context).map_err(|err| crate::context::add_path(err, path))?;
+    match options.unknown_keys {
+        crate::UnknownKeyPolicy::Deny if !tracked.unused_keys.is_empty() => Err(crate::ConfigurationFileError::new()
+            .with_message(format!(
+                "Unknown configuration keys in '{}': {}",
+                path.display(),
+                tracked.unused_keys.join(", ")
+            ))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, path);
+                ctx.insert(
+                    "unknown_keys".to_string(),
+                    serde_value::Value::Seq(tracked.unused_keys.into_iter().map(serde_value::Value::String).collect()),
+                );
+                ctx
+            }))
+            .into()),
+        _ => Ok(tracked.report(&path.display().to_string())),
+    }
+}
+
+/// A deserialization error, along with the path of the key which failed to deserialize.
+pub(crate) struct KeyError<E> {
+    /// The error of the format.
+    error: E,
+    /// Path of the failing key (e.g. `server.listeners[2].port`), unset if the failure is
+    /// not below the root.
+    key: Option<String>,
+}
+
+impl<E> From<E> for KeyError<E> {
+    fn from(error: E) -> Self {
+        Self { error, key: None }
+    }
+}
+
+impl<E: std::fmt::Display> KeyError<E> {
+    /// Converts the error of the format, then adds the key path to the message and details,
+    /// along with a suggestion if a field or variant looks misspelled.
+    pub(crate) fn convert(self, convert: impl FnOnce(E) -> cdumay_core::Error) -> cdumay_core::Error {
+        let suggestion = crate::suggest::from_message(&self.error.to_string());
+        let err = match self.key {
+            Some(key) => crate::context::add_key_path(convert(self.error), &key),
+            None => convert(self.error),
+        };
+        match suggestion {
+            Some(suggestion) => crate::suggest::add_suggestion(err, suggestion),
+            None => err,
+        }
+    }
+}
+
+/// Deserializes through [`serde_path_to_error`], recording the path of the failing key.
+///
+/// The path stops before the first segment whose name is unknown, e.g. on syntax errors.
+pub(crate) fn deserialize_tracked<'de, D: serde::Deserializer<'de>, C: serde::Deserialize<'de>>(
+    deserializer: D,
+) -> Result<C, KeyError<D::Error>> {
+    serde_path_to_error::deserialize(deserializer).map_err(|err| {
+        let mut key = String::new();
+        for segment in err.path().iter() {
+            match segment {
+                serde_path_to_error::Segment::Seq { index } => key.push_str(&format!("[{}]", index)),
+                serde_path_to_error::Segment::Map { key: name } | serde_path_to_error::Segment::Enum { variant: name } => {
+                    if !key.is_empty() {
+                        key.push('.');
+                    }
+                    key.push_str(name);
+                }
+                serde_path_to_error::Segment::Unknown => break,
+            }
+        }
+        KeyError {
+            error: err.into_inner(),
+            key: Some(key).filter(|key| !key.is_empty()),
+        }
+    })
+}
+
+/// Replays the deserialization of a value tree to find the key which fails to deserialize,
+/// for formats whose deserializer cannot be wrapped.
+#[cfg(any(feature = "kdl", feature = "plist"))]
+pub(crate) fn locate_key<C: serde::de::DeserializeOwned, E>(value: Option<serde_value::Value>, error: E) -> KeyError<E> {
+    let key = value.and_then(|value| {
+        deserialize_tracked::<_, C>(serde_value::ValueDeserializer::<serde_value::DeserializerError>::new(value))
+            .err()
+            .and_then(|err| err.key)
+    });
+    KeyError { error, key }
+}
+
+/// Writes content to the file of a manager, `path` being its resolved path.
+///
+/// The file is replaced atomically: the content is written to a sibling temporary file
+/// named `<name>.<pid>.<sequence>.tmp`, flushed to disk, then renamed over the target, so
+/// that readers and crashes never observe a partially written file. Without a mode in the
+/// options, the permissions of the replaced file are kept. A symbolic link is followed and
+/// its target replaced. Temporary files orphaned by a crash are removed by
+/// [`cleanup_stale_files`](crate::cleanup_stale_files).
+///
+/// When a lock mode is set, an exclusive advisory lock is held while the file is written.
+pub(crate) fn write_file<M: Manager + ?Sized>(
+    manager: &M,
+    path: &std::path::Path,
+    content: &[u8],
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<()> {
+    let _lock = match options.lock {
+        Some(mode) => Some(crate::FileLock::exclusive(&path.to_string_lossy(), mode, context)?),
+        None => None,
+    };
+    let encrypted;
+    let content = match &options.cipher {
+        Some(cipher) => {
+            encrypted = crate::cipher::apply("encrypt", cipher.encrypt(content), path, context)?;
+            encrypted.as_slice()
+        }
+        None => content,
+    };
+    let target = match path.is_symlink() {
+        true => std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf()),
+        false => path.to_path_buf(),
+    };
+    let temporary = temporary_path(&target);
+    let replaced = replace_file(manager.file_path(), &target, &temporary, content, context, options);
+    if replaced.is_err() {
+        let _ = std::fs::remove_file(&temporary);
+    }
+    replaced
+}
+
+/// Returns the path of a new temporary file next to `path`, unique within the process.
+fn temporary_path(path: &std::path::Path) -> std::path::PathBuf {
+    static SEQUENCE: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(0);
+    let sequence = SEQUENCE.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.{}.tmp", std::process::id(), sequence));
+    path.with_file_name(name)
+}
+
+/// Tells whether a file name is the one of a temporary file created by [`write_file`].
+pub(crate) fn is_temporary_name(name: &str) -> bool {
+    let is_number = |part: &str| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit());
+    name.strip_suffix(".tmp")
+        .and_then(|rest| rest.rsplit_once('.'))
+        .filter(|(_, sequence)| is_number(sequence))
+        .and_then(|(rest, _)| rest.rsplit_once('.'))
+        .is_some_and(|(file, pid)| !file.is_empty() && is_number(pid))
+}
+
+/// Writes content to a temporary file, flushes it, then renames it over `target`.
+fn replace_file(
+    source: &std::path::Path,
+    target: &std::path::Path,
+    temporary: &std::path::Path,
+    content: &[u8],
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<()> {
+    let error = |message: &str, err: std::io::Error| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("{}: {}", message, err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_resolved_path(context, source, target);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    };
+    let mut file = open_writable(temporary, (source, target), context, options)?;
+    if options.mode.is_none()
+        && let Ok(metadata) = std::fs::metadata(target)
+    {
+        file.set_permissions(metadata.permissions())
+            .map_err(|err| error("Failed to set file permissions", err))?;
+    }
+    std::io::Write::write_all(&mut file, content).map_err(|err| error("Failed to write file", err))?;
+    file.sync_all().map_err(|err| error("Failed to write file", err))?;
+    drop(file);
+    std::fs::rename(temporary, target).map_err(|err| error("Failed to replace file", err))?;
+    #[cfg(unix)]
+    {
+        let dir = target.parent().filter(|dir| !dir.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+        if let Err(err) = std::fs::File::open(dir).and_then(|dir| dir.sync_all()) {
+            log::debug!("Failed to flush directory '{}': {}", dir.display(), err);
+        }
+    }
+    Ok(())
+}
+
+/// Creates (or truncates) a file for writing, applying the mode and the retry policy of the
+/// write options. Errors report the path of the manager and the resolved path it is written
+/// to, given as `reported`.
+fn open_writable(
+    path: &std::path::Path,
+    reported: (&std::path::Path, &std::path::Path),
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<std::fs::File> {
+    let error = |message: &str, err: std::io::Error| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("{}: {}", message, err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_resolved_path(context, reported.0, reported.1);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    };
+    let mut open_options = std::fs::OpenOptions::new();
+    open_options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    if let Some(mode) = options.mode {
+        std::os::unix::fs::OpenOptionsExt::mode(&mut open_options, mode);
+    }
+    let file = options
+        .retry
+        .run(|| open_options.open(path))
+        .map_err(|err| error("Failed to create file", err))?;
+    if let Some(mode) = options.mode {
+        #[cfg(unix)]
+        let permissions = std::os::unix::fs::PermissionsExt::from_mode(mode);
+        #[cfg(not(unix))]
+        let permissions = {
+            let mut permissions = file.metadata().map_err(|err| error("Failed to read file permissions", err))?.permissions();
+            permissions.set_readonly(mode & 0o200 == 0);
+            permissions
+        };
+        file.set_permissions(permissions).map_err(|err| error("Failed to set file permissions", err))?;
+    }
+    Ok(file)
+}
+
+/// Checks that raw content is valid UTF-8, for text formats parsing string slices.
+pub(crate) fn utf8<'a>(content: &'a [u8], context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<&'a str> {
+    Ok(std::str::from_utf8(content).map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Invalid UTF-8 content: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    })?)
+}
+
+/// Returns the kind of the root of a value tree which is not a map: `sequence` or `scalar`.
+#[cfg(any(feature = "kdl", feature = "toml", feature = "xml"))]
+pub(crate) fn root_kind(value: &serde_value::Value) -> Option<&'static str> {
+    match value {
+        serde_value::Value::Map(_) => None,
+        serde_value::Value::Seq(_) => Some("sequence"),
+        serde_value::Value::Option(Some(value)) | serde_value::Value::Newtype(value) => root_kind(value),
+        _ => Some("scalar"),
+    }
+}
+
+/// Returns the error raised when a format requires a map at the root of documents and the
+/// data is not one.
+///
+/// # Parameters
+/// - `format`: Name of the format, for the error message.
+/// - `data`: The data being written.
+/// - `path`: Path of the written file.
+/// - `context`: Context used for error reporting.
+///
+/// # Returns
+/// The error, or `None` if the root of the data is a map (or cannot be serialized).
+#[cfg(any(feature = "toml", feature = "xml"))]
+pub(crate) fn unsupported_root<D: serde::Serialize + ?Sized>(
+    format: &str,
+    data: &D,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> Option<cdumay_core::Error> {
+    let kind = root_kind(&serde_value::to_value(data).ok()?)?;
+    Some(root_error(format, kind, path, context))
+}
+
+/// Builds the error raised when a format cannot hold a root-level value of the given kind.
+#[cfg(any(feature = "kdl", feature = "toml", feature = "xml"))]
+pub(crate) fn root_error(
+    format: &str,
+    kind: &str,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Error {
+    crate::ConfigurationFileError::new()
+        .with_message(format!(
+            "{} documents cannot hold a root-level {}, wrap it in a struct or a map",
+            format, kind
+        ))
+        .with_details(crate::context::bounded({
+            let mut ctx = crate::context::with_path(context, path);
+            ctx.insert("root".to_string(), serde_value::Value::String(kind.to_string()));
+            ctx
+        }))
+        .into()
+}
+
+/// Renders text as a block of line comments followed by a blank line.
+#[cfg(any(feature = "kdl", feature = "ron", feature = "toml", feature = "yaml"))]
+pub(crate) fn line_comments(text: &str, marker: &str) -> String {
+    let mut comment = String::new();
+    for line in text.lines() {
+        match line.is_empty() {
+            true => comment.push_str(marker),
+            false => comment.push_str(&format!("{} {}", marker, line)),
+        }
+        comment.push('\n');
+    }
+    comment.push('\n');
+    comment
+}
+
+/// Converts data into a value tree, whose maps are sorted by key. Errors report the `path`
+/// of the written file.
+pub(crate) fn sorted_value<D: serde::Serialize>(
+    data: D,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<serde_value::Value> {
+    Ok(serde_value::to_value(data).map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to serialize configuration: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, path);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    })?)
+}
+
 /// A trait defining common operations for configuration file managers.
 ///
 /// This abstraction allows handling different formats (e.g. JSON, YAML, etc.)
@@ -128,14 +859,22 @@ pub trait Manager {
     /// Constructs a new instance of the manager with the given file path.
     ///
     /// # Parameters
-    /// - `path`: Path to the configuration file.
+    /// - `path`: Path to the configuration file, as a `&str`, `String`, `&Path` or `PathBuf`.
     ///
     /// # Returns
     /// A new instance of the implementing manager.
-    fn new(path: String) -> Self;
-    
+    fn new<P: AsRef<std::path::Path>>(path: P) -> Self;
+
     /// Returns the file path associated with the manager.
-    fn path(&self) -> String;
+    fn file_path(&self) -> &std::path::Path;
+
+    /// Returns the file path associated with the manager as a string.
+    ///
+    /// Non UTF-8 paths are converted lossily.
+    #[deprecated(note = "use `Manager::file_path`, which does not allocate and preserves non UTF-8 paths")]
+    fn path(&self) -> String {
+        self.file_path().to_string_lossy().into_owned()
+    }
     
     /// Opens the configuration file for reading.
     ///
@@ -145,18 +884,37 @@ pub trait Manager {
     /// # Returns
     /// A readable `File` handle or an error if the file cannot be opened.
     fn open_file(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<std::fs::File> {
-        Ok(std::fs::File::open(self.path()).map_err(|err| {
+        self.open_file_with(context, &crate::ReadOptions::default())
+    }
+
+    /// Opens the configuration file for reading, applying the given read options.
+    ///
+    /// The path is first mapped by the options' [`PathResolver`](crate::PathResolver). Opening is
+    /// retried according to the options' retry policy while the file is busy.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the operation fails.
+    /// - `options`: The read options to apply.
+    ///
+    /// # Returns
+    /// A readable `File` handle or an error if the file cannot be opened.
+    fn open_file_with(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<std::fs::File> {
+        let path = crate::resolver::resolve(options.resolver.as_ref(), self.file_path(), context)?;
+        Ok(options.retry.run(|| std::fs::File::open(&path)).map_err(|err| {
             crate::ConfigurationFileError::new()
                 .with_message(format!("Failed to open file: {}", err))
-                .with_details({
-                    let mut ctx = context.clone();
-                    ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_resolved_path(context, self.file_path(), &path);
                     ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
                     ctx
-                })
+                }))
         })?)
     }
-    
+
     /// Creates (or overwrites) the configuration file for writing.
     ///
     /// # Parameters
@@ -165,18 +923,32 @@ pub trait Manager {
     /// # Returns
     /// A writable `File` handle or an error if the file cannot be created.
     fn create_file(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<std::fs::File> {
-        Ok(std::fs::File::create(self.path()).map_err(|err| {
-            crate::ConfigurationFileError::new()
-                .with_message(format!("Failed to create file: {}", err))
-                .with_details({
-                    let mut ctx = context.clone();
-                    ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
-                    ctx
-                })
-        })?)
+        self.create_file_with(context, &crate::WriteOptions::default())
     }
-    
+
+    /// Creates (or overwrites) the configuration file for writing, applying the given write options.
+    ///
+    /// The path is first mapped by the options' [`PathResolver`](crate::PathResolver). When a
+    /// mode is set, the permissions are applied to the file whether it is created or already
+    /// exists. Creation is retried according to the options' retry policy while the file is busy.
+    /// The file is truncated in place: [`Manager::write_config_with`] replaces it atomically
+    /// instead.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the operation fails.
+    /// - `options`: The write options to apply.
+    ///
+    /// # Returns
+    /// A writable `File` handle or an error if the file cannot be created.
+    fn create_file_with(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::WriteOptions,
+    ) -> cdumay_core::Result<std::fs::File> {
+        let path = crate::resolver::resolve(options.resolver.as_ref(), self.file_path(), context)?;
+        open_writable(&path, (self.file_path(), &path), context, options)
+    }
+
     /// Reads and deserializes configuration data from a readable input stream.
     ///
     /// # Type Parameters
@@ -214,10 +986,41 @@ pub trait Manager {
         data: D,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<()>;
-    
+
+    /// Serializes and writes configuration data to a writable output stream, with the given layout.
+    ///
+    /// The default implementation honors [`WriteStyle::sort_keys`](crate::WriteStyle::sort_keys)
+    /// and otherwise delegates to [`Manager::write`]; formats supporting layout settings
+    /// override it.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write` for output.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable output stream.
+    /// - `data`: The data to serialize.
+    /// - `style`: The layout of the serialized content.
+    /// - `context`: A context used for template substitution or error reporting.
+    ///
+    /// # Returns
+    /// An empty result on success, or an error if writing fails.
+    fn write_styled<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        style: &crate::WriteStyle,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        match style.sort_keys {
+            true => self.write(writer, sorted_value(data, self.file_path(), context)?, context),
+            false => self.write(writer, data, context),
+        }
+    }
+
     /// Reads configuration directly from the file path managed by this instance.
     ///
-    /// Internally calls `open_file` and then `read`.
+    /// Internally calls `read_config_with` with the default read options.
     ///
     /// # Type Parameters
     /// - `C`: The target deserialization type.
@@ -231,12 +1034,138 @@ pub trait Manager {
         &self,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        self.read(self.open_file(context)?, context)
+        self.read_config_with(context, &crate::ReadOptions::default())
+    }
+
+    /// Reads configuration from the managed file path, applying the given read options.
+    ///
+    /// When a lock mode is set, a shared advisory lock is held while the file is read.
+    /// Empty or whitespace-only files are handled according to [`ReadOptions::empty_file`](crate::ReadOptions::empty_file),
+    /// the same way for every format. Keys which `C` does not consume are dropped, reported or
+    /// refused according to [`ReadOptions::unknown_keys`](crate::ReadOptions::unknown_keys).
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type.
+    ///
+    /// # Parameters
+    /// - `context`: A context for error handling and templating.
+    /// - `options`: The read options to apply.
+    ///
+    /// # Returns
+    /// The deserialized configuration object.
+    fn read_config_with<C: serde::de::DeserializeOwned>(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        let _lock = match options.lock {
+            Some(mode) => {
+                let path = crate::resolver::resolve(options.resolver.as_ref(), self.file_path(), context)?;
+                Some(crate::FileLock::shared(&path.to_string_lossy(), mode, context)?)
+            }
+            None => None,
+        };
+        let mut content = Vec::new();
+        std::io::Read::read_to_end(&mut self.open_file_with(context, options)?, &mut content).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read file: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        self.read_content_with(content, context, options)
+    }
+
+    /// Deserializes content obtained for the managed path, applying the given read options.
+    ///
+    /// This is the part of [`Manager::read_config_with`] run once the content is read, meant
+    /// for content which does not come from the file system (e.g. a remote source). The
+    /// options' lock, retry policy and resolver are not used.
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type.
+    ///
+    /// # Parameters
+    /// - `content`: The raw content of the configuration.
+    /// - `context`: A context for error handling and templating.
+    /// - `options`: The read options to apply.
+    ///
+    /// # Returns
+    /// The deserialized configuration object.
+    fn read_content_with<C: serde::de::DeserializeOwned>(
+        &self,
+        content: Vec<u8>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        let content = match &options.cipher {
+            Some(cipher) => crate::cipher::apply("decrypt", cipher.decrypt(&content), self.file_path(), context)?,
+            None => content,
+        };
+        let empty = match Self::is_empty_content(&content) {
+            false => None,
+            true => match options.empty_file {
+                crate::EmptyFilePolicy::Fail => {
+                    return Err(crate::EmptyFileError::new()
+                        .with_message(format!("Configuration file '{}' is empty", self.file_path().display()))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = context.clone();
+                            ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
+                            ctx
+                        }))
+                        .into());
+                }
+                crate::EmptyFilePolicy::EmptyMap => {
+                    log::debug!("Configuration file '{}' is empty, reading it as an empty map", self.file_path().display());
+                    Some(serde_value::Value::Map(std::collections::BTreeMap::new()))
+                }
+            },
+        };
+        if options.schema_version.is_none()
+            && options.renames.is_none()
+            && options.secrets.is_none()
+            && !options.includes
+            && options.unknown_keys == crate::UnknownKeyPolicy::Ignore
+        {
+            return match empty {
+                None => self.read(content.as_slice(), context),
+                Some(value) => from_value(value, context),
+            };
+        }
+        let mut value = match empty {
+            Some(value) => value,
+            None => self.read(content.as_slice(), context)?,
+        };
+        if options.includes {
+            crate::include::resolve(&mut value, self.file_path(), context, options)?;
+        }
+        if let Some(renames) = &options.renames {
+            let applied = renames
+                .apply(&mut value, context)
+                .map_err(|err| crate::context::add_path(err, self.file_path()))?;
+            if !applied.is_empty() {
+                log::warn!(
+                    "Configuration file '{}' uses renamed keys: {}",
+                    self.file_path().display(),
+                    applied.iter().map(|(old, new)| format!("'{}' is now '{}'", old, new)).collect::<Vec<_>>().join(", ")
+                );
+            }
+        }
+        let substitutions = match &options.secrets {
+            Some(secrets) => secrets
+                .substitute_references(&mut value, context)
+                .map_err(|err| crate::context::add_path(err, self.file_path()))?,
+            None => crate::vault::Substitutions::default(),
+        };
+        decode_tree(value, self.file_path(), context, options).map_err(|err| substitutions.mask(err))
     }
     
     /// Writes configuration data directly to the file path managed by this instance.
     ///
-    /// Internally calls `create_file` and then `write`.
+    /// Internally calls `write_config_with` with the default write options.
     ///
     /// # Type Parameters
     /// - `C`: The type of the configuration data to serialize.
@@ -252,13 +1181,52 @@ pub trait Manager {
         data: &C,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<std::path::PathBuf> {
-        let _ = self.write(self.create_file(context)?, data, context)?;
-        Ok(std::path::PathBuf::from(self.path()))
+        self.write_config_with(data, context, &crate::WriteOptions::default())
+    }
+
+    /// Writes configuration data to the managed file path, applying the given write options.
+    ///
+    /// When a lock mode is set, an exclusive advisory lock is held while the file is written.
+    /// Post-write hooks then run once the lock is released.
+    /// The content is first serialized in memory through [`Manager::write_styled`] with the
+    /// options' style, after the header banner if any; the file is then replaced atomically
+    /// through a temporary file renamed over it. A serialization failure (e.g. data a format
+    /// cannot represent) or a crash while writing thus leaves an existing file untouched.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type of the configuration data to serialize.
+    ///
+    /// # Parameters
+    /// - `data`: A reference to the configuration data.
+    /// - `context`: A context used for error details and templating.
+    /// - `options`: The write options to apply.
+    ///
+    /// # Returns
+    /// The path to the file where the configuration was written, as mapped by the options' resolver.
+    fn write_config_with<C: serde::Serialize>(
+        &self,
+        data: &C,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::WriteOptions,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        let path = crate::resolver::resolve(options.resolver.as_ref(), self.file_path(), context)?;
+        let mut content = Vec::new();
+        if let Some(banner) = &options.header {
+            match Self::comment(&banner.render(&crate::config_hash(data, context)?, &path)) {
+                Some(comment) => content.extend_from_slice(comment.as_bytes()),
+                None => log::debug!("Format of '{}' has no comments, skipping the header banner", path.display()),
+            }
+        }
+        self.write_styled(&mut content, data, &options.style, context)?;
+        write_file(self, &path, &content, context, options)?;
+        crate::hooks::run_hooks(&options.hooks, options.hook_failure, &path)?;
+        Ok(path)
     }
     
     /// Reads configuration data from a raw string and deserializes it.
     ///
-    /// This method is static and typically used to parse embedded or in-memory content.
+    /// This method is static and typically used to parse embedded or in-memory content. The
+    /// default implementation delegates to [`Manager::read_bytes`].
     ///
     /// # Type Parameters
     /// - `C`: The type into which the string will be deserialized.
@@ -272,5 +1240,102 @@ pub trait Manager {
     fn read_str<C: serde::de::DeserializeOwned>(
         content: &str,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        Self::read_bytes(content.as_bytes(), context)
+    }
+
+    /// Reads configuration data from raw bytes and deserializes it.
+    ///
+    /// This is the parsing primitive of a format: binary formats decode the bytes as they
+    /// are, text formats check that they are valid UTF-8 first.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the bytes will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The bytes containing the serialized configuration.
+    /// - `context`: A context for templating and error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C>;
+
+    /// Renders text as a comment block of the format, placed at the top of written files.
+    ///
+    /// The default implementation returns `None`, for formats without comments; the
+    /// [`WriteOptions::header`](crate::WriteOptions::header) banner is then skipped.
+    ///
+    /// # Parameters
+    /// - `text`: The comment text, possibly spanning several lines.
+    ///
+    /// # Returns
+    /// The comment block, ending with a blank line, or `None` if the format has no comments.
+    fn comment(text: &str) -> Option<String> {
+        let _ = text;
+        None
+    }
+
+    /// Tells whether raw file content must be considered empty.
+    ///
+    /// Text formats consider whitespace-only content as empty; binary formats should
+    /// override this method to only consider zero-byte content as empty.
+    fn is_empty_content(content: &[u8]) -> bool {
+        content.trim_ascii().is_empty()
+    }
+
+    /// Serializes configuration data into raw bytes.
+    ///
+    /// The default implementation calls [`Manager::write`] on an in-memory buffer.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    ///
+    /// # Parameters
+    /// - `data`: The data to serialize.
+    /// - `context`: A context used for template substitution or error reporting.
+    ///
+    /// # Returns
+    /// The serialized bytes, or an error if serialization fails.
+    fn write_bytes<D: serde::Serialize>(
+        &self,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<u8>> {
+        let mut buffer = Vec::new();
+        self.write(&mut buffer, data, context)?;
+        Ok(buffer)
+    }
+
+    /// Serializes configuration data into a string.
+    ///
+    /// The default implementation calls [`Manager::write_bytes`] and decodes the result as
+    /// UTF-8, so it fails for binary formats which do not produce text.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    ///
+    /// # Parameters
+    /// - `data`: The data to serialize.
+    /// - `context`: A context used for template substitution or error reporting.
+    ///
+    /// # Returns
+    /// The serialized content, or an error if serialization fails.
+    fn write_str<D: serde::Serialize>(
+        &self,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<String> {
+        Ok(String::from_utf8(self.write_bytes(data, context)?).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Serialized content is not valid UTF-8: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, self.file_path());
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?)
+    }
 }
@@ -0,0 +1,116 @@
+/// MessagePack configuration file manager implementing the `Manager` trait.
+///
+/// This struct handles reading and writing binary MessagePack configuration files
+/// using the `rmp-serde` crate. Structures are written with named fields so that
+/// they can be read back as maps.
+pub struct MsgpackManager {
+    /// Path to the MessagePack configuration file.
+    path: std::path::PathBuf,
+}
+
+impl MsgpackManager {
+    /// Builds the error returned when MessagePack content cannot be decoded.
+    fn decode_error(err: rmp_serde::decode::Error, context: &std::collections::BTreeMap<String, serde_value::Value>) -> crate::ConfigurationFileError {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Invalid MessagePack content: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    }
+}
+
+impl crate::Manager for MsgpackManager {
+    /// Creates a new `MsgpackManager` with the specified file path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the MessagePack configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `MsgpackManager`.
+    fn new<P: AsRef<std::path::Path>>(path: P) -> MsgpackManager {
+        MsgpackManager { path: path.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path to the MessagePack configuration file.
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Reads MessagePack content from a `Read` stream and deserializes it into the target type.
+    ///
+    /// # Type Parameters
+    /// - `R`: A type implementing `Read`.
+    /// - `C`: The type into which the data will be deserialized.
+    ///
+    /// # Parameters
+    /// - `reader`: A readable stream containing MessagePack data.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read<R: std::io::Read, C: serde::de::DeserializeOwned>(
+        &self,
+        reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        crate::formats::deserialize_tracked(&mut rmp_serde::Deserializer::new(reader))
+            .map_err(|err| err.convert(|err| Self::decode_error(err, &crate::context::with_path(context, &self.path)).into()))
+    }
+
+    /// Serializes data as MessagePack and writes it to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        mut writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        Ok(rmp_serde::encode::write_named(&mut writer, &data).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to write MessagePack file: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?)
+    }
+
+    /// Deserializes MessagePack content from raw bytes.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the content will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The MessagePack payload.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized object or an error if the content is invalid.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        crate::formats::deserialize_tracked(&mut rmp_serde::Deserializer::from_read_ref(content))
+            .map_err(|err| err.convert(|err| Self::decode_error(err, context).into()))
+    }
+
+    /// Only zero-byte content is empty: whitespace bytes are valid MessagePack integers.
+    fn is_empty_content(content: &[u8]) -> bool {
+        content.is_empty()
+    }
+}
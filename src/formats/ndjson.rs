@@ -0,0 +1,134 @@
+use cdumay_core::ErrorConverter;
+/// JSON Lines (ND-JSON) configuration file manager implementing the `Manager` trait.
+///
+/// This struct handles list configurations stored as one JSON document per line: each
+/// line is deserialized into an element of a sequence (e.g. a `Vec<T>`), and each element
+/// of a sequence is written as a compact JSON document on its own line. Blank lines are
+/// ignored.
+pub struct NdjsonManager {
+    /// Path to the ND-JSON configuration file.
+    path: std::path::PathBuf,
+}
+
+impl crate::Manager for NdjsonManager {
+    /// Creates a new `NdjsonManager` with the specified file path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the ND-JSON configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `NdjsonManager`.
+    fn new<P: AsRef<std::path::Path>>(path: P) -> NdjsonManager {
+        NdjsonManager { path: path.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path to the ND-JSON configuration file.
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Reads ND-JSON content from a `Read` stream, deserializing its lines into a sequence.
+    ///
+    /// # Type Parameters
+    /// - `R`: A type implementing `Read`.
+    /// - `C`: The sequence type into which the lines will be deserialized.
+    ///
+    /// # Parameters
+    /// - `reader`: A readable stream containing ND-JSON data.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read<R: std::io::Read, C: serde::de::DeserializeOwned>(
+        &self,
+        mut reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut buffer = String::new();
+        reader.read_to_string(&mut buffer).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read ND-JSON file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?;
+        Self::read_str(&buffer, context).map_err(|err| crate::context::add_path(err, &self.path))
+    }
+
+    /// Serializes a sequence and writes each of its elements as a JSON line to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The sequence type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize, which must serialize as a sequence.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        mut writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let records = match cdumay_json::convert_json_result!(
+            serde_json::to_value(&data),
+            crate::context::bounded(crate::context::with_path(context, &self.path))
+        )? {
+            serde_json::Value::Array(records) => records,
+            _ => {
+                return Err(crate::ConfigurationFileError::new()
+                    .with_message("ND-JSON content must be a sequence".to_string())
+                    .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+                    .into());
+            }
+        };
+        for record in records {
+            cdumay_json::convert_json_result!(
+                serde_json::to_writer(&mut writer, &record),
+                crate::context::bounded(crate::context::with_path(context, &self.path))
+            )?;
+            writer.write_all(b"\n").map_err(|err| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Failed to write ND-JSON file: {}", err))
+                    .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+            })?;
+        }
+        Ok(())
+    }
+
+    /// Deserializes ND-JSON content from raw bytes, which must be valid UTF-8.
+    ///
+    /// # Type Parameters
+    /// - `C`: The sequence type into which the lines will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The ND-JSON content to parse.
+    /// - `context`: Context used for error reporting. Errors on a given record also
+    ///   report its 1-based `line` number.
+    ///
+    /// # Returns
+    /// The deserialized object or an error if the content is invalid.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let content = crate::formats::utf8(content, context)?;
+        let mut records = Vec::new();
+        for (index, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            records.push(serde_json::from_str::<serde_json::Value>(line).map_err(|err| {
+                let mut ctx = context.clone();
+                ctx.insert("line".to_string(), serde_value::Value::U64(index as u64 + 1));
+                ctx.insert("column".to_string(), serde_value::Value::U64(err.column() as u64));
+                cdumay_json::JsonErrorConverter::convert_error(&err, None, crate::context::bounded(ctx))
+            })?);
+        }
+        crate::formats::deserialize_tracked(serde_json::Value::Array(records)).map_err(|err| {
+            err.convert(|err| cdumay_json::JsonErrorConverter::convert_error(&err, None, crate::context::bounded(context.clone())))
+        })
+    }
+}
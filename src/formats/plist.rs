@@ -0,0 +1,142 @@
+/// Apple property list configuration file manager implementing the `Manager` trait.
+///
+/// This struct handles reading and writing plist configuration files using the `plist`
+/// crate. Both XML and binary property lists are read, the encoding being detected from
+/// the content. Files are written as XML unless the manager is built with
+/// [`PlistManager::binary`].
+pub struct PlistManager {
+    /// Path to the plist configuration file.
+    path: std::path::PathBuf,
+    /// Writes binary property lists instead of XML ones.
+    binary: bool,
+}
+
+impl PlistManager {
+    /// Creates a new `PlistManager` writing binary property lists.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the plist configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `PlistManager`.
+    pub fn binary<P: AsRef<std::path::Path>>(path: P) -> PlistManager {
+        PlistManager {
+            path: path.as_ref().to_path_buf(),
+            binary: true,
+        }
+    }
+
+    /// Builds the error returned when plist content cannot be decoded.
+    fn decode_error(err: plist::Error, context: &std::collections::BTreeMap<String, serde_value::Value>) -> crate::ConfigurationFileError {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Invalid plist content: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    }
+}
+
+impl crate::Manager for PlistManager {
+    /// Creates a new `PlistManager` writing XML property lists.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the plist configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `PlistManager`.
+    fn new<P: AsRef<std::path::Path>>(path: P) -> PlistManager {
+        PlistManager { path: path.as_ref().to_path_buf(), binary: false }
+    }
+
+    /// Returns the path to the plist configuration file.
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Reads XML or binary plist content from a `Read` stream and deserializes it into the target type.
+    ///
+    /// The entire stream is first read into memory, as detecting the encoding and decoding
+    /// binary property lists require random access.
+    ///
+    /// # Type Parameters
+    /// - `R`: A type implementing `Read`.
+    /// - `C`: The type into which the data will be deserialized.
+    ///
+    /// # Parameters
+    /// - `reader`: A readable stream containing plist data.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read<R: std::io::Read, C: serde::de::DeserializeOwned>(
+        &self,
+        mut reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut buffer = Vec::new();
+        reader.read_to_end(&mut buffer).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read plist file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?;
+        Self::read_bytes(&buffer, context).map_err(|err| crate::context::add_path(err, &self.path))
+    }
+
+    /// Serializes data as an XML or binary plist and writes it to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let result = match self.binary {
+            true => plist::to_writer_binary(writer, &data),
+            false => plist::to_writer_xml(writer, &data),
+        };
+        Ok(result.map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to write plist file: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?)
+    }
+
+    /// Deserializes XML or binary plist content from raw bytes.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the content will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The plist payload.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized object or an error if the content is invalid.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        plist::from_bytes(content).map_err(|err| {
+            crate::formats::locate_key::<C, _>(plist::from_bytes(content).ok(), err)
+                .convert(|err| Self::decode_error(err, context).into())
+        })
+    }
+}
@@ -0,0 +1,169 @@
+/// RON configuration file manager implementing the `Manager` trait.
+///
+/// This struct handles reading from and writing to RON (Rusty Object Notation)
+/// configuration files, using the `ron` crate for serialization and deserialization.
+pub struct RonManager {
+    /// Path to the RON configuration file.
+    path: std::path::PathBuf,
+}
+
+impl crate::Manager for RonManager {
+    /// Creates a new `RonManager` with the specified file path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the RON configuration file.
+    ///
+    /// # Returns
+    /// A new instance of `RonManager`.
+    fn new<P: AsRef<std::path::Path>>(path: P) -> RonManager {
+        RonManager { path: path.as_ref().to_path_buf() }
+    }
+
+    /// Returns the path to the RON configuration file.
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Reads RON content from a `Read` stream, deserializing it into the specified type.
+    ///
+    /// The entire stream is first read into a `String`, then parsed as RON.
+    ///
+    /// # Type Parameters
+    /// - `R`: A type implementing `Read`.
+    /// - `C`: The type into which the data will be deserialized.
+    ///
+    /// # Parameters
+    /// - `reader`: A readable stream containing RON data.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    fn read<R: std::io::Read, C: serde::de::DeserializeOwned>(
+        &self,
+        mut reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut buffer = String::new();
+        reader.read_to_string(&mut buffer).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read RON file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?;
+        Self::read_str(&buffer, context).map_err(|err| crate::context::add_path(err, &self.path))
+    }
+
+    /// Serializes and writes data as pretty-printed RON to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        self.write_styled(writer, data, &crate::WriteStyle::default(), context)
+    }
+
+    /// Serializes and writes data as pretty-printed or compact RON to a `Write` stream.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `style`: The layout of the output. Pretty RON is indented with four spaces by default.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write_styled<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        mut writer: W,
+        data: D,
+        style: &crate::WriteStyle,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let result = match style.sort_keys {
+            true => to_string(&crate::formats::sorted_value(data, &self.path, context)?, style),
+            false => to_string(&data, style),
+        };
+        let content = result.map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to serialize RON content: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, &self.path);
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        Ok(writer.write_all(content.as_bytes()).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to write RON file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
+        })?)
+    }
+
+    /// Deserializes RON content from raw bytes, which must be valid UTF-8.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the content will be deserialized.
+    ///
+    /// # Parameters
+    /// - `content`: The RON content to parse.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized object or an error if the content is invalid.
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let content = crate::formats::utf8(content, context)?;
+        let error = |err: ron::error::SpannedError| -> cdumay_core::Error {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Invalid RON content: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+                .into()
+        };
+        let mut deserializer = ron::de::Deserializer::from_str(content).map_err(error)?;
+        let config = crate::formats::deserialize_tracked(&mut deserializer)
+            .map_err(|err| err.convert(|err| error(deserializer.span_error(err))))?;
+        deserializer.end().map_err(|err| error(deserializer.span_error(err)))?;
+        Ok(config)
+    }
+
+    /// Renders text as `//` line comments.
+    fn comment(text: &str) -> Option<String> {
+        Some(crate::formats::line_comments(text, "//"))
+    }
+}
+
+/// Serializes data as RON with the given layout.
+fn to_string<D: serde::Serialize>(data: &D, style: &crate::WriteStyle) -> ron::Result<String> {
+    match style.pretty {
+        true => {
+            let mut config = ron::ser::PrettyConfig::default();
+            if let Some(indent) = style.indent {
+                config = config.indentor(" ".repeat(indent));
+            }
+            ron::ser::to_string_pretty(data, config)
+        }
+        false => ron::ser::to_string(data),
+    }
+}
@@ -5,7 +5,7 @@ use cdumay_core::ErrorConverter;
 /// using the `toml` crate for serialization and deserialization.
 pub struct TomlManager {
     /// Path to the TOML configuration file.
-    path: String,
+    path: std::path::PathBuf,
 }
 
 impl crate::Manager for TomlManager {
@@ -16,12 +16,12 @@ impl crate::Manager for TomlManager {
     ///
     /// # Returns
     /// A new instance of `TomlManager`.
-    fn new(path: String) -> TomlManager {
-        TomlManager { path }
+    fn new<P: AsRef<std::path::Path>>(path: P) -> TomlManager {
+        TomlManager { path: path.as_ref().to_path_buf() }
     }
     /// Returns the path to the TOML configuration file.
-    fn path(&self) -> String {
-        self.path.clone()
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
     }
 
     /// Reads TOML content from a `Read` stream, deserializing it into the specified type.
@@ -47,11 +47,11 @@ impl crate::Manager for TomlManager {
         reader.read_to_string(&mut buffer).map_err(|err| {
             crate::ConfigurationFileError::new()
                 .with_message(format!("Failed to write TOML file: {}", err))
-                .with_details({
+                .with_details(crate::context::bounded({
                     let mut ctx = context.clone();
-                    ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
+                    ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
                     ctx
-                })
+                }))
         })?;
         Self::read_str(&buffer, context)
     }
@@ -70,36 +70,94 @@ impl crate::Manager for TomlManager {
     /// # Returns
     /// Empty result on success, or an error on failure.
     fn write<D: serde::Serialize, W: std::io::Write>(
+        &self,
+        writer: W,
+        data: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        self.write_styled(writer, data, &crate::WriteStyle::default(), context)
+    }
+
+    /// Serializes and writes data as pretty-printed or compact TOML to a `Write` stream.
+    ///
+    /// TOML documents are tables: data whose root is a sequence or a scalar is rejected.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    /// - `W`: A type implementing `Write`.
+    ///
+    /// # Parameters
+    /// - `writer`: A writable stream for output.
+    /// - `data`: The data to serialize.
+    /// - `style`: The layout of the output. The indentation is not configurable.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error on failure.
+    fn write_styled<D: serde::Serialize, W: std::io::Write>(
         &self,
         mut writer: W,
         data: D,
+        style: &crate::WriteStyle,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<()> {
-        let mut ctx = context.clone();
-        ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-        let content = cdumay_toml::convert_serialize_result!(toml::to_string_pretty(&data), ctx.clone())?;
+        let result = match style.sort_keys {
+            true => to_string(&crate::formats::sorted_value(&data, &self.path, context)?, style),
+            false => to_string(&data, style),
+        };
+        if result.is_err()
+            && let Some(err) = crate::formats::unsupported_root("TOML", &data, &self.path, context)
+        {
+            return Err(err);
+        }
+        let content = cdumay_toml::convert_serialize_result!(result, crate::context::bounded(crate::context::with_path(context, &self.path)))?;
         Ok(writer.write_all(content.as_bytes()).map_err(|err| {
             crate::ConfigurationFileError::new()
                 .with_message(format!("Failed to write TOML file: {}", err))
-                .with_details(ctx)
+                .with_details(crate::context::bounded(crate::context::with_path(context, &self.path)))
         })?)
     }
 
-    /// Deserializes TOML content from a string slice.
+    /// Deserializes TOML content from raw bytes, which must be valid UTF-8.
     ///
     /// # Type Parameters
     /// - `C`: The type into which the content will be deserialized.
     ///
     /// # Parameters
-    /// - `content`: The TOML string to parse.
+    /// - `content`: The TOML content to parse.
     /// - `context`: Context used for error reporting.
     ///
     /// # Returns
     /// The deserialized object or an error if the content is invalid.
-    fn read_str<C: serde::de::DeserializeOwned>(
-        content: &str,
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        cdumay_toml::convert_deserialize_result!(toml::from_str(content), context.clone())
+        let content = crate::formats::utf8(content, context)?;
+        crate::formats::deserialize_tracked(toml::Deserializer::new(content)).map_err(|err| {
+            err.convert(|err| {
+                let ctx = match err.span() {
+                    Some(span) => {
+                        let (line, column) = crate::context::location(content, span.start);
+                        crate::context::with_location(context, line, column)
+                    }
+                    None => context.clone(),
+                };
+                cdumay_toml::TomlDeserializeErrorConverter::convert_error(&err, None, crate::context::bounded(ctx))
+            })
+        })
+    }
+
+    /// Renders text as `#` line comments.
+    fn comment(text: &str) -> Option<String> {
+        Some(crate::formats::line_comments(text, "#"))
+    }
+}
+
+/// Serializes data as TOML with the given layout.
+fn to_string<D: serde::Serialize>(data: &D, style: &crate::WriteStyle) -> Result<String, toml::ser::Error> {
+    match style.pretty {
+        true => toml::to_string_pretty(data),
+        false => toml::to_string(data),
     }
 }
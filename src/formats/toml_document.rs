@@ -0,0 +1,320 @@
+/// A TOML configuration file edited in place.
+///
+/// Unlike [`TomlManager`](crate::TomlManager), which serializes the whole configuration from
+/// scratch, a `TomlDocument` keeps the comments, key order and whitespace of the loaded
+/// content: saving it only changes the values which were edited.
+///
+/// Values are addressed by dotted keys (e.g. `server.port`), each segment being a bare key.
+///
+/// # Example
+/// ```rust
+/// fn set_port(port: u16) -> cdumay_core::Result<()> {
+///     let context = std::collections::BTreeMap::new();
+///     let mut document = cdumay_config::TomlDocument::open("server.toml", &context)?;
+///     document.set("server.port", port, &context)?;
+///     document.save(&context)?;
+///     Ok(())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct TomlDocument {
+    /// Path to the TOML configuration file.
+    path: std::path::PathBuf,
+    /// The parsed document, along with its formatting.
+    document: toml_edit::DocumentMut,
+}
+
+impl TomlDocument {
+    /// Loads the TOML configuration file at the given path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the TOML configuration file.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The loaded document, or an error if the file cannot be read or is not valid TOML.
+    pub fn open<P: AsRef<std::path::Path>>(
+        path: P,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<TomlDocument> {
+        let manager = <crate::TomlManager as crate::Manager>::new(path.as_ref());
+        let mut content = String::new();
+        std::io::Read::read_to_string(&mut crate::Manager::open_file(&manager, context)?, &mut content).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read TOML file: {}", err))
+                .with_details(crate::context::bounded(crate::context::with_path(context, path.as_ref())))
+        })?;
+        Self::parse(path, &content, context)
+    }
+
+    /// Parses TOML content, to be saved at the given path.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the TOML configuration file.
+    /// - `content`: The TOML content.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The parsed document, or an error if the content is not valid TOML.
+    pub fn parse<P: AsRef<std::path::Path>>(
+        path: P,
+        content: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<TomlDocument> {
+        let document = content.parse::<toml_edit::DocumentMut>().map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Invalid TOML content: {}", err.message()))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, path.as_ref());
+                    if let Some(span) = err.span() {
+                        let (line, column) = crate::context::location(content, span.start);
+                        ctx = crate::context::with_location(&ctx, line, column);
+                    }
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        Ok(TomlDocument {
+            path: path.as_ref().to_path_buf(),
+            document,
+        })
+    }
+
+    /// Returns the path to the TOML configuration file.
+    pub fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Returns the underlying `toml_edit` document, for edits not covered by this API.
+    pub fn document(&self) -> &toml_edit::DocumentMut {
+        &self.document
+    }
+
+    /// Returns the underlying `toml_edit` document mutably, for edits not covered by this API.
+    pub fn document_mut(&mut self) -> &mut toml_edit::DocumentMut {
+        &mut self.document
+    }
+
+    /// Deserializes a value of the document.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the value will be deserialized.
+    ///
+    /// # Parameters
+    /// - `key`: The dotted key of the value.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized value, `None` if the key is not set, or an error if the value cannot
+    /// be deserialized.
+    pub fn get<C: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Option<C>> {
+        let mut item = self.document.as_item();
+        for segment in key.split('.') {
+            item = match item.get(segment) {
+                Some(item) => item,
+                None => return Ok(None),
+            };
+        }
+        let value = match item.clone().into_value() {
+            Ok(value) => value,
+            Err(_) => return Ok(None),
+        };
+        Ok(Some(C::deserialize(serde::de::IntoDeserializer::into_deserializer(value)).map_err(
+            |err| self.error(&format!("Invalid TOML value for '{}': {}", key, err.message()), key, context),
+        )?))
+    }
+
+    /// Sets a value of the document, creating the missing parent tables.
+    ///
+    /// The decoration of replaced values (such as their comments) is kept. A map replacing a
+    /// table is applied key by key, so that the comments of the values it keeps are preserved;
+    /// new maps are written as tables, unless nested in an inline table.
+    ///
+    /// # Type Parameters
+    /// - `D`: The type of the value.
+    ///
+    /// # Parameters
+    /// - `key`: The dotted key of the value.
+    /// - `value`: The value to set.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// Empty result on success, or an error if the value cannot be represented in TOML or a
+    /// parent of the key is not a table.
+    pub fn set<D: serde::Serialize>(
+        &mut self,
+        key: &str,
+        value: D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let value = value
+            .serialize(toml_edit::ser::ValueSerializer::new())
+            .map_err(|err| self.error(&format!("Failed to serialize TOML value for '{}': {}", key, err), key, context))?;
+        let (parents, name) = split_key(key);
+        let mut inline = false;
+        let mut table: &mut dyn toml_edit::TableLike = self.document.as_table_mut();
+        for segment in parents {
+            let parent = table.entry(segment).or_insert_with(|| toml_edit::Item::Table(implicit_table()));
+            inline |= parent.is_inline_table();
+            table = match parent.as_table_like_mut() {
+                Some(parent) => parent,
+                None => {
+                    return Err(crate::ConfigurationFileError::new()
+                        .with_message(format!("Cannot set '{}': '{}' is not a table", key, segment))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = crate::context::with_path(context, &self.path);
+                            ctx.insert("key".to_string(), serde_value::Value::String(key.to_string()));
+                            ctx
+                        }))
+                        .into());
+                }
+            };
+        }
+        assign(table, name, value, inline);
+        Ok(())
+    }
+
+    /// Removes a value of the document.
+    ///
+    /// # Parameters
+    /// - `key`: The dotted key of the value.
+    ///
+    /// # Returns
+    /// `true` if the key was set.
+    pub fn remove(&mut self, key: &str) -> bool {
+        let (parents, name) = split_key(key);
+        let mut table: &mut dyn toml_edit::TableLike = self.document.as_table_mut();
+        for segment in parents {
+            table = match table.get_mut(segment).and_then(|item| item.as_table_like_mut()) {
+                Some(parent) => parent,
+                None => return false,
+            };
+        }
+        table.remove(name).is_some()
+    }
+
+    /// Deserializes the whole document.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the document will be deserialized.
+    ///
+    /// # Parameters
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The deserialized configuration object or an error.
+    pub fn deserialize<C: serde::de::DeserializeOwned>(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        <crate::TomlManager as crate::Manager>::read_str(&self.to_string(), context).map_err(|err| crate::context::add_path(err, &self.path))
+    }
+
+    /// Writes the document back to its file.
+    ///
+    /// # Parameters
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The path to the written file.
+    pub fn save(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<std::path::PathBuf> {
+        self.save_with(context, &crate::WriteOptions::default())
+    }
+
+    /// Writes the document back to its file, applying the given write options.
+    ///
+    /// The document is written as is: the options' header banner and style are ignored, while
+    /// the resolver, lock, mode, retry policy and post-write hooks apply as in
+    /// [`Manager::write_config_with`](crate::Manager::write_config_with).
+    ///
+    /// # Parameters
+    /// - `context`: Context used for error reporting.
+    /// - `options`: The write options to apply.
+    ///
+    /// # Returns
+    /// The path to the written file, as mapped by the options' resolver.
+    pub fn save_with(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::WriteOptions,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        let manager = <crate::TomlManager as crate::Manager>::new(&self.path);
+        let path = crate::resolver::resolve(options.resolver.as_ref(), &self.path, context)?;
+        crate::formats::write_file(&manager, &path, self.to_string().as_bytes(), context, options)?;
+        crate::hooks::run_hooks(&options.hooks, options.hook_failure, &path)?;
+        Ok(path)
+    }
+
+    /// Builds an error on a key of the document.
+    fn error(&self, message: &str, key: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Error {
+        crate::ConfigurationFileError::new()
+            .with_message(message.to_string())
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, &self.path);
+                ctx.insert("key".to_string(), serde_value::Value::String(key.to_string()));
+                ctx
+            }))
+            .into()
+    }
+}
+
+impl std::fmt::Display for TomlDocument {
+    /// Renders the document, formatting included.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)
+    }
+}
+
+/// Splits a dotted key into its parent segments and its last segment.
+fn split_key(key: &str) -> (Vec<&str>, &str) {
+    match key.rsplit_once('.') {
+        Some((parents, name)) => (parents.split('.').collect(), name),
+        None => (Vec::new(), key),
+    }
+}
+
+/// Creates a table whose header is only written if it holds values.
+fn implicit_table() -> toml_edit::Table {
+    let mut table = toml_edit::Table::new();
+    table.set_implicit(true);
+    table
+}
+
+/// Sets an entry of a table, keeping the formatting of what it replaces.
+///
+/// `inline` tells whether the table is nested in an inline table, which can only hold values.
+fn assign(table: &mut dyn toml_edit::TableLike, name: &str, value: toml_edit::Value, inline: bool) {
+    match (table.get_mut(name), value) {
+        (Some(toml_edit::Item::Value(current)), mut value) => {
+            *value.decor_mut() = current.decor().clone();
+            *current = value;
+        }
+        (Some(toml_edit::Item::Table(current)), toml_edit::Value::InlineTable(map)) => {
+            let removed: Vec<String> = current
+                .iter()
+                .map(|(key, _)| key.to_string())
+                .filter(|key| !map.contains_key(key))
+                .collect();
+            for key in removed {
+                current.remove(&key);
+            }
+            for (key, value) in map {
+                assign(current, &key, value, false);
+            }
+        }
+        (_, toml_edit::Value::InlineTable(map)) if !inline => {
+            let mut new = implicit_table();
+            for (key, value) in map {
+                assign(&mut new, &key, value, false);
+            }
+            table.insert(name, toml_edit::Item::Table(new));
+        }
+        (_, value) => {
+            table.insert(name, toml_edit::Item::Value(value));
+        }
+    }
+}
@@ -1,10 +1,102 @@
+/// Controls the layout of written XML documents, to match schemas imposed by external systems.
+///
+/// The default options keep the backend layout: the root element is named after
+/// the struct, fields are child elements (except those renamed with a leading `@`) and
+/// list items are repeated elements named after the field. Except namespaces, options only
+/// apply to writing: content is always read with the backend, which only maps attributes to
+/// fields renamed with a leading `@` and does not unwrap list wrappers.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct XmlOptions {
+    /// Name of the root element. Defaults to the name of the serialized struct.
+    pub root: Option<String>,
+    /// Writes scalar fields as attributes of their parent element instead of child elements.
+    ///
+    /// Fields renamed with a leading `@` are always written as attributes, and the `#text`
+    /// field as the text content of its parent element.
+    pub scalar_attributes: bool,
+    /// Wraps lists in an element named after the field, each item being written as an
+    /// element of this name. Lists are written as repeated elements if unset.
+    pub list_items: Option<String>,
+    /// Namespaces declared on the root element of written documents, by prefix (the empty
+    /// prefix declaring the default namespace).
+    ///
+    /// When reading through the manager, elements and attributes of these namespaces are
+    /// matched by their local name, whatever prefix the document binds to them; elements
+    /// and attributes of other namespaces are ignored.
+    pub namespaces: std::collections::BTreeMap<String, String>,
+    /// Namespace prefix of the written elements, which must be declared in `namespaces`.
+    /// Attributes are written without prefix.
+    pub prefix: Option<String>,
+}
+
 /// XML configuration file manager implementing the `Manager` trait.
 ///
 /// This struct provides methods to read and write XML-formatted configuration
-/// files using the `serde_xml_rs` crate.
+/// files using the `serde_xml_rs` crate, or the faster `quick-xml` crate when the
+/// `quick-xml` feature is enabled. Both backends map fields renamed with a leading `@`
+/// to attributes; the text content of an element is held by a field renamed `#text`
+/// with `serde_xml_rs` and `$text` with `quick-xml`. The layout of written documents
+/// can be customized with [`XmlManager::with_options`].
 pub struct XmlManager {
     /// Path to the XML configuration file.
-    path: String,
+    path: std::path::PathBuf,
+    /// Layout of written documents.
+    options: XmlOptions,
+}
+
+impl XmlManager {
+    /// Creates a new `XmlManager` writing documents with the given layout.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the XML configuration file.
+    /// - `options`: Layout of written documents.
+    ///
+    /// # Returns
+    /// A new instance of `XmlManager`.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cdumay_config::Manager;
+    ///
+    /// #[derive(serde::Serialize)]
+    /// struct Server {
+    ///     host: String,
+    ///     port: u16,
+    ///     aliases: Vec<String>,
+    /// }
+    ///
+    /// fn save(server: &Server) -> cdumay_core::Result<()> {
+    ///     let manager = cdumay_config::XmlManager::with_options(
+    ///         "server.xml",
+    ///         cdumay_config::XmlOptions {
+    ///             root: Some("server".to_string()),
+    ///             scalar_attributes: true,
+    ///             list_items: Some("alias".to_string()),
+    ///             ..Default::default()
+    ///         },
+    ///     );
+    ///     // <server host="..." port="..."><aliases><alias>...</alias></aliases></server>
+    ///     manager.write_config(server, &std::collections::BTreeMap::new())?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn with_options<P: AsRef<std::path::Path>>(path: P, options: XmlOptions) -> XmlManager {
+        XmlManager {
+            path: path.as_ref().to_path_buf(),
+            options,
+        }
+    }
+
+    /// Returns the layout of written documents.
+    pub fn options(&self) -> &XmlOptions {
+        &self.options
+    }
+
+    /// Serializes data as an XML document laid out according to the manager options.
+    fn to_string<D: serde::Serialize>(&self, data: &D) -> Result<String, crate::formats::xml_tree::NodeError> {
+        let node = crate::formats::xml_tree::to_node(data)?;
+        crate::formats::xml_tree::XmlWriter::document(&self.options, &node)
+    }
 }
 
 impl crate::Manager for XmlManager {
@@ -15,20 +107,26 @@ impl crate::Manager for XmlManager {
     ///
     /// # Returns
     /// A new instance of `XmlManager`.
-    fn new(path: String) -> XmlManager {
-        XmlManager { path }
+    fn new<P: AsRef<std::path::Path>>(path: P) -> XmlManager {
+        XmlManager {
+            path: path.as_ref().to_path_buf(),
+            options: XmlOptions::default(),
+        }
     }
 
     /// Returns the path to the XML configuration file.
     ///
     /// # Returns
     /// The file path as a `String`.
-    fn path(&self) -> String {
-        self.path.clone()
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
     }
 
     /// Reads XML content from a `Read` stream and deserializes it into the target type.
     ///
+    /// Names of the namespaces declared in the manager [`XmlOptions`] are first resolved to
+    /// their local name.
+    ///
     /// # Type Parameters
     /// - `R`: A reader implementing `std::io::Read`.
     /// - `C`: The type to deserialize into, must implement `DeserializeOwned`.
@@ -44,20 +142,41 @@ impl crate::Manager for XmlManager {
         reader: R,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        Ok(serde_xml_rs::from_reader(reader).map_err(|err| {
-            crate::ConfigurationFileError::new()
-                .with_message(format!("Invalid XML file content: {}", err))
-                .with_details({
-                    let mut ctx = context.clone();
-                    ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
-                    ctx
-                })
-        })?)
+        let result = match self.options.namespaces.is_empty() {
+            true => from_reader(reader),
+            false => from_str(
+                &crate::formats::xml_tree::resolve_namespaces(reader, &self.options.namespaces).map_err(|err| {
+                    crate::ConfigurationFileError::new()
+                        .with_message(format!("Invalid XML file content: {}", err))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = crate::context::with_path(context, &self.path);
+                            ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                            ctx
+                        }))
+                })?,
+            ),
+        };
+        result.map_err(|err| {
+            err.convert(|err| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Invalid XML file content: {}", err))
+                    .with_details(crate::context::bounded({
+                        let mut ctx = context.clone();
+                        ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
+                        ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                        ctx
+                    }))
+                    .into()
+            })
+        })
     }
 
     /// Serializes data into XML format and writes it to the given `Write` stream.
     ///
+    /// Documents are laid out according to the manager [`XmlOptions`]. XML documents have a
+    /// single root element: data whose root is a sequence is rejected, as well as scalars
+    /// unless [`XmlOptions::root`] names the root element.
+    ///
     /// # Type Parameters
     /// - `D`: The data type to serialize (must implement `Serialize`).
     /// - `W`: A writable stream implementing `std::io::Write`.
@@ -71,41 +190,106 @@ impl crate::Manager for XmlManager {
     /// An empty result on success or an error on failure.
     fn write<D: serde::Serialize, W: std::io::Write>(
         &self,
-        writer: W,
+        mut writer: W,
         data: D,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<()> {
-        Ok(serde_xml_rs::to_writer(writer, &data).map_err(|err| {
-            crate::ConfigurationFileError::new()
+        if self.options != XmlOptions::default() {
+            let content = self.to_string(&data).map_err(|err| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Failed to write XML file: {}", err))
+                    .with_details(crate::context::bounded({
+                        let mut ctx = context.clone();
+                        ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
+                        ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                        ctx
+                    }))
+            })?;
+            return writer.write_all(content.as_bytes()).map_err(|err| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Failed to write XML file: {}", err))
+                    .with_details(crate::context::bounded(crate::context::with_path(context, self.file_path())))
+                    .into()
+            });
+        }
+        to_writer(writer, &data).map_err(|err| match crate::formats::unsupported_root("XML", &data, &self.path, context) {
+            Some(root_err) => root_err,
+            None => crate::ConfigurationFileError::new()
                 .with_message(format!("Failed to write XML file: {}", err))
-                .with_details({
+                .with_details(crate::context::bounded({
                     let mut ctx = context.clone();
-                    ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
+                    ctx.insert("path".to_string(), serde_value::Value::String(self.file_path().display().to_string()));
                     ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
                     ctx
-                })
-        })?)
+                }))
+                .into(),
+        })
     }
 
-    /// Deserializes a string of XML content into the target type.
+    /// Deserializes raw XML content, which must be valid UTF-8, into the target type.
     ///
     /// # Type Parameters
     /// - `C`: The target type, must implement `DeserializeOwned`.
     ///
     /// # Parameters
-    /// - `content`: A string slice containing XML data.
+    /// - `content`: The bytes of the XML data.
     /// - `context`: Error context metadata.
     ///
     /// # Returns
     /// The deserialized object or an error.
-    fn read_str<C: serde::de::DeserializeOwned>(
-        content: &str,
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        Ok(serde_xml_rs::from_str(content).map_err(|err| {
-            crate::ConfigurationFileError::new()
-                .with_message(format!("Invalid XML content: {}", err))
-                .with_details(context.clone())
-        })?)
+        let content = crate::formats::utf8(content, context)?;
+        from_str(content).map_err(|err| {
+            err.convert(|err| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Invalid XML content: {}", err))
+                    .with_details(crate::context::bounded(context.clone()))
+                    .into()
+            })
+        })
     }
 }
+
+/// Deserializes XML content from a reader with `serde_xml_rs`.
+#[cfg(not(feature = "quick-xml"))]
+fn from_reader<R: std::io::Read, C: serde::de::DeserializeOwned>(reader: R) -> Result<C, crate::formats::KeyError<serde_xml_rs::Error>> {
+    crate::formats::deserialize_tracked(&mut serde_xml_rs::Deserializer::new_from_reader(reader))
+}
+
+/// Deserializes XML content from a reader with `quick-xml`.
+#[cfg(feature = "quick-xml")]
+fn from_reader<R: std::io::Read, C: serde::de::DeserializeOwned>(reader: R) -> Result<C, crate::formats::KeyError<quick_xml::DeError>> {
+    crate::formats::deserialize_tracked(&mut quick_xml::de::Deserializer::from_reader(std::io::BufReader::new(reader)))
+}
+
+/// Deserializes XML content from a string with `serde_xml_rs`.
+#[cfg(not(feature = "quick-xml"))]
+fn from_str<C: serde::de::DeserializeOwned>(content: &str) -> Result<C, crate::formats::KeyError<serde_xml_rs::Error>> {
+    from_reader(content.as_bytes())
+}
+
+/// Deserializes XML content from a string with `quick-xml`.
+#[cfg(feature = "quick-xml")]
+fn from_str<C: serde::de::DeserializeOwned>(content: &str) -> Result<C, crate::formats::KeyError<quick_xml::DeError>> {
+    crate::formats::deserialize_tracked(&mut quick_xml::de::Deserializer::from_str(content))
+}
+
+/// Serializes data as an XML document into a writer with `serde_xml_rs`.
+#[cfg(not(feature = "quick-xml"))]
+fn to_writer<W: std::io::Write, D: serde::Serialize>(writer: W, data: &D) -> Result<(), serde_xml_rs::Error> {
+    serde_xml_rs::to_writer(writer, data)
+}
+
+/// Serializes data as an XML document into a writer with `quick-xml`.
+///
+/// The document is built in memory, as `quick-xml` serializes into a `fmt::Write`, then
+/// written after an XML declaration.
+#[cfg(feature = "quick-xml")]
+fn to_writer<W: std::io::Write, D: serde::Serialize>(mut writer: W, data: &D) -> Result<(), quick_xml::SeError> {
+    let mut content = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
+    data.serialize(quick_xml::se::Serializer::new(&mut content))?;
+    writer.write_all(content.as_bytes()).map_err(|err| quick_xml::SeError::Io(std::sync::Arc::new(err)))
+}
@@ -0,0 +1,501 @@
+//! Order-preserving XML writer driven by [`XmlOptions`](crate::XmlOptions).
+//!
+//! Data is first serialized into a [`Node`] tree, which keeps the declaration order of
+//! struct fields (unlike `serde_value`), then written as XML text.
+
+/// A serialized value, keeping the order of fields.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Node {
+    /// No content (`None`, unit).
+    Empty,
+    /// A scalar, formatted as text.
+    Text(String),
+    /// A sequence.
+    List(Vec<Node>),
+    /// A struct (named) or map, fields in order.
+    Fields(Option<&'static str>, Vec<(String, Node)>),
+}
+
+/// Error raised when data cannot be represented as XML.
+#[derive(Debug)]
+pub(crate) struct NodeError(String);
+
+impl std::fmt::Display for NodeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for NodeError {}
+
+impl serde::ser::Error for NodeError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        NodeError(msg.to_string())
+    }
+}
+
+/// Serializes data into a [`Node`] tree.
+pub(crate) fn to_node<D: serde::Serialize + ?Sized>(data: &D) -> Result<Node, NodeError> {
+    data.serialize(NodeSerializer)
+}
+
+/// Serializer producing [`Node`] trees.
+struct NodeSerializer;
+
+/// Collects the elements of a compound value.
+struct Collector {
+    /// Name of the struct being serialized, if any.
+    name: Option<&'static str>,
+    /// Enum variant wrapping the value, if any.
+    variant: Option<&'static str>,
+    /// Items of a sequence.
+    items: Vec<Node>,
+    /// Fields of a struct or map.
+    fields: Vec<(String, Node)>,
+    /// Key of the map entry being serialized.
+    key: Option<String>,
+}
+
+impl Collector {
+    fn new(name: Option<&'static str>, variant: Option<&'static str>) -> Self {
+        Collector {
+            name,
+            variant,
+            items: Vec::new(),
+            fields: Vec::new(),
+            key: None,
+        }
+    }
+
+    /// Wraps a node in its enum variant, if any.
+    fn wrap(&self, node: Node) -> Node {
+        match self.variant {
+            Some(variant) => Node::Fields(None, vec![(variant.to_string(), node)]),
+            None => node,
+        }
+    }
+}
+
+impl serde::Serializer for NodeSerializer {
+    type Ok = Node;
+    type Error = NodeError;
+    type SerializeSeq = Collector;
+    type SerializeTuple = Collector;
+    type SerializeTupleStruct = Collector;
+    type SerializeTupleVariant = Collector;
+    type SerializeMap = Collector;
+    type SerializeStruct = Collector;
+    type SerializeStructVariant = Collector;
+
+    fn serialize_bool(self, v: bool) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_char(self, v: char) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<Node, NodeError> {
+        Ok(Node::Text(v.to_string()))
+    }
+
+    fn serialize_bytes(self, _: &[u8]) -> Result<Node, NodeError> {
+        Err(NodeError("raw bytes cannot be written as XML".to_string()))
+    }
+
+    fn serialize_none(self) -> Result<Node, NodeError> {
+        Ok(Node::Empty)
+    }
+
+    fn serialize_some<T: serde::Serialize + ?Sized>(self, value: &T) -> Result<Node, NodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<Node, NodeError> {
+        Ok(Node::Empty)
+    }
+
+    fn serialize_unit_struct(self, _: &'static str) -> Result<Node, NodeError> {
+        Ok(Node::Empty)
+    }
+
+    fn serialize_unit_variant(self, _: &'static str, _: u32, variant: &'static str) -> Result<Node, NodeError> {
+        Ok(Node::Text(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: serde::Serialize + ?Sized>(self, _: &'static str, value: &T) -> Result<Node, NodeError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: serde::Serialize + ?Sized>(
+        self,
+        _: &'static str,
+        _: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<Node, NodeError> {
+        Ok(Node::Fields(None, vec![(variant.to_string(), value.serialize(self)?)]))
+    }
+
+    fn serialize_seq(self, _: Option<usize>) -> Result<Collector, NodeError> {
+        Ok(Collector::new(None, None))
+    }
+
+    fn serialize_tuple(self, _: usize) -> Result<Collector, NodeError> {
+        Ok(Collector::new(None, None))
+    }
+
+    fn serialize_tuple_struct(self, _: &'static str, _: usize) -> Result<Collector, NodeError> {
+        Ok(Collector::new(None, None))
+    }
+
+    fn serialize_tuple_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> Result<Collector, NodeError> {
+        Ok(Collector::new(None, Some(variant)))
+    }
+
+    fn serialize_map(self, _: Option<usize>) -> Result<Collector, NodeError> {
+        Ok(Collector::new(None, None))
+    }
+
+    fn serialize_struct(self, name: &'static str, _: usize) -> Result<Collector, NodeError> {
+        Ok(Collector::new(Some(name), None))
+    }
+
+    fn serialize_struct_variant(self, _: &'static str, _: u32, variant: &'static str, _: usize) -> Result<Collector, NodeError> {
+        Ok(Collector::new(None, Some(variant)))
+    }
+}
+
+impl serde::ser::SerializeSeq for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        self.items.push(value.serialize(NodeSerializer)?);
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<Node, NodeError> {
+        let items = std::mem::take(&mut self.items);
+        Ok(self.wrap(Node::List(items)))
+    }
+}
+
+impl serde::ser::SerializeTuple for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_element<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node, NodeError> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+impl serde::ser::SerializeTupleStruct for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node, NodeError> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+impl serde::ser::SerializeTupleVariant for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        serde::ser::SerializeSeq::serialize_element(self, value)
+    }
+
+    fn end(self) -> Result<Node, NodeError> {
+        serde::ser::SerializeSeq::end(self)
+    }
+}
+
+impl serde::ser::SerializeMap for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_key<T: serde::Serialize + ?Sized>(&mut self, key: &T) -> Result<(), NodeError> {
+        match key.serialize(NodeSerializer)? {
+            Node::Text(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(NodeError("XML element names must be scalars".to_string())),
+        }
+    }
+
+    fn serialize_value<T: serde::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NodeError> {
+        let key = self.key.take().ok_or_else(|| NodeError("map value without key".to_string()))?;
+        self.fields.push((key, value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+
+    fn end(mut self) -> Result<Node, NodeError> {
+        let fields = std::mem::take(&mut self.fields);
+        Ok(self.wrap(Node::Fields(self.name, fields)))
+    }
+}
+
+impl serde::ser::SerializeStruct for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), NodeError> {
+        self.fields.push((key.to_string(), value.serialize(NodeSerializer)?));
+        Ok(())
+    }
+
+    fn end(self) -> Result<Node, NodeError> {
+        serde::ser::SerializeMap::end(self)
+    }
+}
+
+impl serde::ser::SerializeStructVariant for Collector {
+    type Ok = Node;
+    type Error = NodeError;
+
+    fn serialize_field<T: serde::Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), NodeError> {
+        serde::ser::SerializeStruct::serialize_field(self, key, value)
+    }
+
+    fn end(self) -> Result<Node, NodeError> {
+        serde::ser::SerializeMap::end(self)
+    }
+}
+
+/// Escapes text for use in XML content and attribute values.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Writes a [`Node`] tree as an XML document.
+pub(crate) struct XmlWriter<'a> {
+    /// The layout options.
+    options: &'a crate::XmlOptions,
+    /// The document being written.
+    output: String,
+    /// Whether the namespaces are still to be declared, on the root element.
+    declare: bool,
+}
+
+impl<'a> XmlWriter<'a> {
+    /// Writes a document whose root element holds the given node.
+    ///
+    /// The root element is named after [`XmlOptions::root`](crate::XmlOptions::root), or
+    /// after the serialized struct.
+    pub(crate) fn document(options: &'a crate::XmlOptions, node: &Node) -> Result<String, NodeError> {
+        let root = match (&options.root, node) {
+            (Some(root), _) => root.as_str(),
+            (None, Node::Fields(Some(name), _)) => name,
+            (None, _) => return Err(NodeError("the root element name is required for data which is not a struct".to_string())),
+        };
+        if let Some(prefix) = &options.prefix
+            && !options.namespaces.contains_key(prefix)
+        {
+            return Err(NodeError(format!("namespace prefix '{}' is not declared", prefix)));
+        }
+        let mut writer = XmlWriter {
+            options,
+            output: String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n"),
+            declare: true,
+        };
+        match node {
+            Node::List(_) => return Err(NodeError("a sequence cannot be the root of an XML document".to_string())),
+            node => writer.element(root, node, 0)?,
+        }
+        Ok(writer.output)
+    }
+
+    /// Tells whether a field must be written as an attribute of its parent element.
+    fn is_attribute(&self, name: &str, node: &Node) -> bool {
+        name.starts_with('@') || (self.options.scalar_attributes && matches!(node, Node::Text(_)) && !is_text(name))
+    }
+
+    /// Returns an element name, prefixed with the namespace prefix of the options if any.
+    fn qualified(&self, name: &str) -> String {
+        match self.options.prefix.as_deref() {
+            Some(prefix) if !prefix.is_empty() => format!("{}:{}", prefix, name),
+            _ => name.to_string(),
+        }
+    }
+
+    /// Writes an element, its attributes and children.
+    fn element(&mut self, name: &str, node: &Node, depth: usize) -> Result<(), NodeError> {
+        let name = &self.qualified(name);
+        let indent = "  ".repeat(depth);
+        self.output.push_str(&indent);
+        self.output.push('<');
+        self.output.push_str(name);
+        if std::mem::take(&mut self.declare) {
+            for (prefix, uri) in &self.options.namespaces {
+                match prefix.is_empty() {
+                    true => self.output.push_str(&format!(" xmlns=\"{}\"", escape(uri))),
+                    false => self.output.push_str(&format!(" xmlns:{}=\"{}\"", prefix, escape(uri))),
+                }
+            }
+        }
+        match node {
+            Node::Empty => self.output.push_str(" />\n"),
+            Node::Text(text) => self.output.push_str(&format!(">{}</{}>\n", escape(text), name)),
+            Node::List(_) => return Err(NodeError(format!("nested sequences cannot be written in element '{}'", name))),
+            Node::Fields(_, fields) => {
+                let mut text = None;
+                let mut children = Vec::new();
+                for (field, value) in fields {
+                    if self.is_attribute(field, value) {
+                        match value {
+                            Node::Text(value) => self.output.push_str(&format!(" {}=\"{}\"", field.trim_start_matches('@'), escape(value))),
+                            Node::Empty => {}
+                            _ => return Err(NodeError(format!("attribute '{}' must be a scalar", field))),
+                        }
+                    } else if is_text(field) {
+                        text = Some(value);
+                    } else if !matches!(value, Node::Empty) {
+                        children.push((field, value));
+                    }
+                }
+                match (text, children.is_empty()) {
+                    (Some(Node::Text(text)), true) => self.output.push_str(&format!(">{}</{}>\n", escape(text), name)),
+                    (None, true) | (Some(Node::Empty), true) => self.output.push_str(" />\n"),
+                    (Some(_), _) => return Err(NodeError(format!("element '{}' mixes text content and child elements", name))),
+                    (None, false) => {
+                        self.output.push_str(">\n");
+                        for (field, value) in children {
+                            self.child(field, value, depth + 1)?;
+                        }
+                        self.output.push_str(&format!("{}</{}>\n", indent, name));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes a field as child element(s), wrapping sequences if configured.
+    fn child(&mut self, name: &str, node: &Node, depth: usize) -> Result<(), NodeError> {
+        match (node, &self.options.list_items) {
+            (Node::List(items), Some(item)) => {
+                let name = &self.qualified(name);
+                let indent = "  ".repeat(depth);
+                self.output.push_str(&format!("{}<{}>\n", indent, name));
+                for value in items {
+                    self.element(item, value, depth + 1)?;
+                }
+                self.output.push_str(&format!("{}</{}>\n", indent, name));
+                Ok(())
+            }
+            (Node::List(items), None) => {
+                for value in items {
+                    self.element(name, value, depth)?;
+                }
+                Ok(())
+            }
+            (node, _) => self.element(name, node, depth),
+        }
+    }
+}
+
+/// Tells whether a field holds the text content of its parent element.
+fn is_text(name: &str) -> bool {
+    matches!(name, "#text" | "$value" | "$text")
+}
+
+/// Rewrites an XML document, naming the elements and attributes which belong to the given
+/// namespaces by their local name.
+///
+/// Namespaces are matched by URI, whatever prefix the document binds to them. Elements (with
+/// their content) and attributes of other namespaces are dropped, as well as namespace
+/// declarations, comments and processing instructions.
+pub(crate) fn resolve_namespaces<R: std::io::Read>(
+    reader: R,
+    namespaces: &std::collections::BTreeMap<String, String>,
+) -> Result<String, xml::reader::Error> {
+    let foreign = |name: &xml::name::OwnedName| name.namespace.as_ref().is_some_and(|uri| !namespaces.values().any(|known| known == uri));
+    let mut output = String::new();
+    // Depth inside a dropped element.
+    let mut skipped = 0usize;
+    for event in xml::reader::EventReader::new(reader) {
+        match event? {
+            xml::reader::XmlEvent::StartElement { name, attributes, .. } => {
+                if skipped > 0 || foreign(&name) {
+                    skipped += 1;
+                    continue;
+                }
+                output.push('<');
+                output.push_str(&name.local_name);
+                for attribute in attributes.iter().filter(|attribute| !foreign(&attribute.name)) {
+                    output.push_str(&format!(" {}=\"{}\"", attribute.name.local_name, escape(&attribute.value)));
+                }
+                output.push('>');
+            }
+            xml::reader::XmlEvent::EndElement { .. } if skipped > 0 => skipped -= 1,
+            xml::reader::XmlEvent::EndElement { name } => output.push_str(&format!("</{}>", name.local_name)),
+            _ if skipped > 0 => {}
+            xml::reader::XmlEvent::Characters(text) | xml::reader::XmlEvent::CData(text) => output.push_str(&escape(&text)),
+            xml::reader::XmlEvent::Whitespace(text) => output.push_str(&text),
+            _ => {}
+        }
+    }
+    Ok(output)
+}
@@ -1,11 +1,417 @@
 use cdumay_core::ErrorConverter;
+
+/// YAML backend: `serde_norway`, the maintained fork of `serde_yaml`, or the archived
+/// `serde_yaml` when the `serde_yaml` feature is enabled.
+#[cfg(not(feature = "serde_yaml"))]
+use serde_norway as backend;
+#[cfg(feature = "serde_yaml")]
+use serde_yaml as backend;
+
+/// Converts `serde_norway` errors exactly as `cdumay_yaml` converts `serde_yaml` ones.
+#[cfg(not(feature = "serde_yaml"))]
+struct YamlErrorConverter;
+
+#[cfg(not(feature = "serde_yaml"))]
+impl ErrorConverter for YamlErrorConverter {
+    type Error = serde_norway::Error;
+
+    fn convert(
+        _: &serde_norway::Error,
+        text: String,
+        context: std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Error {
+        cdumay_yaml::DataError::new().with_message(text).with_details(crate::context::bounded(context)).into()
+    }
+}
+
+#[cfg(feature = "serde_yaml")]
+use cdumay_yaml::YamlErrorConverter;
+
+/// Tag of the YAML values including another configuration file.
+const INCLUDE_TAG: &str = "include";
+
+/// Converts a result of the YAML backend, see `cdumay_yaml::convert_yaml_result`. The
+/// error context, with the `path` of the file if any, is only built on errors.
+fn convert<T, E: Into<crate::formats::KeyError<backend::Error>>>(
+    result: Result<T, E>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    path: Option<&std::path::Path>,
+) -> cdumay_core::Result<T> {
+    result.map_err(|err| {
+        err.into().convert(|err| {
+            let mut ctx = match path {
+                Some(path) => crate::context::with_path(context, path),
+                None => context.clone(),
+            };
+            if let Some(location) = err.location() {
+                ctx = crate::context::with_location(&ctx, location.line(), location.column());
+            }
+            YamlErrorConverter::convert_error(&err, None, crate::context::bounded(ctx))
+        })
+    })
+}
+
+/// Limits on the size of read YAML documents, guarding against "billion laughs" attacks
+/// where a few nested aliases expand into a huge document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct YamlLimits {
+    /// Maximum nesting depth of sequences and mappings.
+    pub max_depth: usize,
+    /// Maximum number of nodes (scalars, sequences and mappings) of a document, once
+    /// aliases are expanded.
+    pub max_nodes: usize,
+}
+
+impl Default for YamlLimits {
+    /// A depth of 128 and a million nodes.
+    fn default() -> Self {
+        Self {
+            max_depth: 128,
+            max_nodes: 1_000_000,
+        }
+    }
+}
+
+/// Walks a YAML document without building it, enforcing the [`YamlLimits`] and detecting
+/// merge keys and `!include` tags.
+struct Guard<'a> {
+    /// The enforced limits.
+    limits: &'a YamlLimits,
+    /// Number of nodes visited so far.
+    nodes: &'a std::cell::Cell<usize>,
+    /// Whether a `<<` merge key or an `!include` tag was found.
+    rewrite: &'a std::cell::Cell<bool>,
+    /// Depth of the visited node.
+    depth: usize,
+    /// Whether the visited node is a mapping key.
+    key: bool,
+}
+
+impl Guard<'_> {
+    /// Walks a document, returning whether it holds merge keys or `!include` tags.
+    fn check(document: backend::Deserializer, limits: &YamlLimits) -> Result<bool, backend::Error> {
+        let (nodes, rewrite) = (std::cell::Cell::new(0), std::cell::Cell::new(false));
+        let guard = Guard {
+            limits,
+            nodes: &nodes,
+            rewrite: &rewrite,
+            depth: 0,
+            key: false,
+        };
+        serde::de::DeserializeSeed::deserialize(guard, document)?;
+        Ok(rewrite.get())
+    }
+
+    /// Returns a guard for a child node.
+    fn child(&self, key: bool) -> Self {
+        Guard {
+            depth: self.depth + 1,
+            key,
+            ..*self
+        }
+    }
+
+    /// Counts the visited node.
+    fn count<E: serde::de::Error>(&self) -> Result<(), E> {
+        self.nodes.set(self.nodes.get() + 1);
+        match self.nodes.get() > self.limits.max_nodes {
+            true => Err(E::custom(format!(
+                "document exceeds the limit of {} nodes once aliases are expanded",
+                self.limits.max_nodes
+            ))),
+            false => Ok(()),
+        }
+    }
+
+    /// Counts the visited collection and checks its depth.
+    fn enter<E: serde::de::Error>(&self) -> Result<(), E> {
+        self.count()?;
+        match self.depth >= self.limits.max_depth {
+            true => Err(E::custom(format!("document exceeds the maximum depth of {}", self.limits.max_depth))),
+            false => Ok(()),
+        }
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for Guard<'_> {
+    type Value = ();
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for Guard<'_> {
+    type Value = ();
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("any YAML node")
+    }
+
+    fn visit_bool<E: serde::de::Error>(self, _: bool) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_i64<E: serde::de::Error>(self, _: i64) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_u64<E: serde::de::Error>(self, _: u64) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_f64<E: serde::de::Error>(self, _: f64) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<(), E> {
+        if self.key && value == "<<" {
+            self.rewrite.set(true);
+        }
+        self.count()
+    }
+
+    fn visit_bytes<E: serde::de::Error>(self, _: &[u8]) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_unit<E: serde::de::Error>(self) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_none<E: serde::de::Error>(self) -> Result<(), E> {
+        self.count()
+    }
+
+    fn visit_some<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_newtype_struct<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_any(self)
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        self.enter()?;
+        while seq.next_element_seed(self.child(false))?.is_some() {}
+        Ok(())
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<(), A::Error> {
+        self.enter()?;
+        while map.next_key_seed(self.child(true))?.is_some() {
+            map.next_value_seed(self.child(false))?;
+        }
+        Ok(())
+    }
+
+    fn visit_enum<A: serde::de::EnumAccess<'de>>(self, data: A) -> Result<(), A::Error> {
+        self.enter()?;
+        let (tag, variant) = data.variant::<String>()?;
+        if tag == INCLUDE_TAG {
+            self.rewrite.set(true);
+        }
+        serde::de::VariantAccess::newtype_variant_seed(variant, self.child(false))
+    }
+}
+
+/// Replaces the values tagged `!include` by `"$include"` maps, resolved by
+/// [`ReadOptions::includes`](crate::ReadOptions::includes).
+fn tag_includes(value: &mut backend::Value) {
+    match value {
+        backend::Value::Tagged(tagged) if tagged.tag == INCLUDE_TAG => {
+            let target = std::mem::replace(&mut tagged.value, backend::Value::Null);
+            let mut mapping = backend::Mapping::new();
+            mapping.insert(backend::Value::String(crate::include::INCLUDE_KEY.to_string()), target);
+            *value = backend::Value::Mapping(mapping);
+        }
+        backend::Value::Tagged(tagged) => tag_includes(&mut tagged.value),
+        backend::Value::Sequence(items) => items.iter_mut().for_each(tag_includes),
+        backend::Value::Mapping(entries) => entries.values_mut().for_each(tag_includes),
+        _ => {}
+    }
+}
+
+/// Deserializes a YAML document which passed the [`Guard`], applying its merge keys and
+/// `!include` tags if any.
+///
+/// Documents with merge keys or `!include` tags are rewritten as a YAML value tree, which is
+/// written back as text before being deserialized, so that scalars keep their YAML typing
+/// rules (e.g. `42` read into a `String` field).
+fn decode<C: serde::de::DeserializeOwned>(
+    document: backend::Deserializer,
+    rewrite: bool,
+) -> Result<C, crate::formats::KeyError<backend::Error>> {
+    if !rewrite {
+        return crate::formats::deserialize_tracked(document);
+    }
+    let mut value = <backend::Value as serde::Deserialize>::deserialize(document)?;
+    value.apply_merge()?;
+    tag_includes(&mut value);
+    crate::formats::deserialize_tracked(backend::Deserializer::from_str(&backend::to_string(&value)?))
+}
+
+/// Deserializes a single YAML document, enforcing the limits and applying merge keys.
+fn from_str<C: serde::de::DeserializeOwned>(content: &str, limits: &YamlLimits) -> Result<C, crate::formats::KeyError<backend::Error>> {
+    let rewrite = Guard::check(backend::Deserializer::from_str(content), limits)?;
+    decode(backend::Deserializer::from_str(content), rewrite)
+}
+
+/// Deserializes every document of a YAML stream, enforcing the limits and applying merge keys.
+fn from_str_all<C: serde::de::DeserializeOwned>(
+    content: &str,
+    limits: &YamlLimits,
+) -> Result<Vec<C>, crate::formats::KeyError<backend::Error>> {
+    let rewrites = backend::Deserializer::from_str(content)
+        .map(|document| Guard::check(document, limits))
+        .collect::<Result<Vec<bool>, backend::Error>>()?;
+    backend::Deserializer::from_str(content)
+        .zip(rewrites)
+        .map(|(document, rewrite)| decode(document, rewrite))
+        .collect()
+}
+
 /// YAML configuration file manager implementing the `Manager` trait.
 ///
-/// This struct handles reading and writing configuration data
-/// in YAML format using the `serde_yaml` crate.
+/// This struct handles reading and writing configuration data in YAML format using the
+/// `serde_norway` crate, the maintained fork of `serde_yaml`. The archived `serde_yaml`
+/// remains available as a backend through the `serde_yaml` feature. Both backends share
+/// the same API, behavior and error conversion.
+///
+/// Anchors and aliases are resolved, and `<<` merge keys are applied, before documents are
+/// deserialized; documents larger than the manager [`YamlLimits`] once aliases are
+/// expanded are rejected.
 pub struct YamlManager {
     /// Path to the YAML configuration file.
-    path: String,
+    path: std::path::PathBuf,
+    /// Limits on the size of read documents.
+    limits: YamlLimits,
+}
+
+impl YamlManager {
+    /// Creates a new `YamlManager` reading documents within the given limits.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the YAML configuration file.
+    /// - `limits`: Limits on the size of read documents.
+    ///
+    /// # Returns
+    /// A new instance of `YamlManager`.
+    pub fn with_limits<P: AsRef<std::path::Path>>(path: P, limits: YamlLimits) -> YamlManager {
+        YamlManager {
+            path: path.as_ref().to_path_buf(),
+            limits,
+        }
+    }
+
+    /// Returns the limits on the size of read documents.
+    pub fn limits(&self) -> &YamlLimits {
+        &self.limits
+    }
+
+    /// Reads the whole content of a YAML stream.
+    fn read_content<R: std::io::Read>(
+        &self,
+        mut reader: R,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<String> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read YAML file: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, &self.path);
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        Ok(content)
+    }
+
+    /// Reads every document of a multi-document YAML file (documents separated by `---`).
+    ///
+    /// # Type Parameters
+    /// - `C`: Type of each document, must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `context`: Contextual information for error reporting.
+    ///
+    /// # Returns
+    /// The documents, in file order, or an error if one of them is invalid.
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(serde::Deserialize)]
+    /// struct Resource {
+    ///     kind: String,
+    /// }
+    ///
+    /// fn kinds() -> cdumay_core::Result<Vec<String>> {
+    ///     use cdumay_config::Manager;
+    ///
+    ///     let manager = cdumay_config::YamlManager::new("deployment.yaml");
+    ///     let resources: Vec<Resource> = manager.read_all(&std::collections::BTreeMap::new())?;
+    ///     Ok(resources.into_iter().map(|resource| resource.kind).collect())
+    /// }
+    /// ```
+    pub fn read_all<C: serde::de::DeserializeOwned>(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<C>> {
+        use crate::Manager;
+
+        let content = self.read_content(self.open_file(context)?, context)?;
+        convert(from_str_all(&content, &self.limits), context, Some(&self.path))
+    }
+
+    /// Deserializes every document of a multi-document YAML string.
+    ///
+    /// # Type Parameters
+    /// - `C`: Type of each document, must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `content`: YAML content as a string.
+    /// - `context`: Contextual information for error reporting.
+    ///
+    /// # Returns
+    /// The documents, in order, or an error if one of them is invalid.
+    pub fn read_all_str<C: serde::de::DeserializeOwned>(
+        content: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<C>> {
+        convert(from_str_all(content, &YamlLimits::default()), context, None)
+    }
+
+    /// Writes documents to the managed file as a multi-document YAML stream.
+    ///
+    /// Documents are separated by `---`. The content is serialized in memory, then the file
+    /// is replaced atomically, so that a failure leaves an existing file untouched.
+    ///
+    /// # Type Parameters
+    /// - `C`: Type of each document, must implement `Serialize`.
+    ///
+    /// # Parameters
+    /// - `documents`: The documents to write, in order.
+    /// - `context`: Contextual information for error reporting.
+    ///
+    /// # Returns
+    /// The path to the file where the documents were written.
+    pub fn write_all<C: serde::Serialize>(
+        &self,
+        documents: &[C],
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        let mut content = Vec::new();
+        {
+            let mut serializer = backend::Serializer::new(&mut content);
+            for document in documents {
+                convert(serde::Serialize::serialize(document, &mut serializer), context, Some(&self.path))?;
+            }
+        }
+        let path = crate::resolver::resolve(None, &self.path, context)?;
+        crate::formats::write_file(self, &path, &content, context, &crate::WriteOptions::default())?;
+        Ok(self.path.clone())
+    }
 }
 
 impl crate::Manager for YamlManager {
@@ -16,16 +422,19 @@ impl crate::Manager for YamlManager {
     ///
     /// # Returns
     /// A new instance of `YamlManager`.
-    fn new(path: String) -> YamlManager {
-        YamlManager { path }
+    fn new<P: AsRef<std::path::Path>>(path: P) -> YamlManager {
+        YamlManager {
+            path: path.as_ref().to_path_buf(),
+            limits: YamlLimits::default(),
+        }
     }
 
     /// Returns the file path associated with this manager.
     ///
     /// # Returns
     /// The file path as a `String`.
-    fn path(&self) -> String {
-        self.path.clone()
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
     }
 
     /// Reads YAML content from a `Read` stream and deserializes it into the target type.
@@ -45,9 +454,8 @@ impl crate::Manager for YamlManager {
         reader: R,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        let mut ctx = context.clone();
-        ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-        cdumay_yaml::convert_yaml_result!(serde_yaml::from_reader(reader), ctx)
+        let content = self.read_content(reader, context)?;
+        convert(from_str(&content, &self.limits), context, Some(&self.path))
     }
 
     /// Serializes data to YAML and writes it to the specified output stream.
@@ -69,26 +477,30 @@ impl crate::Manager for YamlManager {
         data: D,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<()> {
-        let mut ctx = context.clone();
-        ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
-        cdumay_yaml::convert_yaml_result!(serde_yaml::to_writer(writer, &data), ctx)
+        convert(backend::to_writer(writer, &data), context, Some(&self.path))
     }
 
-    /// Deserializes a YAML string into the target type.
+    /// Deserializes raw YAML content, which must be valid UTF-8, into the target type.
     ///
     /// # Type Parameters
     /// - `C`: Type to deserialize into, must implement `DeserializeOwned`.
     ///
     /// # Parameters
-    /// - `content`: YAML content as a string.
+    /// - `content`: YAML content as bytes.
     /// - `context`: Contextual information for error reporting.
     ///
     /// # Returns
     /// Deserialized object or an error.
-    fn read_str<C: serde::de::DeserializeOwned>(
-        content: &str,
+    fn read_bytes<C: serde::de::DeserializeOwned>(
+        content: &[u8],
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        cdumay_yaml::convert_yaml_result!(serde_yaml::from_str(content), context.clone())
+        let content = crate::formats::utf8(content, context)?;
+        convert(from_str(content, &YamlLimits::default()), context, None)
+    }
+
+    /// Renders text as `#` line comments.
+    fn comment(text: &str) -> Option<String> {
+        Some(crate::formats::line_comments(text, "#"))
     }
 }
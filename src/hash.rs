@@ -0,0 +1,90 @@
+//! Stable, format-independent hashing of configuration content.
+//!
+//! The hash covers the logical content of a configuration rather than its bytes: the same
+//! settings written in JSON, YAML or TOML, with keys in any order and any formatting, hash
+//! to the same value. Integers are compared by value regardless of their width or
+//! signedness, but `1` and `1.0` remain distinct.
+
+/// Computes the canonical hash of a configuration value.
+///
+/// # Type Parameters
+/// - `D`: The data type to hash.
+///
+/// # Parameters
+/// - `data`: The configuration to hash.
+/// - `context`: A context used for error details if the operation fails.
+///
+/// # Returns
+/// The hex-encoded SHA-256 digest of the canonical JSON encoding of `data`, with object
+/// keys sorted, or an error if `data` cannot be represented as JSON.
+pub fn config_hash<D: serde::Serialize>(data: &D, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<String> {
+    let value = serde_json::to_value(data).map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to serialize configuration: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    })?;
+    let mut canonical = Vec::new();
+    write_canonical(&value, &mut canonical);
+    Ok(crate::snapshot::sha256_hex(&canonical))
+}
+
+/// Computes the canonical hash of a configuration file, see [`config_hash`].
+///
+/// # Parameters
+/// - `path`: The path to the configuration file. Tilde `~` expansion is supported.
+/// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+/// - `context`: A key-value map used to resolve variables in the file.
+///
+/// # Returns
+/// The hex-encoded canonical hash of the file content, or an error if the file cannot be read.
+///
+/// # Example
+/// ```rust
+/// fn changed(previous: &str) -> cdumay_core::Result<bool> {
+///     let context = std::collections::BTreeMap::new();
+///     Ok(cdumay_config::config_file_hash("~/.config/app.yaml", None, &context)? != previous)
+/// }
+/// ```
+pub fn config_file_hash(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<String> {
+    let value: serde_value::Value = crate::read_config(path, format, context)?;
+    config_hash(&value, context)
+}
+
+/// Writes a JSON value with object keys sorted and no whitespace.
+fn write_canonical(value: &serde_json::Value, out: &mut Vec<u8>) {
+    match value {
+        serde_json::Value::Array(items) => {
+            out.push(b'[');
+            for (index, item) in items.iter().enumerate() {
+                if index > 0 {
+                    out.push(b',');
+                }
+                write_canonical(item, out);
+            }
+            out.push(b']');
+        }
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<_> = map.iter().collect();
+            entries.sort_by_key(|(key, _)| *key);
+            out.push(b'{');
+            for (index, (key, item)) in entries.into_iter().enumerate() {
+                if index > 0 {
+                    out.push(b',');
+                }
+                out.extend_from_slice(serde_json::Value::String(key.clone()).to_string().as_bytes());
+                out.push(b':');
+                write_canonical(item, out);
+            }
+            out.push(b'}');
+        }
+        scalar => out.extend_from_slice(scalar.to_string().as_bytes()),
+    }
+}
@@ -0,0 +1,77 @@
+//! Header comments marking configuration files as generated.
+
+/// A comment written at the top of generated configuration files.
+///
+/// The banner is a template whose placeholders are replaced on each write:
+/// - `{hash}`: the canonical hash of the written content (see [`config_hash`](crate::config_hash)),
+/// - `{file}`: the name of the written file,
+/// - `{timestamp}`: the current UTC time, in RFC 3339 format.
+///
+/// `{timestamp}` changes on every write: leave it out to keep the output of unchanged
+/// content identical and diffs clean. Banners are only written in formats supporting
+/// comments (TOML, YAML, RON and KDL); they are skipped for the other formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HeaderBanner {
+    /// Text of the banner, one comment line per line.
+    template: String,
+}
+
+impl Default for HeaderBanner {
+    /// A do-not-edit warning along with the content hash.
+    fn default() -> Self {
+        Self::new("This file is generated: DO NOT EDIT, changes will be overwritten.\nContent hash: {hash}")
+    }
+}
+
+impl HeaderBanner {
+    /// Creates a banner from a template.
+    ///
+    /// # Parameters
+    /// - `template`: The banner text, which may contain placeholders.
+    ///
+    /// # Returns
+    /// A new `HeaderBanner`.
+    pub fn new(template: &str) -> Self {
+        Self {
+            template: template.to_string(),
+        }
+    }
+
+    /// Creates the default banner, naming the generating tool.
+    ///
+    /// # Parameters
+    /// - `generator`: The name of the tool generating the file.
+    ///
+    /// # Returns
+    /// A new `HeaderBanner`.
+    pub fn generated_by(generator: &str) -> Self {
+        Self::new(&format!(
+            "This file is generated by {}: DO NOT EDIT, changes will be overwritten.\nContent hash: {{hash}}",
+            generator
+        ))
+    }
+
+    /// Returns the template of the banner.
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+
+    /// Renders the banner text.
+    ///
+    /// # Parameters
+    /// - `hash`: The canonical hash of the written content.
+    /// - `path`: The path of the written file.
+    ///
+    /// # Returns
+    /// The banner text, placeholders replaced.
+    pub fn render(&self, hash: &str, path: &std::path::Path) -> String {
+        let mut text = self
+            .template
+            .replace("{hash}", hash)
+            .replace("{file}", &path.file_name().map(|name| name.to_string_lossy()).unwrap_or_default());
+        if text.contains("{timestamp}") {
+            text = text.replace("{timestamp}", &crate::time::rfc3339(std::time::SystemTime::now()));
+        }
+        text
+    }
+}
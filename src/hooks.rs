@@ -0,0 +1,123 @@
+//! Hooks executed after a configuration file was successfully written.
+//!
+//! Hooks are registered on [`WriteOptions::hooks`](crate::WriteOptions::hooks) and run in
+//! order once the file is written and its lock released, e.g. to run a formatter, reload a
+//! service or invalidate a cache.
+
+/// Callback run by a [`WriteHook`].
+type HookCallback = std::sync::Arc<dyn Fn(&std::path::Path) -> cdumay_core::Result<()> + Send + Sync>;
+
+/// What to do when a post-write hook fails.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum HookFailurePolicy {
+    /// Stops at the first failing hook and returns its error. The file stays written.
+    #[default]
+    Fail,
+    /// Logs a warning and runs the remaining hooks.
+    Warn,
+}
+
+/// A named callback run after a configuration file was written.
+#[derive(Clone)]
+pub struct WriteHook {
+    /// Name used in logs and error details.
+    name: String,
+    /// Callback receiving the path of the written file.
+    callback: HookCallback,
+}
+
+impl WriteHook {
+    /// Creates a hook from a callback.
+    ///
+    /// # Parameters
+    /// - `name`: Name used in logs and error details.
+    /// - `callback`: Function receiving the path of the written file.
+    ///
+    /// # Returns
+    /// A new `WriteHook`.
+    pub fn new(name: &str, callback: impl Fn(&std::path::Path) -> cdumay_core::Result<()> + Send + Sync + 'static) -> Self {
+        Self {
+            name: name.to_string(),
+            callback: std::sync::Arc::new(callback),
+        }
+    }
+
+    /// Creates a hook running an external command, which must exit successfully.
+    ///
+    /// Every `{path}` occurrence in the arguments is replaced with the path of the written file.
+    ///
+    /// # Parameters
+    /// - `program`: The program to run, looked up in `PATH` if not absolute.
+    /// - `args`: The program arguments.
+    ///
+    /// # Returns
+    /// A new `WriteHook` named after the command line.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn options() -> cdumay_config::WriteOptions {
+    ///     cdumay_config::WriteOptions {
+    ///         hooks: vec![
+    ///             cdumay_config::WriteHook::command("prettier", &["--write", "{path}"]),
+    ///             cdumay_config::WriteHook::command("systemctl", &["reload", "app.service"]),
+    ///         ],
+    ///         ..Default::default()
+    ///     }
+    /// }
+    /// ```
+    pub fn command(program: &str, args: &[&str]) -> Self {
+        let program = program.to_string();
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        let name = std::iter::once(program.clone()).chain(args.iter().cloned()).collect::<Vec<_>>().join(" ");
+        let command_line = name.clone();
+        Self::new(&name, move |path| {
+            let error = |message: String, origin: String| {
+                crate::WriteHookError::new().with_message(message).with_details(crate::context::bounded({
+                    let mut ctx = std::collections::BTreeMap::new();
+                    ctx.insert("path".to_string(), serde_value::Value::String(path.display().to_string()));
+                    ctx.insert("command".to_string(), serde_value::Value::String(command_line.clone()));
+                    ctx.insert("origin".to_string(), serde_value::Value::String(origin));
+                    ctx
+                }))
+            };
+            let output = std::process::Command::new(&program)
+                .args(args.iter().map(|arg| arg.replace("{path}", &path.to_string_lossy())))
+                .stdin(std::process::Stdio::null())
+                .output()
+                .map_err(|err| error(format!("Failed to run '{}': {}", command_line, err), err.to_string()))?;
+            match output.status.success() {
+                true => Ok(()),
+                false => Err(error(
+                    format!("Command '{}' failed: {}", command_line, output.status),
+                    String::from_utf8_lossy(&output.stderr).trim().to_string(),
+                )
+                .into()),
+            }
+        })
+    }
+
+    /// Returns the name of the hook.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+impl std::fmt::Debug for WriteHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WriteHook").field("name", &self.name).finish_non_exhaustive()
+    }
+}
+
+/// Runs the post-write hooks of a written file according to the failure policy.
+pub(crate) fn run_hooks(hooks: &[WriteHook], policy: HookFailurePolicy, path: &std::path::Path) -> cdumay_core::Result<()> {
+    for hook in hooks {
+        log::debug!("Running post-write hook '{}' on '{}'", hook.name, path.display());
+        if let Err(err) = (hook.callback)(path) {
+            match policy {
+                HookFailurePolicy::Fail => return Err(err),
+                HookFailurePolicy::Warn => log::warn!("Post-write hook '{}' failed on '{}': {}", hook.name, path.display(), err),
+            }
+        }
+    }
+    Ok(())
+}
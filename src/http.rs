@@ -0,0 +1,161 @@
+//! Configuration documents served over HTTP(S).
+//!
+//! With the `http` feature, [`read_config`](crate::read_config) fetches `http://` and
+//! `https://` paths with an [`HttpSource`] and hands the body to the manager of the
+//! requested format, or else of the `Content-Type` of the response or of the URL extension:
+//!
+//! ```rust
+//! fn load() -> cdumay_core::Result<serde_json::Value> {
+//!     let context = std::collections::BTreeMap::new();
+//!     cdumay_config::read_config("https://config.internal/app.json", None, &context)
+//! }
+//! ```
+
+/// A configuration document fetched with an HTTP `GET` request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HttpSource {
+    /// URL of the document.
+    url: String,
+    /// Headers sent with the request (e.g. `Authorization`).
+    headers: Vec<(String, String)>,
+    /// Maximum duration of the whole request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+}
+
+impl HttpSource {
+    /// Creates a source for the document at `url`.
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            headers: Vec::new(),
+            timeout: None,
+        }
+    }
+
+    /// Returns `true` if a configuration path is an `http://` or `https://` URL.
+    pub fn is_url(path: &str) -> bool {
+        ["http://", "https://"]
+            .iter()
+            .any(|scheme| path.get(..scheme.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme)))
+    }
+
+    /// Adds a header sent with the request.
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((name.to_string(), value.to_string()));
+        self
+    }
+
+    /// Sets the maximum duration of the whole request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the URL of the document.
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Fetches the document.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the request fails.
+    ///
+    /// # Returns
+    /// The body of the response, or an error holding the `url` (and the `status` of the
+    /// response, if any) in its details when the request fails or the response is not a
+    /// success.
+    pub fn fetch(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<Vec<u8>> {
+        self.fetch_typed(context).map(|(content, _)| content)
+    }
+
+    /// Fetches the document like [`HttpSource::fetch`], with the format declared by the
+    /// `Content-Type` of the response, if known.
+    pub(crate) fn fetch_typed(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<(Vec<u8>, Option<crate::ContentFormat>)> {
+        let error = |message: String, status: Option<u16>, origin: Option<String>| -> cdumay_core::Error {
+            crate::ConfigurationFileError::new()
+                .with_message(message)
+                .with_details(crate::context::bounded(crate::context::with_request(context, &self.url, status, origin)))
+                .into()
+        };
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let mut request = agent.get(&self.url);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+        let mut response = request
+            .call()
+            .map_err(|err| error(format!("Failed to fetch '{}': {}", self.url, err), None, Some(err.to_string())))?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(error(
+                format!("Failed to fetch '{}': HTTP status {}", self.url, status),
+                Some(status.as_u16()),
+                None,
+            ));
+        }
+        let served = response
+            .headers()
+            .get("content-type")
+            .and_then(|value| value.to_str().ok())
+            .and_then(crate::ContentFormat::from_media_type);
+        let content = response.body_mut().read_to_vec().map_err(|err| {
+            error(
+                format!("Failed to read the response of '{}': {}", self.url, err),
+                Some(status.as_u16()),
+                Some(err.to_string()),
+            )
+        })?;
+        Ok((content, served))
+    }
+
+    /// Fetches the document and deserializes it like [`read_config_with`](crate::read_config_with).
+    ///
+    /// The options' lock, retry policy and resolver only apply to files and are not used.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type to deserialize the configuration into.
+    ///
+    /// # Parameters
+    /// - `format`: Optional format specifier. When not provided, the format is inferred from
+    ///   the `Content-Type` of the response, else from the extension of the URL, and defaults
+    ///   to `JSON`.
+    /// - `context`: A templating context used to resolve variables inside the configuration.
+    /// - `options`: The read options to apply.
+    ///
+    /// # Returns
+    /// The deserialized configuration of type `C`, or an error if fetching or parsing fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn load(token: &str) -> cdumay_core::Result<serde_json::Value> {
+    ///     cdumay_config::HttpSource::new("https://config.internal/app.json")
+    ///         .with_header("Authorization", &format!("Bearer {}", token))
+    ///         .with_timeout(std::time::Duration::from_secs(10))
+    ///         .read_config_with(None, &std::collections::BTreeMap::new(), &cdumay_config::ReadOptions::default())
+    /// }
+    /// ```
+    pub fn read_config_with<C: serde::de::DeserializeOwned>(
+        &self,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        log::info!("Fetching config '{}'", self.url);
+        let (content, served) = self.fetch_typed(context)?;
+        let mut context = context.clone();
+        context.insert("url".to_string(), serde_value::Value::String(self.url.clone()));
+        let format = format
+            .or(served)
+            .or_else(|| crate::ContentFormat::from_uri(&self.url))
+            .unwrap_or_default();
+        format.read_content_with(&self.url, content, &context, options)
+    }
+}
@@ -0,0 +1,160 @@
+//! Composition of configuration files from reusable fragments.
+//!
+//! With [`ReadOptions::includes`](crate::ReadOptions::includes), a map holding an
+//! `"$include"` key is replaced by the content of the files it names, in any supported
+//! format, and its other keys are deep-merged on top of them:
+//!
+//! ```json
+//! {
+//!     "database": { "$include": "common/database.json", "pool_size": 20 },
+//!     "logging": { "$include": ["common/logging.yaml", "logging.prod.yaml"] }
+//! }
+//! ```
+//!
+//! In YAML, the `!include` tag includes a file as the value of a key:
+//!
+//! ```yaml
+//! database: !include common/database.yaml
+//! ```
+//!
+//! Relative paths are resolved against the directory of the including file, the format of
+//! included files is inferred from their extension (see [`FormatRegistry`](crate::FormatRegistry))
+//! and included files may include other files, a file including itself, directly or not,
+//! being an error.
+
+/// Key of the maps including other configuration files.
+pub(crate) const INCLUDE_KEY: &str = "$include";
+
+/// Replaces the include directives of the value tree read from a configuration file.
+///
+/// # Parameters
+/// - `value`: The value tree of the file.
+/// - `path`: Path of the file, which relative includes are resolved against.
+/// - `context`: Context used for reading included files and for error reporting.
+/// - `options`: The read options, applied to the included files except for the options
+///   applied to the whole composed tree (renames, schema version, unknown keys, secrets).
+pub(crate) fn resolve(
+    value: &mut serde_value::Value,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<()> {
+    let options = crate::ReadOptions {
+        renames: None,
+        schema_version: None,
+        unknown_keys: crate::UnknownKeyPolicy::Ignore,
+        secrets: None,
+        includes: false,
+        ..options.clone()
+    };
+    let mut stack = vec![canonical(path)];
+    resolve_in(value, path, &mut stack, context, &options)
+}
+
+/// Replaces the include directives of a value tree read from `path`, `stack` holding the
+/// chain of files including it.
+fn resolve_in(
+    value: &mut serde_value::Value,
+    path: &std::path::Path,
+    stack: &mut Vec<std::path::PathBuf>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<()> {
+    match value {
+        serde_value::Value::Map(entries) => {
+            let include = entries.remove(&serde_value::Value::String(INCLUDE_KEY.to_string()));
+            for item in entries.values_mut() {
+                resolve_in(item, path, stack, context, options)?;
+            }
+            let Some(include) = include else {
+                return Ok(());
+            };
+            let invalid = || -> cdumay_core::Error {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!(
+                        "Invalid include directive in '{}': expected a path or a list of paths",
+                        path.display()
+                    ))
+                    .with_details(crate::context::bounded(crate::context::with_path(context, path)))
+                    .into()
+            };
+            let targets = match include {
+                serde_value::Value::String(target) => vec![target],
+                serde_value::Value::Seq(targets) => targets
+                    .into_iter()
+                    .map(|target| match target {
+                        serde_value::Value::String(target) => Ok(target),
+                        _ => Err(invalid()),
+                    })
+                    .collect::<cdumay_core::Result<_>>()?,
+                _ => return Err(invalid()),
+            };
+            let mut included: Option<serde_value::Value> = None;
+            for target in targets {
+                let fragment = load(&target, path, stack, context, options)?;
+                included = Some(match included {
+                    Some(base) => crate::merge::merge_values(base, fragment),
+                    None => fragment,
+                });
+            }
+            let overlay = std::mem::take(entries);
+            *value = match included {
+                Some(base) if overlay.is_empty() => base,
+                Some(base) => crate::merge::merge_values(base, serde_value::Value::Map(overlay)),
+                None => serde_value::Value::Map(overlay),
+            };
+        }
+        serde_value::Value::Seq(items) => {
+            for item in items {
+                resolve_in(item, path, stack, context, options)?;
+            }
+        }
+        serde_value::Value::Option(Some(item)) | serde_value::Value::Newtype(item) => resolve_in(item, path, stack, context, options)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Reads a file included from `from`, resolving its own include directives.
+fn load(
+    target: &str,
+    from: &std::path::Path,
+    stack: &mut Vec<std::path::PathBuf>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<serde_value::Value> {
+    let expanded = shellexpand::tilde(target);
+    let path = match crate::scheme::scheme(target) {
+        Some(_) => std::path::PathBuf::from(target),
+        None => match from.parent() {
+            Some(directory) => directory.join(expanded.as_ref()),
+            None => std::path::PathBuf::from(expanded.as_ref()),
+        },
+    };
+    let canonical = canonical(&path);
+    if stack.contains(&canonical) {
+        let chain: Vec<String> = stack.iter().chain([&canonical]).map(|path| path.display().to_string()).collect();
+        return Err(crate::ConfigurationFileError::new()
+            .with_message(format!("Include cycle in configuration files: {}", chain.join(" -> ")))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, from);
+                ctx.insert(
+                    "includes".to_string(),
+                    serde_value::Value::Seq(chain.into_iter().map(serde_value::Value::String).collect()),
+                );
+                ctx
+            }))
+            .into());
+    }
+    let mut value: serde_value::Value = crate::FormatRegistry::global().read_config_with(&path.to_string_lossy(), None, context, options)?;
+    stack.push(canonical);
+    let resolved = resolve_in(&mut value, &path, stack, context, options);
+    stack.pop();
+    resolved.map(|_| value)
+}
+
+/// Returns the canonical form of a path identifying a file in the include chain, or the path
+/// itself if it cannot be canonicalized (e.g. a URI).
+fn canonical(path: &std::path::Path) -> std::path::PathBuf {
+    std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
@@ -0,0 +1,260 @@
+//! Random access over large JSON and ND-JSON array configuration files.
+//!
+//! Instead of deserializing a whole generated file, [`open_indexed`] scans it once and
+//! keeps only the byte range of each top-level element. Elements are then deserialized
+//! on demand with [`IndexedConfig::get`].
+
+use cdumay_core::ErrorConverter;
+use std::io::{BufRead, Read, Seek};
+
+/// Layout of an indexed file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Layout {
+    /// A single JSON document whose root is an array.
+    Array,
+    /// One JSON document per line (ND-JSON / JSON Lines).
+    Lines,
+}
+
+/// A lightweight offset index over the elements of a JSON array or ND-JSON file.
+///
+/// The index stores two offsets per element, so memory usage does not depend on the
+/// size of the elements themselves.
+#[derive(Debug)]
+pub struct IndexedConfig {
+    /// Path to the indexed file.
+    path: String,
+    /// Open handle used to seek to the requested elements.
+    file: std::sync::Mutex<std::fs::File>,
+    /// Byte ranges (`start..end`) of each element.
+    entries: Vec<(u64, u64)>,
+    /// Optional upper bound, in bytes, of a single element.
+    max_entry_size: Option<u64>,
+}
+
+/// Opens a JSON array or ND-JSON file and builds an offset index of its elements.
+///
+/// Files with a `.ndjson` or `.jsonl` extension, or whose content does not start with `[`,
+/// are indexed line by line. Otherwise the root array is scanned and each of its elements
+/// is indexed.
+///
+/// # Parameters
+/// - `path`: Path to the file. Tilde `~` expansion is supported.
+/// - `context`: A context used for error details if the operation fails.
+///
+/// # Returns
+/// An [`IndexedConfig`] ready to deserialize elements on demand.
+///
+/// # Example
+/// ```rust
+/// fn tenth() -> cdumay_core::Result<serde_json::Value> {
+///     let context = std::collections::BTreeMap::new();
+///     let index = cdumay_config::open_indexed("~/generated/rules.json", &context)?;
+///     index.get(9, &context)
+/// }
+/// ```
+pub fn open_indexed(path: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<IndexedConfig> {
+    let path = shellexpand::tilde(path).to_string();
+    log::info!("Indexing config file '{}'", path);
+    let error = |message: String, origin: String| {
+        crate::ConfigurationFileError::new().with_message(message).with_details(crate::context::bounded({
+            let mut ctx = context.clone();
+            ctx.insert("path".to_string(), serde_value::Value::String(path.clone()));
+            ctx.insert("origin".to_string(), serde_value::Value::String(origin));
+            ctx
+        }))
+    };
+    let mut file = std::fs::File::open(&path).map_err(|err| error(format!("Failed to open file: {}", err), err.to_string()))?;
+    let by_extension = std::path::Path::new(&path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("ndjson") || ext.eq_ignore_ascii_case("jsonl"))
+        .unwrap_or(false);
+    let entries = scan(std::io::BufReader::new(&mut file), by_extension)
+        .map_err(|err| error(format!("Failed to index file: {}", err), err.to_string()))?;
+    Ok(IndexedConfig {
+        path,
+        file: std::sync::Mutex::new(file),
+        entries,
+        max_entry_size: None,
+    })
+}
+
+impl IndexedConfig {
+    /// Sets the maximum size, in bytes, of an element that [`IndexedConfig::get`] accepts to load.
+    ///
+    /// Elements bigger than this bound are rejected instead of being read into memory.
+    pub fn with_max_entry_size(mut self, size: u64) -> Self {
+        self.max_entry_size = Some(size);
+        self
+    }
+
+    /// Returns the path of the indexed file.
+    pub fn path(&self) -> String {
+        self.path.clone()
+    }
+
+    /// Returns the number of indexed elements.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the file contains no element.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Reads and deserializes the element at the given position.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type into which the element will be deserialized.
+    ///
+    /// # Parameters
+    /// - `index`: Zero-based position of the element.
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// The deserialized element, or an error if the index is out of range, the element
+    /// exceeds the configured size bound, or its content is invalid.
+    pub fn get<C: serde::de::DeserializeOwned>(
+        &self,
+        index: usize,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let details = || {
+            let mut ctx = context.clone();
+            ctx.insert("path".to_string(), serde_value::Value::String(self.path()));
+            ctx.insert("index".to_string(), serde_value::Value::U64(index as u64));
+            ctx
+        };
+        let (start, end) = *self.entries.get(index).ok_or_else(|| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Index {} out of range (len: {})", index, self.len()))
+                .with_details(crate::context::bounded(details()))
+        })?;
+        if let Some(max) = self.max_entry_size
+            && end - start > max
+        {
+            return Err(crate::ConfigurationFileError::new()
+                .with_message(format!("Element {} is {} bytes long, exceeding the limit of {} bytes", index, end - start, max))
+                .with_details(crate::context::bounded(details()))
+                .into());
+        }
+        let mut buffer = vec![0; (end - start) as usize];
+        {
+            let mut file = self.file.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+            file.seek(std::io::SeekFrom::Start(start))
+                .and_then(|_| file.read_exact(&mut buffer))
+                .map_err(|err| {
+                    crate::ConfigurationFileError::new()
+                        .with_message(format!("Failed to read element {}: {}", index, err))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = details();
+                            ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                            ctx
+                        }))
+                })?;
+        }
+        cdumay_json::convert_json_result!(serde_json::from_slice(&buffer), crate::context::bounded(details()))
+    }
+}
+
+/// Scans a JSON array or ND-JSON stream and returns the byte range of each element.
+fn scan<R: BufRead>(mut reader: R, force_lines: bool) -> std::io::Result<Vec<(u64, u64)>> {
+    let mut entries = Vec::new();
+    let mut layout = if force_lines { Some(Layout::Lines) } else { None };
+    let mut position: u64 = 0;
+    // Array scanning state.
+    let mut depth: usize = 0;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut closed = false;
+    // Start of the current element and position right after its last non-blank byte.
+    let mut start: Option<u64> = None;
+    let mut last: u64 = 0;
+
+    loop {
+        let buffer = reader.fill_buf()?;
+        if buffer.is_empty() {
+            break;
+        }
+        let length = buffer.len();
+        for &byte in buffer {
+            let blank = byte.is_ascii_whitespace();
+            match layout {
+                None if blank => {}
+                None => {
+                    layout = Some(if byte == b'[' { Layout::Array } else { Layout::Lines });
+                    if byte == b'[' {
+                        depth = 1;
+                    } else {
+                        start = Some(position);
+                        last = position + 1;
+                    }
+                }
+                Some(Layout::Lines) => {
+                    if byte == b'\n' {
+                        if let Some(begin) = start.take() {
+                            entries.push((begin, last));
+                        }
+                    } else if !blank {
+                        start.get_or_insert(position);
+                        last = position + 1;
+                    }
+                }
+                Some(Layout::Array) => {
+                    if closed {
+                        if !blank {
+                            return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, "trailing content after the root array"));
+                        }
+                    } else if in_string {
+                        if escaped {
+                            escaped = false;
+                        } else if byte == b'\\' {
+                            escaped = true;
+                        } else if byte == b'"' {
+                            in_string = false;
+                        }
+                        last = position + 1;
+                    } else {
+                        match byte {
+                            b',' | b']' if depth == 1 => {
+                                if let Some(begin) = start.take() {
+                                    entries.push((begin, last));
+                                }
+                                if byte == b']' {
+                                    closed = true;
+                                    depth = 0;
+                                }
+                            }
+                            _ if blank => {}
+                            _ => {
+                                start.get_or_insert(position);
+                                last = position + 1;
+                                match byte {
+                                    b'"' => in_string = true,
+                                    b'[' | b'{' => depth += 1,
+                                    b']' | b'}' => depth = depth.saturating_sub(1),
+                                    _ => {}
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            position += 1;
+        }
+        reader.consume(length);
+    }
+
+    match layout {
+        Some(Layout::Array) if !closed => Err(std::io::Error::new(std::io::ErrorKind::UnexpectedEof, "unterminated root array")),
+        Some(Layout::Lines) => {
+            if let Some(begin) = start {
+                entries.push((begin, last));
+            }
+            Ok(entries)
+        }
+        _ => Ok(entries),
+    }
+}
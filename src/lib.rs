@@ -11,10 +11,60 @@
 //! - Generic configuration management through the `Manager` trait
 //! - Support for multiple serialization formats (with feature flags):
 //!   - JSON (default)
-//!   - TOML (feature: "toml")
-//!   - YAML (feature: "yaml")
-//!   - XML (feature: "xml")
-//! - Type-safe error handling with the `cdumay_core::Error` struct
+//!   - JSON Lines / ND-JSON (default)
+//!   - JSON5 (feature: "json5")
+//!   - TOML (feature: "toml"), with comment-preserving edits through `TomlDocument`
+//!   - YAML (feature: "yaml", or "serde_yaml" for the legacy `serde_yaml` backend)
+//!   - XML (feature: "xml", or "quick-xml" for the faster `quick-xml` backend)
+//!   - RON (feature: "ron")
+//!   - KDL (feature: "kdl")
+//!   - MessagePack (feature: "msgpack")
+//!   - Apple plist, XML and binary (feature: "plist")
+//! - JSON Schema generation from configuration types (feature: "schemars")
+//! - Configuration paths dispatched on their URI scheme (`file://`, `env://`, custom schemes
+//!   such as `vault://` registered in a `SchemeRegistry`)
+//! - Composition of configuration files from fragments named by `"$include"` keys or the
+//!   YAML `!include` tag, resolved relative to the including file with cycle detection
+//! - Environment profiles through a `ProfileLoader`, merging `app.<profile>.yaml` over
+//!   `app.yaml` for the profile given explicitly or by an environment variable
+//! - Platform configuration directories of applications (`$XDG_CONFIG_HOME`,
+//!   `~/Library/Application Support`, `%APPDATA%`) through `ConfigLocation::for_app`
+//! - Lookup of the first existing configuration file among candidate paths with
+//!   `read_first_config`
+//! - Drop-in directories merged in lexical order from a glob pattern (e.g.
+//!   `/etc/app/conf.d/*.yaml`) with `read_config_dir`
+//! - Remote configuration documents fetched over HTTP(S) (feature: "http")
+//! - Configuration documents read from S3-compatible object stores with `s3://` paths
+//!   (feature: "s3")
+//! - Configuration read from the keys of an etcd cluster under a prefix (feature: "etcd")
+//! - Configuration read from a Redis string or hash key, reloaded on pub/sub notifications
+//!   (feature: "redis")
+//! - Vault secrets read from a HashiCorp Vault KV v2 mount (feature: "vault-hashicorp"),
+//!   AWS Secrets Manager (feature: "aws"), Azure Key Vault (feature: "azure") or Google
+//!   Cloud Secret Manager (feature: "gcp")
+//! - Vault secrets read from the systemd credentials of a service (`$CREDENTIALS_DIRECTORY`)
+//! - A `SecretProvider` trait fetching secrets by alias, implemented by the vault file and
+//!   the remote stores, which can be plugged behind the loaded secrets for missing aliases
+//! - `vault://<alias>/<field>` references in configuration values, replaced by vault secrets
+//!   before deserialization through `ReadOptions::secrets`
+//! - Encryption of configuration files at rest through a `Cipher`, with age recipients
+//!   and identities (feature: "age"), OpenPGP keys of the gpg keyring (feature: "gpg") or
+//!   a passphrase with AES-256-GCM (feature: "aes-gcm")
+//! - Vault secrets stored encrypted, decrypted on demand with the `Cipher` of a `VaultKeyProvider`
+//! - `Secret` and `SecretString` wrappers masking values as `***` when formatted and zeroizing
+//!   them on drop, used for the values of vault secrets
+//! - Export of vault secrets as environment variables of the process or a `.env` file, for
+//!   child processes only reading their environment
+//! - Rendering of vault secrets into template files with `{{ secret "<alias>" "<field>" }}`
+//!   placeholders, written only readable by their owner
+//! - Audit of the accesses to vault secrets (alias, context, time and outcome, never the
+//!   value) through an `AuditSink`, such as the `log` based `LogAuditSink`
+//! - Type-safe error handling with a single error type, `cdumay_core::Error`, re-exported
+//!   along with its `Result` alias as `cdumay_config::Error` and `cdumay_config::Result`
+//! - Deserialization errors locating the failing key (e.g. `server.listeners[2].port`) in
+//!   their message and `key_path` detail
+//! - Export of errors as a JSON envelope (code, kind, class, message, details) for APIs,
+//!   through `error_to_json`
 //!
 //! # Example Usage
 //!
@@ -43,9 +93,137 @@
 //! }
 //! ```
 //!
+#[cfg(any(feature = "aws", feature = "s3"))]
+mod aws;
+#[cfg(any(feature = "aws", feature = "s3"))]
+pub use aws::*;
+mod bootstrap;
+pub use bootstrap::*;
+pub mod bytes;
+mod cipher;
+pub use cipher::*;
+#[cfg(feature = "aes-gcm")]
+mod cipher_aes;
+#[cfg(feature = "aes-gcm")]
+pub use cipher_aes::*;
+#[cfg(feature = "age")]
+mod cipher_age;
+#[cfg(feature = "age")]
+pub use cipher_age::*;
+#[cfg(feature = "gpg")]
+mod cipher_gpg;
+#[cfg(feature = "gpg")]
+pub use cipher_gpg::*;
+mod cleanup;
+pub use cleanup::*;
+mod context;
+mod envelope;
+pub use envelope::*;
 mod errors;
 pub use errors::*;
+#[cfg(feature = "etcd")]
+mod etcd;
+#[cfg(feature = "etcd")]
+pub use etcd::*;
 mod formats;
 pub use formats::*;
+mod hash;
+pub use hash::*;
+mod header;
+pub use header::*;
+mod hooks;
+pub use hooks::*;
+#[cfg(feature = "http")]
+mod http;
+#[cfg(feature = "http")]
+pub use http::*;
+pub mod include;
+mod indexed;
+pub use indexed::*;
+mod live;
+pub use live::*;
+mod location;
+pub use location::*;
+mod lock;
+pub use lock::*;
+mod matrix;
+pub use matrix::*;
+mod merge;
+mod options;
+pub use options::*;
+mod preview;
+pub use preview::*;
+mod profile;
+pub use profile::*;
+mod raw;
+pub use raw::*;
+mod redact;
+pub use redact::*;
+#[cfg(feature = "redis")]
+mod redis;
+#[cfg(feature = "redis")]
+pub use redis::*;
+mod registry;
+pub use registry::*;
+mod render;
+pub use render::*;
+mod rename;
+pub use rename::*;
+mod report;
+pub use report::*;
+mod resolver;
+pub use resolver::*;
+mod retry;
+pub use retry::*;
+#[cfg(feature = "s3")]
+mod s3;
+#[cfg(feature = "s3")]
+pub use s3::*;
+mod scheme;
+pub use scheme::*;
+mod secret;
+pub use secret::*;
+#[cfg(feature = "schemars")]
+mod schema;
+#[cfg(feature = "schemars")]
+pub use schema::*;
+mod search;
+pub use search::*;
+mod snapshot;
+pub use snapshot::*;
+mod suggest;
+mod tenant;
+pub use tenant::*;
+mod time;
+#[cfg(feature = "tracing-reload")]
+mod tracing_reload;
+#[cfg(feature = "tracing-reload")]
+pub use tracing_reload::*;
+mod tracking;
+pub use tracking::*;
+mod update;
+pub use update::*;
+mod validate;
+pub use validate::*;
 mod vault;
 pub use vault::*;
+#[cfg(feature = "aws")]
+mod vault_aws;
+#[cfg(feature = "aws")]
+pub use vault_aws::*;
+#[cfg(feature = "azure")]
+mod vault_azure;
+#[cfg(feature = "azure")]
+pub use vault_azure::*;
+#[cfg(feature = "gcp")]
+mod vault_gcp;
+#[cfg(feature = "gcp")]
+pub use vault_gcp::*;
+#[cfg(feature = "vault-hashicorp")]
+mod vault_hashicorp;
+#[cfg(feature = "vault-hashicorp")]
+pub use vault_hashicorp::*;
+mod vault_systemd;
+pub use vault_systemd::*;
+mod version;
+pub use version::*;
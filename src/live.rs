@@ -0,0 +1,251 @@
+//! Hot-reloadable configuration handle optimized for read-heavy workloads.
+//!
+//! [`LiveConfig`] publishes the current configuration through an [`arc_swap::ArcSwap`]:
+//!
+//! - [`LiveConfig::load_guard`] never takes a lock, never waits for a writer and does not
+//!   touch the reference count of the shared value, so it can be called on every request of
+//!   a hot path. [`LiveConfig::load`] returns an owned `Arc`, at the cost of a reference
+//!   count update contended across cores.
+//! - [`LiveConfig::reload`] and [`LiveConfig::store`] swap the whole configuration
+//!   atomically: readers observe either the previous or the new value, never a mix.
+//! - Values already handed out stay valid until their last `Arc` is dropped, even after a
+//!   reload.
+//! - A failed reload leaves the published value untouched.
+//!
+//! Configurations read from a file can be watched with [`LiveConfig::watch`], which polls
+//! the file modification time and reloads it when it changes. Callbacks registered with
+//! [`LiveConfig::on_change`] are notified of every published value.
+
+/// Function producing a fresh configuration value.
+type Loader<C> = Box<dyn Fn() -> cdumay_core::Result<C> + Send + Sync>;
+
+/// Callback notified when a new configuration value is published.
+type Subscriber<C> = std::sync::Arc<dyn Fn(&C) + Send + Sync>;
+
+/// A shared, atomically reloadable configuration.
+pub struct LiveConfig<C> {
+    /// Currently published configuration.
+    current: arc_swap::ArcSwap<C>,
+    /// Source of new values for [`LiveConfig::reload`].
+    loader: Loader<C>,
+    /// Number of values published since creation.
+    generation: std::sync::atomic::AtomicU64,
+    /// Callbacks notified on each published value.
+    subscribers: std::sync::RwLock<Vec<Subscriber<C>>>,
+    /// File the configuration is read from, if any.
+    path: Option<String>,
+}
+
+impl<C> LiveConfig<C> {
+    /// Creates a live configuration from a loader, which is called once immediately.
+    ///
+    /// # Parameters
+    /// - `loader`: Function producing the configuration, called again on each reload.
+    ///
+    /// # Returns
+    /// A new `LiveConfig`, or the error returned by the first load.
+    pub fn new(loader: impl Fn() -> cdumay_core::Result<C> + Send + Sync + 'static) -> cdumay_core::Result<Self> {
+        let initial = loader()?;
+        Ok(Self {
+            current: arc_swap::ArcSwap::from_pointee(initial),
+            loader: Box::new(loader),
+            generation: std::sync::atomic::AtomicU64::new(0),
+            subscribers: std::sync::RwLock::new(Vec::new()),
+            path: None,
+        })
+    }
+
+    /// Returns the current configuration.
+    ///
+    /// This call is lock-free and never blocks on a concurrent reload. It increments the
+    /// reference count of the shared value: prefer [`LiveConfig::load_guard`] on hot paths
+    /// which do not keep the value.
+    pub fn load(&self) -> std::sync::Arc<C> {
+        self.current.load_full()
+    }
+
+    /// Returns a short-lived guard on the current configuration.
+    ///
+    /// This call is wait-free in the common case and does not update the reference count of
+    /// the shared value, so concurrent readers do not contend on it. The guard should not be
+    /// held for long: holding many guards at once makes further loads slower.
+    ///
+    /// # Example
+    /// ```rust
+    /// let live = cdumay_config::LiveConfig::new(|| Ok(String::from("upstream:80"))).unwrap();
+    /// assert_eq!(live.load_guard().as_str(), "upstream:80");
+    /// ```
+    pub fn load_guard(&self) -> arc_swap::Guard<std::sync::Arc<C>> {
+        self.current.load()
+    }
+
+    /// Reloads the configuration from its loader and publishes it.
+    ///
+    /// # Returns
+    /// An error if the loader fails, in which case the current value is kept.
+    pub fn reload(&self) -> cdumay_core::Result<()> {
+        let value = (self.loader)()?;
+        self.store(value);
+        Ok(())
+    }
+
+    /// Publishes a new configuration value and notifies the subscribers.
+    pub fn store(&self, value: C) {
+        let value = std::sync::Arc::new(value);
+        self.current.store(value.clone());
+        self.generation.fetch_add(1, std::sync::atomic::Ordering::Release);
+        // Callbacks run without the lock held, so that they may register other callbacks.
+        let subscribers: Vec<Subscriber<C>> = self.subscribers.read().unwrap_or_else(|poisoned| poisoned.into_inner()).clone();
+        for subscriber in subscribers {
+            subscriber(&value);
+        }
+    }
+
+    /// Registers a callback invoked with every value published after this call.
+    ///
+    /// Callbacks run on the thread performing the reload and should return quickly.
+    pub fn on_change(&self, callback: impl Fn(&C) + Send + Sync + 'static) {
+        self.subscribers
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .push(std::sync::Arc::new(callback));
+    }
+
+    /// Returns the path of the file the configuration is read from, if any.
+    pub fn path(&self) -> Option<&str> {
+        self.path.as_deref()
+    }
+
+    /// Returns how many values have been published since creation.
+    pub fn generation(&self) -> u64 {
+        self.generation.load(std::sync::atomic::Ordering::Acquire)
+    }
+}
+
+impl<C: serde::de::DeserializeOwned + Send + Sync + 'static> LiveConfig<C> {
+    /// Creates a live configuration reading the given file on each reload.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the configuration file. Tilde `~` expansion is supported.
+    /// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+    /// - `context`: A templating context used to resolve variables inside the configuration.
+    ///
+    /// # Returns
+    /// A new `LiveConfig`, or an error if the file cannot be read.
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(serde::Deserialize)]
+    /// struct Proxy {
+    ///     upstream: String,
+    /// }
+    ///
+    /// fn serve() -> cdumay_core::Result<()> {
+    ///     let live = cdumay_config::LiveConfig::<Proxy>::from_file("~/.config/proxy.json", None, std::collections::BTreeMap::new())?;
+    ///     let upstream = live.load().upstream.clone();
+    ///     live.reload()
+    /// }
+    /// ```
+    pub fn from_file(
+        path: &str,
+        format: Option<crate::ContentFormat>,
+        context: std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Self> {
+        Self::from_file_with(path, format, context, crate::ReadOptions::default())
+    }
+
+    /// Creates a live configuration reading the given file on each reload, applying the given
+    /// read options.
+    ///
+    /// The path is mapped by the options' [`PathResolver`](crate::PathResolver) once, and the
+    /// resolved path is the one [watched](LiveConfig::watch).
+    ///
+    /// # Parameters
+    /// - `path`: Path to the configuration file.
+    /// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+    /// - `context`: A templating context used to resolve variables inside the configuration.
+    /// - `options`: The read options applied on each reload.
+    ///
+    /// # Returns
+    /// A new `LiveConfig`, or an error if the path cannot be resolved or the file cannot be read.
+    pub fn from_file_with(
+        path: &str,
+        format: Option<crate::ContentFormat>,
+        context: std::collections::BTreeMap<String, serde_value::Value>,
+        options: crate::ReadOptions,
+    ) -> cdumay_core::Result<Self> {
+        let resolved = crate::resolver::resolve(options.resolver.as_ref(), std::path::Path::new(path), &context)?;
+        let source = path.to_string();
+        let mut live = Self::new(move || crate::read_config_with(&source, format, &context, &options))?;
+        live.path = Some(resolved.to_string_lossy().into_owned());
+        Ok(live)
+    }
+}
+
+impl<C: Send + Sync + 'static> LiveConfig<C> {
+    /// Watches the configuration file and reloads it whenever its modification time changes.
+    ///
+    /// The file is polled from a background thread, which stops when the returned
+    /// [`WatchHandle`] or the last reference to the configuration is dropped. Failed reloads
+    /// are logged and keep the previous value.
+    ///
+    /// # Parameters
+    /// - `interval`: Delay between two checks of the file.
+    ///
+    /// # Returns
+    /// A handle stopping the watch on drop, or an error if the configuration was not
+    /// created from a file.
+    pub fn watch(self: &std::sync::Arc<Self>, interval: std::time::Duration) -> cdumay_core::Result<WatchHandle> {
+        let path = self.path.clone().ok_or_else(|| {
+            crate::ConfigurationFileError::new().with_message("Only configurations read from a file can be watched".to_string())
+        })?;
+        let modified = |path: &str| std::fs::metadata(path).and_then(|metadata| metadata.modified()).ok();
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let live = std::sync::Arc::downgrade(self);
+        let thread_stop = stop.clone();
+        let mut last = modified(&path);
+        std::thread::spawn(move || {
+            while !thread_stop.load(std::sync::atomic::Ordering::Relaxed) {
+                std::thread::sleep(interval);
+                let Some(live) = live.upgrade() else {
+                    break;
+                };
+                let current = modified(&path);
+                if current != last {
+                    last = current;
+                    match live.reload() {
+                        Ok(()) => log::info!("Reloaded config file '{}'", path),
+                        Err(err) => log::error!("Failed to reload config file '{}': {}", path, err),
+                    }
+                }
+            }
+        });
+        Ok(WatchHandle { stop })
+    }
+}
+
+/// Handle on the watch of a configuration source, such as a file watch started with
+/// [`LiveConfig::watch`].
+///
+/// The watch stops when the handle is dropped.
+#[derive(Debug)]
+pub struct WatchHandle {
+    /// Flag telling the polling thread to stop.
+    pub(crate) stop: std::sync::Arc<std::sync::atomic::AtomicBool>,
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.stop.store(true, std::sync::atomic::Ordering::Relaxed);
+    }
+}
+
+impl<C: std::fmt::Debug> std::fmt::Debug for LiveConfig<C> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LiveConfig")
+            .field("current", &self.load())
+            .field("generation", &self.generation())
+            .field("path", &self.path)
+            .finish()
+    }
+}
@@ -0,0 +1,145 @@
+//! Platform configuration directories of applications.
+//!
+//! [`ConfigLocation::for_app`] resolves the directory where the platform expects the
+//! configuration of an application:
+//!
+//! | Platform | Directory                                     |
+//! |----------|-----------------------------------------------|
+//! | Linux    | `$XDG_CONFIG_HOME/<app>` or `~/.config/<app>` |
+//! | macOS    | `~/Library/Application Support/<app>`         |
+//! | Windows  | `%APPDATA%\<app>`                             |
+//!
+//! and reads the files of that directory with [`read_config`](crate::read_config).
+
+/// The configuration directory of an application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigLocation {
+    /// The configuration directory.
+    dir: std::path::PathBuf,
+}
+
+impl ConfigLocation {
+    /// Creates a location from an explicit configuration directory.
+    ///
+    /// # Parameters
+    /// - `dir`: The configuration directory. Tilde `~` expansion is supported.
+    pub fn new(dir: &str) -> Self {
+        Self {
+            dir: std::path::PathBuf::from(shellexpand::tilde(dir).to_string()),
+        }
+    }
+
+    /// Resolves the platform configuration directory of an application.
+    ///
+    /// On Unix platforms other than macOS, `$XDG_CONFIG_HOME` is only used if it holds an
+    /// absolute path, as the XDG specification requires.
+    ///
+    /// # Parameters
+    /// - `app`: Name of the application, used as the name of its directory.
+    ///
+    /// # Returns
+    /// The location, or a [`ConfigurationFileError`](crate::ConfigurationFileError) if the
+    /// base directory of the platform cannot be determined (e.g. no home directory).
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(serde::Deserialize)]
+    /// struct Settings {
+    ///     endpoint: String,
+    /// }
+    ///
+    /// fn load() -> cdumay_core::Result<Settings> {
+    ///     let location = cdumay_config::ConfigLocation::for_app("myapp")?;
+    ///     location.read_config("settings.json", None, &std::collections::BTreeMap::new())
+    /// }
+    /// ```
+    pub fn for_app(app: &str) -> cdumay_core::Result<Self> {
+        let base = if cfg!(windows) {
+            std::env::var_os("APPDATA").filter(|dir| !dir.is_empty()).map(std::path::PathBuf::from)
+        } else if cfg!(target_os = "macos") {
+            std::env::home_dir().map(|home| home.join("Library").join("Application Support"))
+        } else {
+            std::env::var_os("XDG_CONFIG_HOME")
+                .map(std::path::PathBuf::from)
+                .filter(|dir| dir.is_absolute())
+                .or_else(|| std::env::home_dir().map(|home| home.join(".config")))
+        };
+        match base {
+            Some(base) => Ok(Self { dir: base.join(app) }),
+            None => Err(crate::ConfigurationFileError::new()
+                .with_message(format!("Cannot resolve the configuration directory of '{}'", app))
+                .with_details(crate::context::bounded(std::collections::BTreeMap::from([(
+                    "app".to_string(),
+                    serde_value::Value::String(app.to_string()),
+                )])))
+                .into()),
+        }
+    }
+
+    /// Returns the configuration directory.
+    pub fn dir(&self) -> &std::path::Path {
+        &self.dir
+    }
+
+    /// Returns the path of a file of the configuration directory.
+    pub fn path(&self, name: &str) -> std::path::PathBuf {
+        self.dir.join(name)
+    }
+
+    /// Reads a file of the configuration directory, see [`read_config`](crate::read_config).
+    ///
+    /// # Parameters
+    /// - `name`: Name of the file, relative to the configuration directory.
+    /// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+    /// - `context`: A templating context used to resolve variables inside the configuration.
+    pub fn read_config<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        self.read_config_with(name, format, context, &crate::ReadOptions::default())
+    }
+
+    /// Reads a file of the configuration directory like [`ConfigLocation::read_config`],
+    /// applying the given read options.
+    pub fn read_config_with<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        crate::read_config_with(&self.path(name).to_string_lossy(), format, context, options)
+    }
+
+    /// Writes a file of the configuration directory, see [`write_config`](crate::write_config).
+    /// The directory is created if it does not exist.
+    ///
+    /// # Parameters
+    /// - `name`: Name of the file, relative to the configuration directory.
+    /// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+    /// - `data`: The configuration to write.
+    /// - `context`: Context used for error reporting.
+    ///
+    /// # Returns
+    /// The path to the written file if successful, or an error otherwise.
+    pub fn write_config<C: serde::Serialize>(
+        &self,
+        name: &str,
+        format: Option<crate::ContentFormat>,
+        data: C,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        std::fs::create_dir_all(&self.dir).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to create directory: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, &self.dir);
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        crate::write_config(&self.path(name).to_string_lossy(), format, data, context)
+    }
+}
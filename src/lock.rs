@@ -0,0 +1,146 @@
+//! Advisory file locking shared by configuration readers and writers.
+//!
+//! Locks are taken on a sidecar `<path>.lock` file rather than on the configuration itself,
+//! so that writers can truncate and rewrite the file while holding the lock.
+
+/// Strategy used to acquire a configuration file lock.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LockMode {
+    /// Wait until the lock becomes available.
+    Blocking,
+    /// Fail immediately if another process holds the lock.
+    Try,
+    /// Retry until the lock is acquired or the given duration elapses.
+    Timeout(std::time::Duration),
+}
+
+/// Guard holding an advisory lock on a configuration file.
+///
+/// The lock is released when the guard is dropped.
+#[derive(Debug)]
+pub struct FileLock {
+    /// Path of the sidecar lock file.
+    path: std::path::PathBuf,
+    /// Handle owning the lock.
+    file: std::fs::File,
+}
+
+impl FileLock {
+    /// Acquires a shared lock, allowing concurrent readers but excluding writers.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the configuration file to protect.
+    /// - `mode`: The acquisition strategy.
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// A guard releasing the lock on drop.
+    pub fn shared(path: &str, mode: LockMode, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<FileLock> {
+        Self::acquire(path, mode, false, context)
+    }
+
+    /// Acquires an exclusive lock, excluding both readers and other writers.
+    ///
+    /// # Parameters
+    /// - `path`: Path to the configuration file to protect.
+    /// - `mode`: The acquisition strategy.
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// A guard releasing the lock on drop.
+    pub fn exclusive(path: &str, mode: LockMode, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<FileLock> {
+        Self::acquire(path, mode, true, context)
+    }
+
+    /// Returns the path of the sidecar lock file.
+    pub fn path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    /// Returns the sidecar lock file path used for a configuration file.
+    pub fn lock_path(path: &str) -> std::path::PathBuf {
+        std::path::PathBuf::from(format!("{}.lock", path))
+    }
+
+    fn acquire(
+        path: &str,
+        mode: LockMode,
+        exclusive: bool,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<FileLock> {
+        let lock_path = Self::lock_path(path);
+        let error = |message: String, origin: String| {
+            crate::FileLockError::new().with_message(message).with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("path".to_string(), serde_value::Value::String(path.to_string()));
+                ctx.insert("lock".to_string(), serde_value::Value::String(lock_path.display().to_string()));
+                ctx.insert("origin".to_string(), serde_value::Value::String(origin));
+                ctx
+            }))
+        };
+        let file = loop {
+            let file = std::fs::OpenOptions::new()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&lock_path)
+                .map_err(|err| error(format!("Failed to open lock file: {}", err), err.to_string()))?;
+            let try_lock = || if exclusive { file.try_lock() } else { file.try_lock_shared() };
+            match mode {
+                LockMode::Blocking => if exclusive { file.lock() } else { file.lock_shared() }
+                    .map_err(|err| error(format!("Failed to lock file: {}", err), err.to_string()))?,
+                LockMode::Try => try_lock().map_err(|err| error(format!("Failed to lock file: {}", err), err.to_string()))?,
+                LockMode::Timeout(timeout) => {
+                    let deadline = std::time::Instant::now() + timeout;
+                    let mut delay = std::time::Duration::from_millis(1);
+                    loop {
+                        match try_lock() {
+                            Ok(()) => break,
+                            Err(std::fs::TryLockError::WouldBlock) if std::time::Instant::now() < deadline => {
+                                std::thread::sleep(delay.min(deadline.saturating_duration_since(std::time::Instant::now())));
+                                delay = (delay * 2).min(std::time::Duration::from_millis(50));
+                            }
+                            Err(err) => {
+                                return Err(error(format!("Failed to lock file within {:?}: {}", timeout, err), err.to_string()).into());
+                            }
+                        }
+                    }
+                }
+            }
+            // A stale lock cleanup may have removed the sidecar while we were waiting on it.
+            if Self::is_linked(&file, &lock_path) {
+                break file;
+            }
+            log::debug!("Lock file '{}' was removed while waiting, retrying", lock_path.display());
+        };
+        // Records the last acquisition, used to detect stale lock files.
+        let _ = file.set_modified(std::time::SystemTime::now());
+        log::debug!(
+            "Acquired {} lock on '{}'",
+            if exclusive { "exclusive" } else { "shared" },
+            lock_path.display()
+        );
+        Ok(FileLock { path: lock_path, file })
+    }
+
+    /// Tells whether the locked handle is still the file found at the sidecar path.
+    fn is_linked(file: &std::fs::File, lock_path: &std::path::Path) -> bool {
+        match (file.metadata(), std::fs::metadata(lock_path)) {
+            #[cfg(unix)]
+            (Ok(held), Ok(current)) => {
+                use std::os::unix::fs::MetadataExt;
+                held.dev() == current.dev() && held.ino() == current.ino()
+            }
+            #[cfg(not(unix))]
+            (Ok(_), Ok(_)) => true,
+            _ => false,
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = self.file.unlock();
+    }
+}
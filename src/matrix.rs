@@ -0,0 +1,165 @@
+//! Rendering of one configuration file per environment from a single template.
+//!
+//! [`materialize_matrix`] resolves a template configuration against the context of each
+//! environment and writes the results, so that a release pipeline renders all its
+//! environment files in a single call.
+
+/// Resolves a template configuration for each environment and writes one file per environment.
+///
+/// String values of the template may hold `{name}` placeholders, replaced by the value of
+/// `name` in the environment context; a string made of a single placeholder takes the
+/// context value as is, keeping its type (e.g. a port number). The output path is rendered
+/// from `pattern` the same way.
+///
+/// Every environment is resolved before any file is written: an unresolved placeholder, a
+/// non scalar value in a string or two environments rendering the same path fail the whole
+/// matrix without writing anything.
+///
+/// # Type Parameters
+/// - `C`: The type of the template configuration.
+///
+/// # Parameters
+/// - `template`: The template configuration.
+/// - `environments`: The context of each environment.
+/// - `pattern`: The path of the output files, with placeholders (e.g. `deploy/{env}.yaml`).
+///   Tilde `~` expansion is supported.
+/// - `format`: Output format. Defaults to `JSON` if not provided.
+/// - `options`: The write options applied to every file.
+///
+/// # Returns
+/// The paths of the written files, in the order of the environments.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Serialize)]
+/// struct Service {
+///     url: String,
+///     port: String,
+/// }
+///
+/// fn render() -> cdumay_core::Result<Vec<std::path::PathBuf>> {
+///     let template = Service {
+///         url: "https://{env}.example.com".to_string(),
+///         port: "{port}".to_string(),
+///     };
+///     let environments: Vec<std::collections::BTreeMap<String, serde_value::Value>> = ["staging", "production"]
+///         .iter()
+///         .map(|env| {
+///             std::collections::BTreeMap::from([
+///                 ("env".to_string(), serde_value::Value::String(env.to_string())),
+///                 ("port".to_string(), serde_value::Value::U16(443)),
+///             ])
+///         })
+///         .collect();
+///     cdumay_config::materialize_matrix(&template, &environments, "deploy/{env}.json", None, &Default::default())
+/// }
+/// ```
+pub fn materialize_matrix<C: serde::Serialize>(
+    template: &C,
+    environments: &[std::collections::BTreeMap<String, serde_value::Value>],
+    pattern: &str,
+    format: Option<crate::ContentFormat>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<Vec<std::path::PathBuf>> {
+    let template = serde_value::to_value(template).map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to serialize the template configuration: {}", err))
+            .with_details(crate::context::bounded(std::collections::BTreeMap::from([(
+                "pattern".to_string(),
+                serde_value::Value::String(pattern.to_string()),
+            )])))
+    })?;
+    let mut rendered = Vec::with_capacity(environments.len());
+    let mut paths = std::collections::BTreeSet::new();
+    for context in environments {
+        let path = render(pattern, context)?;
+        if !paths.insert(path.clone()) {
+            return Err(crate::ConfigurationFileError::new()
+                .with_message(format!("Several environments render the same file '{}'", path))
+                .with_details(crate::context::bounded(crate::context::with_path(context, std::path::Path::new(&path))))
+                .into());
+        }
+        rendered.push((path, resolve(template.clone(), context)?, context));
+    }
+    rendered
+        .into_iter()
+        .map(|(path, value, context)| crate::write_config_with(&path, format, value, context, options))
+        .collect()
+}
+
+/// Replaces the placeholders of every string of a value tree.
+fn resolve(value: serde_value::Value, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<serde_value::Value> {
+    Ok(match value {
+        serde_value::Value::String(text) => match placeholder(&text).and_then(|name| context.get(name)) {
+            Some(value) => value.clone(),
+            None => serde_value::Value::String(render(&text, context)?),
+        },
+        serde_value::Value::Seq(items) => serde_value::Value::Seq(items.into_iter().map(|item| resolve(item, context)).collect::<Result<_, _>>()?),
+        serde_value::Value::Map(entries) => serde_value::Value::Map(
+            entries
+                .into_iter()
+                .map(|(key, value)| Ok((key, resolve(value, context)?)))
+                .collect::<cdumay_core::Result<_>>()?,
+        ),
+        serde_value::Value::Option(Some(value)) => serde_value::Value::Option(Some(Box::new(resolve(*value, context)?))),
+        serde_value::Value::Newtype(value) => serde_value::Value::Newtype(Box::new(resolve(*value, context)?)),
+        value => value,
+    })
+}
+
+/// Returns the name of the placeholder a text is made of, if any.
+fn placeholder(text: &str) -> Option<&str> {
+    text.strip_prefix('{')
+        .and_then(|text| text.strip_suffix('}'))
+        .filter(|name| is_name(name))
+}
+
+/// Tells whether a text between braces is a placeholder name.
+fn is_name(name: &str) -> bool {
+    !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'))
+}
+
+/// Replaces the placeholders of a text by the values of the context.
+fn render(text: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<String> {
+    let error = |message: String, name: &str| {
+        crate::ConfigurationFileError::new().with_message(message).with_details(crate::context::bounded({
+            let mut ctx = context.clone();
+            ctx.insert("placeholder".to_string(), serde_value::Value::String(name.to_string()));
+            ctx
+        }))
+    };
+    let mut rendered = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find('{') {
+        rendered.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let name = match rest.find('}') {
+            Some(end) if is_name(&rest[..end]) => &rest[..end],
+            _ => {
+                rendered.push('{');
+                continue;
+            }
+        };
+        let value = match context.get(name) {
+            Some(serde_value::Value::String(value)) => value.clone(),
+            Some(serde_value::Value::Char(value)) => value.to_string(),
+            Some(serde_value::Value::Bool(value)) => value.to_string(),
+            Some(serde_value::Value::U8(value)) => value.to_string(),
+            Some(serde_value::Value::U16(value)) => value.to_string(),
+            Some(serde_value::Value::U32(value)) => value.to_string(),
+            Some(serde_value::Value::U64(value)) => value.to_string(),
+            Some(serde_value::Value::I8(value)) => value.to_string(),
+            Some(serde_value::Value::I16(value)) => value.to_string(),
+            Some(serde_value::Value::I32(value)) => value.to_string(),
+            Some(serde_value::Value::I64(value)) => value.to_string(),
+            Some(serde_value::Value::F32(value)) => value.to_string(),
+            Some(serde_value::Value::F64(value)) => value.to_string(),
+            Some(_) => return Err(error(format!("Placeholder '{{{}}}' of '{}' is not a scalar", name, text), name).into()),
+            None => return Err(error(format!("Unresolved placeholder '{{{}}}' in '{}'", name, text), name).into()),
+        };
+        rendered.push_str(&value);
+        rest = &rest[name.len() + 1..];
+    }
+    rendered.push_str(rest);
+    Ok(rendered)
+}
@@ -0,0 +1,21 @@
+//! Deep merge of configuration value trees.
+
+/// Merges `overlay` on top of `base`.
+///
+/// Maps are merged recursively key by key; any other value from the overlay replaces
+/// the value from the base.
+pub(crate) fn merge_values(base: serde_value::Value, overlay: serde_value::Value) -> serde_value::Value {
+    match (base, overlay) {
+        (serde_value::Value::Map(mut base), serde_value::Value::Map(overlay)) => {
+            for (key, value) in overlay {
+                let merged = match base.remove(&key) {
+                    Some(previous) => merge_values(previous, value),
+                    None => value,
+                };
+                base.insert(key, merged);
+            }
+            serde_value::Value::Map(base)
+        }
+        (_, overlay) => overlay,
+    }
+}
@@ -0,0 +1,115 @@
+//! Options tuning how configuration files are read and written.
+
+/// Behavior when reading a configuration file which is empty or holds only whitespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum EmptyFilePolicy {
+    /// Fails with an [`EmptyFileError`](crate::EmptyFileError).
+    #[default]
+    Fail,
+    /// Reads the file as an empty map, so that types whose fields all have serde defaults
+    /// (e.g. `#[serde(default)]` structs) deserialize to their default value.
+    EmptyMap,
+}
+
+/// Behavior when a configuration holds keys which the target type does not consume.
+///
+/// Unknown keys are usually typos, which serde silently drops; they are detected with
+/// [`serde_ignored`] and reported by their dotted path (e.g. `server.listeners.2.tls`).
+/// Unless they are ignored, they are also used to suggest a correction when a field is
+/// missing (e.g. ``unknown key `porrt`, did you mean `port`?``), in the `suggestion` detail
+/// of the error.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum UnknownKeyPolicy {
+    /// Drops the unknown keys silently.
+    #[default]
+    Ignore,
+    /// Logs a warning for each unknown key.
+    Warn,
+    /// Fails with a [`ConfigurationFileError`](crate::ConfigurationFileError) listing the unknown keys.
+    Deny,
+}
+
+/// Layout of the serialized content, for formats supporting it.
+///
+/// Formats without layout settings (e.g. YAML, XML or binary formats) ignore `pretty` and
+/// `indent`; `sort_keys` applies to every format.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteStyle {
+    /// Writes human-readable, multi-line content instead of compact content (JSON, TOML, RON).
+    pub pretty: bool,
+    /// Number of spaces per indentation level of pretty content, the format default if unset (JSON, RON).
+    pub indent: Option<usize>,
+    /// Sorts map keys and struct fields alphabetically, for stable diffs.
+    ///
+    /// The data is converted to a value tree first, so structures are written as maps.
+    pub sort_keys: bool,
+}
+
+impl Default for WriteStyle {
+    /// Pretty content with the format default indentation and unsorted keys.
+    fn default() -> Self {
+        Self {
+            pretty: true,
+            indent: None,
+            sort_keys: false,
+        }
+    }
+}
+
+/// Options applied when reading a configuration file.
+#[derive(Debug, Clone, Default)]
+pub struct ReadOptions {
+    /// Takes a shared advisory lock on the file while it is read.
+    pub lock: Option<crate::LockMode>,
+    /// Refuses configurations whose declared schema version is missing or newer than supported.
+    pub schema_version: Option<crate::SchemaVersion>,
+    /// Renames keys of the configuration before its schema version is checked and it is
+    /// deserialized, logging a warning listing the renamed keys.
+    pub renames: Option<crate::KeyRenames>,
+    /// Retries opening the file while it is busy (Windows sharing violations), or S3
+    /// requests failing transiently.
+    pub retry: crate::RetryPolicy,
+    /// Behavior when the file is empty or holds only whitespace.
+    pub empty_file: EmptyFilePolicy,
+    /// Behavior when the configuration holds keys which the target type does not consume.
+    pub unknown_keys: UnknownKeyPolicy,
+    /// Maps the manager path to the file actually read, [`DefaultPathResolver`](crate::DefaultPathResolver) if unset.
+    pub resolver: Option<std::sync::Arc<dyn crate::PathResolver>>,
+    /// Decrypts the content before it is decoded, if set.
+    pub cipher: Option<std::sync::Arc<dyn crate::Cipher>>,
+    /// Replaces the `vault://<alias>[/<field>]` strings of the configuration by the secrets
+    /// they refer to before it is deserialized, if set (see
+    /// [`VaultSecrets::resolve_references`](crate::VaultSecrets::resolve_references)). The
+    /// substituted values are masked in the errors raised while deserializing the result.
+    pub secrets: Option<crate::VaultSecrets>,
+    /// Replaces the maps holding an `"$include"` key, and the YAML values tagged `!include`,
+    /// by the content of the files they name, resolved against the directory of the
+    /// including file (see the [`include`](crate::include) module).
+    pub includes: bool,
+}
+
+/// Options applied when writing a configuration file.
+#[derive(Debug, Clone, Default)]
+pub struct WriteOptions {
+    /// Takes an exclusive advisory lock on the file while it is written.
+    pub lock: Option<crate::LockMode>,
+    /// Permission bits applied to the written file (e.g. `0o600` for secrets).
+    ///
+    /// On Unix the mode is set both on creation and on existing files. On Windows only the
+    /// owner write bit is honored: without it, the file is marked read-only.
+    pub mode: Option<u32>,
+    /// Retries creating the file while it is busy (Windows sharing violations).
+    pub retry: crate::RetryPolicy,
+    /// Layout of the serialized content.
+    pub style: WriteStyle,
+    /// Comment written at the top of the file, for formats supporting comments.
+    pub header: Option<crate::HeaderBanner>,
+    /// Hooks run in order once the file is written and its lock released.
+    pub hooks: Vec<crate::WriteHook>,
+    /// What to do when one of the hooks fails.
+    pub hook_failure: crate::HookFailurePolicy,
+    /// Maps the manager path to the file actually written, [`DefaultPathResolver`](crate::DefaultPathResolver) if unset.
+    pub resolver: Option<std::sync::Arc<dyn crate::PathResolver>>,
+    /// Encrypts the serialized content before it is written, if set.
+    pub cipher: Option<std::sync::Arc<dyn crate::Cipher>>,
+}
@@ -0,0 +1,149 @@
+//! Preview of a layered configuration, meant to be rendered by configuration editors.
+//!
+//! [`compose_preview`] resolves a set of configuration layers the way the application
+//! would, and exposes the result in a single serializable [`ComposedView`]: the merged
+//! tree with its secrets masked, the layer each key comes from and the issues the
+//! application would hit when loading it. Editors can display it without reimplementing
+//! the resolution rules.
+
+/// An issue found while composing a configuration preview.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct PreviewIssue {
+    /// Dotted path of the key concerned, if the issue is about a single key.
+    pub key: Option<String>,
+    /// Path of the layer concerned, if the issue is about a single layer.
+    pub source: Option<String>,
+    /// Description of the issue.
+    pub message: String,
+}
+
+/// Serializable view of a composed configuration.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct ComposedView {
+    /// The merged configuration, with its secrets masked.
+    pub merged: serde_value::Value,
+    /// Path of the layer each leaf value comes from, by dotted key. Sequences are leaves,
+    /// as a layer replaces them as a whole.
+    pub provenance: std::collections::BTreeMap<String, String>,
+    /// Issues the application would hit when loading the configuration, unreadable layers
+    /// included.
+    pub issues: Vec<PreviewIssue>,
+    /// Dotted keys of the masked values.
+    pub redacted: Vec<String>,
+}
+
+impl ComposedView {
+    /// Returns `true` if no issue was found.
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Composes a configuration from layers without failing, collecting everything an editor
+/// needs to display it.
+///
+/// Layers are read and merged in order, later layers overriding earlier ones, like
+/// [`load_from_snapshot`](crate::load_from_snapshot) does. A layer which cannot be read is
+/// reported as an issue and skipped. The merged tree is then deserialized into `C`: a
+/// failure, or a key which `C` does not consume, is reported as an issue too.
+///
+/// # Type Parameters
+/// - `C`: The type the application deserializes the configuration into.
+///
+/// # Parameters
+/// - `layers`: Paths and optional formats of the sources, in resolution order. Tilde `~`
+///   expansion is supported and the format defaults to `JSON`.
+/// - `context`: The context used to resolve the configuration.
+/// - `redaction`: The rules selecting the values to mask.
+///
+/// # Returns
+/// The [`ComposedView`] of the configuration.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Settings {
+///     endpoint: String,
+///     token: String,
+/// }
+///
+/// fn preview() -> String {
+///     let view = cdumay_config::compose_preview::<Settings>(
+///         &[("/etc/app/defaults.json", None), ("~/.config/app.json", None)],
+///         &std::collections::BTreeMap::new(),
+///         &cdumay_config::Redaction::default(),
+///     );
+///     serde_json::to_string(&view).unwrap_or_default()
+/// }
+/// ```
+pub fn compose_preview<C: serde::de::DeserializeOwned>(
+    layers: &[(&str, Option<crate::ContentFormat>)],
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    redaction: &crate::Redaction,
+) -> ComposedView {
+    let mut merged: Option<serde_value::Value> = None;
+    let mut provenance = std::collections::BTreeMap::new();
+    let mut issues = Vec::new();
+    for (path, format) in layers {
+        let value: serde_value::Value = match crate::read_config(path, *format, context) {
+            Ok(value) => value,
+            Err(err) => {
+                issues.push(PreviewIssue {
+                    key: None,
+                    source: Some(path.to_string()),
+                    message: err.message().to_string(),
+                });
+                continue;
+            }
+        };
+        record(&mut provenance, &value, "", path);
+        merged = Some(match merged {
+            Some(base) => crate::merge::merge_values(base, value),
+            None => value,
+        });
+    }
+    let merged = merged.unwrap_or_else(|| serde_value::Value::Map(Default::default()));
+    match crate::tracking::from_value_tracked::<C>(merged.clone(), context) {
+        Ok(tracked) => issues.extend(tracked.unused_keys.into_iter().map(|key| PreviewIssue {
+            source: provenance.get(&key).cloned(),
+            message: format!("Unused configuration key '{}'", key),
+            key: Some(key),
+        })),
+        Err(err) => issues.push(PreviewIssue {
+            key: None,
+            source: None,
+            message: err.message().to_string(),
+        }),
+    }
+    let mut redacted = Vec::new();
+    let merged = redaction.redact_tree(merged, &mut redacted);
+    ComposedView {
+        merged,
+        provenance,
+        issues,
+        redacted,
+    }
+}
+
+/// Records the layer of every leaf of a value tree, replacing what it overrides.
+fn record(provenance: &mut std::collections::BTreeMap<String, String>, value: &serde_value::Value, prefix: &str, source: &str) {
+    match value {
+        serde_value::Value::Map(entries) if !entries.is_empty() => {
+            provenance.remove(prefix);
+            for (key, value) in entries {
+                let key = match key {
+                    serde_value::Value::String(key) => key.clone(),
+                    other => format!("{:?}", other),
+                };
+                let child = if prefix.is_empty() { key } else { format!("{}.{}", prefix, key) };
+                record(provenance, value, &child, source);
+            }
+        }
+        serde_value::Value::Map(_) => {}
+        _ => {
+            let nested = format!("{}.", prefix);
+            provenance.retain(|key, _| !key.starts_with(&nested));
+            provenance.insert(prefix.to_string(), source.to_string());
+        }
+    }
+}
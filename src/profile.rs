@@ -0,0 +1,143 @@
+//! Environment profiles overriding a base configuration file.
+//!
+//! A [`ProfileLoader`] reads a base file such as `config/app.yaml`, then the override file
+//! of the active profile next to it, `config/app.prod.yaml` for the `prod` profile, and
+//! deep-merges the override on top of the base. The profile is given explicitly or read
+//! from an environment variable, `APP_PROFILE` by default, so that the same binary runs
+//! with the settings of each deployment.
+
+/// Environment variable holding the active profile by default.
+pub const DEFAULT_PROFILE_ENV: &str = "APP_PROFILE";
+
+/// Loads a base configuration file merged with the override file of the active profile.
+#[derive(Debug, Clone)]
+pub struct ProfileLoader {
+    /// Path of the base file.
+    path: String,
+    /// Format name of the files, inferred from the base path extension if unset.
+    format: Option<String>,
+    /// Explicit profile, taking precedence over the environment variable.
+    profile: Option<String>,
+    /// Environment variable holding the active profile.
+    env_var: String,
+}
+
+impl ProfileLoader {
+    /// Creates a loader of a base file, with the profile read from [`DEFAULT_PROFILE_ENV`].
+    ///
+    /// # Parameters
+    /// - `path`: Path of the base file. Tilde `~` expansion is supported.
+    ///
+    /// # Returns
+    /// A new instance of `ProfileLoader`.
+    pub fn new(path: &str) -> Self {
+        Self {
+            path: path.to_string(),
+            format: None,
+            profile: None,
+            env_var: DEFAULT_PROFILE_ENV.to_string(),
+        }
+    }
+
+    /// Sets the active profile, ignoring the environment variable.
+    pub fn with_profile(mut self, profile: &str) -> Self {
+        self.profile = Some(profile.to_string());
+        self
+    }
+
+    /// Sets the environment variable holding the active profile.
+    pub fn with_env_var(mut self, name: &str) -> Self {
+        self.env_var = name.to_string();
+        self
+    }
+
+    /// Sets the format name of the base and override files (see
+    /// [`FormatRegistry`](crate::FormatRegistry)), inferred from the extension if unset.
+    pub fn with_format(mut self, format: &str) -> Self {
+        self.format = Some(format.to_string());
+        self
+    }
+
+    /// Returns the active profile: the explicit one, or else the non-empty value of the
+    /// environment variable, if any.
+    pub fn profile(&self) -> Option<String> {
+        self.profile
+            .clone()
+            .or_else(|| std::env::var(&self.env_var).ok().filter(|profile| !profile.is_empty()))
+    }
+
+    /// Returns the path of the override file of a profile, inserting the profile name
+    /// before the extension of the base file (`app.yaml` becomes `app.prod.yaml`).
+    ///
+    /// # Example
+    /// ```rust
+    /// let loader = cdumay_config::ProfileLoader::new("config/app.yaml");
+    /// assert_eq!(loader.profile_path("prod"), "config/app.prod.yaml");
+    /// ```
+    pub fn profile_path(&self, profile: &str) -> String {
+        let name = self.path.rfind('/').map_or(0, |index| index + 1);
+        match self.path[name..].rfind('.') {
+            Some(dot) if dot > 0 => format!("{}.{}{}", &self.path[..name + dot], profile, &self.path[name + dot..]),
+            _ => format!("{}.{}", self.path, profile),
+        }
+    }
+
+    /// Reads the base file, merges the override file of the active profile on top of it if
+    /// the file exists, and deserializes the result.
+    ///
+    /// Maps are merged key by key; any other value of the override file replaces the value
+    /// of the base file.
+    ///
+    /// # Type Parameters
+    /// - `C`: Type of the configuration, must implement `DeserializeOwned`.
+    ///
+    /// # Parameters
+    /// - `context`: A templating context used to resolve variables inside the files.
+    ///
+    /// # Returns
+    /// The merged configuration.
+    ///
+    /// # Errors
+    /// Returns a [`ConfigurationFileError`](crate::ConfigurationFileError) if the profile is
+    /// not a single path component (e.g. `../prod`), or the error of a file which cannot be
+    /// read or of the merged configuration which cannot be deserialized.
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(serde::Deserialize)]
+    /// struct Settings {
+    ///     endpoint: String,
+    ///     debug: bool,
+    /// }
+    ///
+    /// fn load() -> cdumay_core::Result<Settings> {
+    ///     cdumay_config::ProfileLoader::new("/etc/app/app.json").with_env_var("APP_ENV").load(&std::collections::BTreeMap::new())
+    /// }
+    /// ```
+    pub fn load<C: serde::de::DeserializeOwned>(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<C> {
+        let registry = crate::FormatRegistry::global();
+        let base: serde_value::Value = registry.read_config(&self.path, self.format.as_deref(), context)?;
+        let Some(profile) = self.profile() else {
+            return crate::formats::from_value(base, context);
+        };
+        let mut components = std::path::Path::new(&profile).components();
+        if !matches!((components.next(), components.next()), (Some(std::path::Component::Normal(_)), None)) {
+            return Err(crate::ConfigurationFileError::new()
+                .with_message(format!("Invalid configuration profile: '{}'", profile))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("profile".to_string(), serde_value::Value::String(profile.clone()));
+                    ctx
+                }))
+                .into());
+        }
+        let path = self.profile_path(&profile);
+        if !std::path::Path::new(shellexpand::tilde(&path).as_ref()).exists() {
+            log::debug!("No override file '{}' for profile '{}'", path, profile);
+            return crate::formats::from_value(base, context);
+        }
+        log::info!("Reading overrides of profile '{}' from '{}'", profile, path);
+        let overlay: serde_value::Value = registry.read_config(&path, self.format.as_deref(), context)?;
+        crate::formats::from_value(crate::merge::merge_values(base, overlay), context)
+    }
+}
@@ -0,0 +1,22 @@
+//! Verbatim passthrough of embedded configuration fragments.
+//!
+//! Some configurations embed snippets owned by third-party tools which must be written
+//! back exactly as they were read. [`RawJson`] captures the original text of a JSON
+//! subtree during deserialization and emits it untouched on serialization:
+//!
+//! ```rust
+//! #[derive(serde::Serialize, serde::Deserialize)]
+//! struct Config {
+//!     name: String,
+//!     // Kept byte for byte, including whitespace and key order.
+//!     plugin: Box<cdumay_config::RawJson>,
+//! }
+//! ```
+//!
+//! Only the JSON format can carry raw values: they rely on the `serde_json` deserializer
+//! seeing the original input, so they are supported by [`JsonManager`](crate::JsonManager)
+//! reads and writes, but not by other formats nor by read options which go through an
+//! intermediate value tree (such as [`ReadOptions::schema_version`](crate::ReadOptions)).
+
+/// Original text of a JSON subtree, preserved verbatim across read and write.
+pub type RawJson = serde_json::value::RawValue;
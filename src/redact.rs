@@ -0,0 +1,168 @@
+//! Redaction of secret values before sharing a configuration.
+
+/// Rules deciding which configuration values are secrets and how they are masked.
+///
+/// A value is a secret when its key contains one of the patterns (case-insensitive), or
+/// when its dotted path (e.g. `database.url`) equals one of them. The defaults cover
+/// common credential names such as `password`, `secret` or `token`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Redaction {
+    /// Key patterns of the values to mask, lowercased.
+    patterns: Vec<String>,
+    /// Text replacing the masked values.
+    placeholder: String,
+}
+
+impl Default for Redaction {
+    fn default() -> Self {
+        Self {
+            patterns: ["password", "passwd", "secret", "token", "api_key", "apikey", "private_key", "credential"]
+                .iter()
+                .map(|pattern| pattern.to_string())
+                .collect(),
+            placeholder: "***REDACTED***".to_string(),
+        }
+    }
+}
+
+impl Redaction {
+    /// Adds a key pattern or dotted path to mask.
+    pub fn with_key(mut self, pattern: &str) -> Self {
+        self.patterns.push(pattern.to_ascii_lowercase());
+        self
+    }
+
+    /// Sets the text replacing the masked values.
+    pub fn with_placeholder(mut self, placeholder: &str) -> Self {
+        self.placeholder = placeholder.to_string();
+        self
+    }
+
+    /// Returns the text replacing the masked values.
+    pub fn placeholder(&self) -> &str {
+        &self.placeholder
+    }
+
+    /// Serializes a configuration into a value tree with its secrets masked.
+    ///
+    /// # Type Parameters
+    /// - `D`: The data type to serialize.
+    ///
+    /// # Parameters
+    /// - `data`: The configuration to redact.
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// The redacted value tree, or an error if the configuration cannot be serialized.
+    pub fn redact<D: serde::Serialize>(
+        &self,
+        data: &D,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_value::Value> {
+        let value = serde_value::to_value(data).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to serialize configuration: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        Ok(self.redact_value(value, "", &mut Vec::new()))
+    }
+
+    /// Masks the secrets of a value tree, recording the dotted paths of the masked values.
+    pub(crate) fn redact_tree(&self, value: serde_value::Value, masked: &mut Vec<String>) -> serde_value::Value {
+        self.redact_value(value, "", masked)
+    }
+
+    /// Masks the secrets of error details, whose keys are the top-level keys.
+    pub(crate) fn redact_details(
+        &self,
+        details: std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> std::collections::BTreeMap<String, serde_value::Value> {
+        details
+            .into_iter()
+            .map(|(key, value)| {
+                let value = match self.is_secret(&key, &key) {
+                    true => serde_value::Value::String(self.placeholder.clone()),
+                    false => self.redact_value(value, &key, &mut Vec::new()),
+                };
+                (key, value)
+            })
+            .collect()
+    }
+
+    fn redact_value(&self, value: serde_value::Value, path: &str, masked: &mut Vec<String>) -> serde_value::Value {
+        match value {
+            serde_value::Value::Map(map) => serde_value::Value::Map(
+                map.into_iter()
+                    .map(|(key, value)| match &key {
+                        serde_value::Value::String(name) => {
+                            let child = if path.is_empty() { name.clone() } else { format!("{}.{}", path, name) };
+                            let value = match self.is_secret(name, &child) {
+                                true => {
+                                    masked.push(child);
+                                    serde_value::Value::String(self.placeholder.clone())
+                                }
+                                false => self.redact_value(value, &child, masked),
+                            };
+                            (key, value)
+                        }
+                        _ => (key, self.redact_value(value, path, masked)),
+                    })
+                    .collect(),
+            ),
+            serde_value::Value::Seq(items) => {
+                serde_value::Value::Seq(items.into_iter().map(|item| self.redact_value(item, path, masked)).collect())
+            }
+            serde_value::Value::Option(Some(inner)) => {
+                serde_value::Value::Option(Some(Box::new(self.redact_value(*inner, path, masked))))
+            }
+            serde_value::Value::Newtype(inner) => serde_value::Value::Newtype(Box::new(self.redact_value(*inner, path, masked))),
+            other => other,
+        }
+    }
+
+    fn is_secret(&self, key: &str, path: &str) -> bool {
+        let key = key.to_ascii_lowercase();
+        let path = path.to_ascii_lowercase();
+        self.patterns.iter().any(|pattern| key.contains(pattern.as_str()) || path == *pattern)
+    }
+}
+
+/// Writes a configuration file with its secret values replaced by a placeholder.
+///
+/// Meant for sharing a configuration, e.g. attaching it to a support ticket, without
+/// leaking credentials.
+///
+/// # Type Parameters
+/// - `C`: The data type to serialize. Must implement `Serialize`.
+///
+/// # Parameters
+/// - `path`: The file path to write to. Tilde `~` expansion is supported.
+/// - `format`: Optional output format. Defaults to `JSON` if not provided.
+/// - `data`: The data to redact and write to the file.
+/// - `context`: Templating context for value substitution, if applicable.
+/// - `redaction`: The rules selecting the values to mask.
+///
+/// # Returns
+/// The path to the written file if successful, or an error otherwise.
+///
+/// # Example
+/// ```
+/// fn share<S: serde::Serialize>(config: S) -> cdumay_core::Result<std::path::PathBuf> {
+///     let context = std::collections::BTreeMap::new();
+///     let redaction = cdumay_config::Redaction::default().with_key("database.url");
+///     cdumay_config::write_config_redacted("/tmp/app-support.json", None, &config, &context, &redaction)
+/// }
+/// ```
+pub fn write_config_redacted<C: serde::Serialize>(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    data: C,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    redaction: &Redaction,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    crate::write_config(path, format, redaction.redact(&data, context)?, context)
+}
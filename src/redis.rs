@@ -0,0 +1,483 @@
+//! Configuration stored under a Redis key.
+//!
+//! With the `redis` feature, a [`RedisSource`] reads a configuration document stored under
+//! a key of a Redis server, speaking the Redis protocol (RESP) directly over TCP:
+//!
+//! - a string key holds a whole document, decoded by the manager of the chosen format;
+//! - a hash key holds one top-level key per field, each value being parsed in the chosen
+//!   format, values which are not valid in this format (e.g. `localhost` in JSON) being kept
+//!   as strings.
+//!
+//! Writers can publish on a pub/sub channel whenever they update the key, so that
+//! [`RedisSource::watch`] reloads a [`LiveConfig`](crate::LiveConfig) on each message:
+//!
+//! ```rust
+//! #[derive(serde::Deserialize)]
+//! struct Config {
+//!     upstream: String,
+//! }
+//!
+//! fn serve() -> cdumay_core::Result<()> {
+//!     let source = cdumay_config::RedisSource::new("redis.internal:6379", "app:config").with_channel("app:config:updated");
+//!     let loader = source.clone();
+//!     let live = std::sync::Arc::new(cdumay_config::LiveConfig::<Config>::new(move || {
+//!         loader.read_config(&std::collections::BTreeMap::new())
+//!     })?);
+//!     let _watch = source.watch(&live, &std::collections::BTreeMap::new())?;
+//!     println!("Forwarding to {}", live.load().upstream);
+//!     Ok(())
+//! }
+//! ```
+
+/// Delay after which a subscription checks whether it must stop, and waits before
+/// reconnecting once lost.
+const POLL_INTERVAL: std::time::Duration = std::time::Duration::from_millis(500);
+
+/// A configuration read from a key of a Redis server.
+#[derive(Clone, PartialEq)]
+pub struct RedisSource {
+    /// Address of the server (e.g. `redis.internal:6379`).
+    address: String,
+    /// Key holding the configuration.
+    key: String,
+    /// Format of the document, or of the values of a hash.
+    format: crate::ContentFormat,
+    /// User name (if any) and password to authenticate with.
+    credentials: Option<(Option<String>, String)>,
+    /// Index of the logical database holding the key.
+    database: u32,
+    /// Pub/sub channel announcing updates of the key.
+    channel: Option<String>,
+    /// Maximum duration to connect and of each read or write, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+}
+
+impl std::fmt::Debug for RedisSource {
+    /// Formats the source without its password.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RedisSource")
+            .field("address", &self.address)
+            .field("key", &self.key)
+            .field("format", &self.format)
+            .field("user", &self.credentials.as_ref().and_then(|(user, _)| user.as_ref()))
+            .field("database", &self.database)
+            .field("channel", &self.channel)
+            .field("timeout", &self.timeout)
+            .finish()
+    }
+}
+
+impl RedisSource {
+    /// Creates a source reading `key` from the Redis server at `address`.
+    ///
+    /// # Parameters
+    /// - `address`: Address of the server (e.g. `redis.internal:6379`).
+    /// - `key`: Key holding the configuration, a string or a hash.
+    pub fn new(address: &str, key: &str) -> Self {
+        Self {
+            address: address.to_string(),
+            key: key.to_string(),
+            format: crate::ContentFormat::JSON,
+            credentials: None,
+            database: 0,
+            channel: None,
+            timeout: None,
+        }
+    }
+
+    /// Sets the format of the document, or of the values of a hash, JSON by default.
+    pub fn with_format(mut self, format: crate::ContentFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Authenticates with the password of the server (`requirepass`).
+    pub fn with_password(mut self, password: &str) -> Self {
+        self.credentials = Some((None, password.to_string()));
+        self
+    }
+
+    /// Authenticates as an ACL user.
+    pub fn with_credentials(mut self, user: &str, password: &str) -> Self {
+        self.credentials = Some((Some(user.to_string()), password.to_string()));
+        self
+    }
+
+    /// Sets the index of the logical database holding the key, `0` by default.
+    pub fn with_database(mut self, database: u32) -> Self {
+        self.database = database;
+        self
+    }
+
+    /// Sets the pub/sub channel announcing updates of the key, used by [`RedisSource::watch`].
+    pub fn with_channel(mut self, channel: &str) -> Self {
+        self.channel = Some(channel.to_string());
+        self
+    }
+
+    /// Sets the maximum duration to connect and of each read or write.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the URL of the database, as `redis://<address>/<database>`.
+    pub fn url(&self) -> String {
+        format!("redis://{}/{}", self.address, self.database)
+    }
+
+    /// Reads the configuration as a value tree.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// The configuration, or an error holding the `url` of the database and the `key` in its
+    /// details.
+    pub fn read_value(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<serde_value::Value> {
+        self.read_config(context)
+    }
+
+    /// Reads the configuration and deserializes it.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type to deserialize the configuration into.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the operation fails.
+    ///
+    /// # Returns
+    /// The deserialized configuration, or an error if reading or deserializing fails.
+    pub fn read_config<C: serde::de::DeserializeOwned>(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let mut context = context.clone();
+        context.insert("url".to_string(), serde_value::Value::String(self.url()));
+        context.insert("key".to_string(), serde_value::Value::String(self.key.clone()));
+        match self.fetch(&context)? {
+            Document::String(content) => self
+                .format
+                .read_content_with(&self.url(), content, &context, &crate::ReadOptions::default()),
+            Document::Hash(fields) => {
+                let map = fields
+                    .into_iter()
+                    .map(|(field, value)| {
+                        let leaf = match self.format.read_str::<serde_value::Value>(&value, &context) {
+                            Ok(leaf) => leaf,
+                            Err(_) => serde_value::Value::String(value),
+                        };
+                        (serde_value::Value::String(field), leaf)
+                    })
+                    .collect();
+                crate::formats::from_value(serde_value::Value::Map(map), &context)
+            }
+        }
+    }
+
+    /// Reloads a live configuration whenever a message is published on the channel of the
+    /// source.
+    ///
+    /// The subscription is made before returning, then messages are awaited from a
+    /// background thread, which stops when the returned [`WatchHandle`](crate::WatchHandle)
+    /// or the last reference to the configuration is dropped. A lost connection is
+    /// re-established, followed by a reload since messages may have been missed; failures
+    /// are logged and keep the previous value.
+    ///
+    /// # Parameters
+    /// - `live`: The configuration to reload.
+    /// - `context`: A context used for error details if the subscription fails.
+    ///
+    /// # Returns
+    /// A handle stopping the watch on drop, or an error if no channel is set or the
+    /// subscription fails.
+    pub fn watch<C: Send + Sync + 'static>(
+        &self,
+        live: &std::sync::Arc<crate::LiveConfig<C>>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<crate::WatchHandle> {
+        let Some(channel) = self.channel.clone() else {
+            return Err(self.error("No Redis channel set to watch".to_string(), None, context));
+        };
+        let connection = self.subscribe(&channel).map_err(|err| {
+            self.error(
+                format!("Failed to subscribe to Redis channel '{}' at '{}': {}", channel, self.url(), err),
+                Some(err.to_string()),
+                context,
+            )
+        })?;
+        let stop = std::sync::Arc::new(std::sync::atomic::AtomicBool::new(false));
+        let live = std::sync::Arc::downgrade(live);
+        let thread_stop = stop.clone();
+        let source = self.clone();
+        std::thread::spawn(move || {
+            let mut connection = Some(connection);
+            while !thread_stop.load(std::sync::atomic::Ordering::Relaxed) && live.strong_count() > 0 {
+                let Some(current) = connection.as_mut() else {
+                    std::thread::sleep(POLL_INTERVAL);
+                    match source.subscribe(&channel) {
+                        Ok(reconnected) => {
+                            connection = Some(reconnected);
+                            source.reload(&live, &channel);
+                        }
+                        Err(err) => log::error!("Failed to subscribe to Redis channel '{}': {}", channel, err),
+                    }
+                    continue;
+                };
+                match current.read() {
+                    Ok(Reply::Array(Some(items))) if matches!(items.first(), Some(Reply::Bulk(Some(kind))) if kind == b"message") => {
+                        source.reload(&live, &channel)
+                    }
+                    Ok(_) => {}
+                    Err(err) if matches!(err.kind(), std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut) => {}
+                    Err(err) => {
+                        log::error!("Lost Redis subscription to channel '{}': {}", channel, err);
+                        connection = None;
+                    }
+                }
+            }
+        });
+        Ok(crate::WatchHandle { stop })
+    }
+
+    /// Reloads a live configuration on behalf of a subscription.
+    fn reload<C>(&self, live: &std::sync::Weak<crate::LiveConfig<C>>, channel: &str) {
+        let Some(live) = live.upgrade() else {
+            return;
+        };
+        match live.reload() {
+            Ok(()) => log::info!("Reloaded config key '{}' notified on Redis channel '{}'", self.key, channel),
+            Err(err) => log::error!("Failed to reload config key '{}': {}", self.key, err),
+        }
+    }
+
+    /// Opens a connection subscribed to a channel, polling for messages.
+    fn subscribe(&self, channel: &str) -> std::io::Result<Connection> {
+        let mut connection = Connection::open(self)?;
+        connection.send(&[b"SUBSCRIBE", channel.as_bytes()])?;
+        match connection.read()? {
+            Reply::Error(message) => return Err(std::io::Error::other(message)),
+            Reply::Array(Some(_)) => {}
+            _ => return Err(unexpected()),
+        }
+        connection.stream.set_read_timeout(Some(POLL_INTERVAL))?;
+        Ok(connection)
+    }
+
+    /// Reads the content of the key.
+    fn fetch(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<Document> {
+        let failure = |err: std::io::Error| {
+            self.error(
+                format!("Failed to read Redis key '{}' from '{}': {}", self.key, self.url(), err),
+                Some(err.to_string()),
+                context,
+            )
+        };
+        let mut connection = Connection::open(self).map_err(failure)?;
+        let key = self.key.as_bytes();
+        let kind = match connection.command(&[b"TYPE", key]).map_err(failure)? {
+            Reply::Status(kind) => kind,
+            _ => return Err(failure(unexpected())),
+        };
+        match kind.as_str() {
+            "string" => match connection.command(&[b"GET", key]).map_err(failure)? {
+                Reply::Bulk(Some(content)) => Ok(Document::String(content)),
+                _ => Err(failure(unexpected())),
+            },
+            "hash" => match connection.command(&[b"HGETALL", key]).map_err(failure)? {
+                Reply::Array(Some(items)) => {
+                    let mut fields = Vec::new();
+                    for pair in items.chunks(2) {
+                        match pair {
+                            [Reply::Bulk(Some(field)), Reply::Bulk(Some(value))] => fields.push((
+                                String::from_utf8(field.clone()).map_err(|_| failure(invalid_utf8()))?,
+                                String::from_utf8(value.clone()).map_err(|_| failure(invalid_utf8()))?,
+                            )),
+                            _ => return Err(failure(unexpected())),
+                        }
+                    }
+                    Ok(Document::Hash(fields))
+                }
+                _ => Err(failure(unexpected())),
+            },
+            "none" => Err(self.error(format!("Redis key '{}' not found at '{}'", self.key, self.url()), None, context)),
+            kind => Err(self.error(
+                format!("Redis key '{}' holds a {} instead of a string or a hash", self.key, kind),
+                None,
+                context,
+            )),
+        }
+    }
+
+    /// Builds the error of a failed Redis operation.
+    fn error(&self, message: String, origin: Option<String>, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Error {
+        crate::ConfigurationFileError::new()
+            .with_message(message)
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_request(context, &self.url(), None, origin);
+                ctx.insert("key".to_string(), serde_value::Value::String(self.key.clone()));
+                ctx
+            }))
+            .into()
+    }
+}
+
+/// Content of a key.
+enum Document {
+    /// The document of a string key.
+    String(Vec<u8>),
+    /// The fields and values of a hash key.
+    Hash(Vec<(String, String)>),
+}
+
+/// A reply of the Redis protocol (RESP2).
+enum Reply {
+    /// A simple string (e.g. `OK`).
+    Status(String),
+    /// An error message.
+    Error(String),
+    /// An integer, whose value is not needed by the commands used.
+    Integer,
+    /// A binary-safe string, `None` for a null reply.
+    Bulk(Option<Vec<u8>>),
+    /// An array of replies, `None` for a null reply.
+    Array(Option<Vec<Reply>>),
+}
+
+/// A connection to a Redis server.
+struct Connection {
+    /// The underlying socket.
+    stream: std::net::TcpStream,
+    /// Bytes received but not parsed yet.
+    buffer: Vec<u8>,
+}
+
+impl Connection {
+    /// Connects to the server of a source, authenticates and selects its database.
+    fn open(source: &RedisSource) -> std::io::Result<Self> {
+        let stream = match source.timeout {
+            Some(timeout) => {
+                let mut last = std::io::Error::new(std::io::ErrorKind::NotFound, "address resolved to nothing");
+                let mut connected = None;
+                for address in std::net::ToSocketAddrs::to_socket_addrs(&source.address)? {
+                    match std::net::TcpStream::connect_timeout(&address, timeout) {
+                        Ok(stream) => {
+                            connected = Some(stream);
+                            break;
+                        }
+                        Err(err) => last = err,
+                    }
+                }
+                connected.ok_or(last)?
+            }
+            None => std::net::TcpStream::connect(&source.address)?,
+        };
+        stream.set_read_timeout(source.timeout)?;
+        stream.set_write_timeout(source.timeout)?;
+        let mut connection = Self { stream, buffer: Vec::new() };
+        if let Some((user, password)) = &source.credentials {
+            let mut args: Vec<&[u8]> = vec![b"AUTH"];
+            args.extend(user.as_ref().map(String::as_bytes));
+            args.push(password.as_bytes());
+            connection.command(&args)?;
+        }
+        if source.database != 0 {
+            connection.command(&[b"SELECT", source.database.to_string().as_bytes()])?;
+        }
+        Ok(connection)
+    }
+
+    /// Sends a command and returns its reply, failing on an error reply.
+    fn command(&mut self, args: &[&[u8]]) -> std::io::Result<Reply> {
+        self.send(args)?;
+        match self.read()? {
+            Reply::Error(message) => Err(std::io::Error::other(message)),
+            reply => Ok(reply),
+        }
+    }
+
+    /// Sends a command, as an array of bulk strings.
+    fn send(&mut self, args: &[&[u8]]) -> std::io::Result<()> {
+        let mut request = format!("*{}\r\n", args.len()).into_bytes();
+        for arg in args {
+            request.extend_from_slice(format!("${}\r\n", arg.len()).as_bytes());
+            request.extend_from_slice(arg);
+            request.extend_from_slice(b"\r\n");
+        }
+        std::io::Write::write_all(&mut self.stream, &request)
+    }
+
+    /// Reads the next reply, keeping partially received ones on timeouts.
+    fn read(&mut self) -> std::io::Result<Reply> {
+        loop {
+            if let Some((reply, end)) = parse(&self.buffer, 0)? {
+                self.buffer.drain(..end);
+                return Ok(reply);
+            }
+            let mut chunk = [0; 4096];
+            match std::io::Read::read(&mut self.stream, &mut chunk)? {
+                0 => return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)),
+                read => self.buffer.extend_from_slice(&chunk[..read]),
+            }
+        }
+    }
+}
+
+/// Parses the reply starting at `start`, returning it with the offset of its end, or `None`
+/// if it is not fully received yet.
+fn parse(buffer: &[u8], start: usize) -> std::io::Result<Option<(Reply, usize)>> {
+    let Some(length) = buffer[start..].windows(2).position(|window| window == b"\r\n") else {
+        return Ok(None);
+    };
+    if length == 0 {
+        return Err(unexpected());
+    }
+    let line = std::str::from_utf8(&buffer[start + 1..start + length]).map_err(|_| unexpected())?;
+    let mut end = start + length + 2;
+    let reply = match buffer[start] {
+        b'+' => Reply::Status(line.to_string()),
+        b'-' => Reply::Error(line.to_string()),
+        b':' => {
+            line.parse::<i64>().map_err(|_| unexpected())?;
+            Reply::Integer
+        }
+        b'$' => match line.parse::<i64>().map_err(|_| unexpected())? {
+            size if size < 0 => Reply::Bulk(None),
+            size => {
+                let size = size as usize;
+                if buffer.len() < end + size + 2 {
+                    return Ok(None);
+                }
+                let content = buffer[end..end + size].to_vec();
+                end += size + 2;
+                Reply::Bulk(Some(content))
+            }
+        },
+        b'*' => match line.parse::<i64>().map_err(|_| unexpected())? {
+            count if count < 0 => Reply::Array(None),
+            count => {
+                let mut items = Vec::new();
+                for _ in 0..count {
+                    let Some((item, next)) = parse(buffer, end)? else {
+                        return Ok(None);
+                    };
+                    items.push(item);
+                    end = next;
+                }
+                Reply::Array(Some(items))
+            }
+        },
+        _ => return Err(unexpected()),
+    };
+    Ok(Some((reply, end)))
+}
+
+/// Returns the error of a reply which does not match the protocol or the command.
+fn unexpected() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "unexpected reply")
+}
+
+/// Returns the error of a hash holding a field or a value which is not UTF-8.
+fn invalid_utf8() -> std::io::Error {
+    std::io::Error::new(std::io::ErrorKind::InvalidData, "hash fields and values must be UTF-8")
+}
@@ -0,0 +1,252 @@
+//! Runtime registry of user-defined configuration formats.
+//!
+//! [`ContentFormat`](crate::ContentFormat) only lists the formats built into this crate.
+//! Downstream crates can register their own [`Manager`](crate::Manager) implementations in a
+//! [`FormatRegistry`], under a name and file extensions, and read or write configurations
+//! through it: registered formats are looked up first, then the built-in ones. The extensions
+//! registered in [`FormatRegistry::global`] are also dispatched by [`read_config`](crate::read_config)
+//! and [`write_config`](crate::write_config) when no format is given.
+
+/// Builds a manager of a registered format for a file.
+type BuildFn = fn(&std::path::Path) -> Box<dyn crate::DynManager>;
+
+/// A format registered in a [`FormatRegistry`].
+#[derive(Debug, Clone)]
+struct RegisteredFormat {
+    /// Name of the format, lowercased.
+    name: String,
+    /// File extensions handled by the format, lowercased and without leading dot.
+    extensions: Vec<String>,
+    /// Builds the format manager.
+    build: BuildFn,
+}
+
+/// Format resolved for a path.
+enum Resolved {
+    /// A user-defined format.
+    Registered(RegisteredFormat),
+    /// A format built into this crate.
+    BuiltIn(crate::ContentFormat),
+}
+
+/// A set of user-defined configuration formats, dispatched by name or file extension.
+#[derive(Debug, Default)]
+pub struct FormatRegistry {
+    /// Registered formats, the latest registration of a name taking precedence.
+    formats: std::sync::RwLock<Vec<RegisteredFormat>>,
+}
+
+impl FormatRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the process-wide registry.
+    pub fn global() -> &'static FormatRegistry {
+        static GLOBAL: std::sync::OnceLock<FormatRegistry> = std::sync::OnceLock::new();
+        GLOBAL.get_or_init(FormatRegistry::new)
+    }
+
+    /// Registers a manager under a format name and file extensions.
+    ///
+    /// Registering an existing name replaces the previous registration. Registered names
+    /// and extensions take precedence over the built-in formats.
+    ///
+    /// # Type Parameters
+    /// - `M`: The manager implementing the format.
+    ///
+    /// # Parameters
+    /// - `name`: The format name, matched case-insensitively.
+    /// - `extensions`: File extensions handled by the format, with or without leading dot.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn setup() {
+    ///     // Any `Manager` implementation, e.g. a proprietary format defined downstream.
+    ///     cdumay_config::FormatRegistry::global().register::<cdumay_config::JsonManager>("acme", &["acme", ".acmerc"]);
+    /// }
+    /// ```
+    pub fn register<M: crate::Manager + Send + Sync + 'static>(&self, name: &str, extensions: &[&str]) {
+        let format = RegisteredFormat {
+            name: name.to_ascii_lowercase(),
+            extensions: extensions.iter().map(|ext| ext.trim_start_matches('.').to_ascii_lowercase()).collect(),
+            build: |path| Box::new(M::new(path)),
+        };
+        let mut formats = self.formats.write().unwrap_or_else(|poisoned| poisoned.into_inner());
+        formats.retain(|registered| registered.name != format.name);
+        formats.push(format);
+    }
+
+    /// Returns the names of the registered formats, in registration order.
+    pub fn names(&self) -> Vec<String> {
+        self.formats
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .map(|format| format.name.clone())
+            .collect()
+    }
+
+    /// Builds a type-erased manager for a file, dispatching on the format name or the file extension.
+    ///
+    /// # Parameters
+    /// - `path`: The path to the configuration file. Tilde `~` expansion is supported.
+    /// - `format`: Optional format name. When not provided, the format is inferred from the
+    ///   file extension, defaulting to `JSON`.
+    /// - `context`: A context used for error details if the format is unknown.
+    ///
+    /// # Returns
+    /// The manager, behind a [`DynManager`](crate::DynManager) trait object, or an error if
+    /// the format is unknown.
+    pub fn manager(
+        &self,
+        path: &str,
+        format: Option<&str>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Box<dyn crate::DynManager>> {
+        let resolved = self.resolve(path, format, context)?;
+        let path = std::path::PathBuf::from(shellexpand::tilde(path).as_ref());
+        Ok(match resolved {
+            Resolved::BuiltIn(format) => format.manager(path),
+            Resolved::Registered(format) => (format.build)(&path),
+        })
+    }
+
+    /// Reads a configuration file, dispatching on the format name or the file extension.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type to deserialize into.
+    ///
+    /// # Parameters
+    /// - `path`: The path to the configuration file. Tilde `~` expansion is supported.
+    /// - `format`: Optional format name. When not provided, the format is inferred from the
+    ///   file extension, defaulting to `JSON`.
+    /// - `context`: A key-value map used to resolve variables in the file.
+    ///
+    /// # Returns
+    /// The parsed configuration, or an error if the format is unknown or the file invalid.
+    pub fn read_config<C: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        format: Option<&str>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        self.read_config_with(path, format, context, &crate::ReadOptions::default())
+    }
+
+    /// Reads a configuration file like [`FormatRegistry::read_config`], applying the given read options.
+    pub fn read_config_with<C: serde::de::DeserializeOwned>(
+        &self,
+        path: &str,
+        format: Option<&str>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        match self.resolve(path, format, context)? {
+            Resolved::BuiltIn(format) => crate::read_config_with(path, Some(format), context, options),
+            Resolved::Registered(format) => {
+                log::info!("Reading config file '{}' as '{}'", path, format.name);
+                crate::formats::from_value((format.build)(std::path::Path::new(path)).read_value_with(context, options)?, context)
+            }
+        }
+    }
+
+    /// Writes a configuration file, dispatching on the format name or the file extension.
+    ///
+    /// # Type Parameters
+    /// - `C`: The data type to serialize.
+    ///
+    /// # Parameters
+    /// - `path`: The file path to write to. Tilde `~` expansion is supported.
+    /// - `format`: Optional format name. When not provided, the format is inferred from the
+    ///   file extension, defaulting to `JSON`.
+    /// - `data`: The data to serialize and write to the file.
+    /// - `context`: Templating context for value substitution, if applicable.
+    ///
+    /// # Returns
+    /// The path to the written file if successful, or an error otherwise.
+    pub fn write_config<C: serde::Serialize>(
+        &self,
+        path: &str,
+        format: Option<&str>,
+        data: C,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        self.write_config_with(path, format, data, context, &crate::WriteOptions::default())
+    }
+
+    /// Writes a configuration file like [`FormatRegistry::write_config`], applying the given write options.
+    pub fn write_config_with<C: serde::Serialize>(
+        &self,
+        path: &str,
+        format: Option<&str>,
+        data: C,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::WriteOptions,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        match self.resolve(path, format, context)? {
+            Resolved::BuiltIn(format) => crate::write_config_with(path, Some(format), data, context, options),
+            Resolved::Registered(format) => {
+                log::info!("Saving config file '{}' as '{}'", path, format.name);
+                let value = serde_value::to_value(data).map_err(|err| {
+                    crate::ConfigurationFileError::new()
+                        .with_message(format!("Failed to serialize configuration: {}", err))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = context.clone();
+                            ctx.insert("path".to_string(), serde_value::Value::String(path.to_string()));
+                            ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                            ctx
+                        }))
+                })?;
+                (format.build)(std::path::Path::new(path)).write_value_with(&value, context, options)
+            }
+        }
+    }
+
+    /// Tells whether the extension of a path is handled by a registered format.
+    pub(crate) fn handles(&self, path: &str) -> bool {
+        let extension = crate::cipher::plaintext_extension(std::path::Path::new(path));
+        self.formats
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .iter()
+            .any(|format| format.extensions.contains(&extension))
+    }
+
+    /// Finds the format of a file from an explicit name or its extension.
+    fn resolve(
+        &self,
+        path: &str,
+        format: Option<&str>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Resolved> {
+        let formats = self.formats.read().unwrap_or_else(|poisoned| poisoned.into_inner());
+        match format {
+            Some(name) => {
+                let name = name.to_ascii_lowercase();
+                if let Some(format) = formats.iter().rev().find(|format| format.name == name) {
+                    return Ok(Resolved::Registered(format.clone()));
+                }
+                crate::ContentFormat::from_name(&name).map(Resolved::BuiltIn).ok_or_else(|| {
+                    crate::ConfigurationFileError::new()
+                        .with_message(format!("Unknown configuration format '{}'", name))
+                        .with_details(crate::context::bounded({
+                            let mut ctx = context.clone();
+                            ctx.insert("path".to_string(), serde_value::Value::String(path.to_string()));
+                            ctx.insert("format".to_string(), serde_value::Value::String(name.clone()));
+                            ctx
+                        }))
+                        .into()
+                })
+            }
+            None => {
+                let extension = crate::cipher::plaintext_extension(std::path::Path::new(path));
+                if let Some(format) = formats.iter().rev().find(|format| format.extensions.contains(&extension)) {
+                    return Ok(Resolved::Registered(format.clone()));
+                }
+                Ok(Resolved::BuiltIn(crate::ContentFormat::from_name(&extension).unwrap_or_default()))
+            }
+        }
+    }
+}
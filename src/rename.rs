@@ -0,0 +1,134 @@
+//! Declarative renaming of configuration keys.
+//!
+//! Renaming a key or a section breaks the configuration files already deployed. Rather than
+//! a full migration, [`KeyRenames`] maps the old spelling to the new one before the
+//! configuration is deserialized, so that both spellings are accepted for a while.
+
+/// Renames of configuration keys, applied to the parsed configuration before deserialization.
+///
+/// Keys are dotted paths through nested maps (e.g. `database.url`). A renamed key keeps its
+/// value, the missing parent maps of its new path being created. If both spellings are set,
+/// the new one wins and the old one is dropped.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Config {
+///     storage: std::collections::BTreeMap<String, String>,
+/// }
+///
+/// fn load() -> cdumay_core::Result<Config> {
+///     let options = cdumay_config::ReadOptions {
+///         // The `database` section was renamed `storage`.
+///         renames: Some(cdumay_config::KeyRenames::new().rename("database", "storage")),
+///         ..Default::default()
+///     };
+///     cdumay_config::read_config_with("config.json", None, &std::collections::BTreeMap::new(), &options)
+/// }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct KeyRenames {
+    /// Old and new paths of the renamed keys, applied in order.
+    renames: Vec<(String, String)>,
+}
+
+impl KeyRenames {
+    /// Creates an empty set of renames.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a rename, applied after the previously added ones.
+    ///
+    /// # Parameters
+    /// - `old`: The dotted path of the old key.
+    /// - `new`: The dotted path of the new key.
+    ///
+    /// # Returns
+    /// The updated set of renames.
+    pub fn rename(mut self, old: &str, new: &str) -> Self {
+        self.renames.push((old.to_string(), new.to_string()));
+        self
+    }
+
+    /// Returns the old and new paths of the renamed keys.
+    pub fn renames(&self) -> &[(String, String)] {
+        &self.renames
+    }
+
+    /// Applies the renames to a parsed configuration.
+    ///
+    /// # Parameters
+    /// - `value`: The parsed configuration.
+    /// - `context`: A context used for error details if a rename fails.
+    ///
+    /// # Returns
+    /// The old and new paths of the renames which were applied, i.e. whose old key was set,
+    /// or an error if a parent of a new key is not a map.
+    pub fn apply(
+        &self,
+        value: &mut serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<(String, String)>> {
+        let mut applied = Vec::new();
+        for (old, new) in &self.renames {
+            let error = || -> cdumay_core::Error {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Cannot rename '{}' to '{}': a parent of '{}' is not a map", old, new, new))
+                    .with_details(crate::context::bounded(context.clone()))
+                    .into()
+            };
+            let moved = match take(value, old) {
+                Some(moved) => moved,
+                None => continue,
+            };
+            let (parents, name) = split(new);
+            let mut current = &mut *value;
+            for segment in parents {
+                current = match current {
+                    serde_value::Value::Map(entries) => entries
+                        .entry(serde_value::Value::String(segment.to_string()))
+                        .or_insert_with(|| serde_value::Value::Map(Default::default())),
+                    _ => return Err(error()),
+                };
+            }
+            match current {
+                serde_value::Value::Map(entries) => match entries.entry(serde_value::Value::String(name.to_string())) {
+                    std::collections::btree_map::Entry::Occupied(_) => {
+                        log::warn!("Both '{}' and its new name '{}' are set, ignoring '{}'", old, new, old)
+                    }
+                    std::collections::btree_map::Entry::Vacant(entry) => {
+                        entry.insert(moved);
+                    }
+                },
+                _ => return Err(error()),
+            }
+            applied.push((old.clone(), new.clone()));
+        }
+        Ok(applied)
+    }
+}
+
+/// Splits a dotted path into its parent segments and its last segment.
+fn split(path: &str) -> (Vec<&str>, &str) {
+    match path.rsplit_once('.') {
+        Some((parents, name)) => (parents.split('.').collect(), name),
+        None => (Vec::new(), path),
+    }
+}
+
+/// Removes the value at a dotted path of a value tree.
+fn take(value: &mut serde_value::Value, path: &str) -> Option<serde_value::Value> {
+    let (parents, name) = split(path);
+    let mut current = value;
+    for segment in parents {
+        current = match current {
+            serde_value::Value::Map(entries) => entries.get_mut(&serde_value::Value::String(segment.to_string()))?,
+            _ => return None,
+        };
+    }
+    match current {
+        serde_value::Value::Map(entries) => entries.remove(&serde_value::Value::String(name.to_string())),
+        _ => None,
+    }
+}
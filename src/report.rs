@@ -0,0 +1,265 @@
+//! Reports collecting every problem of a configuration file in one pass.
+//!
+//! Reading a configuration stops at the first failure, so users fixing a file discover its
+//! problems one at a time. [`read_config_report`] goes on after a failure as far as it can
+//! and collects everything it detects into a [`ConfigReport`].
+
+/// What a [`ConfigIssue`] is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigIssueKind {
+    /// The file cannot be read or parsed; nothing else can be checked.
+    Read,
+    /// A key rename cannot be applied.
+    Rename,
+    /// The declared schema version is missing, invalid or unsupported.
+    SchemaVersion,
+    /// The content does not match the configuration type.
+    Deserialization,
+    /// A key is not consumed by the configuration type.
+    UnknownKey,
+    /// The validation of the deserialized configuration failed.
+    Validation,
+}
+
+/// How serious a [`ConfigIssue`] is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigIssueSeverity {
+    /// The configuration can still be loaded.
+    Warning,
+    /// The configuration cannot be loaded.
+    Error,
+}
+
+/// A problem found in a configuration file.
+#[derive(Debug, Clone)]
+pub struct ConfigIssue {
+    /// What the issue is about.
+    pub kind: ConfigIssueKind,
+    /// How serious the issue is.
+    pub severity: ConfigIssueSeverity,
+    /// Path of the key concerned (e.g. `server.listeners[2].port`), if known.
+    pub key: Option<String>,
+    /// Description of the issue.
+    pub message: String,
+    /// Details of the underlying error (e.g. `line`, `column` or `suggestion`).
+    pub details: std::collections::BTreeMap<String, serde_value::Value>,
+}
+
+impl ConfigIssue {
+    /// Creates a validation error about a key, to be returned by the validation closure of
+    /// [`read_config_report`].
+    ///
+    /// # Parameters
+    /// - `key`: Path of the invalid key.
+    /// - `message`: Description of the issue.
+    pub fn invalid(key: &str, message: &str) -> Self {
+        Self {
+            kind: ConfigIssueKind::Validation,
+            severity: ConfigIssueSeverity::Error,
+            key: Some(key.to_string()),
+            message: message.to_string(),
+            details: Default::default(),
+        }
+    }
+
+    /// Creates an error issue from an error of the crate, its `key_path` detail being used as key.
+    fn from_error(kind: ConfigIssueKind, err: &cdumay_core::Error) -> Self {
+        let details = err.details();
+        Self {
+            kind,
+            severity: ConfigIssueSeverity::Error,
+            key: match details.get("key_path") {
+                Some(serde_value::Value::String(key)) => Some(key.clone()),
+                _ => None,
+            },
+            message: err.message().to_string(),
+            details,
+        }
+    }
+}
+
+/// Every problem found in a configuration file by [`read_config_report`].
+#[derive(Debug, Clone)]
+pub struct ConfigReport<C> {
+    /// Path of the file.
+    pub path: std::path::PathBuf,
+    /// The configuration, if it could be deserialized.
+    pub config: Option<C>,
+    /// The issues found, in detection order.
+    pub issues: Vec<ConfigIssue>,
+}
+
+impl<C> ConfigReport<C> {
+    /// Returns `true` if the configuration was deserialized and no error was found.
+    pub fn is_valid(&self) -> bool {
+        self.config.is_some() && self.errors().next().is_none()
+    }
+
+    /// Returns the issues preventing the configuration from being loaded.
+    pub fn errors(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.severity == ConfigIssueSeverity::Error)
+    }
+
+    /// Returns the issues which do not prevent the configuration from being loaded.
+    pub fn warnings(&self) -> impl Iterator<Item = &ConfigIssue> {
+        self.issues.iter().filter(|issue| issue.severity == ConfigIssueSeverity::Warning)
+    }
+
+    /// Returns the configuration if it is valid, or a single error listing every error issue.
+    ///
+    /// # Returns
+    /// The configuration, or a [`ConfigurationFileError`](crate::ConfigurationFileError)
+    /// whose `issues` detail holds the message of every error issue.
+    pub fn into_result(self) -> cdumay_core::Result<C> {
+        let messages: Vec<String> = self.errors().map(|issue| issue.message.clone()).collect();
+        if let Some(config) = self.config
+            && messages.is_empty()
+        {
+            return Ok(config);
+        }
+        Err(crate::ConfigurationFileError::new()
+            .with_message(format!(
+                "Configuration file '{}' has {} error(s): {}",
+                self.path.display(),
+                messages.len(),
+                messages.join("; ")
+            ))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(&Default::default(), &self.path);
+                ctx.insert(
+                    "issues".to_string(),
+                    serde_value::Value::Seq(messages.into_iter().map(serde_value::Value::String).collect()),
+                );
+                ctx
+            }))
+            .into())
+    }
+}
+
+impl<C> std::fmt::Display for ConfigReport<C> {
+    /// Formats the report as one line per issue followed by a summary line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for issue in &self.issues {
+            let severity = match issue.severity {
+                ConfigIssueSeverity::Warning => "warning",
+                ConfigIssueSeverity::Error => "error",
+            };
+            writeln!(f, "{}: {}: {}", self.path.display(), severity, issue.message)?;
+        }
+        write!(f, "{} error(s), {} warning(s)", self.errors().count(), self.warnings().count())
+    }
+}
+
+/// Reads a configuration file, collecting every problem found instead of stopping at the
+/// first one.
+///
+/// The checks run in order, each going on after the previous ones failed whenever possible:
+/// - the file is read and parsed; a failure stops the report;
+/// - the key renames of the options are applied;
+/// - the schema version of the options is checked;
+/// - the content is deserialized into `C`: a deserializer stops at its first failure, so at
+///   most one deserialization issue is reported;
+/// - every key which `C` does not consume is reported, as an error with
+///   [`UnknownKeyPolicy::Deny`](crate::UnknownKeyPolicy::Deny) and as a warning otherwise;
+/// - the deserialized configuration is passed to `validate`, whose issues are reported.
+///
+/// # Type Parameters
+/// - `C`: The type to deserialize the configuration into.
+/// - `V`: The validation closure.
+///
+/// # Parameters
+/// - `path`: Path to the configuration file. Tilde `~` expansion is supported.
+/// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+/// - `context`: A templating context used to resolve variables inside the configuration.
+/// - `options`: The read options to apply.
+/// - `validate`: Checks the deserialized configuration, returning the issues found.
+///
+/// # Returns
+/// The report, holding the configuration if it could be deserialized.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Server {
+///     host: String,
+///     port: u16,
+/// }
+///
+/// fn check() -> bool {
+///     let report = cdumay_config::read_config_report(
+///         "~/.config/server.json",
+///         None,
+///         &std::collections::BTreeMap::new(),
+///         &cdumay_config::ReadOptions::default(),
+///         |server: &Server| match server.port {
+///             0 => vec![cdumay_config::ConfigIssue::invalid("port", "port must not be 0")],
+///             _ => Vec::new(),
+///         },
+///     );
+///     println!("{}", report);
+///     report.is_valid()
+/// }
+/// ```
+pub fn read_config_report<C, V>(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+    validate: V,
+) -> ConfigReport<C>
+where
+    C: serde::de::DeserializeOwned,
+    V: FnOnce(&C) -> Vec<ConfigIssue>,
+{
+    let mut report = ConfigReport {
+        path: std::path::PathBuf::from(shellexpand::tilde(path).as_ref()),
+        config: None,
+        issues: Vec::new(),
+    };
+    let read_options = crate::ReadOptions {
+        renames: None,
+        schema_version: None,
+        unknown_keys: crate::UnknownKeyPolicy::Ignore,
+        ..options.clone()
+    };
+    let mut value: serde_value::Value = match crate::read_config_with(path, format, context, &read_options) {
+        Ok(value) => value,
+        Err(err) => {
+            report.issues.push(ConfigIssue::from_error(ConfigIssueKind::Read, &err));
+            return report;
+        }
+    };
+    if let Some(renames) = &options.renames
+        && let Err(err) = renames.apply(&mut value, context)
+    {
+        let err = crate::context::add_path(err, &report.path);
+        report.issues.push(ConfigIssue::from_error(ConfigIssueKind::Rename, &err));
+    }
+    if let Some(schema_version) = &options.schema_version
+        && let Err(err) = schema_version.check(&value, context)
+    {
+        let err = crate::context::add_path(err, &report.path);
+        report.issues.push(ConfigIssue::from_error(ConfigIssueKind::SchemaVersion, &err));
+    }
+    let (result, unused_keys) = crate::tracking::deserialize_tracked::<C>(value, context);
+    if let Err(err) = &result {
+        let err = crate::context::add_path(err.clone(), &report.path);
+        report.issues.push(ConfigIssue::from_error(ConfigIssueKind::Deserialization, &err));
+    }
+    let severity = match options.unknown_keys {
+        crate::UnknownKeyPolicy::Deny => ConfigIssueSeverity::Error,
+        _ => ConfigIssueSeverity::Warning,
+    };
+    report.issues.extend(unused_keys.into_iter().map(|key| ConfigIssue {
+        kind: ConfigIssueKind::UnknownKey,
+        severity,
+        message: format!("Unknown configuration key '{}'", key),
+        key: Some(key),
+        details: Default::default(),
+    }));
+    if let Ok(config) = result {
+        report.issues.extend(validate(&config));
+        report.config = Some(config);
+    }
+    report
+}
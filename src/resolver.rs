@@ -0,0 +1,105 @@
+//! Mapping of configuration paths to the files actually accessed.
+//!
+//! Managers do not open their path verbatim: the path is first given to a [`PathResolver`],
+//! set in [`ReadOptions::resolver`](crate::ReadOptions::resolver) and
+//! [`WriteOptions::resolver`](crate::WriteOptions::resolver). Replacing the default resolver
+//! allows to sandbox an application or to run tests hermetically, e.g. by mapping every
+//! path into a temporary directory with a [`ChrootPathResolver`].
+
+/// Maps the path of a configuration file to the path actually opened.
+///
+/// The resolved path is used to open and create the file, for its lock sidecar and for
+/// post-write hooks.
+pub trait PathResolver: std::fmt::Debug + Send + Sync {
+    /// Resolves a configuration file path.
+    ///
+    /// # Parameters
+    /// - `path`: The path of the manager.
+    ///
+    /// # Returns
+    /// The path to access, or an error if the path must not be accessed.
+    fn resolve(&self, path: &std::path::Path) -> std::io::Result<std::path::PathBuf>;
+}
+
+/// The resolver used when none is set: expands a leading tilde `~` to the home directory.
+///
+/// Relative paths are kept relative, and thus resolved against the current directory.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DefaultPathResolver;
+
+impl PathResolver for DefaultPathResolver {
+    fn resolve(&self, path: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+        Ok(match path.to_str() {
+            Some(path) => std::path::PathBuf::from(shellexpand::tilde(path).as_ref()),
+            None => path.to_path_buf(),
+        })
+    }
+}
+
+/// A resolver confining every path to a root directory, as `chroot` would.
+///
+/// Absolute and relative paths are both interpreted from the root, and `..` components
+/// never go above it. The confinement is lexical: symbolic links inside the root are
+/// followed by the file system and may point outside of it.
+#[derive(Debug, Clone)]
+pub struct ChrootPathResolver {
+    /// Directory acting as file system root.
+    root: std::path::PathBuf,
+}
+
+impl ChrootPathResolver {
+    /// Creates a resolver confining paths to the given directory.
+    ///
+    /// # Parameters
+    /// - `root`: The directory acting as file system root.
+    ///
+    /// # Returns
+    /// A new `ChrootPathResolver`.
+    pub fn new<P: AsRef<std::path::Path>>(root: P) -> Self {
+        Self {
+            root: root.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the directory acting as file system root.
+    pub fn root(&self) -> &std::path::Path {
+        &self.root
+    }
+}
+
+impl PathResolver for ChrootPathResolver {
+    fn resolve(&self, path: &std::path::Path) -> std::io::Result<std::path::PathBuf> {
+        let mut relative = std::path::PathBuf::new();
+        for component in path.components() {
+            match component {
+                std::path::Component::Normal(name) => relative.push(name),
+                std::path::Component::ParentDir => {
+                    relative.pop();
+                }
+                std::path::Component::Prefix(_) | std::path::Component::RootDir | std::path::Component::CurDir => {}
+            }
+        }
+        Ok(self.root.join(relative))
+    }
+}
+
+/// Resolves the path of a manager with the given resolver, or the default one.
+pub(crate) fn resolve(
+    resolver: Option<&std::sync::Arc<dyn PathResolver>>,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    let resolved = match resolver {
+        Some(resolver) => resolver.resolve(path),
+        None => DefaultPathResolver.resolve(path),
+    };
+    Ok(resolved.map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to resolve path: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, path);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    })?)
+}
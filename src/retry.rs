@@ -0,0 +1,66 @@
+//! Bounded retries of file operations failing with transient sharing violations.
+//!
+//! On Windows, editors, indexers and antivirus software briefly open files without
+//! sharing them, making concurrent opens fail with `ERROR_SHARING_VIOLATION`. Such
+//! failures are retried transparently; other errors, and all errors on other platforms,
+//! are returned immediately.
+
+/// Bounded retry policy for file sharing violations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RetryPolicy {
+    /// Maximum number of attempts, including the first one.
+    pub attempts: u32,
+    /// Delay before the first retry, doubled after each failed attempt.
+    pub delay: std::time::Duration,
+}
+
+impl RetryPolicy {
+    /// A policy which never retries.
+    pub fn none() -> Self {
+        Self {
+            attempts: 1,
+            delay: std::time::Duration::ZERO,
+        }
+    }
+
+    /// Runs a file operation, retrying it while it fails with a sharing violation.
+    pub(crate) fn run<T>(&self, mut operation: impl FnMut() -> std::io::Result<T>) -> std::io::Result<T> {
+        let mut delay = self.delay;
+        let mut attempt = 1;
+        loop {
+            match operation() {
+                Err(err) if attempt < self.attempts && is_sharing_violation(&err) => {
+                    log::debug!("File busy ({}), retrying in {:?} (attempt {}/{})", err, delay, attempt, self.attempts);
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                result => return result,
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    /// Retries up to 5 times, starting with a 50ms delay.
+    fn default() -> Self {
+        Self {
+            attempts: 5,
+            delay: std::time::Duration::from_millis(50),
+        }
+    }
+}
+
+/// Returns `true` if the error is a transient sharing or lock violation.
+#[cfg(windows)]
+fn is_sharing_violation(err: &std::io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION.
+    matches!(err.raw_os_error(), Some(32) | Some(33))
+}
+
+/// Returns `true` if the error is a transient sharing or lock violation.
+#[cfg(not(windows))]
+fn is_sharing_violation(_err: &std::io::Error) -> bool {
+    false
+}
+
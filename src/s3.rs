@@ -0,0 +1,288 @@
+//! Configuration documents stored in an S3-compatible object store.
+//!
+//! With the `s3` feature, [`read_config`](crate::read_config) reads `s3://bucket/key` paths
+//! with an [`S3Source`] configured from the environment, and hands the object to the manager
+//! of the requested format, or else of the extension of its key:
+//!
+//! ```rust
+//! fn load() -> cdumay_core::Result<serde_json::Value> {
+//!     let context = std::collections::BTreeMap::new();
+//!     cdumay_config::read_config("s3://my-bucket/app/prod.json", None, &context)
+//! }
+//! ```
+//!
+//! Requests are signed with AWS Signature Version 4 when credentials are available, and
+//! sent anonymously otherwise (public buckets). Transport failures, throttling (`429`) and
+//! server errors (`5xx`) are retried according to a [`RetryPolicy`](crate::RetryPolicy).
+
+/// Hash of an empty payload, sent as the payload hash of `GET` requests.
+const EMPTY_PAYLOAD_SHA256: &str = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+/// A configuration document stored as an object of an S3-compatible object store.
+#[derive(Debug, Clone, PartialEq)]
+pub struct S3Source {
+    /// Name of the bucket.
+    bucket: String,
+    /// Key of the object.
+    key: String,
+    /// Region of the bucket.
+    region: String,
+    /// Endpoint of the object store, the regional AWS endpoint if unset.
+    endpoint: Option<String>,
+    /// Credentials signing the requests, anonymous requests if unset.
+    credentials: Option<crate::AwsCredentials>,
+    /// Retries of transient failures.
+    retry: crate::RetryPolicy,
+    /// Maximum duration of each request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+}
+
+impl S3Source {
+    /// Creates a source for an object, in the `us-east-1` region and without credentials.
+    ///
+    /// # Parameters
+    /// - `bucket`: Name of the bucket.
+    /// - `key`: Key of the object (e.g. `app/prod.yaml`).
+    pub fn new(bucket: &str, key: &str) -> Self {
+        Self {
+            bucket: bucket.to_string(),
+            key: key.trim_start_matches('/').to_string(),
+            region: "us-east-1".to_string(),
+            endpoint: None,
+            credentials: None,
+            retry: crate::RetryPolicy::default(),
+            timeout: None,
+        }
+    }
+
+    /// Returns `true` if a configuration path is an `s3://` URL.
+    pub fn is_url(path: &str) -> bool {
+        path.get(..5).is_some_and(|prefix| prefix.eq_ignore_ascii_case("s3://"))
+    }
+
+    /// Creates a source for the object of an `s3://bucket/key` URL, configured from the
+    /// environment:
+    ///
+    /// - the region is read from `AWS_REGION` (or `AWS_DEFAULT_REGION`);
+    /// - the endpoint from `AWS_ENDPOINT_URL_S3` (or `AWS_ENDPOINT_URL`), e.g. for MinIO;
+    /// - the credentials with [`AwsCredentials::from_env`](crate::AwsCredentials::from_env).
+    ///
+    /// # Parameters
+    /// - `url`: URL of the object (e.g. `s3://my-bucket/app/prod.yaml`).
+    /// - `context`: A context used for error details if the URL is invalid.
+    ///
+    /// # Returns
+    /// The source, or an error if the URL does not name a bucket and a key.
+    pub fn from_env(url: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<Self> {
+        let (bucket, key) = url
+            .get(5..)
+            .filter(|_| Self::is_url(url))
+            .and_then(|location| location.split_once('/'))
+            .filter(|(bucket, key)| !bucket.is_empty() && !key.is_empty())
+            .ok_or_else(|| {
+                crate::ConfigurationFileError::new()
+                    .with_message(format!("Invalid S3 URL '{}': expected s3://<bucket>/<key>", url))
+                    .with_details(crate::context::bounded(crate::context::with_request(context, url, None, None)))
+            })?;
+        let mut source = Self::new(bucket, key);
+        if let Some(region) = crate::aws::region_from_env() {
+            source = source.with_region(&region);
+        }
+        if let Ok(endpoint) = std::env::var("AWS_ENDPOINT_URL_S3").or_else(|_| std::env::var("AWS_ENDPOINT_URL")) {
+            source = source.with_endpoint(&endpoint);
+        }
+        if let Some(credentials) = crate::AwsCredentials::from_env() {
+            source = source.with_credentials(credentials);
+        }
+        Ok(source)
+    }
+
+    /// Sets the region of the bucket.
+    pub fn with_region(mut self, region: &str) -> Self {
+        self.region = region.to_string();
+        self
+    }
+
+    /// Sets the endpoint of the object store (e.g. `http://minio.internal:9000`), addressed
+    /// with path-style URLs.
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Sets the credentials signing the requests.
+    pub fn with_credentials(mut self, credentials: crate::AwsCredentials) -> Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Sets the retries of transient failures, [`RetryPolicy::default`](crate::RetryPolicy::default) by default.
+    pub fn with_retry(mut self, retry: crate::RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Sets the maximum duration of each request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns the HTTP URL of the object: virtual-hosted on AWS, path-style on a custom
+    /// endpoint.
+    pub fn url(&self) -> String {
+        let key = crate::aws::uri_encode(&self.key, true);
+        match &self.endpoint {
+            Some(endpoint) => format!("{}/{}/{}", endpoint, crate::aws::uri_encode(&self.bucket, false), key),
+            None => format!("https://{}.s3.{}.amazonaws.com/{}", self.bucket, self.region, key),
+        }
+    }
+
+    /// Fetches the object, retrying transient failures.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the request fails.
+    ///
+    /// # Returns
+    /// The content of the object, or an error holding the `url`, `bucket` and `key` of the
+    /// object (and the `status` of the response, if any) in its details.
+    pub fn fetch(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<Vec<u8>> {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let url = self.url();
+        let mut delay = self.retry.delay;
+        let mut attempt = 1;
+        loop {
+            match self.get(&agent, &url) {
+                Ok(content) => return Ok(content),
+                Err(failure) if failure.transient && attempt < self.retry.attempts => {
+                    log::debug!(
+                        "{}, retrying in {:?} (attempt {}/{})",
+                        failure.message,
+                        delay,
+                        attempt,
+                        self.retry.attempts
+                    );
+                    std::thread::sleep(delay);
+                    delay *= 2;
+                    attempt += 1;
+                }
+                Err(failure) => {
+                    return Err(crate::ConfigurationFileError::new()
+                        .with_message(failure.message)
+                        .with_details(crate::context::bounded({
+                            let mut ctx = crate::context::with_request(context, &url, failure.status, failure.origin);
+                            ctx.insert("bucket".to_string(), serde_value::Value::String(self.bucket.clone()));
+                            ctx.insert("key".to_string(), serde_value::Value::String(self.key.clone()));
+                            ctx
+                        }))
+                        .into());
+                }
+            }
+        }
+    }
+
+    /// Fetches the object and deserializes it like [`read_config_with`](crate::read_config_with).
+    ///
+    /// The options' lock and resolver only apply to files and are not used; requests are
+    /// retried according to the policy of the source.
+    ///
+    /// # Type Parameters
+    /// - `C`: The type to deserialize the configuration into.
+    ///
+    /// # Parameters
+    /// - `format`: Optional format specifier. When not provided, the format is inferred from
+    ///   the extension of the key, and defaults to `JSON`.
+    /// - `context`: A templating context used to resolve variables inside the configuration.
+    /// - `options`: The read options to apply.
+    ///
+    /// # Returns
+    /// The deserialized configuration of type `C`, or an error if fetching or parsing fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn load() -> cdumay_core::Result<serde_json::Value> {
+    ///     cdumay_config::S3Source::new("my-bucket", "app/prod.json")
+    ///         .with_region("eu-west-3")
+    ///         .with_credentials(cdumay_config::AwsCredentials::new("AKIDEXAMPLE", "secret"))
+    ///         .read_config_with(None, &std::collections::BTreeMap::new(), &cdumay_config::ReadOptions::default())
+    /// }
+    /// ```
+    pub fn read_config_with<C: serde::de::DeserializeOwned>(
+        &self,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<C> {
+        let location = format!("s3://{}/{}", self.bucket, self.key);
+        log::info!("Fetching config '{}'", location);
+        let content = self.fetch(context)?;
+        let mut context = context.clone();
+        context.insert("url".to_string(), serde_value::Value::String(location.clone()));
+        let format = format.or_else(|| crate::ContentFormat::from_uri(&location)).unwrap_or_default();
+        format.read_content_with(&location, content, &context, options)
+    }
+
+    /// Sends a single `GET` request of the object.
+    fn get(&self, agent: &ureq::Agent, url: &str) -> Result<Vec<u8>, Failure> {
+        let mut request = agent.get(url);
+        if let Some(credentials) = &self.credentials {
+            let (host, path) = url
+                .split_once("://")
+                .map_or(url, |(_, rest)| rest)
+                .split_once('/')
+                .map_or((url, String::from("/")), |(host, path)| (host, format!("/{}", path)));
+            let amz_date = crate::aws::amz_date(std::time::SystemTime::now());
+            let mut headers = vec![
+                ("host", host.to_string()),
+                ("x-amz-content-sha256", EMPTY_PAYLOAD_SHA256.to_string()),
+                ("x-amz-date", amz_date.clone()),
+            ];
+            if let Some(token) = &credentials.session_token {
+                headers.push(("x-amz-security-token", token.clone()));
+            }
+            let canonical = crate::aws::canonical_request("GET", &path, "", &headers, "");
+            let authorization = crate::aws::authorization(credentials, &self.region, "s3", &amz_date, canonical);
+            for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+                request = request.header(*name, value);
+            }
+            request = request.header("authorization", &authorization);
+        }
+        let mut response = request.call().map_err(|err| Failure {
+            message: format!("Failed to fetch '{}': {}", url, err),
+            status: None,
+            origin: Some(err.to_string()),
+            transient: true,
+        })?;
+        let status = response.status();
+        if !status.is_success() {
+            return Err(Failure {
+                message: format!("Failed to fetch '{}': HTTP status {}", url, status),
+                status: Some(status.as_u16()),
+                origin: response.body_mut().read_to_string().ok().filter(|body| !body.is_empty()),
+                transient: status.is_server_error() || status.as_u16() == 429,
+            });
+        }
+        response.body_mut().read_to_vec().map_err(|err| Failure {
+            message: format!("Failed to read the response of '{}': {}", url, err),
+            status: Some(status.as_u16()),
+            origin: Some(err.to_string()),
+            transient: true,
+        })
+    }
+}
+
+/// A failed attempt to fetch an object.
+struct Failure {
+    /// Description of the failure.
+    message: String,
+    /// HTTP status of the response, if any.
+    status: Option<u16>,
+    /// Cause of the failure, if known.
+    origin: Option<String>,
+    /// Whether the request may succeed if retried.
+    transient: bool,
+}
@@ -0,0 +1,48 @@
+//! JSON Schema generation from configuration types.
+//!
+//! Configuration types deriving [`schemars::JsonSchema`] can have their schema generated and
+//! written next to the configuration files, so that editor completion, documentation and
+//! validation artifacts stay in sync with the Rust types:
+//!
+//! ```rust
+//! #[derive(serde::Deserialize, schemars::JsonSchema)]
+//! struct Server {
+//!     /// Listening port.
+//!     port: u16,
+//! }
+//!
+//! fn publish() -> cdumay_core::Result<std::path::PathBuf> {
+//!     cdumay_config::write_schema::<Server>("server.schema.json", None, &std::collections::BTreeMap::new())
+//! }
+//! ```
+
+/// Generates the JSON Schema of a configuration type.
+///
+/// # Type Parameters
+/// - `C`: The configuration type.
+///
+/// # Returns
+/// The root schema of the type, doc comments being used as descriptions.
+pub fn schema_for<C: schemars::JsonSchema>() -> schemars::Schema {
+    schemars::schema_for!(C)
+}
+
+/// Generates the JSON Schema of a configuration type and writes it to a file.
+///
+/// # Type Parameters
+/// - `C`: The configuration type.
+///
+/// # Parameters
+/// - `path`: The file path to write to. Tilde `~` expansion is supported.
+/// - `format`: Optional output format. Defaults to `JSON` if not provided.
+/// - `context`: Context used for error reporting.
+///
+/// # Returns
+/// The path to the written file if successful, or an error otherwise.
+pub fn write_schema<C: schemars::JsonSchema>(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    crate::write_config(path, format, schema_for::<C>(), context)
+}
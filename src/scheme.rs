@@ -0,0 +1,199 @@
+//! Dispatch of configuration paths on their URI scheme.
+//!
+//! [`read_config`](crate::read_config) routes paths of the form `<scheme>://...` to a
+//! source fetching their content, which is then decoded by the manager of the requested
+//! format or, if none, of the format inferred from the `Content-Type` of HTTP responses or
+//! from the extension of the URI. Plain paths, and `file://` URIs, are read from the filesystem as before. The
+//! built-in schemes are:
+//!
+//! - `file:///etc/app.json`: a local file;
+//! - `env://APP_CONFIG`: the value of an environment variable;
+//! - `http://` and `https://` with the `http` feature, fetched with an `HttpSource`;
+//! - `s3://bucket/key` with the `s3` feature, fetched with an `S3Source`.
+//!
+//! Other schemes, such as `vault://`, are served by [`SchemeSource`]s registered in a
+//! [`SchemeRegistry`], which take precedence over the built-in ones, `file` excepted:
+//!
+//! ```rust
+//! fn load(vault: cdumay_config::VaultConfig) -> cdumay_core::Result<serde_json::Value> {
+//!     cdumay_config::SchemeRegistry::global().register("vault", cdumay_config::SecretsSource::new(vault));
+//!     cdumay_config::read_config("vault://database", None, &std::collections::BTreeMap::new())
+//! }
+//! ```
+
+/// Source of the content of the configuration URIs of a scheme.
+pub trait SchemeSource: Send + Sync {
+    /// Fetches the raw content of a configuration URI.
+    ///
+    /// # Parameters
+    /// - `uri`: The full URI, scheme included (e.g. `vault://database`).
+    /// - `context`: A context used for error details if the content cannot be fetched.
+    /// - `options`: The read options of the configuration.
+    ///
+    /// # Returns
+    /// The content to decode, or an error if it cannot be fetched.
+    fn fetch(
+        &self,
+        uri: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<Vec<u8>>;
+}
+
+/// A set of sources of configuration URIs, dispatched by scheme.
+#[derive(Default)]
+pub struct SchemeRegistry {
+    /// Registered sources by lowercased scheme.
+    sources: std::sync::RwLock<std::collections::BTreeMap<String, std::sync::Arc<dyn SchemeSource>>>,
+}
+
+impl std::fmt::Debug for SchemeRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SchemeRegistry").field("schemes", &self.schemes()).finish()
+    }
+}
+
+impl SchemeRegistry {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the process-wide registry, used by [`read_config`](crate::read_config).
+    pub fn global() -> &'static SchemeRegistry {
+        static GLOBAL: std::sync::OnceLock<SchemeRegistry> = std::sync::OnceLock::new();
+        GLOBAL.get_or_init(SchemeRegistry::new)
+    }
+
+    /// Registers the source of a scheme, replacing any previous registration and taking
+    /// precedence over a built-in scheme of the same name. `file://` URIs are always read
+    /// from the filesystem by [`read_config`](crate::read_config).
+    ///
+    /// # Parameters
+    /// - `scheme`: The scheme, without `://`, matched case-insensitively.
+    /// - `source`: The source of the URIs of the scheme.
+    pub fn register<S: SchemeSource + 'static>(&self, scheme: &str, source: S) {
+        self.sources
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .insert(scheme.to_ascii_lowercase(), std::sync::Arc::new(source));
+    }
+
+    /// Returns the registered schemes, in alphabetical order.
+    pub fn schemes(&self) -> Vec<String> {
+        self.sources
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .keys()
+            .cloned()
+            .collect()
+    }
+
+    /// Fetches the raw content of a configuration URI with the source of its scheme.
+    ///
+    /// # Parameters
+    /// - `uri`: The URI to fetch (e.g. `env://APP_CONFIG`).
+    /// - `context`: A context used for error details if the content cannot be fetched.
+    /// - `options`: The read options of the configuration.
+    ///
+    /// # Returns
+    /// The content, or an error holding the `url` in its details if the URI has no scheme,
+    /// its scheme is not supported or the content cannot be fetched.
+    pub fn fetch(
+        &self,
+        uri: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<Vec<u8>> {
+        self.fetch_typed(uri, context, options).map(|(content, _)| content)
+    }
+
+    /// Fetches the raw content of a configuration URI like [`SchemeRegistry::fetch`], with the
+    /// format declared by the built-in `http` and `https` sources (`Content-Type`), if known.
+    pub(crate) fn fetch_typed(
+        &self,
+        uri: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<(Vec<u8>, Option<crate::ContentFormat>)> {
+        let error = |message: String| -> cdumay_core::Error {
+            crate::ConfigurationFileError::new()
+                .with_message(message)
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("url".to_string(), serde_value::Value::String(uri.to_string()));
+                    ctx
+                }))
+                .into()
+        };
+        let Some(scheme) = scheme(uri) else {
+            return Err(error(format!("Invalid configuration URI '{}': no scheme found", uri)));
+        };
+        let scheme = scheme.to_ascii_lowercase();
+        let source = self.sources.read().unwrap_or_else(|poisoned| poisoned.into_inner()).get(&scheme).cloned();
+        if let Some(source) = source {
+            return Ok((source.fetch(uri, context, options)?, None));
+        }
+        match scheme.as_str() {
+            "file" => {
+                let path = shellexpand::tilde(&uri[7..]).to_string();
+                match options.retry.run(|| std::fs::read(&path)) {
+                    Ok(content) => Ok((content, None)),
+                    Err(err) => Err(error(format!("Failed to read config file '{}': {}", path, err))),
+                }
+            }
+            "env" => match std::env::var_os(&uri[6..]) {
+                Some(value) => Ok((value.into_encoded_bytes(), None)),
+                None => Err(error(format!("Environment variable '{}' is not set", &uri[6..]))),
+            },
+            #[cfg(feature = "http")]
+            "http" | "https" => crate::HttpSource::new(uri).fetch_typed(context),
+            #[cfg(feature = "s3")]
+            "s3" => Ok((crate::S3Source::from_env(uri, context)?.with_retry(options.retry).fetch(context)?, None)),
+            _ => Err(error(format!("Unsupported scheme '{}' in configuration URI '{}'", scheme, uri))),
+        }
+    }
+}
+
+/// A [`SchemeSource`] serving the secrets of a [`SecretProvider`](crate::SecretProvider)
+/// by alias, e.g. `vault://database` for the secret aliased `database`.
+///
+/// Aliases are resolved in the `env` of the context, like
+/// [`VaultSecrets::alias`](crate::VaultSecrets::alias) does.
+#[derive(Debug, Clone)]
+pub struct SecretsSource<P> {
+    /// The provider of the secrets.
+    provider: P,
+}
+
+impl<P: crate::SecretProvider> SecretsSource<P> {
+    /// Creates a source serving the secrets of a provider.
+    pub fn new(provider: P) -> Self {
+        Self { provider }
+    }
+}
+
+impl<P: crate::SecretProvider> SchemeSource for SecretsSource<P> {
+    fn fetch(
+        &self,
+        uri: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        _options: &crate::ReadOptions,
+    ) -> cdumay_core::Result<Vec<u8>> {
+        let alias = uri.split_once("://").map_or(uri, |(_, alias)| alias);
+        Ok(self.provider.get(alias, context)?.expose().as_bytes().to_vec())
+    }
+}
+
+/// Returns the scheme of a configuration path of the form `<scheme>://...`, if any.
+///
+/// Schemes start with a letter followed by letters, digits, `+`, `-` or `.`, so that
+/// Windows paths such as `C:\app.json` are not mistaken for URIs.
+pub(crate) fn scheme(path: &str) -> Option<&str> {
+    let (scheme, _) = path.split_once("://")?;
+    let mut chars = scheme.chars();
+    match chars.next()?.is_ascii_alphabetic() && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.')) {
+        true => Some(scheme),
+        false => None,
+    }
+}
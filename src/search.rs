@@ -0,0 +1,154 @@
+//! Lookup of configuration files among several paths.
+//!
+//! Applications commonly look for their configuration in a few places, from the most
+//! specific to the most general (working directory, user directory, system directory).
+//! [`read_first_config`] reads the first of them which exists. Packaged software also
+//! reads drop-in directories such as `/etc/app/conf.d`, where each package or administrator
+//! adds its own file: [`read_config_dir`] merges every file matching a glob pattern.
+
+/// Reads the first existing file among candidate paths.
+///
+/// Candidates are tried in order: local paths are skipped if no file exists at them, and
+/// URIs such as `env://APP_CONFIG` are always read. Once a candidate is found, its errors
+/// are returned as is and the next candidates are not tried.
+///
+/// # Type Parameters
+/// - `C`: The type to deserialize the configuration into. Must implement `DeserializeOwned`.
+///
+/// # Parameters
+/// - `candidates`: Paths of the candidate files, in order of preference. Tilde `~`
+///   expansion is supported.
+/// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+/// - `context`: A templating context used to resolve variables inside the configuration.
+///
+/// # Returns
+/// The deserialized configuration of the first existing candidate.
+///
+/// # Errors
+/// Returns a [`ConfigurationFileError`](crate::ConfigurationFileError) listing the paths
+/// tried in its `tried` detail if no candidate exists, or the error of the candidate found.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Settings {
+///     endpoint: String,
+/// }
+///
+/// fn load() -> cdumay_core::Result<Settings> {
+///     cdumay_config::read_first_config(
+///         &["./app.json", "~/.config/app/app.json", "/etc/app/app.json"],
+///         None,
+///         &std::collections::BTreeMap::new(),
+///     )
+/// }
+/// ```
+pub fn read_first_config<C: serde::de::DeserializeOwned>(
+    candidates: &[&str],
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<C> {
+    read_first_config_with(candidates, format, context, &crate::ReadOptions::default())
+}
+
+/// Reads the first existing file among candidate paths like [`read_first_config`], applying
+/// the given [`ReadOptions`](crate::ReadOptions).
+pub fn read_first_config_with<C: serde::de::DeserializeOwned>(
+    candidates: &[&str],
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<C> {
+    let mut tried = Vec::with_capacity(candidates.len());
+    for candidate in candidates {
+        let path = match crate::scheme::scheme(candidate) {
+            Some(scheme) if scheme.eq_ignore_ascii_case("file") => &candidate[7..],
+            Some(_) => return crate::read_config_with(candidate, format, context, options),
+            None => candidate,
+        };
+        let path = shellexpand::tilde(path);
+        if std::path::Path::new(path.as_ref()).exists() {
+            return crate::read_config_with(candidate, format, context, options);
+        }
+        log::debug!("No configuration file at '{}'", path);
+        tried.push(path.to_string());
+    }
+    Err(crate::ConfigurationFileError::new()
+        .with_message(format!("No configuration file found, tried: {}", tried.join(", ")))
+        .with_details(crate::context::bounded({
+            let mut ctx = context.clone();
+            ctx.insert(
+                "tried".to_string(),
+                serde_value::Value::Seq(tried.into_iter().map(serde_value::Value::String).collect()),
+            );
+            ctx
+        }))
+        .into())
+}
+
+/// Reads every file matching a glob pattern, merges them and deserializes the result.
+///
+/// Files are read in the lexical order of their paths, each file overriding the previous
+/// ones: maps are merged key by key and any other value replaces the previous one. Files
+/// are usually prefixed with a number to order them (`10-defaults.yaml`, `50-local.yaml`).
+/// Directories matching the pattern are ignored, and no matching file reads as an empty map.
+///
+/// # Type Parameters
+/// - `C`: The type to deserialize the configuration into. Must implement `DeserializeOwned`.
+///
+/// # Parameters
+/// - `pattern`: Glob pattern of the files (e.g. `/etc/app/conf.d/*.yaml`). Tilde `~`
+///   expansion is supported.
+/// - `format`: Optional format specifier of every file. Defaults to `JSON` if not provided.
+/// - `context`: A templating context used to resolve variables inside the files.
+///
+/// # Returns
+/// The deserialized merged configuration.
+///
+/// # Errors
+/// Returns a [`ConfigurationFileError`](crate::ConfigurationFileError) if the pattern is
+/// invalid or a matching path cannot be read, or the error of a file which cannot be parsed
+/// or of the merged configuration which cannot be deserialized.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Settings {
+///     #[serde(default)]
+///     plugins: Vec<String>,
+/// }
+///
+/// fn load() -> cdumay_core::Result<Settings> {
+///     cdumay_config::read_config_dir("/etc/app/conf.d/*.json", None, &std::collections::BTreeMap::new())
+/// }
+/// ```
+pub fn read_config_dir<C: serde::de::DeserializeOwned>(
+    pattern: &str,
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<C> {
+    let expanded = shellexpand::tilde(pattern);
+    let error = |message: String| -> cdumay_core::Error {
+        crate::ConfigurationFileError::new()
+            .with_message(message)
+            .with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("pattern".to_string(), serde_value::Value::String(pattern.to_string()));
+                ctx
+            }))
+            .into()
+    };
+    let mut paths = glob::glob(expanded.as_ref())
+        .map_err(|err| error(format!("Invalid pattern '{}': {}", pattern, err)))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| error(format!("Failed to read '{}': {}", err.path().display(), err.error())))?;
+    paths.retain(|path| path.is_file());
+    paths.sort();
+    log::info!("Reading {} config files matching '{}'", paths.len(), pattern);
+    let mut merged = serde_value::Value::Map(std::collections::BTreeMap::new());
+    for path in paths {
+        let value: serde_value::Value = crate::read_config(&path.to_string_lossy(), format, context)?;
+        merged = crate::merge::merge_values(merged, value);
+    }
+    crate::formats::from_value(merged, context)
+}
@@ -0,0 +1,105 @@
+//! Wrappers keeping secret values out of logs and memory.
+
+/// A secret value, masked as `***` when formatted and zeroized when dropped.
+///
+/// The value is only reachable through [`Secret::expose`], so that it cannot leak through
+/// `{:?}` logging of the structures holding it. A secret deserializes like its value.
+///
+/// # Example
+/// ```rust
+/// let password = cdumay_config::SecretString::from("hunter2");
+/// assert_eq!(format!("{:?}", password), "***");
+/// assert_eq!(password.expose(), "hunter2");
+/// ```
+#[derive(Clone, Default)]
+pub struct Secret<T: zeroize::Zeroize>(T);
+
+/// A secret string, such as a password or an API key.
+pub type SecretString = Secret<String>;
+
+impl<T: zeroize::Zeroize> Secret<T> {
+    /// Wraps a secret value.
+    pub fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    /// Returns the secret value.
+    pub fn expose(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: zeroize::Zeroize> Drop for Secret<T> {
+    fn drop(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+impl<T: zeroize::Zeroize> std::fmt::Debug for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl<T: zeroize::Zeroize> std::fmt::Display for Secret<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("***")
+    }
+}
+
+impl From<String> for SecretString {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for SecretString {
+    fn from(value: &str) -> Self {
+        Self(value.to_string())
+    }
+}
+
+impl<'de, T: zeroize::Zeroize + serde::Deserialize<'de>> serde::Deserialize<'de> for Secret<T> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        T::deserialize(deserializer).map(Self)
+    }
+}
+
+/// Writes secret content to a file only readable and writable by its owner on Unix (`0o600`).
+///
+/// # Parameters
+/// - `path`: Path of the file to write. Tilde `~` expansion is supported.
+/// - `content`: The content of the file.
+/// - `context`: Context used for error reporting.
+///
+/// # Returns
+/// The path of the written file, or a [`ConfigurationFileError`](crate::ConfigurationFileError)
+/// if it cannot be written.
+pub(crate) fn write_file(
+    path: &str,
+    content: &SecretString,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    let path = std::path::PathBuf::from(shellexpand::tilde(path).to_string());
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+    options
+        .open(&path)
+        .and_then(|mut file| {
+            #[cfg(unix)]
+            file.set_permissions(std::os::unix::fs::PermissionsExt::from_mode(0o600))?;
+            std::io::Write::write_all(&mut file, content.expose().as_bytes())
+        })
+        .map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to write file: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, &path);
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+    Ok(path)
+}
@@ -0,0 +1,131 @@
+//! Reproducible snapshots of the configuration environment.
+//!
+//! [`capture_environment`] records every source a configuration was resolved from, along
+//! with the context and the crate version, into a serializable [`EnvironmentSnapshot`]
+//! that can be attached to bug reports and replayed with [`load_from_snapshot`].
+
+use sha2::Digest;
+
+/// A single configuration source captured in a snapshot.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct SourceSnapshot {
+    /// Path the source was read from.
+    pub path: String,
+    /// Name of the content format (e.g. `json`, `yaml`).
+    pub format: String,
+    /// SHA-256 digest of the raw content, hex encoded.
+    pub sha256: String,
+    /// Raw content of the source.
+    pub content: String,
+}
+
+/// Serializable snapshot of the configuration environment.
+///
+/// Sources are stored in resolution order: later layers override earlier ones.
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug, PartialEq)]
+pub struct EnvironmentSnapshot {
+    /// Version of `cdumay_config` which captured the snapshot.
+    pub crate_version: String,
+    /// Context values used to resolve the configuration.
+    pub context: std::collections::BTreeMap<String, serde_value::Value>,
+    /// Captured sources, in resolution order.
+    pub sources: Vec<SourceSnapshot>,
+}
+
+/// Captures the given configuration layers into a snapshot.
+///
+/// # Parameters
+/// - `layers`: Paths and optional formats of the sources, in resolution order. Tilde `~`
+///   expansion is supported and the format defaults to `JSON`.
+/// - `context`: The context used to resolve the configuration.
+///
+/// # Returns
+/// An [`EnvironmentSnapshot`] holding the content and digest of every source.
+///
+/// # Example
+/// ```rust
+/// fn report() -> cdumay_core::Result<String> {
+///     let context = std::collections::BTreeMap::new();
+///     let snapshot = cdumay_config::capture_environment(&[("~/.config/app.json", None)], &context)?;
+///     Ok(serde_json::to_string_pretty(&snapshot).unwrap_or_default())
+/// }
+/// ```
+pub fn capture_environment(
+    layers: &[(&str, Option<crate::ContentFormat>)],
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<EnvironmentSnapshot> {
+    let mut sources = Vec::with_capacity(layers.len());
+    for (path, format) in layers {
+        let path = shellexpand::tilde(path).to_string();
+        let content = std::fs::read_to_string(&path).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Failed to read file: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("path".to_string(), serde_value::Value::String(path.clone()));
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+        })?;
+        sources.push(SourceSnapshot {
+            format: format.unwrap_or_default().to_string(),
+            sha256: sha256_hex(content.as_bytes()),
+            path,
+            content,
+        });
+    }
+    Ok(EnvironmentSnapshot {
+        crate_version: env!("CARGO_PKG_VERSION").to_string(),
+        context: context.clone(),
+        sources,
+    })
+}
+
+/// Replays a snapshot: parses every captured source, merges them in order and
+/// deserializes the result.
+///
+/// The digest of each source is verified before it is parsed, so an edited snapshot is
+/// rejected rather than silently replayed.
+///
+/// # Type Parameters
+/// - `C`: The type to deserialize the configuration into.
+///
+/// # Parameters
+/// - `snapshot`: The snapshot to replay.
+///
+/// # Returns
+/// The configuration as it was resolved when the snapshot was captured.
+pub fn load_from_snapshot<C: serde::de::DeserializeOwned>(snapshot: &EnvironmentSnapshot) -> cdumay_core::Result<C> {
+    let mut merged: Option<serde_value::Value> = None;
+    for source in &snapshot.sources {
+        let mut ctx = snapshot.context.clone();
+        ctx.insert("path".to_string(), serde_value::Value::String(source.path.clone()));
+        ctx.insert("format".to_string(), serde_value::Value::String(source.format.clone()));
+        let digest = sha256_hex(source.content.as_bytes());
+        if digest != source.sha256 {
+            return Err(crate::ConfigurationFileError::new()
+                .with_message(format!("Snapshot content of '{}' does not match its digest", source.path))
+                .with_details(crate::context::bounded(ctx))
+                .into());
+        }
+        let format = crate::ContentFormat::from_name(&source.format).ok_or_else(|| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Unsupported format: {}", source.format))
+                .with_details(crate::context::bounded(ctx.clone()))
+        })?;
+        let value: serde_value::Value = format.read_str(&source.content, &ctx)?;
+        merged = Some(match merged {
+            Some(base) => crate::merge::merge_values(base, value),
+            None => value,
+        });
+    }
+    crate::formats::from_value(
+        merged.unwrap_or_else(|| serde_value::Value::Map(Default::default())),
+        &snapshot.context,
+    )
+}
+
+/// Computes the hex-encoded SHA-256 digest of some bytes.
+pub(crate) fn sha256_hex(data: &[u8]) -> String {
+    sha2::Sha256::digest(data).iter().map(|byte| format!("{:02x}", byte)).collect()
+}
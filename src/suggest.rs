@@ -0,0 +1,91 @@
+//! Did-you-mean suggestions for misspelled configuration keys.
+//!
+//! Serde reports unknown fields and variants along with the expected names, and missing
+//! fields are often misspelled keys found next to them. The closest name by Levenshtein
+//! distance is added to the error details as a `suggestion` entry.
+
+/// Computes the Levenshtein distance between two strings, in characters.
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Returns the candidate closest to a name, if it is close enough to be a misspelling.
+pub(crate) fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let length = name.chars().count();
+    let threshold = length.div_ceil(3);
+    candidates
+        .into_iter()
+        .filter(|candidate| *candidate != name)
+        .map(|candidate| (levenshtein(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold && *distance < length)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+/// Builds a suggestion from an "unknown field" or "unknown variant" message, which lists the
+/// expected names.
+///
+/// Both the serde default wording (``unknown field `x`, expected one of `a`, `b` ``) and the
+/// `serde_value` one (`Unknown field x. Expected one of a, b`) are understood.
+pub(crate) fn from_message(message: &str) -> Option<String> {
+    let (field, name, candidates) = unknown(message, true).or_else(|| unknown(message, false))?;
+    let candidate = closest(name, candidates.iter().copied())?;
+    Some(match field {
+        true => format!("unknown key `{}`, did you mean `{}`?", name, candidate),
+        false => format!("unknown value `{}`, did you mean `{}`?", name, candidate),
+    })
+}
+
+/// Extracts the unknown name and the expected names of an "unknown field" (or variant) message.
+fn unknown(message: &str, field: bool) -> Option<(bool, &str, Vec<&str>)> {
+    let kind = if field { "field" } else { "variant" };
+    if let Some(index) = message.find(&format!("unknown {} `", kind)) {
+        let rest = &message[index + kind.len() + 10..];
+        let (name, expected) = rest.split_once('`')?;
+        let expected = expected.strip_prefix(", expected ")?;
+        return Some((field, name, expected.split('`').skip(1).step_by(2).collect()));
+    }
+    let index = message.find(&format!("Unknown {} ", kind))?;
+    let (name, expected) = message[index + kind.len() + 9..].split_once(". Expected one of ")?;
+    Some((field, name, expected.split(", ").collect()))
+}
+
+/// Builds a suggestion from a "missing field" message, looking for a misspelling of the field
+/// among the unknown keys found next to it.
+///
+/// `parent` is the path of the map missing the field, `unknown_keys` are dotted paths.
+pub(crate) fn missing_field(message: &str, parent: &str, unknown_keys: &[String]) -> Option<String> {
+    let name = match message.find("missing field `") {
+        Some(index) => message[index + 15..].split('`').next()?,
+        None => message[message.find("Missing field ")? + 14..].split_whitespace().next()?,
+    };
+    let parent = parent.replace('[', ".").replace(']', "");
+    let candidates = unknown_keys.iter().filter_map(|key| match key.rsplit_once('.') {
+        Some((key_parent, key)) if key_parent == parent => Some(key),
+        None if parent.is_empty() => Some(key.as_str()),
+        _ => None,
+    });
+    let unknown = candidates.min_by_key(|candidate| levenshtein(name, candidate))?;
+    closest(unknown, [name])?;
+    Some(format!("unknown key `{}`, did you mean `{}`?", unknown, name))
+}
+
+/// Adds a suggestion to the `suggestion` detail of an error.
+pub(crate) fn add_suggestion(err: cdumay_core::Error, suggestion: String) -> cdumay_core::Error {
+    let mut details = err.details();
+    details.extend(crate::context::bounded(std::collections::BTreeMap::from([(
+        "suggestion".to_string(),
+        serde_value::Value::String(suggestion),
+    )])));
+    cdumay_core::Error::new(err.code(), err.class().to_string(), err.message().to_string(), details)
+}
@@ -0,0 +1,235 @@
+//! Per-tenant configuration store.
+//!
+//! [`TenantConfigStore`] manages one [`LiveConfig`](crate::LiveConfig) per tenant, loaded
+//! lazily on first access and kept in a bounded least-recently-used cache.
+
+/// Function loading the live configuration of a tenant.
+type TenantLoader<C> = Box<dyn Fn(&str) -> cdumay_core::Result<crate::LiveConfig<C>> + Send + Sync>;
+
+/// Cached tenants with their last access tick.
+struct TenantCache<C> {
+    /// Live configuration and last access tick of each loaded tenant.
+    entries: std::collections::HashMap<String, (std::sync::Arc<crate::LiveConfig<C>>, u64)>,
+    /// Tenants ordered by last access tick, oldest first.
+    order: std::collections::BTreeMap<u64, String>,
+    /// Monotonic access counter.
+    tick: u64,
+}
+
+impl<C> TenantCache<C> {
+    /// Marks a tenant as the most recently used one.
+    fn touch(&mut self, tenant: &str) {
+        self.tick += 1;
+        if let Some((_, last)) = self.entries.get_mut(tenant) {
+            self.order.remove(last);
+            *last = self.tick;
+            self.order.insert(self.tick, tenant.to_string());
+        }
+    }
+}
+
+/// A bounded, lazily populated store of configurations keyed by tenant id.
+pub struct TenantConfigStore<C> {
+    /// Loader of tenant configurations.
+    loader: TenantLoader<C>,
+    /// Maximum number of tenants kept loaded.
+    capacity: usize,
+    /// Loaded tenants.
+    cache: std::sync::Mutex<TenantCache<C>>,
+}
+
+impl<C: Send + Sync + 'static> TenantConfigStore<C> {
+    /// Creates a store loading tenant configurations with the given function.
+    ///
+    /// This is the entry point for keyed remote sources: the loader receives the tenant id
+    /// and is called again whenever the tenant is reloaded.
+    ///
+    /// # Parameters
+    /// - `capacity`: Maximum number of tenants kept loaded (at least one).
+    /// - `loader`: Function returning the configuration of a tenant.
+    ///
+    /// # Returns
+    /// A new, empty `TenantConfigStore`.
+    pub fn new(capacity: usize, loader: impl Fn(&str) -> cdumay_core::Result<C> + Send + Sync + 'static) -> Self {
+        let loader = std::sync::Arc::new(loader);
+        Self::with_live_loader(capacity, move |tenant| {
+            let loader = loader.clone();
+            let tenant = tenant.to_string();
+            crate::LiveConfig::new(move || loader(&tenant))
+        })
+    }
+
+    /// Creates a store from a custom live configuration loader.
+    fn with_live_loader(capacity: usize, loader: impl Fn(&str) -> cdumay_core::Result<crate::LiveConfig<C>> + Send + Sync + 'static) -> Self {
+        Self {
+            loader: Box::new(loader),
+            capacity: capacity.max(1),
+            cache: std::sync::Mutex::new(TenantCache {
+                entries: std::collections::HashMap::new(),
+                order: std::collections::BTreeMap::new(),
+                tick: 0,
+            }),
+        }
+    }
+
+    /// Returns the live configuration of a tenant, loading it if needed.
+    ///
+    /// Loading a tenant beyond the store capacity evicts the least recently used one.
+    ///
+    /// # Parameters
+    /// - `tenant`: The tenant id.
+    ///
+    /// # Returns
+    /// The tenant live configuration, or an error if it cannot be loaded.
+    pub fn live(&self, tenant: &str) -> cdumay_core::Result<std::sync::Arc<crate::LiveConfig<C>>> {
+        if let Some(live) = {
+            let mut cache = self.lock();
+            cache.touch(tenant);
+            cache.entries.get(tenant).map(|(live, _)| live.clone())
+        } {
+            return Ok(live);
+        }
+        log::debug!("Loading configuration of tenant '{}'", tenant);
+        let live = std::sync::Arc::new((self.loader)(tenant)?);
+        let mut cache = self.lock();
+        if let Some((existing, _)) = cache.entries.get(tenant) {
+            // Loaded concurrently by another caller: keep the first one.
+            let existing = existing.clone();
+            cache.touch(tenant);
+            return Ok(existing);
+        }
+        while cache.entries.len() >= self.capacity {
+            let Some((_, oldest)) = cache.order.pop_first() else {
+                break;
+            };
+            log::debug!("Evicting configuration of tenant '{}'", oldest);
+            cache.entries.remove(&oldest);
+        }
+        cache.tick += 1;
+        let tick = cache.tick;
+        cache.order.insert(tick, tenant.to_string());
+        cache.entries.insert(tenant.to_string(), (live.clone(), tick));
+        Ok(live)
+    }
+
+    /// Returns the current configuration of a tenant, loading it if needed.
+    pub fn get(&self, tenant: &str) -> cdumay_core::Result<std::sync::Arc<C>> {
+        Ok(self.live(tenant)?.load())
+    }
+
+    /// Reloads the configuration of a tenant, loading it if needed.
+    pub fn reload(&self, tenant: &str) -> cdumay_core::Result<()> {
+        self.live(tenant)?.reload()
+    }
+
+    /// Reloads every loaded tenant.
+    ///
+    /// # Returns
+    /// The errors of the tenants which failed to reload, keyed by tenant id. Those tenants
+    /// keep their previous configuration.
+    pub fn reload_all(&self) -> std::collections::BTreeMap<String, cdumay_core::Error> {
+        let loaded: Vec<_> = self
+            .lock()
+            .entries
+            .iter()
+            .map(|(tenant, (live, _))| (tenant.clone(), live.clone()))
+            .collect();
+        loaded
+            .into_iter()
+            .filter_map(|(tenant, live)| live.reload().err().map(|err| (tenant, err)))
+            .collect()
+    }
+
+    /// Watches the configuration file of a tenant, see [`LiveConfig::watch`](crate::LiveConfig::watch).
+    ///
+    /// The watch stops by itself if the tenant is evicted from the store.
+    pub fn watch(&self, tenant: &str, interval: std::time::Duration) -> cdumay_core::Result<crate::WatchHandle> {
+        self.live(tenant)?.watch(interval)
+    }
+
+    /// Removes a tenant from the store, returning `true` if it was loaded.
+    pub fn evict(&self, tenant: &str) -> bool {
+        let mut cache = self.lock();
+        match cache.entries.remove(tenant) {
+            Some((_, tick)) => {
+                cache.order.remove(&tick);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Returns the ids of the loaded tenants, sorted.
+    pub fn tenants(&self) -> Vec<String> {
+        let mut tenants: Vec<_> = self.lock().entries.keys().cloned().collect();
+        tenants.sort();
+        tenants
+    }
+
+    /// Returns the number of loaded tenants.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Returns `true` if no tenant is loaded.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, TenantCache<C>> {
+        self.cache.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl<C: serde::de::DeserializeOwned + Send + Sync + 'static> TenantConfigStore<C> {
+    /// Creates a store reading each tenant configuration from its own directory,
+    /// as `<root>/<tenant>/<file_name>`.
+    ///
+    /// Tenant ids must be single path components: empty ids, `.`, `..` and ids containing
+    /// path separators are rejected.
+    ///
+    /// # Parameters
+    /// - `root`: Directory holding one sub-directory per tenant. Tilde `~` expansion is supported.
+    /// - `file_name`: Name of the configuration file inside each tenant directory.
+    /// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+    /// - `context`: A templating context used to resolve variables inside the configurations.
+    /// - `capacity`: Maximum number of tenants kept loaded (at least one).
+    ///
+    /// # Example
+    /// ```rust
+    /// #[derive(serde::Deserialize)]
+    /// struct Limits {
+    ///     requests_per_second: u32,
+    /// }
+    ///
+    /// fn limit(tenant: &str) -> cdumay_core::Result<u32> {
+    ///     let store = cdumay_config::TenantConfigStore::<Limits>::from_directory(
+    ///         "/etc/saas/tenants", "limits.json", None, std::collections::BTreeMap::new(), 1000,
+    ///     );
+    ///     Ok(store.get(tenant)?.requests_per_second)
+    /// }
+    /// ```
+    pub fn from_directory(
+        root: &str,
+        file_name: &str,
+        format: Option<crate::ContentFormat>,
+        context: std::collections::BTreeMap<String, serde_value::Value>,
+        capacity: usize,
+    ) -> Self {
+        let root = std::path::PathBuf::from(shellexpand::tilde(root).to_string());
+        let file_name = file_name.to_string();
+        Self::with_live_loader(capacity, move |tenant| {
+            let mut ctx = context.clone();
+            ctx.insert("tenant".to_string(), serde_value::Value::String(tenant.to_string()));
+            let mut components = std::path::Path::new(tenant).components();
+            if !matches!((components.next(), components.next()), (Some(std::path::Component::Normal(_)), None)) {
+                return Err(crate::ConfigurationFileError::new()
+                    .with_message(format!("Invalid tenant id: '{}'", tenant))
+                    .with_details(crate::context::bounded(ctx))
+                    .into());
+            }
+            let path = root.join(tenant).join(&file_name);
+            crate::LiveConfig::from_file(&path.to_string_lossy(), format, ctx)
+        })
+    }
+}
@@ -0,0 +1,85 @@
+//! Conversions between system times and RFC 3339 timestamps.
+
+/// Formats a time as an RFC 3339 UTC timestamp, with a second precision.
+pub(crate) fn rfc3339(time: std::time::SystemTime) -> String {
+    let seconds = time
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|elapsed| elapsed.as_secs())
+        .unwrap_or_default();
+    let (days, seconds) = (seconds / 86400, seconds % 86400);
+    // Civil date from days since the epoch (Howard Hinnant's algorithm).
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z.rem_euclid(146097);
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}Z",
+        year,
+        month,
+        day,
+        seconds / 3600,
+        seconds % 3600 / 60,
+        seconds % 60
+    )
+}
+
+/// Parses an RFC 3339 timestamp (e.g. `2025-01-31T12:00:00Z` or
+/// `2025-01-31T13:00:00.5+01:00`), or a date alone standing for its midnight UTC.
+///
+/// # Returns
+/// The time, or `None` if the timestamp is invalid or precedes the Unix epoch.
+pub(crate) fn parse_rfc3339(timestamp: &str) -> Option<std::time::SystemTime> {
+    let number = |text: &str| -> Option<i64> {
+        match !text.is_empty() && text.bytes().all(|byte| byte.is_ascii_digit()) {
+            true => text.parse().ok(),
+            false => None,
+        }
+    };
+    let (date, time) = match timestamp.split_once(['T', 't', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (timestamp, None),
+    };
+    let mut parts = date.splitn(3, '-');
+    let (year, month, day) = (number(parts.next()?)?, number(parts.next()?)?, number(parts.next()?)?);
+    if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+        return None;
+    }
+    let mut seconds = 0;
+    if let Some(time) = time {
+        let (clock, offset) = match time.strip_suffix(['Z', 'z']) {
+            Some(clock) => (clock, 0),
+            None => {
+                let index = time.rfind(['+', '-'])?;
+                let (hours, minutes) = time[index + 1..].split_once(':')?;
+                let offset = number(hours)? * 3600 + number(minutes)? * 60;
+                (&time[..index], if &time[index..index + 1] == "-" { -offset } else { offset })
+            }
+        };
+        let clock = match clock.split_once('.') {
+            Some((clock, fraction)) if !fraction.is_empty() && fraction.bytes().all(|byte| byte.is_ascii_digit()) => clock,
+            Some(_) => return None,
+            None => clock,
+        };
+        let mut parts = clock.splitn(3, ':');
+        let (hour, minute, second) = (number(parts.next()?)?, number(parts.next()?)?, number(parts.next()?)?);
+        if hour > 23 || minute > 59 || second > 60 {
+            return None;
+        }
+        seconds = hour * 3600 + minute * 60 + second - offset;
+    }
+    // Days since the epoch from a civil date (Howard Hinnant's algorithm).
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+    let seconds = u64::try_from(days * 86400 + seconds).ok()?;
+    std::time::UNIX_EPOCH.checked_add(std::time::Duration::from_secs(seconds))
+}
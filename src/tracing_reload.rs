@@ -0,0 +1,85 @@
+//! Live log filter reload driven by a configuration key.
+//!
+//! Binds a key of a [`LiveConfig`](crate::LiveConfig) (e.g. `log.filter`) to a
+//! `tracing_subscriber` reload handle, so that editing the configuration file adjusts
+//! log levels without restarting the process:
+//!
+//! ```rust
+//! use tracing_subscriber::{EnvFilter, Registry, reload};
+//!
+//! fn setup(live: &cdumay_config::LiveConfig<serde_json::Value>) -> cdumay_core::Result<reload::Layer<EnvFilter, Registry>> {
+//!     let (layer, handle) = reload::Layer::new(EnvFilter::new("info"));
+//!     cdumay_config::bind_log_filter(live, "log.filter", handle)?;
+//!     Ok(layer)
+//! }
+//! ```
+
+/// Applies the filter found at `key` in a configuration to a reload handle.
+///
+/// # Parameters
+/// - `config`: The configuration to read the filter from.
+/// - `key`: Dotted path of the filter directives (e.g. `log.filter`).
+/// - `handle`: The reload handle of the filter layer.
+///
+/// # Returns
+/// `true` if a filter was found and applied, `false` if the key is absent, or an error if
+/// the directives are invalid or the subscriber is gone.
+pub fn apply_log_filter<C: serde::Serialize, S>(
+    config: &C,
+    key: &str,
+    handle: &tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, S>,
+) -> cdumay_core::Result<bool> {
+    let error = |message: String| {
+        crate::ConfigurationFileError::new().with_message(message).with_details(crate::context::bounded({
+            let mut ctx = std::collections::BTreeMap::new();
+            ctx.insert("key".to_string(), serde_value::Value::String(key.to_string()));
+            ctx
+        }))
+    };
+    let value = serde_value::to_value(config).map_err(|err| error(format!("Failed to serialize configuration: {}", err)))?;
+    let directives = match key.split('.').try_fold(&value, |value, segment| match value {
+        serde_value::Value::Map(map) => map.get(&serde_value::Value::String(segment.to_string())),
+        _ => None,
+    }) {
+        Some(serde_value::Value::String(directives)) => directives,
+        Some(serde_value::Value::Option(None)) | Some(serde_value::Value::Unit) | None => return Ok(false),
+        Some(_) => return Err(error(format!("Log filter '{}' must be a string", key)).into()),
+    };
+    let filter = tracing_subscriber::EnvFilter::try_new(directives)
+        .map_err(|err| error(format!("Invalid log filter '{}': {}", directives, err)))?;
+    handle
+        .reload(filter)
+        .map_err(|err| error(format!("Failed to reload log filter: {}", err)))?;
+    log::info!("Log filter set to '{}'", directives);
+    Ok(true)
+}
+
+/// Applies the filter found at `key` now, then again each time the configuration changes.
+///
+/// Invalid filters found on later reloads are logged and leave the current filter in place.
+///
+/// # Parameters
+/// - `live`: The live configuration to follow.
+/// - `key`: Dotted path of the filter directives (e.g. `log.filter`).
+/// - `handle`: The reload handle of the filter layer.
+///
+/// # Returns
+/// An error if the current configuration holds an invalid filter.
+pub fn bind_log_filter<C, S>(
+    live: &crate::LiveConfig<C>,
+    key: &str,
+    handle: tracing_subscriber::reload::Handle<tracing_subscriber::EnvFilter, S>,
+) -> cdumay_core::Result<()>
+where
+    C: serde::Serialize,
+    S: 'static,
+{
+    apply_log_filter(live.load().as_ref(), key, &handle)?;
+    let key = key.to_string();
+    live.on_change(move |config| {
+        if let Err(err) = apply_log_filter(config, &key, &handle) {
+            log::error!("Failed to apply log filter: {}", err);
+        }
+    });
+    Ok(())
+}
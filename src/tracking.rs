@@ -0,0 +1,126 @@
+//! Key usage tracking, used to detect configuration keys that nothing consumes.
+
+/// A configuration along with the keys of its source that were not consumed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TrackedConfig<C> {
+    /// The deserialized configuration.
+    pub config: C,
+    /// Dotted paths (e.g. `server.listeners.2.tls`) of the keys ignored during deserialization.
+    pub unused_keys: Vec<String>,
+}
+
+impl<C> TrackedConfig<C> {
+    /// Logs a warning for each unused key and returns the configuration.
+    ///
+    /// # Parameters
+    /// - `source`: A label identifying the configuration in the log messages (e.g. its path).
+    pub fn report(self, source: &str) -> C {
+        for key in &self.unused_keys {
+            log::warn!("Unused configuration key '{}' in '{}'", key, source);
+        }
+        self.config
+    }
+}
+
+/// Reads a configuration file, recording which keys the target type did not consume.
+///
+/// # Type Parameters
+/// - `C`: The type to deserialize the configuration into.
+///
+/// # Parameters
+/// - `path`: Path to the configuration file. Tilde `~` expansion is supported.
+/// - `format`: Optional format specifier. Defaults to `JSON` if not provided.
+/// - `context`: A templating context used to resolve variables inside the configuration.
+/// - `options`: The read options to apply.
+///
+/// # Returns
+/// A [`TrackedConfig`] holding the configuration and its unused keys.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Server {
+///     port: u16,
+/// }
+///
+/// fn load() -> cdumay_core::Result<Server> {
+///     let context = std::collections::BTreeMap::new();
+///     let options = cdumay_config::ReadOptions::default();
+///     let tracked = cdumay_config::read_config_tracked("~/.config/app.json", None, &context, &options)?;
+///     Ok(tracked.report("~/.config/app.json"))
+/// }
+/// ```
+pub fn read_config_tracked<C: serde::de::DeserializeOwned>(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::ReadOptions,
+) -> cdumay_core::Result<TrackedConfig<C>> {
+    let value: serde_value::Value = crate::read_config_with(path, format, context, options)?;
+    from_value_tracked(value, context).map_err(|err| crate::context::add_path(err, std::path::Path::new(shellexpand::tilde(path).as_ref())))
+}
+
+/// Deserializes an intermediate value tree, recording the keys ignored by the target type.
+pub(crate) fn from_value_tracked<C: serde::de::DeserializeOwned>(
+    value: serde_value::Value,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<TrackedConfig<C>> {
+    let (result, unused_keys) = deserialize_tracked(value, context);
+    Ok(TrackedConfig { config: result?, unused_keys })
+}
+
+/// Deserializes an intermediate value tree, returning the keys ignored by the target type
+/// even if the deserialization fails (up to the failure).
+pub(crate) fn deserialize_tracked<C: serde::de::DeserializeOwned>(
+    value: serde_value::Value,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> (cdumay_core::Result<C>, Vec<String>) {
+    let mut unused_keys = Vec::new();
+    let mut callback = |path: serde_ignored::Path| unused_keys.push(path_to_string(&path));
+    let deserializer = serde_ignored::Deserializer::new(
+        serde_value::ValueDeserializer::<serde_value::DeserializerError>::new(value),
+        &mut callback,
+    );
+    let result = crate::formats::deserialize_tracked(deserializer).map_err(|err| {
+        err.convert(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Invalid configuration content: {}", err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+                .into()
+        })
+    });
+    let result = match result {
+        Err(err) if !err.details_ref().contains_key("suggestion") => {
+            let parent = match err.details_ref().get("key_path") {
+                Some(serde_value::Value::String(key)) => key.clone(),
+                _ => String::new(),
+            };
+            Err(match crate::suggest::missing_field(err.message(), &parent, &unused_keys) {
+                Some(suggestion) => crate::suggest::add_suggestion(err, suggestion),
+                None => err,
+            })
+        }
+        result => result,
+    };
+    (result, unused_keys)
+}
+
+/// Formats an ignored path as dotted segments, skipping option and newtype wrappers.
+fn path_to_string(path: &serde_ignored::Path) -> String {
+    let (parent, segment) = match path {
+        serde_ignored::Path::Root => return String::new(),
+        serde_ignored::Path::Seq { parent, index } => (parent, index.to_string()),
+        serde_ignored::Path::Map { parent, key } => (parent, key.clone()),
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => return path_to_string(parent),
+    };
+    match path_to_string(parent) {
+        prefix if prefix.is_empty() => segment,
+        prefix => format!("{}.{}", prefix, segment),
+    }
+}
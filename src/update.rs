@@ -0,0 +1,160 @@
+//! Partial in-place updates of configuration files.
+//!
+//! [`update_config`] reads a configuration file into a value tree, lets a closure change the
+//! subtree at a dotted key and writes the tree back, so that the keys the caller does not
+//! know about are kept.
+
+/// Updates the subtree of a configuration file at a dotted key.
+///
+/// See [`update_config_with`].
+///
+/// # Example
+/// ```rust
+/// fn set_port(port: u16) -> cdumay_core::Result<std::path::PathBuf> {
+///     cdumay_config::update_config(
+///         "~/.config/app.json",
+///         None,
+///         "server.port",
+///         |value| {
+///             *value = Some(serde_value::Value::U16(port));
+///             Ok(())
+///         },
+///         &std::collections::BTreeMap::new(),
+///     )
+/// }
+/// ```
+pub fn update_config<F: FnOnce(&mut Option<serde_value::Value>) -> cdumay_core::Result<()>>(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    key: &str,
+    update: F,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    update_config_with(path, format, key, update, context, &crate::WriteOptions::default())
+}
+
+/// Updates the subtree of a configuration file at a dotted key, applying the given write options.
+///
+/// The file is read into a value tree and the closure receives the value at `key`, `None`
+/// if it is not set: setting it to `None` removes the key. Key segments address map entries,
+/// or items by index in sequences; the missing parent maps are created. An empty key
+/// addresses the whole document. The tree is then written back, keeping the keys the caller
+/// does not know about; as the tree is rebuilt, formatting, comments and key order are not
+/// preserved (see [`TomlDocument`](crate::TomlDocument) to keep them in TOML files).
+///
+/// When a lock mode is set, an exclusive advisory lock is held from the read to the write,
+/// so that concurrent updates are not lost. If the closure fails, the file is left untouched.
+///
+/// # Type Parameters
+/// - `F`: The closure changing the subtree.
+///
+/// # Parameters
+/// - `path`: The file path to update. Tilde `~` expansion is supported.
+/// - `format`: Optional file format. Defaults to `JSON` if not provided.
+/// - `key`: The dotted key of the subtree.
+/// - `update`: The closure changing the subtree.
+/// - `context`: Context used for error reporting.
+/// - `options`: The write options to apply.
+///
+/// # Returns
+/// The path to the written file, or an error if the file cannot be read, the key does not
+/// address a subtree, the closure fails or the file cannot be written.
+pub fn update_config_with<F: FnOnce(&mut Option<serde_value::Value>) -> cdumay_core::Result<()>>(
+    path: &str,
+    format: Option<crate::ContentFormat>,
+    key: &str,
+    update: F,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+    options: &crate::WriteOptions,
+) -> cdumay_core::Result<std::path::PathBuf> {
+    let resolved = crate::resolver::resolve(options.resolver.as_ref(), std::path::Path::new(path), context)?;
+    let _lock = match options.lock {
+        Some(mode) => Some(crate::FileLock::exclusive(&resolved.to_string_lossy(), mode, context)?),
+        None => None,
+    };
+    let read_options = crate::ReadOptions {
+        retry: options.retry,
+        resolver: options.resolver.clone(),
+        ..Default::default()
+    };
+    let mut value: serde_value::Value = crate::read_config_with(path, format, context, &read_options)?;
+    patch(&mut value, key, update, &resolved, context)?;
+    let write_options = crate::WriteOptions {
+        lock: None,
+        ..options.clone()
+    };
+    crate::write_config_with(path, format, value, context, &write_options)
+}
+
+/// Applies a closure to the subtree of a value tree at a dotted key.
+fn patch<F: FnOnce(&mut Option<serde_value::Value>) -> cdumay_core::Result<()>>(
+    root: &mut serde_value::Value,
+    key: &str,
+    update: F,
+    path: &std::path::Path,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<()> {
+    let error = |segment: &str| -> cdumay_core::Error {
+        crate::ConfigurationFileError::new()
+            .with_message(format!(
+                "Cannot update '{}': '{}' does not address a map entry or a sequence item",
+                key, segment
+            ))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, path);
+                ctx.insert("key".to_string(), serde_value::Value::String(key.to_string()));
+                ctx
+            }))
+            .into()
+    };
+    if key.is_empty() {
+        let mut slot = Some(std::mem::replace(root, serde_value::Value::Unit));
+        update(&mut slot)?;
+        *root = slot.unwrap_or_else(|| serde_value::Value::Map(Default::default()));
+        return Ok(());
+    }
+    let (parents, name) = match key.rsplit_once('.') {
+        Some((parents, name)) => (parents.split('.').collect(), name),
+        None => (Vec::new(), key),
+    };
+    let mut current = root;
+    for segment in parents {
+        current = match current {
+            serde_value::Value::Map(entries) => entries
+                .entry(serde_value::Value::String(segment.to_string()))
+                .or_insert_with(|| serde_value::Value::Map(Default::default())),
+            serde_value::Value::Seq(items) => match segment.parse::<usize>().ok().and_then(|index| items.get_mut(index)) {
+                Some(item) => item,
+                None => return Err(error(segment)),
+            },
+            _ => return Err(error(segment)),
+        };
+    }
+    match current {
+        serde_value::Value::Map(entries) => {
+            let name = serde_value::Value::String(name.to_string());
+            let mut slot = entries.remove(&name);
+            update(&mut slot)?;
+            if let Some(value) = slot {
+                entries.insert(name, value);
+            }
+        }
+        serde_value::Value::Seq(items) => {
+            let index = name
+                .parse::<usize>()
+                .ok()
+                .filter(|index| *index < items.len())
+                .ok_or_else(|| error(name))?;
+            let mut slot = Some(std::mem::replace(&mut items[index], serde_value::Value::Unit));
+            update(&mut slot)?;
+            match slot {
+                Some(value) => items[index] = value,
+                None => {
+                    items.remove(index);
+                }
+            }
+        }
+        _ => return Err(error(name)),
+    }
+    Ok(())
+}
@@ -0,0 +1,201 @@
+//! Batch validation of a directory tree of configuration files.
+//!
+//! [`validate_tree`] walks a directory, matches every file against the glob patterns of a
+//! set of [`ValidationRules`] and checks that it parses and deserializes into the Rust type
+//! mapped to the pattern. All the failures are collected into a single
+//! [`TreeValidationReport`], so that a CI job can gate a whole repository in one pass.
+
+/// Deserializes a value tree into a schema type, discarding the result.
+type SchemaCheck = fn(serde_value::Value, &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<()>;
+
+/// A glob pattern mapped to a schema type.
+#[derive(Debug, Clone)]
+struct ValidationRule {
+    /// Glob pattern matched against paths relative to the validated root.
+    pattern: String,
+    /// Name of the schema type, for reporting.
+    schema: &'static str,
+    /// Checks a value tree against the schema type.
+    check: SchemaCheck,
+}
+
+/// Schemas to validate the files of a directory tree against, selected by glob pattern.
+///
+/// Patterns are matched against paths relative to the validated root, using `/` as
+/// separator (e.g. `services/*.yaml` or `**/*.toml`); `*` does not cross directories. When
+/// several patterns match a file, the first registered one applies. Files matching no
+/// pattern are not read.
+#[derive(Debug, Clone, Default)]
+pub struct ValidationRules {
+    /// Rules, in registration order.
+    rules: Vec<ValidationRule>,
+}
+
+impl ValidationRules {
+    /// Creates an empty set of rules.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the files matching a glob pattern to a schema type.
+    ///
+    /// # Type Parameters
+    /// - `T`: The schema type, which the matching files must deserialize into.
+    ///
+    /// # Parameters
+    /// - `pattern`: Glob pattern relative to the validated root.
+    ///
+    /// # Returns
+    /// The updated rules.
+    pub fn with_schema<T: serde::de::DeserializeOwned>(mut self, pattern: &str) -> Self {
+        self.rules.push(ValidationRule {
+            pattern: pattern.to_string(),
+            schema: std::any::type_name::<T>(),
+            check: |value, context| crate::formats::from_value::<T>(value, context).map(|_| ()),
+        });
+        self
+    }
+}
+
+/// A file which failed validation.
+#[derive(Debug, Clone)]
+pub struct TreeValidationFailure {
+    /// Path of the file.
+    pub path: std::path::PathBuf,
+    /// Name of the schema type the file was checked against.
+    pub schema: &'static str,
+    /// The read or deserialization error.
+    pub error: cdumay_core::Error,
+}
+
+/// Outcome of a [`validate_tree`] run.
+#[derive(Debug, Clone, Default)]
+pub struct TreeValidationReport {
+    /// Files which matched a rule and were validated, successfully or not.
+    pub checked: Vec<std::path::PathBuf>,
+    /// Files which failed validation.
+    pub failures: Vec<TreeValidationFailure>,
+}
+
+impl TreeValidationReport {
+    /// Returns `true` if every checked file is valid.
+    pub fn is_valid(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+impl std::fmt::Display for TreeValidationReport {
+    /// Formats the report as one line per failure followed by a summary line.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for failure in &self.failures {
+            writeln!(f, "{}: {} ({})", failure.path.display(), failure.error.message(), failure.schema)?;
+        }
+        write!(f, "{} file(s) checked, {} invalid", self.checked.len(), self.failures.len())
+    }
+}
+
+/// Validates every file of a directory tree matched by the given rules.
+///
+/// The tree is walked recursively, in lexicographic order, without following symbolic links
+/// to directories. The format of each matched file is resolved from its extension through
+/// the global [`FormatRegistry`](crate::FormatRegistry), so that user-defined formats are
+/// supported. Invalid files do not stop the walk: they are reported in the returned
+/// [`TreeValidationReport`].
+///
+/// # Parameters
+/// - `root_dir`: The directory to validate. Tilde `~` expansion is supported.
+/// - `rules`: The schemas to check the files against.
+/// - `context`: A context used for error details.
+///
+/// # Returns
+/// The validation report, or an error if a pattern is invalid or a directory cannot be listed.
+///
+/// # Example
+/// ```rust
+/// #[derive(serde::Deserialize)]
+/// struct Service {
+///     name: String,
+///     port: u16,
+/// }
+///
+/// fn gate() -> cdumay_core::Result<bool> {
+///     let context = std::collections::BTreeMap::new();
+///     let rules = cdumay_config::ValidationRules::new().with_schema::<Service>("services/*.json");
+///     let report = cdumay_config::validate_tree("./config", &rules, &context)?;
+///     println!("{}", report);
+///     Ok(report.is_valid())
+/// }
+/// ```
+pub fn validate_tree(
+    root_dir: &str,
+    rules: &ValidationRules,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<TreeValidationReport> {
+    let root = std::path::PathBuf::from(shellexpand::tilde(root_dir).as_ref());
+    let mut patterns = Vec::with_capacity(rules.rules.len());
+    for rule in &rules.rules {
+        let pattern = glob::Pattern::new(&rule.pattern).map_err(|err| {
+            crate::ConfigurationFileError::new()
+                .with_message(format!("Invalid validation pattern '{}': {}", rule.pattern, err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = context.clone();
+                    ctx.insert("pattern".to_string(), serde_value::Value::String(rule.pattern.clone()));
+                    ctx
+                }))
+        })?;
+        patterns.push((pattern, rule));
+    }
+    let options = glob::MatchOptions {
+        require_literal_separator: true,
+        ..Default::default()
+    };
+    let mut files = Vec::new();
+    list_files(&root, &mut files, context)?;
+    let mut report = TreeValidationReport::default();
+    for path in files {
+        let relative = path.strip_prefix(&root).unwrap_or(&path);
+        let Some((_, rule)) = patterns.iter().find(|(pattern, _)| pattern.matches_path_with(relative, options)) else {
+            continue;
+        };
+        log::debug!("Validating '{}' against {}", path.display(), rule.schema);
+        let result = crate::FormatRegistry::global()
+            .read_config::<serde_value::Value>(&path.to_string_lossy(), None, context)
+            .and_then(|value| (rule.check)(value, context).map_err(|err| crate::context::add_path(err, &path)));
+        if let Err(error) = result {
+            report.failures.push(TreeValidationFailure {
+                path: path.clone(),
+                schema: rule.schema,
+                error,
+            });
+        }
+        report.checked.push(path);
+    }
+    Ok(report)
+}
+
+/// Collects the regular files of a directory tree, sorted.
+fn list_files(
+    dir: &std::path::Path,
+    files: &mut Vec<std::path::PathBuf>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<()> {
+    let entries = std::fs::read_dir(dir).map_err(|err| {
+        crate::ConfigurationFileError::new()
+            .with_message(format!("Failed to list directory: {}", err))
+            .with_details(crate::context::bounded({
+                let mut ctx = crate::context::with_path(context, dir);
+                ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                ctx
+            }))
+    })?;
+    let mut entries: Vec<std::fs::DirEntry> = entries.flatten().collect();
+    entries.sort_by_key(|entry| entry.file_name());
+    for entry in entries {
+        match entry.file_type() {
+            Ok(file_type) if file_type.is_dir() => list_files(&entry.path(), files, context)?,
+            Ok(_) => files.push(entry.path()),
+            Err(err) => log::warn!("Skipping '{}': {}", entry.path().display(), err),
+        }
+    }
+    Ok(())
+}
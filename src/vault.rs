@@ -1,80 +1,1415 @@
 //! This module defines a structure for managing secrets (like credentials or API keys)
 //! retrieved from a vault-like configuration. It supports dynamic format parsing (e.g., JSON, YAML)
 //! and deserialization into typed Rust values using context-aware templating.
+//!
+//! Secrets are read from a local JSON file by [`VaultConfig::init`]. They can also be read
+//! from a HashiCorp Vault server with `HashicorpVault` (feature `vault-hashicorp`), from AWS
+//! Secrets Manager with `AwsSecretsManager` (feature `aws`), from Azure Key Vault with
+//! `AzureKeyVault` (feature `azure`), from Google Cloud Secret Manager with
+//! `GcpSecretManager` (feature `gcp`) or from the systemd credentials of a service with
+//! [`SystemdCredentials`](crate::SystemdCredentials); all of them implement [`SecretProvider`].
+//!
+//! A [`SecretProvider`] returns the value of a secret by its alias. The loaded secrets, read
+//! from a file by default, are one; other stores, including those which cannot list their
+//! secrets, are plugged behind them with [`VaultSecrets::with_fallback`]: lookups of aliases
+//! missing from the loaded secrets fall back to the store, so that [`VaultSecrets::alias`]
+//! callers do not change.
+//!
+//! Values of secrets flagged `encrypted` are stored ciphered, and are decrypted on demand by
+//! [`VaultSecrets::alias`] with the [`Cipher`](crate::Cipher) supplied by a
+//! [`VaultKeyProvider`], such as the cipher itself:
+//!
+//! ```rust
+//! fn database_password(cipher: std::sync::Arc<dyn cdumay_config::Cipher>) -> cdumay_core::Result<String> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let config = cdumay_config::VaultConfig::init("vault.json", &context)?;
+//!     let secrets = config.secrets(&context)?.with_key_provider(cipher);
+//!     secrets.alias("db_password".to_string(), cdumay_config::ContentFormat::JSON, &context)
+//! }
+//! ```
 
-use crate::formats::Manager;
-use cdumay_core::define_errors;
-use cdumay_error::InvalidConfiguration;
+use crate::VaultSecretError;
 
-define_errors! {
-    VaultSecretError = InvalidConfiguration
+/// Prefix of the configuration strings referring to a vault secret.
+const REFERENCE_PREFIX: &str = "vault://";
+
+/// The secret values substituted to the references of a value tree, recorded by key path so
+/// that they can be masked in the errors raised from the resolved tree (e.g. a secret string
+/// given to an integer field, which the deserializer quotes in its message).
+#[derive(Default)]
+pub(crate) struct Substitutions {
+    /// Key paths of the substituted references.
+    paths: Vec<String>,
+    /// Texts of the substituted values, longest first.
+    texts: Vec<zeroize::Zeroizing<String>>,
+}
+
+impl Substitutions {
+    /// Records the value substituted to the reference at the given key path.
+    fn record(&mut self, path: &str, value: &serde_value::Value) {
+        self.paths.push(path.to_string());
+        self.collect(value);
+        self.texts.sort_by_key(|text| std::cmp::Reverse(text.len()));
+        self.texts.dedup();
+    }
+
+    fn collect(&mut self, value: &serde_value::Value) {
+        match value {
+            serde_value::Value::String(text) if !text.is_empty() => self.texts.push(zeroize::Zeroizing::new(text.clone())),
+            serde_value::Value::Char(char) => self.texts.push(zeroize::Zeroizing::new(char.to_string())),
+            serde_value::Value::Seq(items) => items.iter().for_each(|item| self.collect(item)),
+            serde_value::Value::Map(entries) => entries.values().for_each(|entry| self.collect(entry)),
+            serde_value::Value::Option(Some(inner)) | serde_value::Value::Newtype(inner) => self.collect(inner),
+            _ => {}
+        }
+    }
+
+    /// Replaces the substituted values by `***` in the message and the details of an error.
+    /// When a value was masked, the key paths of the references are added to the
+    /// `masked_references` detail.
+    pub(crate) fn mask(&self, err: cdumay_core::Error) -> cdumay_core::Error {
+        if self.texts.is_empty() {
+            return err;
+        }
+        let message = self.mask_text(err.message());
+        let mut details: std::collections::BTreeMap<String, serde_value::Value> = err
+            .details_ref()
+            .iter()
+            .map(|(key, value)| (key.clone(), self.mask_value(value.clone())))
+            .collect();
+        if message != err.message() || details != *err.details_ref() {
+            details.insert(
+                "masked_references".to_string(),
+                serde_value::Value::Seq(self.paths.iter().cloned().map(serde_value::Value::String).collect()),
+            );
+        }
+        cdumay_core::Error::new(err.code(), err.class().to_string(), message, details)
+    }
+
+    fn mask_text(&self, text: &str) -> String {
+        self.texts
+            .iter()
+            .fold(text.to_string(), |text, secret| text.replace(secret.as_str(), "***"))
+    }
+
+    fn mask_value(&self, value: serde_value::Value) -> serde_value::Value {
+        match value {
+            serde_value::Value::String(text) => serde_value::Value::String(self.mask_text(&text)),
+            serde_value::Value::Seq(items) => serde_value::Value::Seq(items.into_iter().map(|item| self.mask_value(item)).collect()),
+            serde_value::Value::Map(entries) => {
+                serde_value::Value::Map(entries.into_iter().map(|(key, entry)| (key, self.mask_value(entry))).collect())
+            }
+            serde_value::Value::Option(Some(inner)) => serde_value::Value::Option(Some(Box::new(self.mask_value(*inner)))),
+            serde_value::Value::Newtype(inner) => serde_value::Value::Newtype(Box::new(self.mask_value(*inner))),
+            other => other,
+        }
+    }
 }
 
 /// Represents a single secret stored in the vault.
 ///
 /// Each secret has a user-defined alias, an internal key, and a string value
-/// which can be deserialized later using a specific format.
-#[derive(serde::Deserialize, Clone, Debug)]
+/// which can be deserialized later using a specific format. A secret may be bound to an
+/// environment, so that the same alias resolves to a different secret per environment
+/// (see [`VaultSecrets::alias`]). The value of an `encrypted` secret is the text produced by
+/// a [`Cipher`](crate::Cipher), such as an armored age or OpenPGP message.
+#[derive(serde::Deserialize, serde::Serialize, Clone, Debug)]
 pub struct VaultSecret {
     /// A human-readable name or identifier for the secret.
     alias: String,
     /// A technical or symbolic key identifier for the secret.
     key: String,
-    /// The actual string value of the secret (e.g., a password or API key).
-    value: String,
+    /// The actual string value of the secret (e.g., a password or API key), masked when
+    /// formatted.
+    #[serde(serialize_with = "expose")]
+    value: crate::SecretString,
+    /// The environment the secret belongs to, shared by all environments if unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    env: Option<String>,
+    /// The version of the secret in its source, if the source versions secrets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    version: Option<String>,
+    /// Whether the value is stored ciphered, to be decrypted with a [`VaultKeyProvider`].
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    encrypted: bool,
+    /// The format of the value (e.g. `"yaml"`), used by [`VaultSecrets::get`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    format: Option<crate::ContentFormat>,
+    /// Tags organizing the secrets (e.g. `db`), see [`VaultSecrets::with_tag`].
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tags: Vec<String>,
+    /// A free-form description of the secret.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    description: Option<String>,
+    /// When the secret was created, as an RFC 3339 timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    created_at: Option<String>,
+    /// When the secret expires, as an RFC 3339 timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    expires_at: Option<String>,
+    /// The namespace of the component the secret belongs to, see [`VaultSecrets::scope`].
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    namespace: Option<String>,
+}
+
+impl VaultSecret {
+    /// Creates a new `VaultSecret` instance with the given alias, key, and value.
+    ///
+    /// # Parameters
+    /// - `alias`: A human-readable identifier for the secret.
+    /// - `key`: A technical or symbolic key representing the secret.
+    /// - `value`: The actual string value of the secret (e.g., a token or password).
+    ///
+    /// # Returns
+    /// A new `VaultSecret` instance.
+    pub fn new(alias: &str, key: &str, value: &str) -> Self {
+        Self {
+            alias: alias.to_string(),
+            key: key.to_string(),
+            value: crate::SecretString::from(value),
+            env: None,
+            version: None,
+            encrypted: false,
+            format: None,
+            tags: Vec::new(),
+            description: None,
+            created_at: None,
+            expires_at: None,
+            namespace: None,
+        }
+    }
+
+    /// Binds the secret to an environment.
+    ///
+    /// # Parameters
+    /// - `env`: The environment name (e.g. `prod`).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_env(mut self, env: &str) -> Self {
+        self.env = Some(env.to_string());
+        self
+    }
+
+    /// Records the version of the secret in its source.
+    ///
+    /// # Parameters
+    /// - `version`: The version identifier (e.g. `3`).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    /// Returns the human-readable identifier of the secret.
+    pub fn alias(&self) -> &str {
+        &self.alias
+    }
+
+    /// Returns the technical identifier of the secret.
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// Returns the value of the secret, masked when formatted, and still ciphered if the
+    /// secret is `encrypted`.
+    pub fn value(&self) -> &crate::SecretString {
+        &self.value
+    }
+
+    /// Returns the environment the secret is bound to, if any.
+    pub fn env(&self) -> Option<&str> {
+        self.env.as_deref()
+    }
+
+    /// Returns the version of the secret in its source, if known.
+    pub fn version(&self) -> Option<&str> {
+        self.version.as_deref()
+    }
+
+    /// Flags the value of the secret as stored ciphered.
+    ///
+    /// # Parameters
+    /// - `encrypted`: Whether the value is ciphered.
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_encrypted(mut self, encrypted: bool) -> Self {
+        self.encrypted = encrypted;
+        self
+    }
+
+    /// Returns whether the value of the secret is stored ciphered.
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypted
+    }
+
+    /// Declares the format of the value, so that it is decoded without the caller naming it.
+    ///
+    /// # Parameters
+    /// - `format`: The format of the value (e.g. YAML).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_format(mut self, format: crate::ContentFormat) -> Self {
+        self.format = Some(format);
+        self
+    }
+
+    /// Returns the declared format of the value, if any.
+    pub fn format(&self) -> Option<crate::ContentFormat> {
+        self.format
+    }
+
+    /// Adds a tag to the secret.
+    ///
+    /// # Parameters
+    /// - `tag`: The tag (e.g. `db`).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_tag(mut self, tag: &str) -> Self {
+        self.tags.push(tag.to_string());
+        self
+    }
+
+    /// Returns the tags of the secret.
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    /// Returns whether the secret has a tag.
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|item| item == tag)
+    }
+
+    /// Describes the secret.
+    ///
+    /// # Parameters
+    /// - `description`: A free-form description.
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    /// Returns the description of the secret, if any.
+    pub fn description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+
+    /// Records when the secret was created.
+    ///
+    /// # Parameters
+    /// - `created_at`: An RFC 3339 timestamp (e.g. `2025-01-31T12:00:00Z`).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_created_at(mut self, created_at: &str) -> Self {
+        self.created_at = Some(created_at.to_string());
+        self
+    }
+
+    /// Returns when the secret was created, as an RFC 3339 timestamp, if known.
+    pub fn created_at(&self) -> Option<&str> {
+        self.created_at.as_deref()
+    }
+
+    /// Records when the secret expires.
+    ///
+    /// # Parameters
+    /// - `expires_at`: An RFC 3339 timestamp (e.g. `2025-12-31T00:00:00Z`).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_expires_at(mut self, expires_at: &str) -> Self {
+        self.expires_at = Some(expires_at.to_string());
+        self
+    }
+
+    /// Returns when the secret expires, as an RFC 3339 timestamp, if it does.
+    pub fn expires_at(&self) -> Option<&str> {
+        self.expires_at.as_deref()
+    }
+
+    /// Returns whether the secret has expired. A secret whose `expires_at` is not a valid
+    /// RFC 3339 timestamp is considered expired.
+    pub fn is_expired(&self) -> bool {
+        self.expires_at
+            .as_deref()
+            .is_some_and(|expires_at| crate::time::parse_rfc3339(expires_at).is_none_or(|expires_at| expires_at <= std::time::SystemTime::now()))
+    }
+
+    /// Puts the secret in the namespace of a component, so that its alias does not collide
+    /// with the secrets of other components.
+    ///
+    /// # Parameters
+    /// - `namespace`: The namespace (e.g. `payments`).
+    ///
+    /// # Returns
+    /// The updated `VaultSecret`.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Returns the namespace of the secret, if any.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+}
+
+/// Serializes the value of a secret, so that vaults can be written back.
+fn expose<S: serde::Serializer>(value: &crate::SecretString, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(value.expose())
+}
+
+/// Supplies the keys decrypting the values of the `encrypted` secrets of [`VaultSecrets`].
+///
+/// Any `Arc<dyn Cipher>` is a key provider decrypting every secret with this cipher.
+pub trait VaultKeyProvider: std::fmt::Debug + Send + Sync {
+    /// Returns the cipher decrypting the value of a secret.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias of the secret to decrypt.
+    /// - `context`: A context used for error details if the key cannot be supplied.
+    ///
+    /// # Returns
+    /// The cipher, or an error if the key of the secret cannot be supplied.
+    fn cipher(
+        &self,
+        alias: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::sync::Arc<dyn crate::Cipher>>;
+}
+
+impl VaultKeyProvider for std::sync::Arc<dyn crate::Cipher> {
+    fn cipher(
+        &self,
+        _alias: &str,
+        _context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::sync::Arc<dyn crate::Cipher>> {
+        Ok(self.clone())
+    }
+}
+
+/// A store of secrets, queried by alias, so that code retrieving secrets does not depend on
+/// where they are stored.
+///
+/// [`VaultSecrets`], e.g. read from a file by [`VaultConfig`], is the default implementation.
+/// Other stores (e.g. HashiCorp Vault, a cloud secret manager or an in-house service) are
+/// plugged behind it with [`VaultSecrets::with_fallback`], or used on their own. A store
+/// implements [`SecretProvider::get`], [`SecretProvider::secrets`] if it can list its
+/// secrets, or both.
+///
+/// # Example
+/// ```rust
+/// /// Reads the secrets from the environment, e.g. `db_password` from `$SECRET_DB_PASSWORD`.
+/// #[derive(Debug)]
+/// struct EnvSecrets;
+///
+/// impl cdumay_config::SecretProvider for EnvSecrets {
+///     fn get(
+///         &self,
+///         alias: &str,
+///         context: &std::collections::BTreeMap<String, serde_value::Value>,
+///     ) -> cdumay_core::Result<cdumay_config::SecretString> {
+///         std::env::var(format!("SECRET_{}", alias.to_uppercase()))
+///             .map(cdumay_config::SecretString::from)
+///             .map_err(|_| {
+///                 cdumay_config::VaultSecretError::new()
+///                     .with_message(format!("Invalid alias: {}", alias))
+///                     .with_details(context.clone())
+///                     .into()
+///             })
+///     }
+/// }
+///
+/// let secrets = cdumay_config::VaultSecrets::new(vec![]).with_fallback(EnvSecrets);
+/// ```
+pub trait SecretProvider: std::fmt::Debug + Send + Sync {
+    /// Returns the value of a secret.
+    ///
+    /// The default implementation looks the alias up in the [`SecretProvider::secrets`] of
+    /// the store.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias of the secret.
+    /// - `context`: The context of the lookup, holding the `env` the alias is resolved in, if
+    ///   any, and used for error details.
+    ///
+    /// # Returns
+    /// The value of the secret, to be deserialized by the caller, or an error if the alias
+    /// is unknown or the secret cannot be read.
+    fn get(&self, alias: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::SecretString> {
+        SecretProvider::get(&self.secrets(context)?, alias, context)
+    }
+
+    /// Returns all the secrets of the store.
+    ///
+    /// The default implementation fails, for stores which can only be queried alias by alias.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the secrets cannot be read.
+    ///
+    /// # Returns
+    /// The secrets, or an error if they cannot be read or listed.
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<VaultSecrets> {
+        Err(VaultSecretError::new()
+            .with_message(format!("Secret store {} cannot list its secrets", std::any::type_name::<Self>()))
+            .with_details(crate::context::bounded(context.clone()))
+            .into())
+    }
+}
+
+impl<P: SecretProvider + ?Sized> SecretProvider for std::sync::Arc<P> {
+    fn get(&self, alias: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::SecretString> {
+        (**self).get(alias, context)
+    }
+
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<VaultSecrets> {
+        (**self).secrets(context)
+    }
+}
+
+/// An access to a secret by its alias, recorded by an [`AuditSink`]. It never holds the
+/// value of the secret.
+#[derive(Debug, Clone)]
+pub struct SecretAccess {
+    /// The alias (or key) the secret was looked up by.
+    pub alias: String,
+    /// The namespace of the lookup (see [`VaultSecrets::scope`]), if any.
+    pub namespace: Option<String>,
+    /// The context given by the caller of the lookup.
+    pub context: std::collections::BTreeMap<String, serde_value::Value>,
+    /// When the secret was accessed.
+    pub timestamp: std::time::SystemTime,
+    /// The class of the error of a failed access, whose message is left out as it may quote
+    /// the value; `None` if the access succeeded.
+    pub error: Option<String>,
+}
+
+impl SecretAccess {
+    /// Returns whether the secret was retrieved.
+    pub fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+}
+
+/// Receives the accesses to the secrets of [`VaultSecrets`], so that the usage of secrets
+/// can be traced for compliance.
+///
+/// # Example
+/// ```rust
+/// #[derive(Debug, Default)]
+/// struct Counter(std::sync::atomic::AtomicUsize);
+///
+/// impl cdumay_config::AuditSink for Counter {
+///     fn record(&self, _access: &cdumay_config::SecretAccess) {
+///         self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+///     }
+/// }
+///
+/// let counter = std::sync::Arc::new(Counter::default());
+/// let secrets = cdumay_config::VaultSecrets::new(vec![cdumay_config::VaultSecret::new("db", "db_key", "\"1234\"")])
+///     .with_audit_sink(counter.clone());
+/// let _: cdumay_core::Result<String> = secrets.alias("db".to_string(), cdumay_config::ContentFormat::JSON, &Default::default());
+/// assert_eq!(counter.0.load(std::sync::atomic::Ordering::Relaxed), 1);
+/// ```
+pub trait AuditSink: std::fmt::Debug + Send + Sync {
+    /// Records an access to a secret. Called once the access succeeded or failed.
+    fn record(&self, access: &SecretAccess);
 }
 
-impl VaultSecret {
-    /// Creates a new `VaultSecret` instance with the given alias, key, and value.
+impl<A: AuditSink + ?Sized> AuditSink for std::sync::Arc<A> {
+    fn record(&self, access: &SecretAccess) {
+        (**self).record(access)
+    }
+}
+
+/// An [`AuditSink`] logging the accesses to secrets with the `log` crate, at the info level
+/// for successes and the warn level for failures, under the `cdumay_config::audit` target.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LogAuditSink;
+
+impl AuditSink for LogAuditSink {
+    fn record(&self, access: &SecretAccess) {
+        let alias = match &access.namespace {
+            Some(namespace) => format!("{}/{}", namespace, access.alias),
+            None => access.alias.clone(),
+        };
+        let at = crate::time::rfc3339(access.timestamp);
+        match &access.error {
+            None => log::info!(target: "cdumay_config::audit", "Secret '{}' accessed at {}", alias, at),
+            Some(error) => log::warn!(target: "cdumay_config::audit", "Secret '{}' access failed at {}: {}", alias, at, error),
+        }
+    }
+}
+
+/// A collection of multiple secrets loaded from a configuration source.
+///
+/// Provides utility methods for accessing secrets by alias and deserializing
+/// them into strongly typed values.
+#[derive(serde::Deserialize, Clone, Debug)]
+pub struct VaultSecrets {
+    data: Vec<VaultSecret>,
+    /// The provider of the keys of the encrypted secrets, if any.
+    #[serde(skip)]
+    keys: Option<std::sync::Arc<dyn VaultKeyProvider>>,
+    /// The sink recording the accesses to the secrets, if any.
+    #[serde(skip)]
+    audit: Option<std::sync::Arc<dyn AuditSink>>,
+    /// The store queried for the aliases missing from the secrets, if any.
+    #[serde(skip)]
+    fallback: Option<std::sync::Arc<dyn SecretProvider>>,
+    /// The namespace the lookups are scoped to, `None` for the secrets without namespace.
+    #[serde(skip)]
+    namespace: Option<String>,
+}
+
+impl VaultSecrets {
+    /// Creates a new `VaultSecrets` instance from a given list of secrets.
+    ///
+    /// # Parameters
+    /// - `data`: A vector of `VaultSecret` items representing the stored secrets.
+    ///
+    /// # Returns
+    /// A `VaultSecrets` instance containing the provided secrets. Duplicate secrets (see
+    /// [`VaultSecrets::validate`]) are logged as a warning.
+    ///
+    /// # Example
+    /// ```rust
+    /// use cdumay_config::{VaultSecrets, VaultSecret};
+    ///
+    /// let secrets = vec![
+    ///     VaultSecret::new("api", "api_key", "1234")
+    /// ];
+    /// let vault = VaultSecrets::new(secrets);
+    /// ```
+    pub fn new(data: Vec<VaultSecret>) -> Self {
+        let secrets = Self {
+            data,
+            keys: None,
+            audit: None,
+            fallback: None,
+            namespace: None,
+        };
+        let (aliases, keys) = (secrets.duplicates(VaultSecret::alias), secrets.duplicates(VaultSecret::key));
+        if !aliases.is_empty() || !keys.is_empty() {
+            log::warn!(
+                "Duplicate vault secrets, the last ones win: aliases [{}], keys [{}]",
+                aliases.join(", "),
+                keys.join(", ")
+            );
+        }
+        secrets
+    }
+
+    /// Returns the identifiers returned by `field` shared by several secrets of the same
+    /// namespace, environment and version, as `<id>` prefixed by `<namespace>/` and followed
+    /// by `@<env>` and `#<version>` when set, in alphabetical order.
+    fn duplicates(&self, field: fn(&VaultSecret) -> &str) -> Vec<String> {
+        let mut seen = std::collections::BTreeSet::new();
+        let mut duplicates = std::collections::BTreeSet::new();
+        for secret in &self.data {
+            let mut id = field(secret).to_string();
+            if let Some(namespace) = &secret.namespace {
+                id = format!("{}/{}", namespace, id);
+            }
+            if let Some(env) = &secret.env {
+                id = format!("{}@{}", id, env);
+            }
+            if let Some(version) = &secret.version {
+                id = format!("{}#{}", id, version);
+            }
+            if !seen.insert(id.clone()) {
+                duplicates.insert(id);
+            }
+        }
+        duplicates.into_iter().collect()
+    }
+
+    /// Checks that no two secrets of the same namespace, environment and version share an
+    /// alias or a key, as only the last of them could be retrieved. Secrets differing by
+    /// their version are the history of a secret (see [`VaultSecrets::alias_version`]).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if duplicates are found.
+    ///
+    /// # Returns
+    /// A [`VaultSecretError`] listing the duplicates, as `<alias>` prefixed by `<namespace>/`
+    /// and followed by `@<env>` and `#<version>` when set, in its `duplicate_aliases` and
+    /// `duplicate_keys` details.
+    pub fn validate(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<()> {
+        let (aliases, keys) = (self.duplicates(VaultSecret::alias), self.duplicates(VaultSecret::key));
+        if aliases.is_empty() && keys.is_empty() {
+            return Ok(());
+        }
+        let list = |ids: &[String]| serde_value::Value::Seq(ids.iter().cloned().map(serde_value::Value::String).collect());
+        Err(VaultSecretError::new()
+            .with_message(format!(
+                "Duplicate vault secrets: aliases [{}], keys [{}]",
+                aliases.join(", "),
+                keys.join(", ")
+            ))
+            .with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("duplicate_aliases".to_string(), list(&aliases));
+                ctx.insert("duplicate_keys".to_string(), list(&keys));
+                ctx
+            }))
+            .into())
+    }
+
+    /// Sets the provider of the keys decrypting the values of the `encrypted` secrets.
+    ///
+    /// # Parameters
+    /// - `provider`: The key provider, such as an `Arc<dyn Cipher>`.
+    ///
+    /// # Returns
+    /// The updated `VaultSecrets`.
+    pub fn with_key_provider<K: VaultKeyProvider + 'static>(mut self, provider: K) -> Self {
+        self.keys = Some(std::sync::Arc::new(provider));
+        self
+    }
+
+    /// Sets the fallback supplying the secrets whose alias is missing from these secrets,
+    /// e.g. a remote store queried on demand.
+    ///
+    /// Lookups by alias, such as [`VaultSecrets::alias`], [`VaultSecrets::get`] and
+    /// [`VaultSecrets::lookup`], fall back to it, and its values are deserialized
+    /// in the requested format, JSON by default. Lookups by key or version and the functions
+    /// listing aliases only see the held secrets.
+    ///
+    /// # Parameters
+    /// - `fallback`: The secret store queried for the missing aliases, such as other [`VaultSecrets`].
+    ///
+    /// # Returns
+    /// The updated `VaultSecrets`.
+    ///
+    /// # Example
+    /// ```rust
+    /// let remote = cdumay_config::VaultSecrets::new(vec![cdumay_config::VaultSecret::new("api", "api_key", "\"abcd\"")]);
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![cdumay_config::VaultSecret::new("db", "db_key", "\"1234\"")])
+    ///     .with_fallback(remote);
+    /// let token: String = secrets.alias("api".to_string(), cdumay_config::ContentFormat::JSON, &Default::default()).unwrap();
+    /// assert_eq!(token, "abcd");
+    /// ```
+    pub fn with_fallback<F: SecretProvider + 'static>(mut self, fallback: F) -> Self {
+        self.fallback = Some(std::sync::Arc::new(fallback));
+        self
+    }
+
+    /// Sets the sink recording every retrieval of a secret value by alias or key, whether it
+    /// succeeds or fails, from [`VaultSecrets::alias`] and the other lookups, the bulk
+    /// decoding, the export to environment variables and the rendering of templates.
+    ///
+    /// # Parameters
+    /// - `sink`: The audit sink, such as a [`LogAuditSink`].
+    ///
+    /// # Returns
+    /// The updated `VaultSecrets`.
+    pub fn with_audit_sink<A: AuditSink + 'static>(mut self, sink: A) -> Self {
+        self.audit = Some(std::sync::Arc::new(sink));
+        self
+    }
+
+    /// Records the access to a secret in the audit sink, if any.
+    fn audit<T>(&self, name: &str, context: &std::collections::BTreeMap<String, serde_value::Value>, result: &cdumay_core::Result<T>) {
+        if let Some(audit) = &self.audit {
+            audit.record(&SecretAccess {
+                alias: name.to_string(),
+                namespace: self.namespace.clone(),
+                context: context.clone(),
+                timestamp: std::time::SystemTime::now(),
+                error: result.as_ref().err().map(|err| err.class().to_string()),
+            });
+        }
+    }
+
+    /// Adds a secret, replacing the secret with the same alias bound to the same environment,
+    /// with the same version and namespace, if any. A secret with a new version is added as the latest
+    /// version of its alias, the previous versions remaining available.
+    ///
+    /// # Parameters
+    /// - `secret`: The secret to add.
+    ///
+    /// # Returns
+    /// The replaced secret, if any.
+    pub fn insert(&mut self, secret: VaultSecret) -> Option<VaultSecret> {
+        match self.data.iter().position(|item| {
+            item.alias == secret.alias && item.env == secret.env && item.version == secret.version && item.namespace == secret.namespace
+        }) {
+            Some(index) => Some(std::mem::replace(&mut self.data[index], secret)),
+            None => {
+                self.data.push(secret);
+                None
+            }
+        }
+    }
+
+    /// Replaces the value of an existing secret in the scope of the lookups, e.g. to rotate
+    /// a credential. The version
+    /// of the secret is cleared, and the value of an `encrypted` secret must be ciphered.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias of the secret.
+    /// - `env`: The environment the secret is bound to, or `None` for a shared secret.
+    /// - `value`: The new value.
+    /// - `context`: A context used for error details if the secret does not exist.
+    ///
+    /// # Returns
+    /// An error if no secret has this alias in this environment.
+    pub fn update(
+        &mut self,
+        alias: &str,
+        env: Option<&str>,
+        value: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        let namespace = self.namespace.clone();
+        match self
+            .data
+            .iter_mut()
+            .rev()
+            .find(|item| item.alias == alias && item.env.as_deref() == env && item.namespace == namespace)
+        {
+            Some(secret) => {
+                secret.value = crate::SecretString::from(value);
+                secret.version = None;
+                Ok(())
+            }
+            None => Err(VaultSecretError::new()
+                .with_message(format!("Invalid alias: {}", alias))
+                .with_details(crate::context::bounded(self.error_context(context)))
+                .into()),
+        }
+    }
+
+    /// Removes the secrets with an alias bound to an environment, in the scope of the
+    /// lookups.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias of the secret.
+    /// - `env`: The environment the secret is bound to, or `None` for a shared secret.
+    ///
+    /// # Returns
+    /// The last removed secret, if any.
+    pub fn remove(&mut self, alias: &str, env: Option<&str>) -> Option<VaultSecret> {
+        let mut removed = None;
+        let namespace = self.namespace.clone();
+        self.data.retain(
+            |item| match item.alias == alias && item.env.as_deref() == env && item.namespace == namespace {
+                true => {
+                    removed = Some(item.clone());
+                    false
+                }
+                false => true,
+            },
+        );
+        removed
+    }
+
+    /// Replaces the expired secrets by the secrets with the same alias and environment
+    /// fetched again from their backing source, e.g. after an external rotation.
+    ///
+    /// # Parameters
+    /// - `provider`: The source of the secrets.
+    /// - `context`: A context used for error details if the secrets cannot be fetched.
+    ///
+    /// # Returns
+    /// The number of refreshed secrets, or an error if the secrets cannot be fetched. Expired
+    /// secrets missing from the source are kept.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn refreshed(mut secrets: cdumay_config::VaultSecrets, source: &dyn cdumay_config::SecretProvider) -> cdumay_core::Result<cdumay_config::VaultSecrets> {
+    ///     secrets.refresh(source, &std::collections::BTreeMap::new())?;
+    ///     Ok(secrets)
+    /// }
+    /// ```
+    pub fn refresh<P: SecretProvider + ?Sized>(
+        &mut self,
+        provider: &P,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<usize> {
+        if !self.data.iter().any(VaultSecret::is_expired) {
+            return Ok(0);
+        }
+        let fresh = provider.secrets(context)?;
+        let mut refreshed = 0;
+        for secret in self.data.iter_mut().filter(|secret| secret.is_expired()) {
+            if let Some(item) = fresh
+                .data
+                .iter()
+                .rev()
+                .find(|item| item.alias == secret.alias && item.env == secret.env && item.namespace == secret.namespace)
+            {
+                *secret = item.clone();
+                refreshed += 1;
+            }
+        }
+        Ok(refreshed)
+    }
+
+    /// Returns the secrets, in their loading order.
+    pub fn secrets(&self) -> &[VaultSecret] {
+        &self.data
+    }
+
+    /// Returns the secrets matching a predicate, sharing the key provider, the audit sink and
+    /// the fallback store of these secrets.
+    ///
+    /// # Parameters
+    /// - `predicate`: Returns whether a secret is kept.
+    pub fn filter<F: Fn(&VaultSecret) -> bool>(&self, predicate: F) -> VaultSecrets {
+        VaultSecrets {
+            data: self.data.iter().filter(|secret| predicate(secret)).cloned().collect(),
+            keys: self.keys.clone(),
+            audit: self.audit.clone(),
+            fallback: self.fallback.clone(),
+            namespace: self.namespace.clone(),
+        }
+    }
+
+    /// Returns the secrets of a namespace, sharing the key provider, the audit sink and the
+    /// fallback store of these secrets.
+    ///
+    /// Lookups of the returned secrets only match the secrets of the namespace, while the
+    /// lookups of unscoped secrets only match the secrets without namespace, so that one
+    /// vault can serve several components without alias collisions. Errors of failed lookups
+    /// record the namespace in their `namespace` detail.
+    ///
+    /// # Example
+    /// ```rust
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![
+    ///     cdumay_config::VaultSecret::new("db", "payments_db", "\"1234\"").with_namespace("payments"),
+    ///     cdumay_config::VaultSecret::new("db", "billing_db", "\"5678\"").with_namespace("billing"),
+    /// ]);
+    /// let context = std::collections::BTreeMap::new();
+    /// let password: String = secrets.scope("payments").alias("db".to_string(), cdumay_config::ContentFormat::JSON, &context).unwrap();
+    /// assert_eq!(password, "1234");
+    /// ```
+    pub fn scope(&self, namespace: &str) -> VaultSecrets {
+        VaultSecrets {
+            data: self.data.iter().filter(|secret| secret.namespace() == Some(namespace)).cloned().collect(),
+            keys: self.keys.clone(),
+            audit: self.audit.clone(),
+            fallback: self.fallback.clone(),
+            namespace: Some(namespace.to_string()),
+        }
+    }
+
+    /// Returns the namespace the lookups are scoped to, if any.
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    /// Returns whether a secret belongs to the scope of the lookups.
+    fn in_scope(&self, secret: &VaultSecret) -> bool {
+        secret.namespace == self.namespace
+    }
+
+    /// Returns the context of the errors of a lookup, holding the `namespace` of the scope.
+    fn error_context(
+        &self,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> std::collections::BTreeMap<String, serde_value::Value> {
+        let mut ctx = context.clone();
+        if let Some(namespace) = &self.namespace {
+            ctx.insert("namespace".to_string(), serde_value::Value::String(namespace.clone()));
+        }
+        ctx
+    }
+
+    /// Returns the secrets having a tag, sharing the key provider of these secrets.
+    ///
+    /// # Example
+    /// ```rust
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![
+    ///     cdumay_config::VaultSecret::new("db_password", "db_password", "\"1234\"").with_tag("db"),
+    ///     cdumay_config::VaultSecret::new("api_token", "api_token", "\"abcd\"").with_tag("api"),
+    /// ]);
+    /// assert_eq!(secrets.with_tag("db").aliases(), vec!["db_password"]);
+    /// ```
+    pub fn with_tag(&self, tag: &str) -> VaultSecrets {
+        self.filter(|secret| secret.has_tag(tag))
+    }
+
+    /// Returns an iterator over the secrets, in their loading order.
+    pub fn iter(&self) -> std::slice::Iter<'_, VaultSecret> {
+        self.data.iter()
+    }
+
+    /// Returns the number of secrets, all environments included.
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    /// Returns whether there is no secret.
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Retrieves and deserializes the secrets of every alias, e.g. the credentials of all
+    /// tenants.
+    ///
+    /// Each alias is resolved like [`VaultSecrets::alias`] does; aliases whose secrets are
+    /// all bound to another environment are skipped.
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type of every secret.
+    ///
+    /// # Parameters
+    /// - `format`: The format used to deserialize the values, or `None` to use the format
+    ///   declared by each secret, JSON by default.
+    /// - `context`: A templating context used for value substitution (e.g. variables).
+    ///
+    /// # Returns
+    /// The deserialized secrets by alias, or the error of the first secret that cannot be
+    /// decrypted or deserialized.
+    pub fn decode_all<C: serde::de::DeserializeOwned>(
+        &self,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::collections::BTreeMap<String, C>> {
+        let mut decoded = std::collections::BTreeMap::new();
+        for alias in self.aliases() {
+            if let Some(secret) = self.resolve(alias, context) {
+                let format = format.or(secret.format).unwrap_or_default();
+                let value = self
+                    .plaintext(secret, context)
+                    .and_then(|plaintext| format.read_str(plaintext.expose(), context));
+                self.audit(alias, context, &value);
+                decoded.insert(alias.to_string(), value?);
+            }
+        }
+        Ok(decoded)
+    }
+
+    /// Returns the secrets of every alias as environment variables, e.g. to hand credentials
+    /// to a child process which only reads its environment.
+    ///
+    /// Each alias is resolved like [`VaultSecrets::alias`] does and named after the prefix
+    /// followed by the alias in upper case, any character other than an ASCII letter or digit
+    /// being replaced by `_` (`db-password` becomes `APP_DB_PASSWORD` with the prefix `APP_`).
+    /// Values decoding to a string, in the format declared by the secret or JSON, are
+    /// exported as is; other values are exported as JSON, and values which cannot be decoded
+    /// as their plaintext.
+    ///
+    /// # Parameters
+    /// - `prefix`: The prefix of the variable names, which may be empty.
+    /// - `context`: The context holding the `env` the aliases are resolved in, if any.
+    ///
+    /// # Returns
+    /// The values by variable name, or the error of the first secret that cannot be
+    /// decrypted or has expired.
+    ///
+    /// # Example
+    /// ```rust
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![
+    ///     cdumay_config::VaultSecret::new("db-password", "db_key", "\"hunter2\""),
+    ///     cdumay_config::VaultSecret::new("db-port", "db_port", "5432"),
+    /// ]);
+    /// let vars = secrets.env_vars("APP_", &std::collections::BTreeMap::new()).unwrap();
+    /// assert_eq!(vars["APP_DB_PASSWORD"].expose(), "hunter2");
+    /// assert_eq!(vars["APP_DB_PORT"].expose(), "5432");
+    /// ```
+    pub fn env_vars(
+        &self,
+        prefix: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::collections::BTreeMap<String, crate::SecretString>> {
+        let mut vars = std::collections::BTreeMap::new();
+        for alias in self.aliases() {
+            if self.resolve(alias, context).is_none() {
+                continue;
+            }
+            let value = match self.decoded(alias, None, context)? {
+                serde_json::Value::String(value) => value,
+                value => value.to_string(),
+            };
+            let name: String = alias
+                .chars()
+                .map(|c| match c.is_ascii_alphanumeric() {
+                    true => c.to_ascii_uppercase(),
+                    false => '_',
+                })
+                .collect();
+            vars.insert(format!("{}{}", prefix, name), crate::SecretString::from(value));
+        }
+        Ok(vars)
+    }
+
+    /// Sets the secrets of every alias as environment variables of the current process, named
+    /// and valued like [`VaultSecrets::env_vars`] does, so that they are inherited by the
+    /// child processes spawned afterwards.
     ///
     /// # Parameters
-    /// - `alias`: A human-readable identifier for the secret.
-    /// - `key`: A technical or symbolic key representing the secret.
-    /// - `value`: The actual string value of the secret (e.g., a token or password).
+    /// - `prefix`: The prefix of the variable names, which may be empty.
+    /// - `context`: The context holding the `env` the aliases are resolved in, if any.
     ///
     /// # Returns
-    /// A new `VaultSecret` instance.
-    pub fn new(alias: &str, key: &str, value: &str) -> Self {
-        Self {
-            alias: alias.to_string(),
-            key: key.to_string(),
-            value: value.to_string(),
+    /// The names of the variables set. No variable is set if a secret cannot be exported.
+    ///
+    /// # Safety
+    /// Like [`std::env::set_var`], this function must not be called while other threads may
+    /// read or write the environment of the process. Prefer passing
+    /// [`VaultSecrets::env_vars`] to [`std::process::Command::envs`] in multi-threaded
+    /// programs.
+    pub unsafe fn export_env(
+        &self,
+        prefix: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Vec<String>> {
+        let vars = self.env_vars(prefix, context)?;
+        for (name, value) in &vars {
+            // SAFETY: upheld by the caller.
+            unsafe { std::env::set_var(name, value.expose()) };
         }
+        Ok(vars.into_keys().collect())
     }
-}
-
-/// A collection of multiple secrets loaded from a configuration source.
-///
-/// Provides utility methods for accessing secrets by alias and deserializing
-/// them into strongly typed values.
-#[derive(serde::Deserialize, Clone, Debug)]
-pub struct VaultSecrets {
-    data: Vec<VaultSecret>,
-}
 
-impl VaultSecrets {
-    /// Creates a new `VaultSecrets` instance from a given list of secrets.
+    /// Writes the secrets of every alias to a `.env` file, named and valued like
+    /// [`VaultSecrets::env_vars`] does, for tools loading their environment from such files.
+    ///
+    /// Each variable is written on a line as `NAME="value"`, where backslashes, double
+    /// quotes, dollar signs and line breaks are escaped. On Unix, the file is only readable
+    /// and writable by its owner (`0o600`).
     ///
     /// # Parameters
-    /// - `data`: A vector of `VaultSecret` items representing the stored secrets.
+    /// - `path`: Path of the file to write. Tilde `~` expansion is supported.
+    /// - `prefix`: The prefix of the variable names, which may be empty.
+    /// - `context`: The context holding the `env` the aliases are resolved in, if any.
     ///
     /// # Returns
-    /// A `VaultSecrets` instance containing the provided secrets.
+    /// The path of the written file.
+    ///
+    /// # Errors
+    /// Returns the error of the first secret that cannot be exported, or a
+    /// [`ConfigurationFileError`](crate::ConfigurationFileError) if the file cannot be written.
+    pub fn write_env_file(
+        &self,
+        path: &str,
+        prefix: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        let mut content = String::new();
+        for (name, value) in self.env_vars(prefix, context)? {
+            content.push_str(&name);
+            content.push_str("=\"");
+            for c in value.expose().chars() {
+                match c {
+                    '\\' | '"' | '$' => {
+                        content.push('\\');
+                        content.push(c);
+                    }
+                    '\n' => content.push_str("\\n"),
+                    '\r' => content.push_str("\\r"),
+                    _ => content.push(c),
+                }
+            }
+            content.push_str("\"\n");
+        }
+        crate::secret::write_file(path, &crate::SecretString::from(content), context)
+    }
+
+    /// Replaces the `vault://<alias>` strings of a value tree by the value of the secret with
+    /// this alias, and the `vault://<alias>/<field>` strings by a field of a secret holding a
+    /// map, e.g. to keep the passwords of an ordinary configuration file in a vault.
+    ///
+    /// Secrets are resolved like [`VaultSecrets::alias`] does and decoded in the format they
+    /// declare, JSON by default, so that a reference takes the type of the secret (e.g. a
+    /// port number); values which cannot be decoded are inserted as strings. Only whole
+    /// strings are references: `vault://` within a longer text is left untouched.
+    ///
+    /// # Parameters
+    /// - `value`: The value tree, such as a configuration before its typed deserialization.
+    /// - `context`: The context holding the `env` the aliases are resolved in, if any.
+    ///
+    /// # Errors
+    /// Returns the error of the first reference which cannot be resolved, locating it by its
+    /// key path (e.g. `database.password`).
     ///
     /// # Example
     /// ```rust
-    /// use cdumay_config::{VaultSecrets, VaultSecret};
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![cdumay_config::VaultSecret::new(
+    ///     "db",
+    ///     "db_key",
+    ///     r#"{"password": "hunter2", "port": 5432}"#,
+    /// )]);
+    /// let mut config: serde_value::Value = serde_value::to_value(std::collections::BTreeMap::from([
+    ///     ("password", "vault://db/password"),
+    ///     ("port", "vault://db/port"),
+    /// ]))
+    /// .unwrap();
+    /// secrets.resolve_references(&mut config, &std::collections::BTreeMap::new()).unwrap();
+    /// let config: std::collections::BTreeMap<String, serde_json::Value> = config.deserialize_into().unwrap();
+    /// assert_eq!(config["password"], "hunter2");
+    /// assert_eq!(config["port"], 5432);
+    /// ```
+    pub fn resolve_references(
+        &self,
+        value: &mut serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<()> {
+        self.resolve_references_at(value, "", context, &mut Substitutions::default())
+    }
+
+    /// Replaces the references of a value tree like [`VaultSecrets::resolve_references`],
+    /// returning the substituted values to mask in later errors.
+    pub(crate) fn substitute_references(
+        &self,
+        value: &mut serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<Substitutions> {
+        let mut substitutions = Substitutions::default();
+        self.resolve_references_at(value, "", context, &mut substitutions)?;
+        Ok(substitutions)
+    }
+
+    /// Replaces the references of a value tree whose root is at the given key path.
+    fn resolve_references_at(
+        &self,
+        value: &mut serde_value::Value,
+        path: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        substitutions: &mut Substitutions,
+    ) -> cdumay_core::Result<()> {
+        match value {
+            serde_value::Value::String(text) if text.starts_with(REFERENCE_PREFIX) => {
+                let reference = &text[REFERENCE_PREFIX.len()..];
+                let (alias, field) = match reference.split_once('/') {
+                    Some((alias, field)) => (alias, Some(field)),
+                    None => (reference, None),
+                };
+                let resolved = self.decoded(alias, field, context).and_then(|resolved| {
+                    serde_value::to_value(resolved).map_err(|err| {
+                        VaultSecretError::new()
+                            .with_message(format!("Failed to convert secret '{}': {}", alias, err))
+                            .with_details(crate::context::bounded(self.error_context(context)))
+                            .into()
+                    })
+                });
+                *value = resolved.map_err(|err| match path.is_empty() {
+                    true => err,
+                    false => crate::context::add_key_path(err, path),
+                })?;
+                substitutions.record(path, value);
+            }
+            serde_value::Value::Seq(items) => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    self.resolve_references_at(item, &format!("{}[{}]", path, index), context, substitutions)?;
+                }
+            }
+            serde_value::Value::Map(entries) => {
+                for (key, item) in entries.iter_mut() {
+                    let key = match key {
+                        serde_value::Value::String(key) => key.clone(),
+                        key => format!("{:?}", key),
+                    };
+                    let path = match path.is_empty() {
+                        true => key,
+                        false => format!("{}.{}", path, key),
+                    };
+                    self.resolve_references_at(item, &path, context, substitutions)?;
+                }
+            }
+            serde_value::Value::Option(Some(item)) | serde_value::Value::Newtype(item) => self.resolve_references_at(item, path, context, substitutions)?,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Returns the distinct aliases of the secrets in the scope of the lookups (see
+    /// [`VaultSecrets::scope`]), in alphabetical order, whatever their environment.
     ///
-    /// let secrets = vec![
-    ///     VaultSecret::new("api", "api_key", "1234")
-    /// ];
-    /// let vault = VaultSecrets::new(secrets);
+    /// # Example
+    /// ```rust
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![
+    ///     cdumay_config::VaultSecret::new("db", "db_key", "\"dev\""),
+    ///     cdumay_config::VaultSecret::new("db", "db_key", "\"prod\"").with_env("prod"),
+    ///     cdumay_config::VaultSecret::new("api", "api_key", "\"1234\""),
+    /// ]);
+    /// assert_eq!(secrets.aliases(), vec!["api", "db"]);
     /// ```
-    pub fn new(data: Vec<VaultSecret>) -> Self {
-        Self { data }
+    pub fn aliases(&self) -> Vec<&str> {
+        let aliases: std::collections::BTreeSet<&str> = self.data.iter().filter(|secret| self.in_scope(secret)).map(VaultSecret::alias).collect();
+        aliases.into_iter().collect()
+    }
+
+    /// Finds the secret matching an alias in the environment of the context.
+    ///
+    /// When the context holds an `env` string, the secret with the alias bound to this
+    /// environment is preferred, then the secret aliased `<alias>@<env>`, then the secret
+    /// with the alias bound to no environment. Secrets bound to another environment never
+    /// match. When several secrets match equally, the last one wins.
+    fn resolve(&self, name: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> Option<&VaultSecret> {
+        self.resolve_by(name, context, |secret| &secret.alias, None)
+    }
+
+    /// Finds the secret whose identifier returned by `field` matches a name, in the
+    /// environment of the context, like [`VaultSecrets::resolve`] does for aliases. With a
+    /// `version`, only the secrets of this version match.
+    fn resolve_by(
+        &self,
+        name: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+        field: fn(&VaultSecret) -> &str,
+        version: Option<&str>,
+    ) -> Option<&VaultSecret> {
+        let find = |id: &str, env: Option<&str>| {
+            self.data.iter().rev().find(|item| {
+                field(item) == id
+                    && item.env.as_deref() == env
+                    && self.in_scope(item)
+                    && version.is_none_or(|version| item.version() == Some(version))
+            })
+        };
+        match context.get("env") {
+            Some(serde_value::Value::String(env)) => find(name, Some(env))
+                .or_else(|| find(&format!("{}@{}", name, env), None))
+                .or_else(|| find(name, None)),
+            _ => find(name, None),
+        }
+    }
+
+    /// Returns the plaintext value of a secret, decrypting it if it is `encrypted`, or an
+    /// [`ExpiredSecretError`](crate::ExpiredSecretError) if it has expired.
+    fn plaintext<'a>(
+        &self,
+        secret: &'a VaultSecret,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::borrow::Cow<'a, crate::SecretString>> {
+        if secret.is_expired() {
+            return Err(crate::ExpiredSecretError::new()
+                .with_message(format!(
+                    "Secret '{}' expired at {}",
+                    secret.alias,
+                    secret.expires_at.as_deref().unwrap_or_default()
+                ))
+                .with_details(crate::context::bounded({
+                    let mut ctx = self.error_context(context);
+                    ctx.insert("alias".to_string(), serde_value::Value::String(secret.alias.clone()));
+                    ctx.insert(
+                        "expires_at".to_string(),
+                        serde_value::Value::String(secret.expires_at.clone().unwrap_or_default()),
+                    );
+                    ctx
+                }))
+                .into());
+        }
+        if !secret.encrypted {
+            return Ok(std::borrow::Cow::Borrowed(&secret.value));
+        }
+        let error = |message: String| -> cdumay_core::Error {
+            VaultSecretError::new()
+                .with_message(message)
+                .with_details(crate::context::bounded({
+                    let mut ctx = self.error_context(context);
+                    ctx.insert("alias".to_string(), serde_value::Value::String(secret.alias.clone()));
+                    ctx
+                }))
+                .into()
+        };
+        let Some(keys) = &self.keys else {
+            return Err(error(format!("Secret '{}' is encrypted but no key provider is set", secret.alias)));
+        };
+        let plaintext = keys
+            .cipher(&secret.alias, context)?
+            .decrypt(secret.value.expose().as_bytes())
+            .map_err(|err| error(format!("Failed to decrypt secret '{}': {}", secret.alias, err)))?;
+        String::from_utf8(plaintext)
+            .map(|plaintext| std::borrow::Cow::Owned(crate::SecretString::from(plaintext)))
+            .map_err(|err| {
+                zeroize::Zeroize::zeroize(&mut err.into_bytes());
+                error(format!("Decrypted secret '{}' is not valid UTF-8", secret.alias))
+            })
+    }
+
+    /// Returns the plaintext value of a resolved secret or, if none was resolved and an alias
+    /// is given, the value supplied by the fallback store, if any.
+    fn fetch<'a>(
+        &'a self,
+        secret: Option<&'a VaultSecret>,
+        alias: Option<&str>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> Option<cdumay_core::Result<std::borrow::Cow<'a, crate::SecretString>>> {
+        match (secret, alias, &self.fallback) {
+            (Some(secret), _, _) => Some(self.plaintext(secret, context)),
+            (None, Some(alias), Some(fallback)) => Some(fallback.get(alias, context).map(std::borrow::Cow::Owned)),
+            _ => None,
+        }
     }
+
+    /// Returns the value of the secret resolved for an alias, or one of its fields, decoded in
+    /// the format declared by the secret or JSON. A value which cannot be decoded is returned
+    /// as a string holding its plaintext.
+    pub(crate) fn decoded(
+        &self,
+        name: &str,
+        field: Option<&str>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_json::Value> {
+        let secret = self.resolve(name, context);
+        let format = secret.and_then(VaultSecret::format).unwrap_or_default();
+        let value = match self.fetch(secret, Some(name), context) {
+            Some(plaintext) => plaintext.map(|plaintext| {
+                format
+                    .read_str(plaintext.expose(), context)
+                    .unwrap_or_else(|_| serde_json::Value::String(plaintext.expose().clone()))
+            }),
+            None => Err(self.missing(&format!("Invalid alias: {}", name), context)),
+        };
+        let value = match (value, field) {
+            (Ok(serde_json::Value::Object(mut fields)), Some(field)) => fields
+                .remove(field)
+                .ok_or_else(|| self.invalid_field(name, format!("Secret '{}' has no field '{}'", name, field), context)),
+            (Ok(_), Some(_)) => Err(self.invalid_field(name, format!("Secret '{}' is not a map of fields", name), context)),
+            (value, _) => value,
+        };
+        self.audit(name, context, &value);
+        value
+    }
+
+    /// Returns the error of a lookup of a field missing from a secret.
+    fn invalid_field(&self, name: &str, message: String, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Error {
+        VaultSecretError::new()
+            .with_message(message)
+            .with_details(crate::context::bounded({
+                let mut ctx = self.error_context(context);
+                ctx.insert("alias".to_string(), serde_value::Value::String(name.to_string()));
+                ctx
+            }))
+            .into()
+    }
+
+    /// Returns the version of the secret resolved for an alias, if the secret exists and its
+    /// version is known.
+    ///
+    /// The alias is resolved like [`VaultSecrets::alias`] does.
+    ///
+    /// # Parameters
+    /// - `name`: The alias of the secret.
+    /// - `context`: The context holding the `env` the alias is resolved in, if any.
+    pub fn version(&self, name: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> Option<&str> {
+        self.resolve(name, context).and_then(VaultSecret::version)
+    }
+
     /// Retrieves and deserializes a secret value by its alias.
     ///
+    /// The alias is resolved in the environment named by the `env` string of the context,
+    /// if any: a secret bound to this environment (see [`VaultSecret::with_env`]) or aliased
+    /// `<alias>@<env>` takes precedence over a secret shared by all environments. The value
+    /// of an `encrypted` secret is decrypted with the key provider of the secrets.
+    ///
+    /// The given format takes precedence over the one declared by the secret, which
+    /// [`VaultSecrets::get`] uses.
+    ///
     /// # Type Parameters
     /// - `C`: The target deserialization type.
     ///
@@ -88,30 +1423,234 @@ impl VaultSecrets {
     /// if the alias doesn't exist or deserialization fails.
     ///
     /// # Errors
-    /// Returns a [`VaultSecretError`] if the alias is not found or deserialization fails.
+    /// Returns a [`VaultSecretError`] if the alias is not found, the secret cannot be
+    /// decrypted or deserialization fails, and an [`ExpiredSecretError`](crate::ExpiredSecretError)
+    /// if the secret has expired (see [`VaultSecret::with_expires_at`]).
     pub fn alias<C: serde::de::DeserializeOwned>(
         &self,
         name: String,
         format: crate::ContentFormat,
         context: &std::collections::BTreeMap<String, serde_value::Value>,
     ) -> cdumay_core::Result<C> {
-        let aliases: std::collections::BTreeMap<String, String> = self.data.clone().into_iter().map(|item| (item.alias, item.value)).collect();
-        match aliases.get(&name) {
-            Some(value) => match format {
-                crate::ContentFormat::JSON => crate::JsonManager::read_str(value, &context),
-                #[cfg(feature = "yaml")]
-                crate::ContentFormat::YAML => crate::YamlManager::read_str(value, &context),
-                #[cfg(feature = "xml")]
-                crate::ContentFormat::XML => crate::XmlManager::read_str(value, &context),
-                #[cfg(feature = "toml")]
-                crate::ContentFormat::TOML => crate::TomlManager::read_str(value, &context),
-            },
-            None => Err(VaultSecretError::new()
-                .with_message(format!("Invalid alias: {}", name))
-                .with_details(context.clone())
-                .into()),
+        let value = self.fetch(self.resolve(&name, context), Some(&name), context);
+        self.read(&name, value, &format!("Invalid alias: {}", name), format, context)
+    }
+
+    /// Retrieves and deserializes a secret value by its alias, in the format declared by the
+    /// secret (see [`VaultSecret::with_format`]), or JSON if it declares none.
+    ///
+    /// The alias is resolved like [`VaultSecrets::alias`] does.
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type.
+    ///
+    /// # Parameters
+    /// - `name`: The alias of the secret to retrieve.
+    /// - `context`: A templating context used for value substitution (e.g. variables).
+    ///
+    /// # Returns
+    /// The deserialized secret as type `C` if successful, or an error
+    /// if the alias doesn't exist or deserialization fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![
+    ///     cdumay_config::VaultSecret::new("port", "db_port", "5432").with_format(cdumay_config::ContentFormat::JSON),
+    /// ]);
+    /// let port: u16 = secrets.get("port", &std::collections::BTreeMap::new()).unwrap();
+    /// assert_eq!(port, 5432);
+    /// ```
+    pub fn get<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let secret = self.resolve(name, context);
+        let format = secret.and_then(VaultSecret::format).unwrap_or_default();
+        let value = self.fetch(secret, Some(name), context);
+        self.read(name, value, &format!("Invalid alias: {}", name), format, context)
+    }
+
+    /// Returns the latest version of the secret resolved for an alias, if the secret exists.
+    ///
+    /// The alias is resolved like [`VaultSecrets::alias`] does: the latest version of a
+    /// secret is the last one listed.
+    ///
+    /// # Parameters
+    /// - `name`: The alias of the secret.
+    /// - `context`: The context holding the `env` the alias is resolved in, if any.
+    pub fn latest(&self, name: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> Option<&VaultSecret> {
+        self.resolve(name, context)
+    }
+
+    /// Returns the versions of the secret resolved for an alias, from the oldest to the
+    /// latest, e.g. to keep the previous credential available during a rotation.
+    ///
+    /// # Parameters
+    /// - `name`: The alias of the secret.
+    /// - `context`: The context holding the `env` the alias is resolved in, if any.
+    pub fn versions(&self, name: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> Vec<&str> {
+        match self.resolve(name, context) {
+            Some(latest) => self
+                .data
+                .iter()
+                .filter(|item| item.alias == latest.alias && item.env == latest.env && item.namespace == latest.namespace)
+                .filter_map(VaultSecret::version)
+                .collect(),
+            None => Vec::new(),
         }
     }
+
+    /// Retrieves and deserializes a given version of a secret value by its alias.
+    ///
+    /// The alias is resolved like [`VaultSecrets::alias`] does, among the secrets of this
+    /// version.
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type.
+    ///
+    /// # Parameters
+    /// - `name`: The alias of the secret to retrieve.
+    /// - `version`: The version of the secret (see [`VaultSecret::with_version`]).
+    /// - `format`: The format used to deserialize the secret's value (e.g. JSON, YAML).
+    /// - `context`: A templating context used for value substitution (e.g. variables).
+    ///
+    /// # Returns
+    /// The deserialized secret as type `C` if successful, or an error
+    /// if the alias has no such version or deserialization fails.
+    ///
+    /// # Example
+    /// ```rust
+    /// let secrets = cdumay_config::VaultSecrets::new(vec![
+    ///     cdumay_config::VaultSecret::new("db", "db_key", "\"old\"").with_version("1"),
+    ///     cdumay_config::VaultSecret::new("db", "db_key", "\"new\"").with_version("2"),
+    /// ]);
+    /// let context = std::collections::BTreeMap::new();
+    /// let previous: String = secrets.alias_version("db", "1", cdumay_config::ContentFormat::JSON, &context).unwrap();
+    /// assert_eq!(previous, "old");
+    /// assert_eq!(secrets.latest("db", &context).and_then(cdumay_config::VaultSecret::version), Some("2"));
+    /// ```
+    pub fn alias_version<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        version: &str,
+        format: crate::ContentFormat,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let secret = self.resolve_by(name, context, |secret| &secret.alias, Some(version));
+        let value = self.fetch(secret, None, context);
+        self.read(name, value, &format!("Invalid alias: {} (version {})", name, version), format, context)
+    }
+
+    /// Retrieves and deserializes a secret value by its key.
+    ///
+    /// The key is resolved in the environment of the context like an alias is by
+    /// [`VaultSecrets::alias`], a secret keyed `<key>@<env>` included.
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type.
+    ///
+    /// # Parameters
+    /// - `name`: The key of the secret to retrieve.
+    /// - `format`: The format used to deserialize the secret's value (e.g. JSON, YAML).
+    /// - `context`: A templating context used for value substitution (e.g. variables).
+    ///
+    /// # Returns
+    /// The deserialized secret as type `C` if successful, or an error
+    /// if the key doesn't exist or deserialization fails.
+    ///
+    /// # Errors
+    /// Returns a [`VaultSecretError`] if the key is not found, the secret cannot be
+    /// decrypted or deserialization fails.
+    pub fn key<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        format: crate::ContentFormat,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let secret = self.resolve_by(name, context, |secret| &secret.key, None);
+        let value = self.fetch(secret, None, context);
+        self.read(name, value, &format!("Invalid key: {}", name), format, context)
+    }
+
+    /// Retrieves and deserializes a secret value by its alias or, if no secret has this
+    /// alias, by its key.
+    ///
+    /// # Type Parameters
+    /// - `C`: The target deserialization type.
+    ///
+    /// # Parameters
+    /// - `name`: The alias or key of the secret to retrieve.
+    /// - `format`: The format used to deserialize the secret's value (e.g. JSON, YAML).
+    /// - `context`: A templating context used for value substitution (e.g. variables).
+    ///
+    /// # Returns
+    /// The deserialized secret as type `C` if successful, or an error
+    /// if neither an alias nor a key matches or deserialization fails.
+    pub fn lookup<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        format: crate::ContentFormat,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let secret = self
+            .resolve(name, context)
+            .or_else(|| self.resolve_by(name, context, |secret| &secret.key, None));
+        let value = self.fetch(secret, Some(name), context);
+        self.read(name, value, &format!("Invalid alias or key: {}", name), format, context)
+    }
+
+    /// Deserializes the value fetched for a name, or fails with the given message if no value
+    /// was fetched, recording the access in the audit sink.
+    fn read<C: serde::de::DeserializeOwned>(
+        &self,
+        name: &str,
+        value: Option<cdumay_core::Result<std::borrow::Cow<'_, crate::SecretString>>>,
+        missing: &str,
+        format: crate::ContentFormat,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<C> {
+        let value = match value {
+            Some(plaintext) => plaintext.and_then(|plaintext| format.read_str(plaintext.expose(), context)),
+            None => Err(self.missing(missing, context)),
+        };
+        self.audit(name, context, &value);
+        value
+    }
+
+    /// Returns the error of a lookup which resolved no secret.
+    fn missing(&self, message: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Error {
+        VaultSecretError::new()
+            .with_message(message.to_string())
+            .with_details(crate::context::bounded(self.error_context(context)))
+            .into()
+    }
+}
+
+impl SecretProvider for VaultSecrets {
+    /// Returns the plaintext value of the secret resolved for an alias like
+    /// [`VaultSecrets::alias`] does, then querying the fallback of these secrets.
+    fn get(&self, alias: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::SecretString> {
+        let value = match self.fetch(self.resolve(alias, context), Some(alias), context) {
+            Some(value) => value.map(std::borrow::Cow::into_owned),
+            None => Err(self.missing(&format!("Invalid alias: {}", alias), context)),
+        };
+        self.audit(alias, context, &value);
+        value
+    }
+
+    fn secrets(&self, _: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<VaultSecrets> {
+        Ok(self.clone())
+    }
+}
+
+impl<'a> IntoIterator for &'a VaultSecrets {
+    type Item = &'a VaultSecret;
+    type IntoIter = std::slice::Iter<'a, VaultSecret>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
 }
 
 /// Configuration structure for loading secrets from an external file.
@@ -134,6 +1673,13 @@ pub struct VaultConfig {
     pub secrets: Option<VaultSecrets>,
 }
 
+/// The layout of TOML vault files, whose root must be a table.
+#[cfg(feature = "toml")]
+#[derive(serde::Deserialize, serde::Serialize)]
+struct VaultTable {
+    secrets: Vec<VaultSecret>,
+}
+
 impl VaultConfig {
     /// Initializes a new `VaultConfig` instance from a JSON configuration file.
     ///
@@ -145,14 +1691,158 @@ impl VaultConfig {
     /// A `VaultConfig` populated with secrets if successful.
     ///
     /// # Errors
-    /// Returns a deserialization or file read error if the JSON cannot be parsed.
+    /// Returns a deserialization or file read error if the JSON cannot be parsed, or a
+    /// [`VaultSecretError`] if secrets are duplicated (see [`VaultSecrets::validate`]).
     pub fn init(path: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<VaultConfig> {
-        Ok(VaultConfig {
-            secrets: Some(VaultSecrets {
-                data: crate::JsonManager::new(path.to_string()).read_config(context)?,
-            }),
-        })
+        VaultConfig::init_with_format(path, None, context)
+    }
+
+    /// Initializes a new `VaultConfig` instance from a configuration file of any format.
+    ///
+    /// The file holds the list of secrets, except in TOML whose root must be a table: the
+    /// secrets are then listed under `secrets` (`[[secrets]]` tables).
+    ///
+    /// # Parameters
+    /// - `path`: The file path to the configuration containing the secrets.
+    /// - `format`: Optional format of the file. Defaults to `JSON` if not provided.
+    /// - `context`: A context used to resolve templated values in the configuration.
+    ///
+    /// # Returns
+    /// A `VaultConfig` populated with secrets if successful.
+    ///
+    /// # Errors
+    /// Returns a deserialization or file read error if the file cannot be parsed, or a
+    /// [`VaultSecretError`] if secrets are duplicated (see [`VaultSecrets::validate`]).
+    ///
+    /// # Example
+    /// ```rust
+    /// fn load() -> cdumay_core::Result<cdumay_config::VaultConfig> {
+    ///     cdumay_config::VaultConfig::init_with_format("vault.json", Some(cdumay_config::ContentFormat::JSON), &std::collections::BTreeMap::new())
+    /// }
+    /// ```
+    pub fn init_with_format(
+        path: &str,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<VaultConfig> {
+        let data = match format.unwrap_or(crate::ContentFormat::JSON) {
+            #[cfg(feature = "toml")]
+            crate::ContentFormat::TOML => crate::read_config::<VaultTable>(path, format, context)?.secrets,
+            format => crate::read_config(path, Some(format), context)?,
+        };
+        let secrets = VaultSecrets {
+            data,
+            keys: None,
+            audit: None,
+            fallback: None,
+            namespace: None,
+        };
+        secrets
+            .validate(context)
+            .map_err(|err| crate::context::add_path(err, std::path::Path::new(path)))?;
+        Ok(VaultConfig { secrets: Some(secrets) })
+    }
+    /// Returns the process-wide vault read from a JSON file, loading it on first use.
+    ///
+    /// Every call with the same file returns the same instance, so that subsystems share one
+    /// parsed vault instead of each re-reading the file; concurrent first calls read the file
+    /// only once. Instances are keyed by the canonical path of the file, so that `vault.json`,
+    /// `./vault.json` or a symlink to it share one vault. Loading a vault does not block calls
+    /// for other files. A failed first load is not cached.
+    ///
+    /// The vault is published through a [`LiveConfig`](crate::LiveConfig): call
+    /// [`LiveConfig::reload`](crate::LiveConfig::reload) to re-read the file. The context of
+    /// the call which first loaded the vault is kept for every reload; the `context` passed to
+    /// later calls is only used to resolve the path.
+    ///
+    /// # Parameters
+    /// - `path`: The file path to the JSON configuration containing the secrets.
+    /// - `context`: A context used to resolve templated values in the configuration.
+    ///
+    /// # Returns
+    /// The shared vault, or an error if it could not be loaded.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn database_password() -> cdumay_core::Result<String> {
+    ///     let context = std::collections::BTreeMap::new();
+    ///     let vault = cdumay_config::VaultConfig::shared("/etc/app/vault.json", &context)?;
+    ///     vault.load().secrets(&context)?.alias("db_password".to_string(), cdumay_config::ContentFormat::JSON, &context)
+    /// }
+    /// ```
+    pub fn shared(
+        path: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::sync::Arc<crate::LiveConfig<VaultConfig>>> {
+        type SharedVault = std::sync::Arc<std::sync::Mutex<Option<std::sync::Arc<crate::LiveConfig<VaultConfig>>>>>;
+        static SHARED: std::sync::OnceLock<std::sync::Mutex<std::collections::BTreeMap<std::path::PathBuf, SharedVault>>> =
+            std::sync::OnceLock::new();
+        let resolved = crate::resolver::resolve(None, std::path::Path::new(path), context)?;
+        let key = std::fs::canonicalize(&resolved).unwrap_or(resolved);
+        // The global lock only guards the slot lookup; the slot lock is held while loading, so
+        // that concurrent first calls for one file share one read.
+        let slot = SHARED
+            .get_or_init(Default::default)
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .entry(key)
+            .or_default()
+            .clone();
+        let mut vault = slot.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(vault) = vault.as_ref() {
+            return Ok(vault.clone());
+        }
+        let (file, ctx) = (path.to_string(), context.clone());
+        let loaded = std::sync::Arc::new(crate::LiveConfig::new(move || VaultConfig::init(&file, &ctx))?);
+        *vault = Some(loaded.clone());
+        Ok(loaded)
+    }
+
+    /// Writes the secrets back to a file, e.g. after rotating a credential, in the layout read
+    /// by [`VaultConfig::init_with_format`]. Values are written as they are held: `encrypted`
+    /// secrets stay ciphered. The file is only readable and writable by its owner on Unix
+    /// (`0o600`). It is replaced atomically through a temporary file renamed over it, so that
+    /// a crash while saving never leaves a truncated vault behind.
+    ///
+    /// # Parameters
+    /// - `path`: The file path to write to. Tilde `~` expansion is supported.
+    /// - `format`: Optional output format. Defaults to `JSON`, the format read by
+    ///   [`VaultConfig::init`].
+    /// - `context`: A context used for error details if the secrets cannot be written.
+    ///
+    /// # Returns
+    /// The path to the written file, or an error if the secrets are not loaded or cannot be
+    /// written.
+    ///
+    /// # Example
+    /// ```rust
+    /// fn rotate(password: &str) -> cdumay_core::Result<std::path::PathBuf> {
+    ///     let context = std::collections::BTreeMap::new();
+    ///     let mut config = cdumay_config::VaultConfig::init("vault.json", &context)?;
+    ///     if let Some(secrets) = config.secrets.as_mut() {
+    ///         secrets.update("db_password", None, password, &context)?;
+    ///     }
+    ///     config.save("vault.json", None, &context)
+    /// }
+    /// ```
+    pub fn save(
+        &self,
+        path: &str,
+        format: Option<crate::ContentFormat>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<std::path::PathBuf> {
+        let secrets = self.secrets(context)?;
+        let options = crate::WriteOptions {
+            mode: Some(0o600),
+            ..Default::default()
+        };
+        match format {
+            #[cfg(feature = "toml")]
+            Some(crate::ContentFormat::TOML) => crate::write_config_with(path, format, VaultTable { secrets: secrets.data }, context, &options),
+            _ => crate::write_config_with(path, format, secrets.secrets(), context, &options),
+        }
     }
+
     /// Returns the list of secrets if they have been loaded.
     ///
     /// # Parameters
@@ -164,9 +1854,23 @@ impl VaultConfig {
         match self.secrets.clone() {
             None => Err(VaultSecretError::new()
                 .with_message("Failed to read vault data".to_string())
-                .with_details(context.clone())
+                .with_details(crate::context::bounded(context.clone()))
                 .into()),
             Some(secrets) => Ok(secrets),
         }
     }
 }
+
+impl SecretProvider for VaultConfig {
+    fn get(&self, alias: &str, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::SecretString> {
+        match &self.secrets {
+            Some(secrets) => SecretProvider::get(secrets, alias, context),
+            // Fails with the error of the missing secrets.
+            None => SecretProvider::get(&VaultConfig::secrets(self, context)?, alias, context),
+        }
+    }
+
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<VaultSecrets> {
+        VaultConfig::secrets(self, context)
+    }
+}
@@ -0,0 +1,263 @@
+//! AWS Secrets Manager backend for [`VaultSecrets`](crate::VaultSecrets).
+//!
+//! With the `aws` feature, secrets are read with the `GetSecretValue` action of AWS Secrets
+//! Manager, signed with AWS Signature Version 4. Each secret is registered under an alias
+//! and its string value is retrieved through the usual
+//! [`VaultSecrets::alias`](crate::VaultSecrets::alias) API:
+//!
+//! ```rust
+//! use cdumay_config::SecretProvider;
+//!
+//! #[derive(serde::Deserialize)]
+//! struct Database {
+//!     username: String,
+//!     password: String,
+//! }
+//!
+//! fn database() -> cdumay_core::Result<Database> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let secrets = cdumay_config::AwsSecretsManager::from_env(&context)?
+//!         .with_secret("db", "prod/app/database")
+//!         .secrets(&context)?;
+//!     secrets.alias("db".to_string(), cdumay_config::ContentFormat::JSON, &context)
+//! }
+//! ```
+
+/// Client reading secrets from AWS Secrets Manager.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AwsSecretsManager {
+    /// Region of the secrets (e.g. `eu-west-1`).
+    region: String,
+    /// Credentials signing the requests.
+    credentials: crate::AwsCredentials,
+    /// Endpoint of the service, the regional AWS endpoint if unset.
+    endpoint: Option<String>,
+    /// Maximum duration of each request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+    /// Secrets to read, as `(alias, secret identifier, environment)`.
+    secrets: Vec<(String, String, Option<String>)>,
+}
+
+impl AwsSecretsManager {
+    /// Creates a client of the given region.
+    ///
+    /// # Parameters
+    /// - `region`: Region of the secrets (e.g. `eu-west-1`).
+    /// - `credentials`: Credentials signing the requests.
+    pub fn new(region: &str, credentials: crate::AwsCredentials) -> Self {
+        Self {
+            region: region.to_string(),
+            credentials,
+            endpoint: None,
+            timeout: None,
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Creates a client from the environment: the region is read from `AWS_REGION` (or
+    /// `AWS_DEFAULT_REGION`) and the credentials with [`AwsCredentials::from_env`](crate::AwsCredentials::from_env).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a variable is missing.
+    ///
+    /// # Returns
+    /// The client, or a [`VaultSecretError`](crate::VaultSecretError) if the region or the
+    /// credentials are not set.
+    pub fn from_env(context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<Self> {
+        let missing = |what: &str| -> cdumay_core::Error {
+            crate::VaultSecretError::new()
+                .with_message(format!("Missing AWS {} in the environment", what))
+                .with_details(crate::context::bounded(context.clone()))
+                .into()
+        };
+        let region = crate::aws::region_from_env().ok_or_else(|| missing("region"))?;
+        Ok(Self::new(
+            &region,
+            crate::AwsCredentials::from_env().ok_or_else(|| missing("credentials"))?,
+        ))
+    }
+
+    /// Sets the endpoint of the service (e.g. a VPC endpoint or a local emulator).
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = Some(endpoint.trim_end_matches('/').to_string());
+        self
+    }
+
+    /// Sets the maximum duration of each request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Registers a secret to read, shared by all environments.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `secret_id`: Name or ARN of the secret.
+    pub fn with_secret(mut self, alias: &str, secret_id: &str) -> Self {
+        self.secrets.push((alias.to_string(), secret_id.to_string(), None));
+        self
+    }
+
+    /// Registers a secret to read, bound to an environment (see [`VaultSecret::with_env`](crate::VaultSecret::with_env)).
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `secret_id`: Name or ARN of the secret.
+    /// - `env`: The environment name (e.g. `prod`).
+    pub fn with_env_secret(mut self, alias: &str, secret_id: &str, env: &str) -> Self {
+        self.secrets.push((alias.to_string(), secret_id.to_string(), Some(env.to_string())));
+        self
+    }
+
+    /// Reads the registered secrets.
+    ///
+    /// The value of each secret is its `SecretString`, or its `SecretBinary` decoded as UTF-8.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// The secrets, or a [`VaultSecretError`](crate::VaultSecretError) holding the `url` and
+    /// `status` of the failed request in its details.
+    pub fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let url = match &self.endpoint {
+            Some(endpoint) => endpoint.clone(),
+            None => format!("https://secretsmanager.{}.amazonaws.com", self.region),
+        };
+        let mut secrets = Vec::new();
+        for (alias, secret_id, env) in &self.secrets {
+            let response = self.get_secret_value(&agent, &url, secret_id, context)?;
+            let value = match (response.get("SecretString"), response.get("SecretBinary")) {
+                (Some(serde_json::Value::String(value)), _) => value.clone(),
+                (_, Some(serde_json::Value::String(encoded))) => {
+                    use base64::Engine;
+                    base64::engine::general_purpose::STANDARD
+                        .decode(encoded)
+                        .ok()
+                        .and_then(|bytes| String::from_utf8(bytes).ok())
+                        .ok_or_else(|| {
+                            error(
+                                format!("Invalid secret '{}': binary value is not UTF-8", secret_id),
+                                &url,
+                                None,
+                                None,
+                                context,
+                            )
+                        })?
+                }
+                _ => {
+                    return Err(error(
+                        format!("Invalid secret '{}': no value found", secret_id),
+                        &url,
+                        None,
+                        None,
+                        context,
+                    ));
+                }
+            };
+            let secret = crate::VaultSecret::new(alias, secret_id, &value);
+            secrets.push(match env {
+                Some(env) => secret.with_env(env),
+                None => secret,
+            });
+        }
+        Ok(crate::VaultSecrets::new(secrets))
+    }
+
+    /// Reads the registered secrets into a [`VaultConfig`](crate::VaultConfig).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// A `VaultConfig` holding the secrets, or an error if they could not be read.
+    pub fn config(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultConfig> {
+        Ok(crate::VaultConfig {
+            secrets: Some(self.secrets(context)?),
+        })
+    }
+
+    /// Sends a signed `GetSecretValue` request, returning the JSON body of the response.
+    fn get_secret_value(
+        &self,
+        agent: &ureq::Agent,
+        url: &str,
+        secret_id: &str,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_json::Value> {
+        let body = serde_json::json!({ "SecretId": secret_id }).to_string();
+        let host = url.split_once("://").map_or(url, |(_, rest)| rest).split('/').next().unwrap_or_default();
+        let amz_date = crate::aws::amz_date(std::time::SystemTime::now());
+        let mut headers = vec![
+            ("content-type", "application/x-amz-json-1.1".to_string()),
+            ("host", host.to_string()),
+            ("x-amz-date", amz_date.clone()),
+            ("x-amz-target", "secretsmanager.GetSecretValue".to_string()),
+        ];
+        if let Some(token) = &self.credentials.session_token {
+            headers.push(("x-amz-security-token", token.clone()));
+        }
+        headers.sort();
+        let canonical = crate::aws::canonical_request("POST", "/", "", &headers, &body);
+        let authorization = crate::aws::authorization(&self.credentials, &self.region, "secretsmanager", &amz_date, canonical);
+        let mut request = agent.post(url);
+        for (name, value) in headers.iter().filter(|(name, _)| *name != "host") {
+            request = request.header(*name, value);
+        }
+        let mut response = request.header("authorization", &authorization).send(body).map_err(|err| {
+            error(
+                format!("Failed to reach AWS Secrets Manager at '{}': {}", url, err),
+                url,
+                None,
+                Some(err.to_string()),
+                context,
+            )
+        })?;
+        let status = response.status().as_u16();
+        let content = response.body_mut().read_to_string().unwrap_or_default();
+        if !response.status().is_success() {
+            return Err(error(
+                format!("AWS Secrets Manager failed to return '{}' with HTTP status {}", secret_id, status),
+                url,
+                Some(status),
+                Some(content),
+                context,
+            ));
+        }
+        serde_json::from_str(&content).map_err(|err| {
+            error(
+                format!("Invalid AWS Secrets Manager response from '{}': {}", url, err),
+                url,
+                Some(status),
+                Some(err.to_string()),
+                context,
+            )
+        })
+    }
+}
+
+impl crate::SecretProvider for AwsSecretsManager {
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        AwsSecretsManager::secrets(self, context)
+    }
+}
+
+/// Builds the error of a failed AWS request.
+fn error(
+    message: String,
+    url: &str,
+    status: Option<u16>,
+    origin: Option<String>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Error {
+    crate::VaultSecretError::new()
+        .with_message(message)
+        .with_details(crate::context::bounded(crate::context::with_request(context, url, status, origin)))
+        .into()
+}
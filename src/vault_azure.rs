@@ -0,0 +1,298 @@
+//! Azure Key Vault backend for [`VaultSecrets`](crate::VaultSecrets).
+//!
+//! With the `azure` feature, secrets are read from an Azure Key Vault with an access token of
+//! Microsoft Entra ID, obtained with a managed identity or with client credentials. Each
+//! secret is registered under an alias and its value is retrieved through the usual
+//! [`VaultSecrets::alias`](crate::VaultSecrets::alias) API:
+//!
+//! ```rust
+//! use cdumay_config::SecretProvider;
+//!
+//! fn api_key() -> cdumay_core::Result<String> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let secrets = cdumay_config::AzureKeyVault::new("https://my-vault.vault.azure.net", cdumay_config::AzureAuth::ManagedIdentity { client_id: None })
+//!         .with_secret("api", "api-key")
+//!         .secrets(&context)?;
+//!     secrets.alias("api".to_string(), cdumay_config::ContentFormat::JSON, &context)
+//! }
+//! ```
+
+/// Default endpoint of the Azure Instance Metadata Service issuing managed identity tokens.
+const IDENTITY_ENDPOINT: &str = "http://169.254.169.254/metadata/identity/oauth2/token";
+
+/// Default authority of Microsoft Entra ID issuing client credentials tokens.
+const AUTHORITY: &str = "https://login.microsoftonline.com";
+
+/// Version of the Key Vault REST API used.
+const API_VERSION: &str = "7.4";
+
+/// Authentication method against Azure Key Vault.
+#[derive(Clone, PartialEq)]
+pub enum AzureAuth {
+    /// The managed identity of the Azure resource running the process.
+    ManagedIdentity {
+        /// Client identifier of a user-assigned identity, the system-assigned one if unset.
+        client_id: Option<String>,
+    },
+    /// The client credentials of an application registration.
+    ClientCredentials {
+        /// Directory (tenant) identifier.
+        tenant_id: String,
+        /// Application (client) identifier.
+        client_id: String,
+        /// Client secret.
+        client_secret: String,
+    },
+    /// An access token obtained by other means, sent as is.
+    Token(String),
+}
+
+impl std::fmt::Debug for AzureAuth {
+    /// Formats the method without its credentials.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AzureAuth::ManagedIdentity { client_id } => f.debug_struct("ManagedIdentity").field("client_id", client_id).finish(),
+            AzureAuth::ClientCredentials { tenant_id, client_id, .. } => f
+                .debug_struct("ClientCredentials")
+                .field("tenant_id", tenant_id)
+                .field("client_id", client_id)
+                .field("client_secret", &"***")
+                .finish(),
+            AzureAuth::Token(_) => f.write_str("Token(***)"),
+        }
+    }
+}
+
+/// Client reading secrets from an Azure Key Vault.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AzureKeyVault {
+    /// URL of the vault (e.g. `https://my-vault.vault.azure.net`).
+    vault_url: String,
+    /// How the client authenticates.
+    auth: AzureAuth,
+    /// Endpoint issuing managed identity tokens.
+    identity_endpoint: String,
+    /// Authority issuing client credentials tokens.
+    authority: String,
+    /// Maximum duration of each request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+    /// Secrets to read, as `(alias, name, version, environment)`.
+    secrets: Vec<(String, String, Option<String>, Option<String>)>,
+}
+
+impl AzureKeyVault {
+    /// Creates a client of the vault at `vault_url`.
+    ///
+    /// # Parameters
+    /// - `vault_url`: URL of the vault (e.g. `https://my-vault.vault.azure.net`).
+    /// - `auth`: How the client authenticates.
+    pub fn new(vault_url: &str, auth: AzureAuth) -> Self {
+        Self {
+            vault_url: vault_url.trim_end_matches('/').to_string(),
+            auth,
+            identity_endpoint: IDENTITY_ENDPOINT.to_string(),
+            authority: AUTHORITY.to_string(),
+            timeout: None,
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Sets the endpoint issuing managed identity tokens, the Azure Instance Metadata Service
+    /// by default.
+    pub fn with_identity_endpoint(mut self, endpoint: &str) -> Self {
+        self.identity_endpoint = endpoint.to_string();
+        self
+    }
+
+    /// Sets the authority issuing client credentials tokens (e.g. for sovereign clouds),
+    /// `https://login.microsoftonline.com` by default.
+    pub fn with_authority(mut self, authority: &str) -> Self {
+        self.authority = authority.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the maximum duration of each request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Registers the latest version of a secret to read, shared by all environments.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `name`: Name of the secret in the vault.
+    pub fn with_secret(mut self, alias: &str, name: &str) -> Self {
+        self.secrets.push((alias.to_string(), name.to_string(), None, None));
+        self
+    }
+
+    /// Registers a given version of a secret to read, shared by all environments.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `name`: Name of the secret in the vault.
+    /// - `version`: Identifier of the version.
+    pub fn with_secret_version(mut self, alias: &str, name: &str, version: &str) -> Self {
+        self.secrets.push((alias.to_string(), name.to_string(), Some(version.to_string()), None));
+        self
+    }
+
+    /// Registers the latest version of a secret to read, bound to an environment (see
+    /// [`VaultSecret::with_env`](crate::VaultSecret::with_env)).
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `name`: Name of the secret in the vault.
+    /// - `env`: The environment name (e.g. `prod`).
+    pub fn with_env_secret(mut self, alias: &str, name: &str, env: &str) -> Self {
+        self.secrets.push((alias.to_string(), name.to_string(), None, Some(env.to_string())));
+        self
+    }
+
+    /// Reads the registered secrets.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// The secrets, or a [`VaultSecretError`](crate::VaultSecretError) holding the `url` and
+    /// `status` of the failed request in its details.
+    pub fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let token = self.token(&agent, context)?;
+        let mut secrets = Vec::new();
+        for (alias, name, version, env) in &self.secrets {
+            let url = match version {
+                Some(version) => format!("{}/secrets/{}/{}", self.vault_url, name, version),
+                None => format!("{}/secrets/{}", self.vault_url, name),
+            };
+            let request = agent
+                .get(&url)
+                .query("api-version", API_VERSION)
+                .header("Authorization", &format!("Bearer {}", token));
+            let response = call(request.call(), &url, context)?;
+            let value = match response.get("value") {
+                Some(serde_json::Value::String(value)) => value,
+                _ => return Err(error(format!("Invalid secret '{}': no value found", name), &url, None, None, context)),
+            };
+            let secret = crate::VaultSecret::new(alias, name, value);
+            secrets.push(match env {
+                Some(env) => secret.with_env(env),
+                None => secret,
+            });
+        }
+        Ok(crate::VaultSecrets::new(secrets))
+    }
+
+    /// Reads the registered secrets into a [`VaultConfig`](crate::VaultConfig).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// A `VaultConfig` holding the secrets, or an error if they could not be read.
+    pub fn config(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultConfig> {
+        Ok(crate::VaultConfig {
+            secrets: Some(self.secrets(context)?),
+        })
+    }
+
+    /// Returns an access token for Key Vault.
+    fn token(&self, agent: &ureq::Agent, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<String> {
+        let (url, result) = match &self.auth {
+            AzureAuth::Token(token) => return Ok(token.clone()),
+            AzureAuth::ManagedIdentity { client_id } => {
+                let request = agent
+                    .get(&self.identity_endpoint)
+                    .query("api-version", "2018-02-01")
+                    .query("resource", "https://vault.azure.net")
+                    .header("Metadata", "true");
+                let request = match client_id {
+                    Some(client_id) => request.query("client_id", client_id),
+                    None => request,
+                };
+                (self.identity_endpoint.clone(), request.call())
+            }
+            AzureAuth::ClientCredentials {
+                tenant_id,
+                client_id,
+                client_secret,
+            } => {
+                let url = format!("{}/{}/oauth2/v2.0/token", self.authority, tenant_id);
+                let result = agent.post(&url).send_form([
+                    ("grant_type", "client_credentials"),
+                    ("client_id", client_id),
+                    ("client_secret", client_secret),
+                    ("scope", "https://vault.azure.net/.default"),
+                ]);
+                (url, result)
+            }
+        };
+        match call(result, &url, context)?.get("access_token") {
+            Some(serde_json::Value::String(token)) => Ok(token.clone()),
+            _ => Err(error("Invalid token response: no access token".to_string(), &url, None, None, context)),
+        }
+    }
+}
+
+impl crate::SecretProvider for AzureKeyVault {
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        AzureKeyVault::secrets(self, context)
+    }
+}
+
+/// Returns the JSON body of a successful response.
+fn call(
+    result: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+    url: &str,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<serde_json::Value> {
+    let mut response = result.map_err(|err| {
+        error(
+            format!("Failed to reach Azure at '{}': {}", url, err),
+            url,
+            None,
+            Some(err.to_string()),
+            context,
+        )
+    })?;
+    let status = response.status().as_u16();
+    let content = response.body_mut().read_to_string().unwrap_or_default();
+    if !response.status().is_success() {
+        return Err(error(
+            format!("Azure request '{}' failed with HTTP status {}", url, status),
+            url,
+            Some(status),
+            Some(content),
+            context,
+        ));
+    }
+    serde_json::from_str(&content).map_err(|err| {
+        error(
+            format!("Invalid Azure response from '{}': {}", url, err),
+            url,
+            Some(status),
+            Some(err.to_string()),
+            context,
+        )
+    })
+}
+
+/// Builds the error of a failed Azure request.
+fn error(
+    message: String,
+    url: &str,
+    status: Option<u16>,
+    origin: Option<String>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Error {
+    crate::VaultSecretError::new()
+        .with_message(message)
+        .with_details(crate::context::bounded(crate::context::with_request(context, url, status, origin)))
+        .into()
+}
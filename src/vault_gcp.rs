@@ -0,0 +1,266 @@
+//! Google Cloud Secret Manager backend for [`VaultSecrets`](crate::VaultSecrets).
+//!
+//! With the `gcp` feature, secret versions are read from Secret Manager with an OAuth 2.0
+//! access token, either given or obtained from the metadata server of the Google Cloud
+//! resource running the process. Each secret is registered under an alias and its payload is
+//! retrieved through the usual [`VaultSecrets::alias`](crate::VaultSecrets::alias) API, the
+//! version read being available with [`VaultSecrets::version`](crate::VaultSecrets::version):
+//!
+//! ```rust
+//! use cdumay_config::SecretProvider;
+//!
+//! fn api_key() -> cdumay_core::Result<String> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let secrets = cdumay_config::GcpSecretManager::new("my-project", cdumay_config::GcpAuth::MetadataServer)
+//!         .with_secret("api", "api-key")
+//!         .secrets(&context)?;
+//!     println!("Using version {:?} of the API key", secrets.version("api", &context));
+//!     secrets.alias("api".to_string(), cdumay_config::ContentFormat::JSON, &context)
+//! }
+//! ```
+
+/// Default endpoint of the metadata server issuing access tokens of the attached service account.
+const METADATA_ENDPOINT: &str = "http://metadata.google.internal/computeMetadata/v1/instance/service-accounts/default/token";
+
+/// Default endpoint of Secret Manager.
+const ENDPOINT: &str = "https://secretmanager.googleapis.com";
+
+/// Authentication method against Google Cloud Secret Manager.
+#[derive(Clone, PartialEq)]
+pub enum GcpAuth {
+    /// The service account attached to the Google Cloud resource running the process,
+    /// through the metadata server.
+    MetadataServer,
+    /// An OAuth 2.0 access token obtained by other means (e.g. `gcloud auth print-access-token`),
+    /// sent as is.
+    Token(String),
+}
+
+impl std::fmt::Debug for GcpAuth {
+    /// Formats the method without its credentials.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GcpAuth::MetadataServer => f.write_str("MetadataServer"),
+            GcpAuth::Token(_) => f.write_str("Token(***)"),
+        }
+    }
+}
+
+/// Client reading secrets from Google Cloud Secret Manager.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GcpSecretManager {
+    /// Identifier of the project holding the secrets.
+    project: String,
+    /// How the client authenticates.
+    auth: GcpAuth,
+    /// Endpoint of Secret Manager.
+    endpoint: String,
+    /// Endpoint of the metadata server issuing access tokens.
+    metadata_endpoint: String,
+    /// Maximum duration of each request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+    /// Secrets to read, as `(alias, secret, version, environment)`.
+    secrets: Vec<(String, String, String, Option<String>)>,
+}
+
+impl GcpSecretManager {
+    /// Creates a client of the secrets of a project.
+    ///
+    /// # Parameters
+    /// - `project`: Identifier (or number) of the project holding the secrets.
+    /// - `auth`: How the client authenticates.
+    pub fn new(project: &str, auth: GcpAuth) -> Self {
+        Self {
+            project: project.to_string(),
+            auth,
+            endpoint: ENDPOINT.to_string(),
+            metadata_endpoint: METADATA_ENDPOINT.to_string(),
+            timeout: None,
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Sets the endpoint of Secret Manager (e.g. a regional or private endpoint).
+    pub fn with_endpoint(mut self, endpoint: &str) -> Self {
+        self.endpoint = endpoint.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Sets the endpoint of the metadata server issuing access tokens.
+    pub fn with_metadata_endpoint(mut self, endpoint: &str) -> Self {
+        self.metadata_endpoint = endpoint.to_string();
+        self
+    }
+
+    /// Sets the maximum duration of each request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Registers the latest version of a secret to read, shared by all environments.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `secret`: Name of the secret in the project.
+    pub fn with_secret(self, alias: &str, secret: &str) -> Self {
+        self.with_secret_version(alias, secret, "latest")
+    }
+
+    /// Registers a given version of a secret to read, shared by all environments.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `secret`: Name of the secret in the project.
+    /// - `version`: Number or alias of the version (e.g. `3` or `latest`).
+    pub fn with_secret_version(mut self, alias: &str, secret: &str, version: &str) -> Self {
+        self.secrets.push((alias.to_string(), secret.to_string(), version.to_string(), None));
+        self
+    }
+
+    /// Registers the latest version of a secret to read, bound to an environment (see
+    /// [`VaultSecret::with_env`](crate::VaultSecret::with_env)).
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `secret`: Name of the secret in the project.
+    /// - `env`: The environment name (e.g. `prod`).
+    pub fn with_env_secret(mut self, alias: &str, secret: &str, env: &str) -> Self {
+        self.secrets
+            .push((alias.to_string(), secret.to_string(), "latest".to_string(), Some(env.to_string())));
+        self
+    }
+
+    /// Reads the registered secrets.
+    ///
+    /// The payload of each secret must be UTF-8. The number of the version read (e.g. `3`
+    /// when `latest` was requested) is recorded with [`VaultSecret::with_version`](crate::VaultSecret::with_version).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// The secrets, or a [`VaultSecretError`](crate::VaultSecretError) holding the `url` and
+    /// `status` of the failed request in its details.
+    pub fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        use base64::Engine;
+
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let token = self.token(&agent, context)?;
+        let mut secrets = Vec::new();
+        for (alias, secret, version, env) in &self.secrets {
+            let url = format!(
+                "{}/v1/projects/{}/secrets/{}/versions/{}:access",
+                self.endpoint, self.project, secret, version
+            );
+            let request = agent.get(&url).header("Authorization", &format!("Bearer {}", token));
+            let response = call(request.call(), &url, context)?;
+            let value = match response.pointer("/payload/data") {
+                Some(serde_json::Value::String(data)) => base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .ok()
+                    .and_then(|bytes| String::from_utf8(bytes).ok())
+                    .ok_or_else(|| error(format!("Invalid secret '{}': payload is not UTF-8", secret), &url, None, None, context))?,
+                _ => return Err(error(format!("Invalid secret '{}': no payload found", secret), &url, None, None, context)),
+            };
+            // The name of the version read, e.g. `projects/123/secrets/api-key/versions/3`.
+            let version = match response.get("name") {
+                Some(serde_json::Value::String(name)) => name.rsplit('/').next().unwrap_or(version),
+                _ => version,
+            };
+            let secret = crate::VaultSecret::new(alias, secret, &value).with_version(version);
+            secrets.push(match env {
+                Some(env) => secret.with_env(env),
+                None => secret,
+            });
+        }
+        Ok(crate::VaultSecrets::new(secrets))
+    }
+
+    /// Reads the registered secrets into a [`VaultConfig`](crate::VaultConfig).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// A `VaultConfig` holding the secrets, or an error if they could not be read.
+    pub fn config(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultConfig> {
+        Ok(crate::VaultConfig {
+            secrets: Some(self.secrets(context)?),
+        })
+    }
+
+    /// Returns an access token for Secret Manager.
+    fn token(&self, agent: &ureq::Agent, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<String> {
+        if let GcpAuth::Token(token) = &self.auth {
+            return Ok(token.clone());
+        }
+        let url = &self.metadata_endpoint;
+        let result = agent.get(url).header("Metadata-Flavor", "Google").call();
+        match call(result, url, context)?.get("access_token") {
+            Some(serde_json::Value::String(token)) => Ok(token.clone()),
+            _ => Err(error("Invalid token response: no access token".to_string(), url, None, None, context)),
+        }
+    }
+}
+
+impl crate::SecretProvider for GcpSecretManager {
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        GcpSecretManager::secrets(self, context)
+    }
+}
+
+/// Returns the JSON body of a successful response.
+fn call(
+    result: Result<ureq::http::Response<ureq::Body>, ureq::Error>,
+    url: &str,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Result<serde_json::Value> {
+    let mut response = result.map_err(|err| {
+        error(
+            format!("Failed to reach Google Cloud at '{}': {}", url, err),
+            url,
+            None,
+            Some(err.to_string()),
+            context,
+        )
+    })?;
+    let status = response.status().as_u16();
+    let content = response.body_mut().read_to_string().unwrap_or_default();
+    if !response.status().is_success() {
+        return Err(error(
+            format!("Google Cloud request '{}' failed with HTTP status {}", url, status),
+            url,
+            Some(status),
+            Some(content),
+            context,
+        ));
+    }
+    serde_json::from_str(&content).map_err(|err| {
+        error(
+            format!("Invalid Google Cloud response from '{}': {}", url, err),
+            url,
+            Some(status),
+            Some(err.to_string()),
+            context,
+        )
+    })
+}
+
+/// Builds the error of a failed Google Cloud request.
+fn error(
+    message: String,
+    url: &str,
+    status: Option<u16>,
+    origin: Option<String>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Error {
+    crate::VaultSecretError::new()
+        .with_message(message)
+        .with_details(crate::context::bounded(crate::context::with_request(context, url, status, origin)))
+        .into()
+}
@@ -0,0 +1,281 @@
+//! HashiCorp Vault backend for [`VaultSecrets`](crate::VaultSecrets).
+//!
+//! With the `vault-hashicorp` feature, secrets are read from a KV v2 mount instead of a local
+//! file. Each secret is registered under an alias; its data is stored as JSON, so it is
+//! deserialized through the usual [`VaultSecrets::alias`](crate::VaultSecrets::alias) API:
+//!
+//! ```rust
+//! #[derive(serde::Deserialize)]
+//! struct Database {
+//!     username: String,
+//!     password: String,
+//! }
+//!
+//! fn database() -> cdumay_core::Result<Database> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let secrets = cdumay_config::HashicorpVault::new("https://vault.internal:8200", cdumay_config::HashicorpAuth::Token("s.xxx".to_string()))
+//!         .with_secret("db", "app/database")
+//!         .secrets(&context)?;
+//!     secrets.alias("db".to_string(), cdumay_config::ContentFormat::JSON, &context)
+//! }
+//! ```
+
+/// Authentication method against a HashiCorp Vault server.
+#[derive(Clone, PartialEq)]
+pub enum HashicorpAuth {
+    /// A Vault token, sent as is.
+    Token(String),
+    /// An AppRole login, exchanged for a token on each read.
+    AppRole {
+        /// Identifier of the role.
+        role_id: String,
+        /// Secret identifier of the role.
+        secret_id: String,
+        /// Path of the AppRole auth method, `approle` if unset.
+        mount: Option<String>,
+    },
+}
+
+impl std::fmt::Debug for HashicorpAuth {
+    /// Formats the method without its credentials.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            HashicorpAuth::Token(_) => f.write_str("Token(***)"),
+            HashicorpAuth::AppRole { role_id, mount, .. } => f
+                .debug_struct("AppRole")
+                .field("role_id", role_id)
+                .field("secret_id", &"***")
+                .field("mount", mount)
+                .finish(),
+        }
+    }
+}
+
+/// Client reading secrets from the KV v2 secrets engine of a HashiCorp Vault server.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HashicorpVault {
+    /// Address of the server (e.g. `https://vault.internal:8200`).
+    address: String,
+    /// How the client authenticates.
+    auth: HashicorpAuth,
+    /// Path of the KV v2 mount, `secret` by default.
+    mount: String,
+    /// Vault Enterprise namespace, sent in the `X-Vault-Namespace` header.
+    namespace: Option<String>,
+    /// Maximum duration of each request, unbounded if unset.
+    timeout: Option<std::time::Duration>,
+    /// Secrets to read, as `(alias, path, environment)`.
+    secrets: Vec<(String, String, Option<String>)>,
+}
+
+impl HashicorpVault {
+    /// Creates a client of the server at `address`, reading the `secret` mount.
+    ///
+    /// # Parameters
+    /// - `address`: Address of the server (e.g. `https://vault.internal:8200`).
+    /// - `auth`: How the client authenticates.
+    pub fn new(address: &str, auth: HashicorpAuth) -> Self {
+        Self {
+            address: address.trim_end_matches('/').to_string(),
+            auth,
+            mount: "secret".to_string(),
+            namespace: None,
+            timeout: None,
+            secrets: Vec::new(),
+        }
+    }
+
+    /// Sets the path of the KV v2 mount.
+    pub fn with_mount(mut self, mount: &str) -> Self {
+        self.mount = mount.trim_matches('/').to_string();
+        self
+    }
+
+    /// Sets the Vault Enterprise namespace.
+    pub fn with_namespace(mut self, namespace: &str) -> Self {
+        self.namespace = Some(namespace.to_string());
+        self
+    }
+
+    /// Sets the maximum duration of each request.
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Registers a secret to read, shared by all environments.
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `path`: Path of the secret in the mount (e.g. `app/database`).
+    pub fn with_secret(mut self, alias: &str, path: &str) -> Self {
+        self.secrets.push((alias.to_string(), path.trim_matches('/').to_string(), None));
+        self
+    }
+
+    /// Registers a secret to read, bound to an environment (see [`VaultSecret::with_env`](crate::VaultSecret::with_env)).
+    ///
+    /// # Parameters
+    /// - `alias`: The alias the secret is retrieved with.
+    /// - `path`: Path of the secret in the mount (e.g. `prod/app/database`).
+    /// - `env`: The environment name (e.g. `prod`).
+    pub fn with_env_secret(mut self, alias: &str, path: &str, env: &str) -> Self {
+        self.secrets
+            .push((alias.to_string(), path.trim_matches('/').to_string(), Some(env.to_string())));
+        self
+    }
+
+    /// Reads the registered secrets.
+    ///
+    /// The data of each secret (its latest version) is stored as a JSON object, to be
+    /// deserialized with [`ContentFormat::JSON`](crate::ContentFormat::JSON).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// The secrets, or a [`VaultSecretError`](crate::VaultSecretError) holding the `url` and
+    /// `status` of the failed request in its details.
+    pub fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        let agent: ureq::Agent = ureq::Agent::config_builder()
+            .http_status_as_error(false)
+            .timeout_global(self.timeout)
+            .build()
+            .into();
+        let token = self.token(&agent, context)?;
+        let mut secrets = Vec::new();
+        for (alias, path, env) in &self.secrets {
+            let url = format!("{}/v1/{}/data/{}", self.address, self.mount, path);
+            let response = self.call(&agent, &url, Some(&token), None, context)?;
+            let data = match response.pointer("/data/data") {
+                Some(data) if data.is_object() => data.to_string(),
+                _ => return Err(error(format!("Invalid secret '{}': no data found", path), &url, None, None, context)),
+            };
+            let secret = crate::VaultSecret::new(alias, path, &data);
+            secrets.push(match env {
+                Some(env) => secret.with_env(env),
+                None => secret,
+            });
+        }
+        Ok(crate::VaultSecrets::new(secrets))
+    }
+
+    /// Reads the registered secrets into a [`VaultConfig`](crate::VaultConfig).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a request fails.
+    ///
+    /// # Returns
+    /// A `VaultConfig` holding the secrets, or an error if they could not be read.
+    pub fn config(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultConfig> {
+        Ok(crate::VaultConfig {
+            secrets: Some(self.secrets(context)?),
+        })
+    }
+
+    /// Returns the token of the client, logging in with AppRole if needed.
+    fn token(&self, agent: &ureq::Agent, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<String> {
+        let (role_id, secret_id, mount) = match &self.auth {
+            HashicorpAuth::Token(token) => return Ok(token.clone()),
+            HashicorpAuth::AppRole { role_id, secret_id, mount } => (role_id, secret_id, mount.as_deref().unwrap_or("approle")),
+        };
+        let url = format!("{}/v1/auth/{}/login", self.address, mount.trim_matches('/'));
+        let body = serde_json::json!({"role_id": role_id, "secret_id": secret_id});
+        let response = self.call(agent, &url, None, Some(body), context)?;
+        match response.pointer("/auth/client_token") {
+            Some(serde_json::Value::String(token)) => Ok(token.clone()),
+            _ => Err(error(
+                "Invalid AppRole login response: no client token".to_string(),
+                &url,
+                None,
+                None,
+                context,
+            )),
+        }
+    }
+
+    /// Sends a request, a `POST` of `body` if any and a `GET` otherwise, returning the JSON
+    /// body of a successful response.
+    fn call(
+        &self,
+        agent: &ureq::Agent,
+        url: &str,
+        token: Option<&str>,
+        body: Option<serde_json::Value>,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<serde_json::Value> {
+        let result = match body {
+            Some(body) => self
+                .headers(agent.post(url), token)
+                .header("Content-Type", "application/json")
+                .send(body.to_string()),
+            None => self.headers(agent.get(url), token).call(),
+        };
+        let mut response = result.map_err(|err| {
+            error(
+                format!("Failed to reach Vault at '{}': {}", url, err),
+                url,
+                None,
+                Some(err.to_string()),
+                context,
+            )
+        })?;
+        let status = response.status().as_u16();
+        let content = response.body_mut().read_to_string().unwrap_or_default();
+        if !response.status().is_success() {
+            // Vault explains failures in an `errors` list.
+            let reason = serde_json::from_str::<serde_json::Value>(&content)
+                .ok()
+                .and_then(|body| body.get("errors").cloned())
+                .map(|errors| errors.to_string());
+            return Err(error(
+                format!("Vault request '{}' failed with HTTP status {}", url, status),
+                url,
+                Some(status),
+                reason,
+                context,
+            ));
+        }
+        serde_json::from_str(&content).map_err(|err| {
+            error(
+                format!("Invalid Vault response from '{}': {}", url, err),
+                url,
+                Some(status),
+                Some(err.to_string()),
+                context,
+            )
+        })
+    }
+
+    /// Adds the token and namespace headers to a request.
+    fn headers<B>(&self, request: ureq::RequestBuilder<B>, token: Option<&str>) -> ureq::RequestBuilder<B> {
+        let request = match token {
+            Some(token) => request.header("X-Vault-Token", token),
+            None => request,
+        };
+        match &self.namespace {
+            Some(namespace) => request.header("X-Vault-Namespace", namespace),
+            None => request,
+        }
+    }
+}
+
+impl crate::SecretProvider for HashicorpVault {
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        HashicorpVault::secrets(self, context)
+    }
+}
+
+/// Builds the error of a failed Vault request.
+fn error(
+    message: String,
+    url: &str,
+    status: Option<u16>,
+    origin: Option<String>,
+    context: &std::collections::BTreeMap<String, serde_value::Value>,
+) -> cdumay_core::Error {
+    crate::VaultSecretError::new()
+        .with_message(message)
+        .with_details(crate::context::bounded(crate::context::with_request(context, url, status, origin)))
+        .into()
+}
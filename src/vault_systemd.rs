@@ -0,0 +1,118 @@
+//! systemd credentials backend for [`VaultSecrets`](crate::VaultSecrets).
+//!
+//! systemd passes the credentials of a service (`LoadCredential=`, `SetCredential=`,
+//! `LoadCredentialEncrypted=`...) as files of a private directory named by the
+//! `$CREDENTIALS_DIRECTORY` environment variable, readable by the service only. Each file is
+//! a secret aliased by its name:
+//!
+//! ```rust
+//! use cdumay_config::SecretProvider;
+//!
+//! // With `LoadCredential=db.json:/etc/app/db.json` in the unit file.
+//! fn database() -> cdumay_core::Result<serde_json::Value> {
+//!     let context = std::collections::BTreeMap::new();
+//!     let secrets = cdumay_config::SystemdCredentials::from_env(&context)?.secrets(&context)?;
+//!     secrets.alias("db.json".to_string(), cdumay_config::ContentFormat::JSON, &context)
+//! }
+//! ```
+
+/// Environment variable naming the credentials directory of a service.
+const CREDENTIALS_DIRECTORY: &str = "CREDENTIALS_DIRECTORY";
+
+/// Provider reading the credentials passed to a service by systemd.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SystemdCredentials {
+    /// The credentials directory.
+    directory: std::path::PathBuf,
+}
+
+impl SystemdCredentials {
+    /// Creates a provider reading the credentials of a directory.
+    ///
+    /// # Parameters
+    /// - `directory`: The credentials directory.
+    pub fn new<P: AsRef<std::path::Path>>(directory: P) -> Self {
+        Self {
+            directory: directory.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Creates a provider reading the credentials directory of the running service.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if the directory is not set.
+    ///
+    /// # Returns
+    /// The provider, or a [`VaultSecretError`](crate::VaultSecretError) if
+    /// `$CREDENTIALS_DIRECTORY` is not set, i.e. the process was not started by systemd with
+    /// credentials.
+    pub fn from_env(context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<Self> {
+        match std::env::var_os(CREDENTIALS_DIRECTORY) {
+            Some(directory) if !directory.is_empty() => Ok(Self::new(directory)),
+            _ => Err(crate::VaultSecretError::new()
+                .with_message(format!("${} is not set, no systemd credentials available", CREDENTIALS_DIRECTORY))
+                .with_details(crate::context::bounded(context.clone()))
+                .into()),
+        }
+    }
+
+    /// Returns the credentials directory.
+    pub fn directory(&self) -> &std::path::Path {
+        &self.directory
+    }
+
+    /// Reads the credentials, each file of the directory being a secret aliased by its name.
+    ///
+    /// Credentials must be UTF-8; their content is kept as is, trailing newline included.
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a credential cannot be read.
+    ///
+    /// # Returns
+    /// The secrets, or a [`VaultSecretError`](crate::VaultSecretError) holding the `path` of
+    /// the credential (or directory) which cannot be read.
+    pub fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        let error = |path: &std::path::Path, err: std::io::Error| -> cdumay_core::Error {
+            crate::VaultSecretError::new()
+                .with_message(format!("Failed to read systemd credential '{}': {}", path.display(), err))
+                .with_details(crate::context::bounded({
+                    let mut ctx = crate::context::with_path(context, path);
+                    ctx.insert("origin".to_string(), serde_value::Value::String(err.to_string()));
+                    ctx
+                }))
+                .into()
+        };
+        let mut secrets = Vec::new();
+        for entry in std::fs::read_dir(&self.directory).map_err(|err| error(&self.directory, err))? {
+            let path = entry.map_err(|err| error(&self.directory, err))?.path();
+            if !path.is_file() {
+                continue;
+            }
+            let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+                continue;
+            };
+            let value = std::fs::read_to_string(&path).map_err(|err| error(&path, err))?;
+            secrets.push(crate::VaultSecret::new(name, name, &value));
+        }
+        Ok(crate::VaultSecrets::new(secrets))
+    }
+
+    /// Reads the credentials into a [`VaultConfig`](crate::VaultConfig).
+    ///
+    /// # Parameters
+    /// - `context`: A context used for error details if a credential cannot be read.
+    ///
+    /// # Returns
+    /// A `VaultConfig` holding the credentials, or an error if they could not be read.
+    pub fn config(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultConfig> {
+        Ok(crate::VaultConfig {
+            secrets: Some(self.secrets(context)?),
+        })
+    }
+}
+
+impl crate::SecretProvider for SystemdCredentials {
+    fn secrets(&self, context: &std::collections::BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<crate::VaultSecrets> {
+        SystemdCredentials::secrets(self, context)
+    }
+}
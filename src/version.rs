@@ -0,0 +1,99 @@
+//! Schema version gating for configuration files.
+//!
+//! A configuration written by a newer release of an application may carry fields or
+//! semantics an older binary does not understand. [`SchemaVersion`] makes readers refuse
+//! such files instead of silently misreading them.
+
+/// Requirement on the schema version declared inside a configuration file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaVersion {
+    /// Name of the top-level field holding the version.
+    field: String,
+    /// Highest version supported by this binary.
+    supported: u64,
+}
+
+impl SchemaVersion {
+    /// Creates a requirement on the `schema_version` field.
+    ///
+    /// # Parameters
+    /// - `supported`: The highest schema version this binary is able to read.
+    ///
+    /// # Returns
+    /// A new `SchemaVersion` requirement.
+    pub fn new(supported: u64) -> Self {
+        Self {
+            field: "schema_version".to_string(),
+            supported,
+        }
+    }
+
+    /// Uses another top-level field name to read the version from (e.g. `version`).
+    pub fn with_field(mut self, field: &str) -> Self {
+        self.field = field.to_string();
+        self
+    }
+
+    /// Returns the name of the field holding the version.
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Returns the highest supported version.
+    pub fn supported(&self) -> u64 {
+        self.supported
+    }
+
+    /// Checks the version declared in a parsed configuration.
+    ///
+    /// The field must exist at the root of the configuration and hold a non-negative
+    /// integer (or a string containing one).
+    ///
+    /// # Parameters
+    /// - `value`: The parsed configuration.
+    /// - `context`: A context used for error details if the check fails.
+    ///
+    /// # Returns
+    /// The declared version, or a [`SchemaVersionError`](crate::SchemaVersionError) if it is
+    /// missing, invalid or newer than the supported one.
+    pub fn check(
+        &self,
+        value: &serde_value::Value,
+        context: &std::collections::BTreeMap<String, serde_value::Value>,
+    ) -> cdumay_core::Result<u64> {
+        let error = |message: String| {
+            crate::SchemaVersionError::new().with_message(message).with_details(crate::context::bounded({
+                let mut ctx = context.clone();
+                ctx.insert("field".to_string(), serde_value::Value::String(self.field.clone()));
+                ctx.insert("supported".to_string(), serde_value::Value::U64(self.supported));
+                ctx
+            }))
+        };
+        let declared = match value {
+            serde_value::Value::Map(map) => map.get(&serde_value::Value::String(self.field.clone())),
+            _ => None,
+        }
+        .ok_or_else(|| error(format!("Missing required field '{}' at the root of the configuration", self.field)))?;
+        let version = match declared {
+            serde_value::Value::U8(v) => Some(*v as u64),
+            serde_value::Value::U16(v) => Some(*v as u64),
+            serde_value::Value::U32(v) => Some(*v as u64),
+            serde_value::Value::U64(v) => Some(*v),
+            serde_value::Value::I8(v) => u64::try_from(*v).ok(),
+            serde_value::Value::I16(v) => u64::try_from(*v).ok(),
+            serde_value::Value::I32(v) => u64::try_from(*v).ok(),
+            serde_value::Value::I64(v) => u64::try_from(*v).ok(),
+            serde_value::Value::String(v) => v.trim().parse().ok(),
+            _ => None,
+        }
+        .ok_or_else(|| error(format!("Field '{}' must hold a non-negative integer version", self.field)))?;
+        if version > self.supported {
+            return Err(error(format!(
+                "Configuration schema version {} is newer than the highest supported version {}; upgrade the application to load this file",
+                version, self.supported
+            ))
+            .into());
+        }
+        Ok(version)
+    }
+}
@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use cdumay_config::{BootstrapSource, ConfigurationFileError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    endpoint: String,
+}
+
+fn settings(endpoint: &str) -> Settings {
+    Settings {
+        endpoint: endpoint.to_string(),
+    }
+}
+
+#[test]
+fn test_bootstrap_remote_saves_local_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("settings.json");
+    let loaded = cdumay_config::bootstrap_config(
+        || Ok(settings("remote")),
+        Duration::from_secs(5),
+        cache.to_str().unwrap(),
+        None,
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    assert!(matches!(loaded.source, BootstrapSource::Remote));
+    assert_eq!(loaded.config, settings("remote"));
+    let cached: Settings = cdumay_config::read_config(cache.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(cached, settings("remote"));
+}
+
+#[test]
+fn test_bootstrap_falls_back_on_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("settings.json");
+    cdumay_config::write_config(cache.to_str().unwrap(), None, settings("cached"), &BTreeMap::new()).unwrap();
+    let loaded = cdumay_config::bootstrap_config::<Settings, _>(
+        || Err(ConfigurationFileError::new().with_message("service down".to_string()).into()),
+        Duration::from_secs(5),
+        cache.to_str().unwrap(),
+        None,
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    assert!(loaded.is_fallback());
+    assert_eq!(loaded.config, settings("cached"));
+    match loaded.source {
+        BootstrapSource::Cache { remote_error } => assert_eq!(remote_error.message(), "service down"),
+        BootstrapSource::Remote => panic!("expected the local copy"),
+    }
+}
+
+#[test]
+fn test_bootstrap_falls_back_on_timeout() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("settings.json");
+    cdumay_config::write_config(cache.to_str().unwrap(), None, settings("cached"), &BTreeMap::new()).unwrap();
+    let loaded = cdumay_config::bootstrap_config(
+        || {
+            std::thread::sleep(Duration::from_secs(2));
+            Ok(settings("late"))
+        },
+        Duration::from_millis(50),
+        cache.to_str().unwrap(),
+        None,
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    assert_eq!(loaded.config, settings("cached"));
+    match loaded.source {
+        BootstrapSource::Cache { remote_error } => assert!(remote_error.message().contains("not received within")),
+        BootstrapSource::Remote => panic!("expected the local copy"),
+    }
+}
+
+#[test]
+fn test_bootstrap_without_local_copy() {
+    let dir = tempfile::tempdir().unwrap();
+    let cache = dir.path().join("missing.json");
+    let err = cdumay_config::bootstrap_config::<Settings, _>(
+        || Err(ConfigurationFileError::new().with_message("service down".to_string()).into()),
+        Duration::from_secs(5),
+        cache.to_str().unwrap(),
+        None,
+        &BTreeMap::new(),
+    )
+    .unwrap_err();
+    assert!(err.message().contains("local copy unreadable"));
+    assert_eq!(err.details().get("remote"), Some(&serde_value::Value::String("service down".to_string())));
+}
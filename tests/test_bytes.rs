@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use cdumay_config::{JsonManager, Manager};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Tls {
+    #[serde(with = "cdumay_config::bytes::base64")]
+    certificate: Vec<u8>,
+    #[serde(with = "cdumay_config::bytes::hex")]
+    fingerprint: [u8; 4],
+}
+
+#[test]
+fn test_bytes_round_trip_json() {
+    let tls = Tls {
+        certificate: b"-----BEGIN CERTIFICATE-----".to_vec(),
+        fingerprint: [0xde, 0xad, 0xbe, 0xef],
+    };
+    let json = serde_json::to_value(&tls).unwrap();
+    assert_eq!(json["fingerprint"], "deadbeef");
+    assert_eq!(json["certificate"], "LS0tLS1CRUdJTiBDRVJUSUZJQ0FURS0tLS0t");
+
+    let read: Tls = JsonManager::read_str(&json.to_string(), &BTreeMap::new()).unwrap();
+    assert_eq!(read, tls);
+}
+
+#[test]
+fn test_bytes_round_trip_through_value_tree() {
+    let content = r#"{"certificate": "AAEC", "fingerprint": "DEADBEEF"}"#;
+    let value: serde_value::Value = JsonManager::read_str(content, &BTreeMap::new()).unwrap();
+    let tls: Tls = value.deserialize_into().unwrap();
+    assert_eq!(tls.certificate, vec![0, 1, 2]);
+    assert_eq!(tls.fingerprint, [0xde, 0xad, 0xbe, 0xef]);
+}
+
+#[test]
+fn test_bytes_invalid_encoding() {
+    let result: cdumay_core::Result<Tls> = JsonManager::read_str(r#"{"certificate": "not base64!", "fingerprint": "00"}"#, &BTreeMap::new());
+    assert!(result.unwrap_err().to_string().contains("invalid base64 data"));
+}
+
+#[test]
+fn test_bytes_wrong_length() {
+    let result: cdumay_core::Result<Tls> = JsonManager::read_str(r#"{"certificate": "AAEC", "fingerprint": "dead"}"#, &BTreeMap::new());
+    assert!(result.unwrap_err().to_string().contains("unexpected length of 2 bytes"));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_bytes_yaml() {
+    let tls: Tls = cdumay_config::YamlManager::read_str("certificate: AAEC\nfingerprint: deadbeef\n", &BTreeMap::new()).unwrap();
+    assert_eq!(tls.certificate, vec![0, 1, 2]);
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "aes-gcm")]
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use cdumay_config::{AesGcmCipher, Cipher, FormatRegistry, ReadOptions, WriteOptions};
+
+#[test]
+fn test_aes_gcm_roundtrip() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.json.enc");
+    let path = path.to_str().unwrap();
+
+    let cipher: Arc<dyn Cipher> = Arc::new(AesGcmCipher::new("correct horse").with_params(1024, 1, 1));
+    let data = BTreeMap::from([("password".to_string(), "hunter2".to_string())]);
+    let write = WriteOptions {
+        cipher: Some(cipher.clone()),
+        ..Default::default()
+    };
+    FormatRegistry::new()
+        .write_config_with(path, None, &data, &BTreeMap::new(), &write)
+        .unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.starts_with("$aes-256-gcm$argon2id$m=1024,t=1,p=1$"));
+    assert!(!content.contains("hunter2"));
+
+    // Decryption uses the parameters stored in the content.
+    let read = ReadOptions {
+        cipher: Some(Arc::new(AesGcmCipher::new("correct horse"))),
+        ..Default::default()
+    };
+    let decrypted: BTreeMap<String, String> = FormatRegistry::new().read_config_with(path, None, &BTreeMap::new(), &read).unwrap();
+    assert_eq!(decrypted, data);
+
+    // Salts and nonces are random.
+    assert_ne!(cipher.encrypt(b"{}").unwrap(), cipher.encrypt(b"{}").unwrap());
+}
+
+#[test]
+fn test_aes_gcm_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.json.enc");
+    let path = path.to_str().unwrap();
+    let cipher = AesGcmCipher::new("correct horse").with_params(1024, 1, 1);
+    std::fs::write(path, cipher.encrypt(br#"{"password": "hunter2"}"#).unwrap()).unwrap();
+
+    let read = ReadOptions {
+        cipher: Some(Arc::new(AesGcmCipher::new("wrong"))),
+        ..Default::default()
+    };
+    let err = cdumay_config::read_config_with::<BTreeMap<String, String>>(path, None, &BTreeMap::new(), &read).unwrap_err();
+    assert!(err.message().starts_with("Failed to decrypt"));
+    assert!(err.message().contains("wrong passphrase or corrupted content"));
+    assert_eq!(err.details()["path"], serde_value::Value::String(path.to_string()));
+
+    assert!(cipher.decrypt(br#"{"password": "hunter2"}"#).is_err());
+    assert!(cipher.decrypt(b"$aes-256-gcm$argon2id$m=1024,t=1$AAAA$AAAA$AAAA").is_err());
+
+    // Parameters costing more than 4 times the ones of the cipher are refused before the
+    // key derivation.
+    for params in ["m=4194304,t=1,p=1", "m=1024,t=5,p=1", "m=1024,t=1,p=16"] {
+        let content = format!("$aes-256-gcm$argon2id${}$AAAAAAAAAAAAAAAAAAAAAA==$AAAAAAAAAAAAAAAA$AAAA", params);
+        let err = cipher.decrypt(content.as_bytes()).unwrap_err();
+        assert_eq!(err.to_string(), "argon2 parameters exceed the allowed cost");
+    }
+    let stronger = AesGcmCipher::new("correct horse").with_params(4096, 4, 4);
+    assert_eq!(cipher.decrypt(&stronger.encrypt(b"{}").unwrap()).unwrap(), b"{}");
+    let stronger = AesGcmCipher::new("correct horse").with_params(4097, 1, 1);
+    assert_eq!(
+        cipher.decrypt(&stronger.encrypt(b"{}").unwrap()).unwrap_err().to_string(),
+        "argon2 parameters exceed the allowed cost"
+    );
+
+    let write = WriteOptions {
+        cipher: Some(Arc::new(AesGcmCipher::new("correct horse").with_params(0, 0, 0))),
+        ..Default::default()
+    };
+    let err = cdumay_config::write_config_with(path, None, BTreeMap::from([("a", 1)]), &BTreeMap::new(), &write).unwrap_err();
+    assert!(err.message().contains("invalid argon2 parameters"));
+    assert!(!format!("{:?}", cipher).contains("correct horse"));
+}
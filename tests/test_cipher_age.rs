@@ -0,0 +1,89 @@
+#![cfg(feature = "age")]
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use age::secrecy::ExposeSecret;
+use cdumay_config::{AgeCipher, Cipher, ReadOptions, WriteOptions};
+
+#[test]
+fn test_age_roundtrip() {
+    let identity = age::x25519::Identity::generate();
+    let other = age::x25519::Identity::generate();
+    let dir = tempfile::tempdir().unwrap();
+    let identity_file = dir.path().join("keys.txt");
+    std::fs::write(&identity_file, identity.to_string().expose_secret()).unwrap();
+    let path = dir.path().join("secrets.json.age");
+    let path = path.to_str().unwrap();
+
+    let cipher: Arc<dyn Cipher> = Arc::new(
+        AgeCipher::new()
+            .with_recipient(&identity.to_public().to_string())
+            .with_recipient(&other.to_public().to_string())
+            .with_identity_file(identity_file.to_str().unwrap()),
+    );
+    let data = BTreeMap::from([("password".to_string(), "hunter2".to_string())]);
+    let write = WriteOptions {
+        cipher: Some(cipher.clone()),
+        ..Default::default()
+    };
+    cdumay_config::write_config_with(path, None, &data, &BTreeMap::new(), &write).unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.starts_with("-----BEGIN AGE ENCRYPTED FILE-----"));
+    assert!(!content.contains("hunter2"));
+
+    let read = ReadOptions {
+        cipher: Some(cipher),
+        ..Default::default()
+    };
+    let decrypted: BTreeMap<String, String> = cdumay_config::read_config_with(path, None, &BTreeMap::new(), &read).unwrap();
+    assert_eq!(decrypted, data);
+
+    // Any recipient can decrypt, and identities may be given inline.
+    let read = ReadOptions {
+        cipher: Some(Arc::new(AgeCipher::new().with_identity(other.to_string().expose_secret()))),
+        ..Default::default()
+    };
+    let decrypted: BTreeMap<String, String> = cdumay_config::read_config_with(path, None, &BTreeMap::new(), &read).unwrap();
+    assert_eq!(decrypted, data);
+    assert!(cdumay_config::read_config::<BTreeMap<String, String>>(path, None, &BTreeMap::new()).is_err());
+}
+
+#[test]
+fn test_age_errors() {
+    let identity = age::x25519::Identity::generate();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.json.age");
+    let path = path.to_str().unwrap();
+    let data = BTreeMap::from([("password", "hunter2")]);
+
+    let write = WriteOptions {
+        cipher: Some(Arc::new(AgeCipher::new().with_recipient("age1invalid"))),
+        ..Default::default()
+    };
+    let err = cdumay_config::write_config_with(path, None, &data, &BTreeMap::new(), &write).unwrap_err();
+    assert!(err.message().contains("invalid age recipient"));
+    assert!(!std::path::Path::new(path).exists());
+
+    let write = WriteOptions {
+        cipher: Some(Arc::new(
+            AgeCipher::new().with_recipient(&identity.to_public().to_string()).with_binary(true),
+        )),
+        ..Default::default()
+    };
+    cdumay_config::write_config_with(path, None, &data, &BTreeMap::new(), &write).unwrap();
+    assert!(!std::fs::read(path).unwrap().starts_with(b"-----BEGIN"));
+
+    let read = ReadOptions {
+        cipher: Some(Arc::new(
+            AgeCipher::new().with_identity(age::x25519::Identity::generate().to_string().expose_secret()),
+        )),
+        ..Default::default()
+    };
+    let err = cdumay_config::read_config_with::<BTreeMap<String, String>>(path, None, &BTreeMap::new(), &read).unwrap_err();
+    assert_eq!(err.details()["path"], serde_value::Value::String(path.to_string()));
+    assert!(err.message().starts_with("Failed to decrypt"));
+
+    let cipher = AgeCipher::new().with_identity(identity.to_string().expose_secret());
+    assert!(!format!("{:?}", cipher).contains("AGE-SECRET-KEY"));
+}
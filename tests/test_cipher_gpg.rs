@@ -0,0 +1,122 @@
+#![cfg(all(feature = "gpg", unix))]
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use cdumay_config::{Cipher, FormatRegistry, GpgCipher, ReadOptions, WriteOptions};
+
+/// A temporary gpg home, whose agent is stopped when dropped.
+struct GpgHome(tempfile::TempDir);
+
+impl GpgHome {
+    /// Creates an empty gpg home, or `None` if gpg is not installed.
+    fn new() -> Option<Self> {
+        std::process::Command::new("gpg").arg("--version").output().ok()?;
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::set_permissions(dir.path(), std::os::unix::fs::PermissionsExt::from_mode(0o700)).unwrap();
+        Some(Self(dir))
+    }
+
+    fn path(&self) -> &str {
+        self.0.path().to_str().unwrap()
+    }
+
+    /// Generates a key without passphrase for the given user ID.
+    fn generate_key(&self, uid: &str) {
+        let status = std::process::Command::new("gpg")
+            .args(["--homedir", self.path(), "--batch", "--quiet", "--passphrase", "", "--quick-gen-key", uid])
+            .args(["future-default", "default", "never"])
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+}
+
+impl Drop for GpgHome {
+    fn drop(&mut self) {
+        let _ = std::process::Command::new("gpgconf")
+            .args(["--homedir", self.path(), "--kill", "gpg-agent"])
+            .status();
+    }
+}
+
+#[test]
+fn test_gpg_symmetric_roundtrip() {
+    let Some(home) = GpgHome::new() else {
+        return;
+    };
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.json.gpg");
+    let path = path.to_str().unwrap();
+
+    let cipher: Arc<dyn Cipher> = Arc::new(GpgCipher::new().with_home(home.path()).with_passphrase("correct horse"));
+    let data = BTreeMap::from([("password".to_string(), "hunter2".to_string())]);
+    let write = WriteOptions {
+        cipher: Some(cipher.clone()),
+        ..Default::default()
+    };
+    cdumay_config::write_config_with(path, None, &data, &BTreeMap::new(), &write).unwrap();
+    let content = std::fs::read_to_string(path).unwrap();
+    assert!(content.starts_with("-----BEGIN PGP MESSAGE-----"));
+    assert!(!content.contains("hunter2"));
+
+    let read = ReadOptions {
+        cipher: Some(cipher),
+        ..Default::default()
+    };
+    let decrypted: BTreeMap<String, String> = cdumay_config::read_config_with(path, None, &BTreeMap::new(), &read).unwrap();
+    assert_eq!(decrypted, data);
+
+    let read = ReadOptions {
+        cipher: Some(Arc::new(GpgCipher::new().with_home(home.path()).with_passphrase("wrong"))),
+        ..Default::default()
+    };
+    let err = cdumay_config::read_config_with::<BTreeMap<String, String>>(path, None, &BTreeMap::new(), &read).unwrap_err();
+    assert!(err.message().starts_with("Failed to decrypt"));
+    assert_eq!(err.details()["path"], serde_value::Value::String(path.to_string()));
+
+    let cipher = GpgCipher::new().with_passphrase("correct horse");
+    assert!(!format!("{:?}", cipher).contains("correct horse"));
+}
+
+#[test]
+fn test_gpg_recipients() {
+    let Some(home) = GpgHome::new() else {
+        return;
+    };
+    home.generate_key("Ops <ops@example.com>");
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("records.ndjson.gpg");
+    let path = path.to_str().unwrap();
+
+    // The format is inferred from the extension preceding `.gpg`.
+    let cipher = GpgCipher::new()
+        .with_home(home.path())
+        .with_recipient("ops@example.com")
+        .with_binary(true);
+    let records = vec![BTreeMap::from([("id", 1)]), BTreeMap::from([("id", 2)])];
+    let write = WriteOptions {
+        cipher: Some(Arc::new(cipher.clone())),
+        ..Default::default()
+    };
+    FormatRegistry::new()
+        .write_config_with(path, None, &records, &BTreeMap::new(), &write)
+        .unwrap();
+    let content = std::fs::read(path).unwrap();
+    assert!(!content.starts_with(b"-----BEGIN"));
+    assert_eq!(String::from_utf8(cipher.decrypt(&content).unwrap()).unwrap().lines().count(), 2);
+
+    let read = ReadOptions {
+        cipher: Some(Arc::new(GpgCipher::new().with_home(home.path()).with_secret_key("ops@example.com"))),
+        ..Default::default()
+    };
+    let decrypted: Vec<BTreeMap<String, u32>> = FormatRegistry::new().read_config_with(path, None, &BTreeMap::new(), &read).unwrap();
+    assert_eq!(decrypted.len(), 2);
+
+    let write = WriteOptions {
+        cipher: Some(Arc::new(GpgCipher::new().with_home(home.path()))),
+        ..Default::default()
+    };
+    let err = cdumay_config::write_config_with(path, None, &records, &BTreeMap::new(), &write).unwrap_err();
+    assert!(err.message().contains("no gpg recipient nor passphrase"));
+}
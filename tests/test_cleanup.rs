@@ -0,0 +1,117 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, SystemTime};
+
+use cdumay_config::{FileLock, LockMode};
+
+fn age(path: &std::path::Path, by: Duration) {
+    let file = std::fs::OpenOptions::new().write(true).open(path).unwrap();
+    file.set_modified(SystemTime::now() - by).unwrap();
+}
+
+#[test]
+fn test_cleanup_removes_stale_files_only() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("app.json");
+    let old_lock = dir.path().join("app.json.lock");
+    let fresh_lock = dir.path().join("fresh.json.lock");
+    let fresh = dir.path().join("fresh.json");
+    let orphan_lock = dir.path().join("removed.json.lock");
+    let foreign = [
+        dir.path().join("Cargo.lock"),
+        dir.path().join("yarn.lock"),
+        dir.path().join("app.json.tmp"),
+    ];
+    for path in [&config, &old_lock, &fresh_lock, &fresh, &orphan_lock].into_iter().chain(&foreign) {
+        std::fs::write(path, "").unwrap();
+    }
+    for path in [&config, &old_lock, &orphan_lock].into_iter().chain(&foreign) {
+        age(path, Duration::from_secs(7200));
+    }
+
+    let report = cdumay_config::cleanup_stale_files(dir.path().to_str().unwrap(), Duration::from_secs(3600), &BTreeMap::new()).unwrap();
+    assert_eq!(report.removed, vec![old_lock.clone()]);
+    assert!(report.held.is_empty());
+    assert!(!old_lock.exists());
+    assert!(config.exists() && fresh_lock.exists() && orphan_lock.exists());
+    assert!(foreign.iter().all(|path| path.exists()));
+}
+
+#[test]
+fn test_cleanup_keeps_held_locks() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, "{}").unwrap();
+    let guard = FileLock::shared(path.to_str().unwrap(), LockMode::Try, &BTreeMap::new()).unwrap();
+    age(guard.path(), Duration::from_secs(7200));
+
+    let report = cdumay_config::cleanup_stale_files(dir.path().to_str().unwrap(), Duration::from_secs(3600), &BTreeMap::new()).unwrap();
+    assert!(report.removed.is_empty());
+    assert_eq!(report.held, vec![guard.path().to_path_buf()]);
+    assert!(guard.path().exists());
+}
+
+#[test]
+fn test_lock_acquisition_refreshes_lock_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, "{}").unwrap();
+    let lock_path = FileLock::lock_path(path.to_str().unwrap());
+    std::fs::write(&lock_path, "").unwrap();
+    age(&lock_path, Duration::from_secs(7200));
+
+    drop(FileLock::exclusive(path.to_str().unwrap(), LockMode::Try, &BTreeMap::new()).unwrap());
+    let report = cdumay_config::cleanup_stale_files(dir.path().to_str().unwrap(), Duration::from_secs(3600), &BTreeMap::new()).unwrap();
+    assert!(report.removed.is_empty());
+    assert!(lock_path.exists());
+}
+
+#[test]
+fn test_cleanup_missing_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+    assert!(cdumay_config::cleanup_stale_files(missing.to_str().unwrap(), Duration::from_secs(1), &BTreeMap::new()).is_err());
+}
+
+#[test]
+fn test_cleanup_resolves_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("etc")).unwrap();
+    let config = dir.path().join("etc/app.json");
+    let lock = dir.path().join("etc/app.json.lock");
+    for path in [&config, &lock] {
+        std::fs::write(path, "").unwrap();
+        age(path, Duration::from_secs(7200));
+    }
+
+    let options = cdumay_config::WriteOptions {
+        resolver: Some(std::sync::Arc::new(cdumay_config::ChrootPathResolver::new(dir.path()))),
+        ..Default::default()
+    };
+    let report = cdumay_config::cleanup_stale_files_with("/etc", Duration::from_secs(3600), &BTreeMap::new(), &options).unwrap();
+    assert_eq!(report.removed, vec![lock.clone()]);
+    assert!(!lock.exists());
+}
+
+#[test]
+fn test_cleanup_removes_orphaned_temporary_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = dir.path().join("app.json");
+    std::fs::write(&config, "{}").unwrap();
+    let orphan = dir.path().join("app.json.4242.0.tmp");
+    let new_file_orphan = dir.path().join("new.json.4242.3.tmp");
+    let writing = dir.path().join("app.json.4243.0.tmp");
+    let foreign = dir.path().join("app.json.backup.tmp");
+    for path in [&orphan, &new_file_orphan, &writing, &foreign] {
+        std::fs::write(path, "{\"partial\"").unwrap();
+    }
+    for path in [&orphan, &new_file_orphan, &foreign] {
+        age(path, Duration::from_secs(7200));
+    }
+
+    let report = cdumay_config::cleanup_stale_files(dir.path().to_str().unwrap(), Duration::from_secs(3600), &BTreeMap::new()).unwrap();
+    let mut removed = report.removed.clone();
+    removed.sort();
+    assert_eq!(removed, vec![orphan.clone(), new_file_orphan.clone()]);
+    assert!(!orphan.exists() && !new_file_orphan.exists());
+    assert!(config.exists() && writing.exists() && foreign.exists());
+}
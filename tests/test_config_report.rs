@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{ConfigIssue, ConfigIssueKind, ConfigIssueSeverity, ReadOptions, UnknownKeyPolicy};
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+fn write(dir: &tempfile::TempDir, content: &str) -> String {
+    let path = dir.path().join("server.json");
+    std::fs::write(&path, content).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn validate(server: &Server) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    if server.host.is_empty() {
+        issues.push(ConfigIssue::invalid("host", "host must not be empty"));
+    }
+    if server.port < 1024 {
+        issues.push(ConfigIssue::invalid("port", "port must not be privileged"));
+    }
+    issues
+}
+
+#[test]
+fn test_config_report_collects_every_issue() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(&dir, r#"{"host": "", "port": 80, "hots": "x", "debug": true}"#);
+    let options = ReadOptions {
+        schema_version: Some(cdumay_config::SchemaVersion::new(1)),
+        ..Default::default()
+    };
+    let report = cdumay_config::read_config_report(&path, None, &BTreeMap::new(), &options, validate);
+    let kinds: Vec<_> = report
+        .issues
+        .iter()
+        .map(|issue| (issue.kind, issue.severity, issue.key.clone()))
+        .collect();
+    assert_eq!(
+        kinds,
+        vec![
+            (ConfigIssueKind::SchemaVersion, ConfigIssueSeverity::Error, None),
+            (ConfigIssueKind::UnknownKey, ConfigIssueSeverity::Warning, Some("debug".to_string())),
+            (ConfigIssueKind::UnknownKey, ConfigIssueSeverity::Warning, Some("hots".to_string())),
+            (ConfigIssueKind::Validation, ConfigIssueSeverity::Error, Some("host".to_string())),
+            (ConfigIssueKind::Validation, ConfigIssueSeverity::Error, Some("port".to_string())),
+        ]
+    );
+    assert!(report.config.is_some());
+    assert!(!report.is_valid());
+    assert_eq!(report.errors().count(), 3);
+    assert!(report.to_string().ends_with("3 error(s), 2 warning(s)"));
+
+    let err = report.into_result().unwrap_err();
+    assert!(err.message().contains("has 3 error(s)"), "{}", err.message());
+    assert!(matches!(&err.details()["issues"], serde_value::Value::Seq(items) if items.len() == 3));
+}
+
+#[test]
+fn test_config_report_deserialization_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(&dir, r#"{"debug": true, "host": "localhost", "port": "http"}"#);
+    let options = ReadOptions {
+        unknown_keys: UnknownKeyPolicy::Deny,
+        ..Default::default()
+    };
+    let report = cdumay_config::read_config_report(&path, None, &BTreeMap::new(), &options, validate);
+    assert!(report.config.is_none());
+    assert_eq!(report.issues.len(), 2);
+    assert_eq!(report.issues[0].kind, ConfigIssueKind::Deserialization);
+    assert_eq!(report.issues[0].key.as_deref(), Some("port"));
+    assert!(report.issues[0].details.contains_key("path"));
+    assert_eq!(report.issues[1].kind, ConfigIssueKind::UnknownKey);
+    assert_eq!(report.issues[1].severity, ConfigIssueSeverity::Error);
+}
+
+#[test]
+fn test_config_report_read_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(&dir, r#"{"host": "#);
+    let report = cdumay_config::read_config_report(&path, None, &BTreeMap::new(), &ReadOptions::default(), validate);
+    assert_eq!(report.issues.len(), 1);
+    assert_eq!(report.issues[0].kind, ConfigIssueKind::Read);
+    assert!(report.issues[0].details.contains_key("line"));
+    assert!(!report.is_valid());
+}
+
+#[test]
+fn test_config_report_valid() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = write(&dir, r#"{"host": "localhost", "port": 8080}"#);
+    let report = cdumay_config::read_config_report(&path, None, &BTreeMap::new(), &ReadOptions::default(), validate);
+    assert!(report.is_valid());
+    assert_eq!(report.to_string(), "0 error(s), 0 warning(s)");
+    assert_eq!(report.into_result().unwrap().port, 8080);
+}
@@ -0,0 +1,36 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::ContentFormat;
+
+#[test]
+fn test_content_format_display_and_parse() {
+    assert_eq!(ContentFormat::JSON.to_string(), "json");
+    assert_eq!(ContentFormat::NDJSON.to_string(), "ndjson");
+    assert_eq!("JSON".parse::<ContentFormat>().unwrap(), ContentFormat::JSON);
+    assert_eq!("jsonl".parse::<ContentFormat>().unwrap(), ContentFormat::NDJSON);
+    assert!("ini".parse::<ContentFormat>().unwrap_err().message().contains("'ini'"));
+}
+
+#[test]
+fn test_content_format_is_copy_and_comparable() {
+    let format = ContentFormat::default();
+    let copy = format;
+    assert_eq!(format, copy);
+    assert_ne!(format, ContentFormat::NDJSON);
+}
+
+#[test]
+fn test_content_format_serde() {
+    let formats = BTreeMap::from([("input", ContentFormat::NDJSON), ("output", ContentFormat::JSON)]);
+    let content = serde_json::to_string(&formats).unwrap();
+    assert_eq!(content, r#"{"input":"ndjson","output":"json"}"#);
+    assert_eq!(serde_json::from_str::<BTreeMap<&str, ContentFormat>>(&content).unwrap(), formats);
+    assert!(serde_json::from_str::<ContentFormat>(r#""ini""#).is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_content_format_feature_gated_names() {
+    assert_eq!("yml".parse::<ContentFormat>().unwrap(), ContentFormat::YAML);
+    assert_eq!(ContentFormat::YAML.to_string(), "yaml");
+}
@@ -0,0 +1,79 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{ContentFormat, DynManager, FormatRegistry};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    host: String,
+    port: u16,
+}
+
+fn settings() -> Settings {
+    Settings {
+        host: "localhost".to_string(),
+        port: 8080,
+    }
+}
+
+#[test]
+fn test_dyn_manager_selected_from_format_name() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.conf");
+    let format: ContentFormat = "json".parse().unwrap();
+    let manager: Box<dyn DynManager> = format.manager(&path);
+    assert_eq!(manager.config_path(), path.as_path());
+
+    let written = manager
+        .write_value(&serde_value::to_value(settings()).unwrap(), &BTreeMap::new())
+        .unwrap();
+    assert_eq!(written, path);
+    let read: Settings = manager.read_value(&BTreeMap::new()).unwrap().deserialize_into().unwrap();
+    assert_eq!(read, settings());
+}
+
+#[test]
+fn test_dyn_manager_encode_decode() {
+    let manager = ContentFormat::JSON.manager("memory.json");
+    let bytes = manager
+        .encode_value(&serde_value::to_value(settings()).unwrap(), &BTreeMap::new())
+        .unwrap();
+    let value = manager.decode_value(&bytes, &BTreeMap::new()).unwrap();
+    assert_eq!(value.deserialize_into::<Settings>().unwrap(), settings());
+    assert!(manager.decode_value(b"{", &BTreeMap::new()).is_err());
+}
+
+#[test]
+fn test_dyn_manager_from_registry() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let registry = FormatRegistry::new();
+    let managers: Vec<Box<dyn DynManager>> = vec![
+        registry.manager(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap(),
+        registry.manager(path.to_str().unwrap(), Some("NDJSON"), &BTreeMap::new()).unwrap(),
+    ];
+    managers[0]
+        .write_value(&serde_value::to_value(settings()).unwrap(), &BTreeMap::new())
+        .unwrap();
+    assert!(managers[0].read_value(&BTreeMap::new()).is_ok());
+    assert!(registry.manager(path.to_str().unwrap(), Some("unknown"), &BTreeMap::new()).is_err());
+}
+
+#[test]
+fn test_dyn_manager_streams() {
+    let manager = ContentFormat::JSON.manager("memory.json");
+    let mut buffer = Vec::new();
+    let writer: &mut dyn std::io::Write = &mut buffer;
+    manager
+        .write_value_to(writer, &serde_value::to_value(settings()).unwrap(), &BTreeMap::new())
+        .unwrap();
+    let mut reader: &[u8] = &buffer;
+    let read: Settings = manager
+        .read_value_from(&mut reader, &BTreeMap::new())
+        .unwrap()
+        .deserialize_into()
+        .unwrap();
+    assert_eq!(read, settings());
+    let err = manager.read_value_from(&mut &b"{"[..], &BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["path"], serde_value::Value::String("memory.json".to_string()));
+}
@@ -0,0 +1,71 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use cdumay_config::{EmptyFilePolicy, ReadOptions};
+
+#[derive(Debug, Default, Deserialize, PartialEq)]
+#[serde(default)]
+struct Settings {
+    name: String,
+    workers: u32,
+}
+
+fn empty_map() -> ReadOptions {
+    ReadOptions {
+        empty_file: EmptyFilePolicy::EmptyMap,
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_empty_file_fails_with_dedicated_error() {
+    let dir = tempfile::tempdir().unwrap();
+    for (name, content) in [("zero.json", ""), ("blank.json", " \n\t\n")] {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        let err = cdumay_config::read_config::<Settings>(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap_err();
+        assert_eq!(err.class(), cdumay_config::EmptyFileError::new().class());
+        assert!(err.message().contains("is empty"));
+        assert_eq!(
+            err.details().get("path"),
+            Some(&serde_value::Value::String(path.to_str().unwrap().to_string()))
+        );
+    }
+}
+
+#[test]
+fn test_empty_file_as_empty_map() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("blank.json");
+    std::fs::write(&path, "\n").unwrap();
+
+    let settings: Settings = cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), &empty_map()).unwrap();
+    assert_eq!(settings, Settings::default());
+
+    let map: BTreeMap<String, u32> = cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), &empty_map()).unwrap();
+    assert!(map.is_empty());
+}
+
+#[test]
+fn test_empty_map_still_requires_fields() {
+    #[derive(Debug, Deserialize)]
+    #[allow(dead_code)]
+    struct Strict {
+        name: String,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("blank.json");
+    std::fs::write(&path, "").unwrap();
+    assert!(cdumay_config::read_config_with::<Strict>(path.to_str().unwrap(), None, &BTreeMap::new(), &empty_map()).is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_empty_yaml_file_fails_like_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("blank.yaml");
+    std::fs::write(&path, "   \n").unwrap();
+    let err = cdumay_config::read_config::<Settings>(path.to_str().unwrap(), Some(cdumay_config::ContentFormat::YAML), &BTreeMap::new()).unwrap_err();
+    assert_eq!(err.class(), cdumay_config::EmptyFileError::new().class());
+}
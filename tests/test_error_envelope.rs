@@ -0,0 +1,50 @@
+use std::collections::BTreeMap;
+
+#[test]
+fn test_error_to_json_from_read_failure() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"port": "eighty"}"#).unwrap();
+
+    #[allow(dead_code)]
+    #[derive(Debug, serde::Deserialize)]
+    struct Config {
+        port: u16,
+    }
+
+    let err = cdumay_config::read_config::<Config>(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap_err();
+    let json = cdumay_config::error_to_json(&err);
+    assert_eq!(json["code"], 400);
+    assert_eq!(json["kind"], "JsonData");
+    assert_eq!(json["class"], err.class());
+    assert_eq!(json["message"], err.message());
+    assert_eq!(json["details"]["key_path"], "port");
+    assert_eq!(json["details"]["line"], 1);
+    assert_eq!(json["details"]["path"], path.to_str().unwrap());
+}
+
+#[test]
+fn test_error_envelope_details_are_plain_json() {
+    let details = BTreeMap::from([
+        ("raw".to_string(), serde_value::Value::Bytes(b"hello".to_vec())),
+        (
+            "ports".to_string(),
+            serde_value::Value::Map(BTreeMap::from([(serde_value::Value::U16(80), serde_value::Value::Option(None))])),
+        ),
+        ("retries".to_string(), serde_value::Value::Newtype(Box::new(serde_value::Value::U8(3)))),
+    ]);
+    let err = cdumay_core::Error::new(500, "Server::IoError::FileRead".to_string(), "Cannot read".to_string(), details);
+    let envelope = cdumay_config::ErrorEnvelope::from(&err);
+    assert_eq!(envelope.kind, "IoError");
+    assert_eq!(
+        envelope.to_json(),
+        serde_json::json!({
+            "code": 500,
+            "kind": "IoError",
+            "class": "Server::IoError::FileRead",
+            "message": "Cannot read",
+            "details": {"raw": "aGVsbG8=", "ports": {"80": null}, "retries": 3},
+        })
+    );
+    assert_eq!(serde_json::to_value(&envelope).unwrap(), envelope.to_json());
+}
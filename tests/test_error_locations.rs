@@ -0,0 +1,65 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::Manager;
+
+fn location(err: &cdumay_core::Error) -> (serde_value::Value, serde_value::Value) {
+    let details = err.details();
+    (details["line"].clone(), details["column"].clone())
+}
+
+fn at(line: u64, column: u64) -> (serde_value::Value, serde_value::Value) {
+    (serde_value::Value::U64(line), serde_value::Value::U64(column))
+}
+
+#[test]
+fn test_error_locations_json() {
+    let err = cdumay_config::JsonManager::read_str::<serde_value::Value>("{\n  \"a\": 1,\n  \"b\" 2\n}", &BTreeMap::new()).unwrap_err();
+    assert_eq!(location(&err), at(3, 7));
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, "{\n  \"port\": \"http\"\n}").unwrap();
+    let err = cdumay_config::read_config::<BTreeMap<String, u16>>(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap_err();
+    assert_eq!(location(&err), at(2, 16));
+    assert!(err.details().contains_key("path"));
+}
+
+#[test]
+fn test_error_locations_ndjson() {
+    let err = cdumay_config::NdjsonManager::read_str::<Vec<serde_value::Value>>("{\"a\": 1}\n{\"a\": }\n", &BTreeMap::new()).unwrap_err();
+    assert_eq!(location(&err), at(2, 7));
+}
+
+#[test]
+fn test_error_locations_not_set_without_position() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"port": "http"}"#).unwrap();
+    let options = cdumay_config::ReadOptions {
+        unknown_keys: cdumay_config::UnknownKeyPolicy::Deny,
+        ..Default::default()
+    };
+    let err = cdumay_config::read_config_with::<BTreeMap<String, u16>>(path.to_str().unwrap(), None, &BTreeMap::new(), &options).unwrap_err();
+    assert!(!err.details().contains_key("line"));
+    assert!(!err.details().contains_key("column"));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_error_locations_toml() {
+    let err = cdumay_config::TomlManager::read_str::<serde_value::Value>("[server]\nhost = \"é\"\nport = = 1\n", &BTreeMap::new()).unwrap_err();
+    assert_eq!(location(&err), at(3, 8));
+    let err = cdumay_config::TomlManager::read_str::<BTreeMap<String, u16>>("a = 1\nb = \"x\"\n", &BTreeMap::new()).unwrap_err();
+    assert_eq!(location(&err), at(2, 5));
+    let err = cdumay_config::TomlDocument::parse("config.toml", "a = 1\nb = [\n", &BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["line"], serde_value::Value::U64(3));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_error_locations_yaml() {
+    let err = cdumay_config::YamlManager::read_str::<serde_value::Value>("a: 1\nb: [1, 2\n", &BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["line"], serde_value::Value::U64(3));
+    let err = cdumay_config::YamlManager::read_str::<BTreeMap<String, u16>>("a: 1\nb: x\n", &BTreeMap::new()).unwrap_err();
+    assert_eq!(location(&err), at(2, 4));
+}
@@ -0,0 +1,122 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::Manager;
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Listener {
+    port: u16,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Server {
+    listeners: Vec<Listener>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Config {
+    server: Server,
+}
+
+fn assert_key_path(err: cdumay_core::Error, key: &str) {
+    assert!(err.message().starts_with(&format!("{}: ", key)), "{}", err.message());
+    assert_eq!(err.details()["key_path"], serde_value::Value::String(key.to_string()));
+}
+
+#[test]
+fn test_error_paths_json() {
+    let content = r#"{"server": {"listeners": [{"port": 80}, {"port": 443}, {"port": "https"}]}}"#;
+    let err = cdumay_config::JsonManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.listeners[2].port");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, content).unwrap();
+    let err = cdumay_config::read_config::<Config>(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("invalid type"), "{}", err.message());
+    assert!(err.details().contains_key("path"));
+    assert_key_path(err, "server.listeners[2].port");
+}
+
+#[test]
+fn test_error_paths_json_missing_field() {
+    let err = cdumay_config::JsonManager::read_str::<Config>(r#"{"server": {"listeners": [{}]}}"#, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("missing field `port`"), "{}", err.message());
+    assert_key_path(err, "server.listeners[0]");
+}
+
+#[test]
+fn test_error_paths_syntax_errors() {
+    let err = cdumay_config::JsonManager::read_str::<Config>(r#"{"server": {"listeners": [{"port": "#, &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.listeners[0].port");
+    let err = cdumay_config::JsonManager::read_str::<Config>("{", &BTreeMap::new()).unwrap_err();
+    assert!(!err.details().contains_key("key_path"));
+    let err = cdumay_config::JsonManager::read_str::<Config>(r#"{"server": {"listeners": []}} trailing"#, &BTreeMap::new()).unwrap_err();
+    assert!(!err.details().contains_key("key_path"));
+}
+
+#[test]
+fn test_error_paths_value_tree() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"server": {"listeners": [{"port": -1}]}}"#).unwrap();
+    for unknown_keys in [cdumay_config::UnknownKeyPolicy::Ignore, cdumay_config::UnknownKeyPolicy::Deny] {
+        let options = cdumay_config::ReadOptions {
+            renames: Some(cdumay_config::KeyRenames::new().rename("service", "server")),
+            unknown_keys,
+            ..Default::default()
+        };
+        let err = cdumay_config::read_config_with::<Config>(path.to_str().unwrap(), None, &BTreeMap::new(), &options).unwrap_err();
+        assert_key_path(err, "server.listeners[0].port");
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_error_paths_toml() {
+    let content = "[[server.listeners]]\nport = 80\n\n[[server.listeners]]\nport = true\n";
+    let err = cdumay_config::TomlManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.listeners[1].port");
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_error_paths_yaml() {
+    let content = "server:\n  listeners:\n    - port: 80\n    - port: [1]\n";
+    let err = cdumay_config::YamlManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.listeners[1].port");
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_error_paths_ron() {
+    let content = r#"(server: (listeners: [(port: 80), (port: "x")]))"#;
+    let err = cdumay_config::RonManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.listeners[1].port");
+}
+
+#[cfg(feature = "kdl")]
+#[test]
+fn test_error_paths_kdl() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Service {
+        server: Listener,
+    }
+    let err = cdumay_config::KdlManager::read_str::<Service>("server {\n    port \"x\"\n}\n", &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.port");
+}
+
+#[cfg(feature = "plist")]
+#[test]
+fn test_error_paths_plist() {
+    let content = r#"<?xml version="1.0" encoding="UTF-8"?>
+<plist version="1.0"><dict><key>server</key><dict><key>listeners</key><array>
+<dict><key>port</key><string>x</string></dict>
+</array></dict></dict></plist>"#;
+    let err = cdumay_config::PlistManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_key_path(err, "server.listeners[0].port");
+}
@@ -0,0 +1,102 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{ConfigurationFileError, JsonManager, Manager, VaultSecretError};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    port: u16,
+}
+
+/// Generic code over managers only deals with the crate error type.
+fn load<M: Manager>(content: &str) -> cdumay_config::Result<Settings> {
+    M::read_str(content, &BTreeMap::new())
+}
+
+#[test]
+fn test_errors_single_result_type() {
+    assert_eq!(load::<JsonManager>("{\"port\": 80}").unwrap(), Settings { port: 80 });
+    let err: cdumay_config::Error = load::<JsonManager>("{").unwrap_err();
+    let core: cdumay_core::Result<Settings> = Err(err);
+    assert!(core.is_err());
+    #[cfg(feature = "toml")]
+    assert_eq!(load::<cdumay_config::TomlManager>("port = 80").unwrap(), Settings { port: 80 });
+    #[cfg(feature = "yaml")]
+    assert_eq!(load::<cdumay_config::YamlManager>("port: 80").unwrap(), Settings { port: 80 });
+}
+
+#[test]
+fn test_errors_conversions() {
+    let err: cdumay_config::Error = ConfigurationFileError::new().with_message("invalid".to_string()).into();
+    assert_eq!(err.message(), "invalid");
+    let err: cdumay_config::Error = VaultSecretError::new().with_message("missing".to_string()).into();
+    assert_eq!(err.message(), "missing");
+    let io: std::io::Error = VaultSecretError::new().with_message("missing".to_string()).into();
+    assert_eq!(io.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn test_errors_detail_limit() {
+    assert_eq!(cdumay_config::error_detail_limit(), cdumay_config::DEFAULT_ERROR_DETAIL_LIMIT);
+    let context = BTreeMap::from([
+        ("payload".to_string(), serde_value::Value::String("é".repeat(20))),
+        ("short".to_string(), serde_value::Value::String("kept".to_string())),
+        (
+            "nested".to_string(),
+            serde_value::Value::Seq(vec![serde_value::Value::String("x".repeat(100))]),
+        ),
+    ]);
+    cdumay_config::set_error_detail_limit(15);
+    let err = JsonManager::read_str::<Settings>("{", &context).unwrap_err();
+    let path = std::env::temp_dir().join("missing").join("settings.json");
+    let io_err = cdumay_config::read_config::<Settings>(path.to_str().unwrap(), None, &context).unwrap_err();
+    cdumay_config::set_error_detail_limit(cdumay_config::DEFAULT_ERROR_DETAIL_LIMIT);
+
+    // Strings are cut on a char boundary.
+    assert_eq!(
+        err.details()["payload"],
+        serde_value::Value::String(format!("{}… (26 bytes truncated)", "é".repeat(7)))
+    );
+    assert_eq!(err.details()["short"], serde_value::Value::String("kept".to_string()));
+    assert_eq!(
+        err.details()["nested"],
+        serde_value::Value::Seq(vec![serde_value::Value::String(format!("{}… (85 bytes truncated)", "x".repeat(15)))])
+    );
+    assert_eq!(io_err.details()["payload"], err.details()["payload"]);
+}
+
+#[test]
+fn test_errors_redaction() {
+    let context = BTreeMap::from([
+        ("db_password".to_string(), serde_value::Value::String("s3cr3t".to_string())),
+        (
+            "auth".to_string(),
+            serde_value::Value::Map(BTreeMap::from([
+                (serde_value::Value::String("Token".to_string()), serde_value::Value::String("abc".to_string())),
+                (serde_value::Value::String("user".to_string()), serde_value::Value::String("john".to_string())),
+            ])),
+        ),
+        ("dsn".to_string(), serde_value::Value::String("postgres://john:s3cr3t@db".to_string())),
+    ]);
+    let err = JsonManager::read_str::<Settings>("{", &context).unwrap_err();
+    assert_eq!(err.details()["db_password"], serde_value::Value::String("***".to_string()));
+    assert_eq!(
+        err.details()["auth"],
+        serde_value::Value::Map(BTreeMap::from([
+            (serde_value::Value::String("Token".to_string()), serde_value::Value::String("***".to_string())),
+            (serde_value::Value::String("user".to_string()), serde_value::Value::String("john".to_string())),
+        ]))
+    );
+    assert_eq!(err.details()["dsn"], context["dsn"]);
+
+    cdumay_config::set_error_redaction(Some(cdumay_config::Redaction::default().with_key("dsn").with_placeholder("<hidden>")));
+    let custom = JsonManager::read_str::<Settings>("{", &context).unwrap_err();
+    cdumay_config::set_error_redaction(None);
+    let disabled = JsonManager::read_str::<Settings>("{", &context).unwrap_err();
+    cdumay_config::set_error_redaction(Some(cdumay_config::Redaction::default().with_placeholder("***")));
+
+    assert_eq!(custom.details()["dsn"], serde_value::Value::String("<hidden>".to_string()));
+    assert_eq!(custom.details()["db_password"], serde_value::Value::String("<hidden>".to_string()));
+    assert_eq!(disabled.details()["db_password"], context["db_password"]);
+    assert_eq!(cdumay_config::error_redaction(), Some(cdumay_config::Redaction::default().with_placeholder("***")));
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "etcd")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::EtcdSource;
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Database {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    db: Database,
+    features: Vec<String>,
+    debug: String,
+}
+
+/// Serves the given responses, one per connection, returning the address of the server and
+/// the requests received (head and body).
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                if let Some(index) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break index + 4;
+                }
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_ascii_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            while request.len() < head_end + length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (address, handle)
+}
+
+const KVS: &str = r#"{"header": {"revision": "12"}, "kvs": [{"key": "L2FwcC9kYi9ob3N0", "value": "bG9jYWxob3N0", "mod_revision": "3"}, {"key": "L2FwcC9kYi9wb3J0", "value": "NTQzMg=="}, {"key": "L2FwcC9mZWF0dXJlcy8=", "value": "WyJhIiwgImIiXQ=="}, {"key": "L2FwcC9kZWJ1Zw=="}], "count": "4"}"#;
+
+#[test]
+fn test_etcd_read_config() {
+    let (address, handle) = serve(vec![("200 OK", KVS)]);
+    let config: Config = EtcdSource::new(&address, "/app/").read_config(&BTreeMap::new()).unwrap();
+    assert_eq!(
+        config,
+        Config {
+            db: Database {
+                host: "localhost".to_string(),
+                port: 5432
+            },
+            features: vec!["a".to_string(), "b".to_string()],
+            debug: String::new(),
+        }
+    );
+    let requests = handle.join().unwrap();
+    assert!(requests[0].starts_with("POST /v3/kv/range "));
+    // `/app/` to `/app0`.
+    assert!(requests[0].ends_with(r#"{"key":"L2FwcC8=","range_end":"L2FwcDA="}"#));
+}
+
+#[test]
+fn test_etcd_authentication() {
+    let (address, handle) = serve(vec![("200 OK", r#"{"token": "abc.def"}"#), ("200 OK", r#"{"kvs": []}"#)]);
+    let source = EtcdSource::new(&address, "/empty/").with_credentials("root", "pass");
+    assert!(!format!("{:?}", source).contains("pass"));
+    assert_eq!(source.read_value(&BTreeMap::new()).unwrap(), serde_value::Value::Map(BTreeMap::new()));
+    let requests = handle.join().unwrap();
+    assert!(requests[0].starts_with("POST /v3/auth/authenticate "));
+    assert!(requests[0].ends_with(r#"{"name":"root","password":"pass"}"#));
+    assert!(requests[1].to_ascii_lowercase().contains("authorization: abc.def"));
+}
+
+#[test]
+fn test_etcd_errors() {
+    let (address, handle) = serve(vec![
+        (
+            "200 OK",
+            r#"{"kvs": [{"key": "L2FwcC9kYg==", "value": "eA=="}, {"key": "L2FwcC9kYi9wb3J0", "value": "NTQzMg=="}]}"#,
+        ),
+        ("401 Unauthorized", r#"{"error": "etcdserver: user name is empty"}"#),
+    ]);
+    let err = EtcdSource::new(&address, "/app/").read_value(&BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["key"], serde_value::Value::String("/app/db/port".to_string()));
+    let err = EtcdSource::new(&address, "/app/").read_config::<Config>(&BTreeMap::new()).unwrap_err();
+    handle.join().unwrap();
+    assert_eq!(err.details()["status"], serde_value::Value::U16(401));
+    assert_eq!(err.details()["url"], serde_value::Value::String(format!("{}/v3/kv/range", address)));
+}
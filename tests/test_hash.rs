@@ -0,0 +1,74 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+#[derive(Serialize)]
+struct Ordered {
+    name: String,
+    port: u16,
+    tags: Vec<&'static str>,
+}
+
+#[derive(Serialize)]
+struct Reversed {
+    tags: Vec<&'static str>,
+    port: i64,
+    name: String,
+}
+
+#[test]
+fn test_config_hash_ignores_key_order_and_integer_types() {
+    let context = BTreeMap::new();
+    let ordered = Ordered {
+        name: "app".to_string(),
+        port: 8080,
+        tags: vec!["a", "b"],
+    };
+    let reversed = Reversed {
+        tags: vec!["a", "b"],
+        port: 8080,
+        name: "app".to_string(),
+    };
+    let hash = cdumay_config::config_hash(&ordered, &context).unwrap();
+    assert_eq!(hash.len(), 64);
+    assert_eq!(hash, cdumay_config::config_hash(&reversed, &context).unwrap());
+
+    let reordered_tags = Ordered {
+        tags: vec!["b", "a"],
+        ..ordered
+    };
+    assert_ne!(hash, cdumay_config::config_hash(&reordered_tags, &context).unwrap());
+}
+
+#[test]
+fn test_config_file_hash_ignores_formatting() {
+    let context = BTreeMap::new();
+    let dir = tempfile::tempdir().unwrap();
+    let compact = dir.path().join("compact.json");
+    let pretty = dir.path().join("pretty.json");
+    std::fs::write(&compact, r#"{"b":{"y":1,"x":[true,null]},"a":"v"}"#).unwrap();
+    std::fs::write(
+        &pretty,
+        "{\n  \"a\": \"v\",\n  \"b\": {\n    \"x\": [true, null],\n    \"y\": 1\n  }\n}\n",
+    )
+    .unwrap();
+
+    let hash = cdumay_config::config_file_hash(compact.to_str().unwrap(), None, &context).unwrap();
+    assert_eq!(hash, cdumay_config::config_file_hash(pretty.to_str().unwrap(), None, &context).unwrap());
+    let value: serde_json::Value = serde_json::from_str(r#"{"a":"v","b":{"x":[true,null],"y":1}}"#).unwrap();
+    assert_eq!(hash, cdumay_config::config_hash(&value, &context).unwrap());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_config_file_hash_is_format_independent() {
+    let context = BTreeMap::new();
+    let dir = tempfile::tempdir().unwrap();
+    let json = dir.path().join("app.json");
+    let yaml = dir.path().join("app.yaml");
+    std::fs::write(&json, r#"{"name": "app", "port": 8080}"#).unwrap();
+    std::fs::write(&yaml, "port: 8080\nname: app\n").unwrap();
+    assert_eq!(
+        cdumay_config::config_file_hash(json.to_str().unwrap(), None, &context).unwrap(),
+        cdumay_config::config_file_hash(yaml.to_str().unwrap(), Some(cdumay_config::ContentFormat::YAML), &context).unwrap()
+    );
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{HeaderBanner, WriteOptions};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    name: String,
+    port: u16,
+}
+
+fn settings() -> Settings {
+    Settings {
+        name: "api".to_string(),
+        port: 8080,
+    }
+}
+
+fn with_header(banner: HeaderBanner) -> WriteOptions {
+    WriteOptions {
+        header: Some(banner),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_banner_render() {
+    let banner = HeaderBanner::new("{file} ({hash})\n\nGenerated at {timestamp}");
+    let text = banner.render("abc", std::path::Path::new("/etc/app.toml"));
+    let (head, timestamp) = text.split_once("Generated at ").unwrap();
+    assert_eq!(head, "app.toml (abc)\n\n");
+    assert_eq!(timestamp.len(), "2024-01-01T00:00:00Z".len());
+    assert!(timestamp.ends_with('Z') && timestamp.as_bytes()[10] == b'T');
+}
+
+#[test]
+fn test_banner_skipped_for_json() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    cdumay_config::write_config_with(
+        path.to_str().unwrap(),
+        None,
+        settings(),
+        &BTreeMap::new(),
+        &with_header(HeaderBanner::default()),
+    )
+    .unwrap();
+    let read: Settings = cdumay_config::read_config(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_banner_toml_stable() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.toml");
+    let options = with_header(HeaderBanner::generated_by("confgen"));
+    let write = || {
+        cdumay_config::write_config_with(
+            path.to_str().unwrap(),
+            Some(cdumay_config::ContentFormat::TOML),
+            settings(),
+            &BTreeMap::new(),
+            &options,
+        )
+        .unwrap();
+        std::fs::read_to_string(&path).unwrap()
+    };
+    let first = write();
+    let hash = cdumay_config::config_hash(&settings(), &BTreeMap::new()).unwrap();
+    assert_eq!(
+        first,
+        format!(
+            "# This file is generated by confgen: DO NOT EDIT, changes will be overwritten.\n# Content hash: {}\n\nname = \"api\"\nport = 8080\n",
+            hash
+        )
+    );
+    assert_eq!(write(), first);
+    let read: Settings = cdumay_config::read_config(path.to_str().unwrap(), Some(cdumay_config::ContentFormat::TOML), &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_banner_yaml() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.yaml");
+    let options = with_header(HeaderBanner::new("Managed by ops\n\nDO NOT EDIT"));
+    cdumay_config::write_config_with(
+        path.to_str().unwrap(),
+        Some(cdumay_config::ContentFormat::YAML),
+        settings(),
+        &BTreeMap::new(),
+        &options,
+    )
+    .unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# Managed by ops\n#\n# DO NOT EDIT\n\n"));
+    let read: Settings = cdumay_config::read_config(path.to_str().unwrap(), Some(cdumay_config::ContentFormat::YAML), &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+}
@@ -0,0 +1,129 @@
+#![cfg(feature = "http")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+/// Serves a single HTTP response on a local port, returning the base URL and the request
+/// received.
+fn serve(status: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    serve_with_headers(status, "", body)
+}
+
+/// Serves a single HTTP response with extra header lines (each ending with `\r\n`) like
+/// [`serve`].
+fn serve_with_headers(status: &str, headers: &str, body: &str) -> (String, std::thread::JoinHandle<String>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let response = format!(
+        "HTTP/1.1 {}\r\n{}Content-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        headers,
+        body.len(),
+        body
+    );
+    let handle = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let mut request = Vec::new();
+        let mut buffer = [0; 1024];
+        while !request.ends_with(b"\r\n\r\n") {
+            let read = stream.read(&mut buffer).unwrap();
+            request.extend_from_slice(&buffer[..read]);
+        }
+        stream.write_all(response.as_bytes()).unwrap();
+        String::from_utf8(request).unwrap()
+    });
+    (url, handle)
+}
+
+#[test]
+fn test_http_read_config() {
+    let (url, handle) = serve("200 OK", r#"{"host": "localhost", "port": 8080}"#);
+    let server: Server = cdumay_config::read_config(&format!("{}/app.json", url), None, &BTreeMap::new()).unwrap();
+    assert_eq!(
+        server,
+        Server {
+            host: "localhost".to_string(),
+            port: 8080
+        }
+    );
+    assert!(handle.join().unwrap().starts_with("GET /app.json "));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_http_format_inference() {
+    let expected = Server {
+        host: "localhost".to_string(),
+        port: 8080,
+    };
+    let (url, handle) = serve_with_headers(
+        "200 OK",
+        "Content-Type: application/x-yaml; charset=utf-8\r\n",
+        "host: localhost\nport: 8080\n",
+    );
+    let server: Server = cdumay_config::read_config(&format!("{}/config", url), None, &BTreeMap::new()).unwrap();
+    assert_eq!(server, expected);
+    handle.join().unwrap();
+
+    let (url, handle) = serve_with_headers("200 OK", "Content-Type: text/plain\r\n", "host: localhost\nport: 8080\n");
+    let server: Server = cdumay_config::read_config(&format!("{}/app.yaml?ref=main", url), None, &BTreeMap::new()).unwrap();
+    assert_eq!(server, expected);
+    handle.join().unwrap();
+
+    let (url, handle) = serve("200 OK", "host: localhost\nport: 8080\n");
+    let source = cdumay_config::HttpSource::new(&format!("{}/app.yml", url));
+    assert_eq!(
+        source.read_config_with::<Server>(None, &BTreeMap::new(), &Default::default()).unwrap(),
+        expected
+    );
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_http_source_headers_and_options() {
+    let (url, handle) = serve("200 OK", r#"{"host": "localhost", "port": 8080, "debug": true}"#);
+    let source = cdumay_config::HttpSource::new(&format!("{}/app.json", url))
+        .with_header("Authorization", "Bearer abc")
+        .with_timeout(std::time::Duration::from_secs(5));
+    let options = cdumay_config::ReadOptions {
+        unknown_keys: cdumay_config::UnknownKeyPolicy::Deny,
+        ..Default::default()
+    };
+    let err = source.read_config_with::<Server>(None, &BTreeMap::new(), &options).unwrap_err();
+    assert!(err.message().contains("debug"));
+    assert_eq!(err.details()["path"], serde_value::Value::String(source.url().to_string()));
+    assert!(handle.join().unwrap().to_ascii_lowercase().contains("authorization: bearer abc"));
+}
+
+#[test]
+fn test_http_error_details() {
+    let (url, handle) = serve("404 Not Found", "missing");
+    let url = format!("{}/missing.json", url);
+    let err = cdumay_config::read_config::<Server>(&url, None, &BTreeMap::new()).unwrap_err();
+    handle.join().unwrap();
+    assert!(err.message().contains("404"));
+    assert_eq!(err.details()["url"], serde_value::Value::String(url));
+    assert_eq!(err.details()["status"], serde_value::Value::U16(404));
+
+    let (url, handle) = serve("200 OK", "{not json");
+    let url = format!("{}/broken.json", url);
+    let err = cdumay_config::read_config::<Server>(&url, None, &BTreeMap::new()).unwrap_err();
+    handle.join().unwrap();
+    assert_eq!(err.details()["url"], serde_value::Value::String(url));
+}
+
+#[test]
+fn test_http_is_url() {
+    assert!(cdumay_config::HttpSource::is_url("https://config.internal/app.json"));
+    assert!(cdumay_config::HttpSource::is_url("HTTP://config.internal/app.json"));
+    assert!(!cdumay_config::HttpSource::is_url("/etc/app.json"));
+    assert!(!cdumay_config::HttpSource::is_url("http"));
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+
+fn options() -> cdumay_config::ReadOptions {
+    cdumay_config::ReadOptions {
+        includes: true,
+        ..Default::default()
+    }
+}
+
+fn read(path: &std::path::Path, options: &cdumay_config::ReadOptions) -> cdumay_core::Result<serde_json::Value> {
+    cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), options)
+}
+
+#[test]
+fn test_include_overrides() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("common.json"),
+        r#"{"host": "localhost", "port": 5432, "pool": {"size": 5, "idle": 30}}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("logging.json"), r#"{"level": "info", "format": "text"}"#).unwrap();
+    std::fs::write(dir.path().join("logging.prod.json"), r#"{"level": "warn"}"#).unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(
+        &path,
+        r#"{
+            "database": {"$include": "common.json", "port": 5433, "pool": {"size": 20}},
+            "logging": {"$include": ["logging.json", "logging.prod.json"]}
+        }"#,
+    )
+    .unwrap();
+
+    let config = read(&path, &options()).unwrap();
+    assert_eq!(
+        config,
+        serde_json::json!({
+            "database": {"host": "localhost", "port": 5433, "pool": {"size": 20, "idle": 30}},
+            "logging": {"level": "warn", "format": "text"}
+        })
+    );
+
+    let config = read(&path, &Default::default()).unwrap();
+    assert_eq!(config["database"]["$include"], "common.json");
+}
+
+#[test]
+fn test_include_relative() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("conf/shared")).unwrap();
+    std::fs::write(
+        dir.path().join("conf/database.json"),
+        r#"{"$include": "shared/credentials.json", "name": "app"}"#,
+    )
+    .unwrap();
+    std::fs::write(dir.path().join("conf/shared/credentials.json"), r#"{"user": "admin"}"#).unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, r#"{"$include": "conf/database.json", "debug": true}"#).unwrap();
+
+    let config = read(&path, &options()).unwrap();
+    assert_eq!(config, serde_json::json!({"user": "admin", "name": "app", "debug": true}));
+}
+
+#[test]
+fn test_include_errors() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("a.json");
+    let second = dir.path().join("b.json");
+    std::fs::write(&first, r#"{"nested": {"$include": "b.json"}}"#).unwrap();
+    std::fs::write(&second, r#"{"$include": "a.json"}"#).unwrap();
+
+    let err = read(&first, &options()).unwrap_err();
+    let (first, second) = (std::fs::canonicalize(&first).unwrap(), std::fs::canonicalize(&second).unwrap());
+    assert_eq!(
+        err.message(),
+        format!(
+            "Include cycle in configuration files: {} -> {} -> {}",
+            first.display(),
+            second.display(),
+            first.display()
+        )
+    );
+    assert_eq!(
+        err.details()["includes"],
+        serde_value::Value::Seq(
+            [&first, &second, &first]
+                .iter()
+                .map(|path| serde_value::Value::String(path.display().to_string()))
+                .collect()
+        )
+    );
+
+    let path = dir.path().join("invalid.json");
+    std::fs::write(&path, r#"{"$include": 42}"#).unwrap();
+    let err = read(&path, &options()).unwrap_err();
+    assert!(err.message().starts_with("Invalid include directive"));
+
+    std::fs::write(&path, r#"{"$include": "missing.json"}"#).unwrap();
+    assert!(read(&path, &options()).is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_include_yaml_tag() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("database.yaml"), "host: localhost\nport: 5432\n").unwrap();
+    std::fs::write(dir.path().join("features.json"), r#"["search", "export"]"#).unwrap();
+    let path = dir.path().join("app.yaml");
+    std::fs::write(&path, "name: app\ndatabase: !include database.yaml\nfeatures: !include features.json\n").unwrap();
+
+    let config: serde_json::Value = cdumay_config::read_config_with(
+        path.to_str().unwrap(),
+        Some(cdumay_config::ContentFormat::YAML),
+        &BTreeMap::new(),
+        &options(),
+    )
+    .unwrap();
+    assert_eq!(
+        config,
+        serde_json::json!({
+            "name": "app",
+            "database": {"host": "localhost", "port": 5432},
+            "features": ["search", "export"]
+        })
+    );
+}
@@ -0,0 +1,81 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+use cdumay_config::open_indexed;
+use serde_value::Value;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Rule {
+    name: String,
+    value: i32,
+}
+
+fn write_temp(suffix: &str, content: &str) -> tempfile::NamedTempFile {
+    let mut file = tempfile::Builder::new().suffix(suffix).tempfile().expect("temp file");
+    file.write_all(content.as_bytes()).unwrap();
+    file
+}
+
+#[test]
+fn test_indexed_json_array() {
+    let file = write_temp(
+        ".json",
+        r#"[
+            {"name": "a, [b]", "value": 1},
+            {"name": "c\"]", "value": 2} ,
+            {"name": "d", "value": 3}
+        ]"#,
+    );
+    let context = BTreeMap::new();
+    let index = open_indexed(file.path().to_str().unwrap(), &context).unwrap();
+    assert_eq!(index.len(), 3);
+
+    let rule: Rule = index.get(1, &context).unwrap();
+    assert_eq!(rule, Rule { name: "c\"]".to_string(), value: 2 });
+    let rule: Rule = index.get(0, &context).unwrap();
+    assert_eq!(rule.name, "a, [b]");
+}
+
+#[test]
+fn test_indexed_ndjson_lines() {
+    let file = write_temp(".ndjson", "{\"name\": \"a\", \"value\": 1}\n\n[1, 2]\r\n{\"name\": \"c\", \"value\": 3}");
+    let context = BTreeMap::new();
+    let index = open_indexed(file.path().to_str().unwrap(), &context).unwrap();
+    assert_eq!(index.len(), 3);
+
+    let values: Vec<i32> = index.get(1, &context).unwrap();
+    assert_eq!(values, vec![1, 2]);
+    let rule: Rule = index.get(2, &context).unwrap();
+    assert_eq!(rule.value, 3);
+}
+
+#[test]
+fn test_indexed_out_of_range() {
+    let file = write_temp(".json", "[]");
+    let context = BTreeMap::new();
+    let index = open_indexed(file.path().to_str().unwrap(), &context).unwrap();
+    assert!(index.is_empty());
+
+    let result: cdumay_core::Result<Value> = index.get(0, &context);
+    assert!(format!("{}", result.unwrap_err()).contains("out of range"));
+}
+
+#[test]
+fn test_indexed_max_entry_size() {
+    let file = write_temp(".json", r#"[{"name": "a", "value": 1}, 2]"#);
+    let context = BTreeMap::new();
+    let index = open_indexed(file.path().to_str().unwrap(), &context).unwrap().with_max_entry_size(4);
+
+    let small: i32 = index.get(1, &context).unwrap();
+    assert_eq!(small, 2);
+    let result: cdumay_core::Result<Rule> = index.get(0, &context);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_indexed_unterminated_array() {
+    let file = write_temp(".json", r#"[{"name": "a", "value": 1},"#);
+    let result = open_indexed(file.path().to_str().unwrap(), &BTreeMap::new());
+    assert!(result.is_err());
+}
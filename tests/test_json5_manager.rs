@@ -0,0 +1,65 @@
+#![cfg(feature = "json5")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use cdumay_config::{ContentFormat, Json5Manager, Manager};
+use serde_value::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TestConfig {
+    name: String,
+    values: Vec<i32>,
+}
+
+fn default_context() -> BTreeMap<String, Value> {
+    BTreeMap::new()
+}
+
+#[test]
+fn test_json5_manager_new_and_path() {
+    let manager = Json5Manager::new("app.json5");
+    assert_eq!(manager.file_path(), std::path::Path::new("app.json5"));
+}
+
+#[test]
+fn test_json5_manager_read_str_with_comments_and_trailing_commas() {
+    let content = r#"{
+        // The application name
+        name: 'example',
+        /* several values */
+        values: [1, 2, 3,],
+    }"#;
+    let result: TestConfig = Json5Manager::read_str(content, &default_context()).unwrap();
+    assert_eq!(result.name, "example");
+    assert_eq!(result.values, vec![1, 2, 3]);
+}
+
+#[test]
+fn test_json5_manager_read_str_failure() {
+    let result: cdumay_core::Result<TestConfig> = Json5Manager::read_str("{ name: ", &default_context());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_json5_manager_writes_strict_json() {
+    let data = TestConfig {
+        name: "strict".to_string(),
+        values: vec![4],
+    };
+    let manager = Json5Manager::new("dummy.json5");
+    let mut buffer = Cursor::new(Vec::new());
+    manager.write(&mut buffer, &data, &default_context()).unwrap();
+
+    let written: TestConfig = serde_json::from_slice(buffer.get_ref()).unwrap();
+    assert_eq!(written, data);
+}
+
+#[test]
+fn test_json5_read_config() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), "// generated\n{name: \"file\", values: [],}").unwrap();
+    let result: TestConfig = cdumay_config::read_config(file.path().to_str().unwrap(), Some(ContentFormat::JSON5), &default_context()).unwrap();
+    assert_eq!(result.name, "file");
+}
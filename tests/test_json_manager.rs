@@ -15,12 +15,24 @@ fn default_context() -> BTreeMap<String, Value> {
     BTreeMap::new()
 }
 
+// Callers passing a `String` and reading the deprecated `path()` keep compiling.
 #[test]
+#[allow(deprecated, clippy::unnecessary_to_owned)]
 fn test_json_manager_new_and_path() {
     let manager = JsonManager::new("test.json".to_string());
     assert_eq!(manager.path(), "test.json");
 }
 
+#[test]
+fn test_json_manager_new_from_path() {
+    let manager = JsonManager::new(std::path::Path::new("test.json"));
+    assert_eq!(manager.file_path(), std::path::Path::new("test.json"));
+
+    let path = std::path::PathBuf::from("dir").join("test.json");
+    let manager = JsonManager::new(&path);
+    assert_eq!(manager.file_path(), path.as_path());
+}
+
 #[test]
 fn test_json_manager_read_str_success() {
     let json = r#"{ "name": "example", "value": 42 }"#;
@@ -43,7 +55,7 @@ fn test_json_manager_read_success() {
     let json = r#"{ "name": "reader_test", "value": 10 }"#;
     let reader = Cursor::new(json);
     let context = default_context();
-    let manager = JsonManager::new("dummy.json".to_string());
+    let manager = JsonManager::new("dummy.json");
 
     let result: TestConfig = manager.read(reader, &context).unwrap();
     assert_eq!(result.name, "reader_test");
@@ -55,7 +67,7 @@ fn test_json_manager_read_failure() {
     let json = r#"{ "name": "bad", "value": "oops" }"#;
     let reader = Cursor::new(json);
     let context = default_context();
-    let manager = JsonManager::new("dummy.json".to_string());
+    let manager = JsonManager::new("dummy.json");
 
     let result: cdumay_core::Result<TestConfig> = manager.read(reader, &context);
     assert!(result.is_err());
@@ -69,7 +81,7 @@ fn test_json_manager_write_success() {
     };
 
     let context = default_context();
-    let manager = JsonManager::new("write.json".to_string());
+    let manager = JsonManager::new("write.json");
     let mut buffer = Cursor::new(Vec::new());
 
     manager.write(&mut buffer, &data, &context).unwrap();
@@ -87,7 +99,7 @@ fn test_json_manager_write_failure() {
 
     impl Write for BrokenWriter {
         fn write(&mut self, _buf: &[u8]) -> io::Result<usize> {
-            Err(io::Error::new(io::ErrorKind::Other, "write failed"))
+            Err(io::Error::other("write failed"))
         }
 
         fn flush(&mut self) -> io::Result<()> {
@@ -96,7 +108,7 @@ fn test_json_manager_write_failure() {
     }
 
     let context = default_context();
-    let manager = JsonManager::new("broken.json".to_string());
+    let manager = JsonManager::new("broken.json");
 
     let data = TestConfig {
         name: "fail".to_string(),
@@ -106,3 +118,46 @@ fn test_json_manager_write_failure() {
     let result = manager.write(BrokenWriter, &data, &context);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_json_manager_bytes_round_trip() {
+    let data = TestConfig {
+        name: "bytes".to_string(),
+        value: 5,
+    };
+    let context = default_context();
+    let manager = JsonManager::new("bytes.json");
+
+    let bytes = manager.write_bytes(&data, &context).unwrap();
+    let result: TestConfig = JsonManager::read_bytes(&bytes, &context).unwrap();
+    assert_eq!(result, data);
+
+    // JSON is parsed from the bytes as they are, invalid UTF-8 is reported by the parser.
+    let invalid: cdumay_core::Result<TestConfig> = JsonManager::read_bytes(b"{\"name\": \"\xff\", \"value\": 5}", &context);
+    assert!(invalid.unwrap_err().message().contains("invalid unicode code point"));
+}
+
+#[test]
+fn test_json_manager_str_round_trip() {
+    let data = TestConfig {
+        name: "text".to_string(),
+        value: 7,
+    };
+    let context = default_context();
+    let manager = JsonManager::new("text.json");
+
+    let content = manager.write_str(&data, &context).unwrap();
+    let result: TestConfig = JsonManager::read_str(&content, &context).unwrap();
+    assert_eq!(result, data);
+}
+
+#[test]
+fn test_json_manager_error_details_keep_context_and_path() {
+    let mut context = default_context();
+    context.insert("service".to_string(), Value::String("api".to_string()));
+    let manager = JsonManager::new("broken.json");
+
+    let err = manager.read::<_, TestConfig>(Cursor::new(b"{\"name\": ".to_vec()), &context).unwrap_err();
+    assert_eq!(err.details().get("path"), Some(&Value::String("broken.json".to_string())));
+    assert_eq!(err.details().get("service"), Some(&Value::String("api".to_string())));
+}
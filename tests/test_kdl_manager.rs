@@ -0,0 +1,106 @@
+#![cfg(feature = "kdl")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Write};
+
+use cdumay_config::{ContentFormat, KdlManager, Manager, VaultSecret, VaultSecrets};
+use serde_value::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Pane {
+    command: String,
+    size: u32,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TestConfig {
+    name: String,
+    value: i32,
+    tags: Vec<String>,
+    pane: Pane,
+}
+
+fn default_context() -> BTreeMap<String, Value> {
+    BTreeMap::new()
+}
+
+fn sample() -> TestConfig {
+    TestConfig {
+        name: "alpha".to_string(),
+        value: 42,
+        tags: vec!["a".to_string(), "b".to_string()],
+        pane: Pane {
+            command: "htop".to_string(),
+            size: 50,
+        },
+    }
+}
+
+#[test]
+fn test_kdl_manager_new_and_path() {
+    let manager = KdlManager::new("example.kdl");
+    assert_eq!(manager.file_path(), std::path::Path::new("example.kdl"));
+}
+
+#[test]
+fn test_kdl_manager_read_str_success() {
+    let kdl = r#"
+        name alpha
+        value 42
+        tags a b
+        pane command=htop size=50
+    "#;
+    let result: TestConfig = KdlManager::read_str(kdl, &default_context()).unwrap();
+    assert_eq!(result, sample());
+}
+
+#[test]
+fn test_kdl_manager_read_str_failure() {
+    let result: cdumay_core::Result<TestConfig> = KdlManager::read_str("name {", &default_context());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_kdl_manager_write_and_read() {
+    let manager = KdlManager::new("dummy.kdl");
+    let mut buffer = Cursor::new(Vec::new());
+    manager.write(&mut buffer, sample(), &default_context()).unwrap();
+    buffer.set_position(0);
+    let result: TestConfig = manager.read(buffer, &default_context()).unwrap();
+    assert_eq!(result, sample());
+}
+
+#[test]
+fn test_kdl_manager_write_failure() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("write error"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let manager = KdlManager::new("fail.kdl");
+    assert!(manager.write(FailingWriter, sample(), &default_context()).is_err());
+}
+
+#[test]
+fn test_kdl_read_write_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("layout.kdl");
+    cdumay_config::write_config(path.to_str().unwrap(), Some(ContentFormat::KDL), sample(), &default_context()).unwrap();
+    let read: TestConfig = cdumay_config::read_config(path.to_str().unwrap(), Some(ContentFormat::KDL), &default_context()).unwrap();
+    assert_eq!(read, sample());
+}
+
+#[test]
+fn test_kdl_vault_alias() {
+    let secrets = VaultSecrets::new(vec![VaultSecret::new("pane", "pane_key", "command vim\nsize 80")]);
+    let result: Pane = secrets.alias("pane".to_string(), ContentFormat::KDL, &default_context()).unwrap();
+    assert_eq!(result.command, "vim");
+}
@@ -0,0 +1,101 @@
+use std::cell::Cell;
+use std::collections::BTreeMap;
+
+use cdumay_config::{JsonManager, Manager};
+
+/// Counts the allocations of the current thread, so that tests running in parallel do not
+/// interfere.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl std::alloc::GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: std::alloc::Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { std::alloc::System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: std::alloc::Layout) {
+        unsafe { std::alloc::System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+/// Number of entries of the context, each of them allocating at least once when cloned.
+const CONTEXT_SIZE: usize = 10_000;
+
+/// Returns the number of allocations made by a closure.
+fn allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    std::hint::black_box(f());
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+fn large_context() -> BTreeMap<String, serde_value::Value> {
+    (0..CONTEXT_SIZE)
+        .map(|index| (format!("key{}", index), serde_value::Value::String(index.to_string())))
+        .collect()
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+struct Settings {
+    name: String,
+    port: u16,
+}
+
+#[test]
+fn test_successful_reads_do_not_build_contexts() {
+    let context = large_context();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, r#"{"name": "app", "port": 8080}"#).unwrap();
+    let path = path.to_str().unwrap();
+
+    let manager = JsonManager::new(path);
+    let count = allocations(|| {
+        manager
+            .read::<_, Settings>(std::io::Cursor::new(r#"{"name": "app", "port": 8080}"#), &context)
+            .unwrap()
+    });
+    assert!(count < CONTEXT_SIZE, "JSON read made {} allocations", count);
+    let count = allocations(|| cdumay_config::read_config::<Settings>(path, None, &context).unwrap());
+    assert!(count < CONTEXT_SIZE, "read_config made {} allocations", count);
+
+    let documents = [
+        ("app.ndjson", cdumay_config::ContentFormat::NDJSON, r#"{"name": "app", "port": 8080}"#),
+        #[cfg(feature = "toml")]
+        ("app.toml", cdumay_config::ContentFormat::TOML, "name = \"app\"\nport = 8080\n"),
+        #[cfg(feature = "yaml")]
+        ("app.yaml", cdumay_config::ContentFormat::YAML, "name: app\nport: 8080\n"),
+    ];
+    for (name, format, content) in documents {
+        let path = dir.path().join(name);
+        std::fs::write(&path, content).unwrap();
+        let count = allocations(|| cdumay_config::read_config::<serde_value::Value>(path.to_str().unwrap(), Some(format), &context).unwrap());
+        assert!(count < CONTEXT_SIZE, "{} read made {} allocations", format, count);
+    }
+
+    // A failing read does build its error context.
+    let count = allocations(|| manager.read::<_, Settings>(std::io::Cursor::new(r#"{"name": 1}"#), &context).unwrap_err());
+    assert!(count >= CONTEXT_SIZE, "failed JSON read made {} allocations", count);
+}
+
+#[test]
+fn test_successful_writes_do_not_build_contexts() {
+    let context = large_context();
+    let data = Settings {
+        name: "app".to_string(),
+        port: 8080,
+    };
+    let style = cdumay_config::WriteStyle {
+        sort_keys: true,
+        ..Default::default()
+    };
+    let manager = JsonManager::new("app.json");
+    let count = allocations(|| manager.write_styled(Vec::new(), &data, &style, &context).unwrap());
+    assert!(count < CONTEXT_SIZE, "sorted JSON write made {} allocations", count);
+}
@@ -0,0 +1,127 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use cdumay_config::LiveConfig;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Proxy {
+    upstream: String,
+}
+
+#[test]
+fn test_live_config_reload_from_file() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), r#"{"upstream": "a:80"}"#).unwrap();
+    let live = LiveConfig::<Proxy>::from_file(file.path().to_str().unwrap(), None, BTreeMap::new()).unwrap();
+    let before = live.load();
+    assert_eq!(before.upstream, "a:80");
+    assert_eq!(live.generation(), 0);
+
+    std::fs::write(file.path(), r#"{"upstream": "b:80"}"#).unwrap();
+    live.reload().unwrap();
+    assert_eq!(live.load().upstream, "b:80");
+    assert_eq!(live.generation(), 1);
+    // Values handed out before the reload stay valid.
+    assert_eq!(before.upstream, "a:80");
+}
+
+#[test]
+fn test_live_config_failed_reload_keeps_value() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), r#"{"upstream": "a:80"}"#).unwrap();
+    let live = LiveConfig::<Proxy>::from_file(file.path().to_str().unwrap(), None, BTreeMap::new()).unwrap();
+
+    std::fs::write(file.path(), "{ not json").unwrap();
+    assert!(live.reload().is_err());
+    assert_eq!(live.load().upstream, "a:80");
+    assert_eq!(live.generation(), 0);
+}
+
+#[test]
+fn test_live_config_concurrent_readers() {
+    let live = Arc::new(LiveConfig::new(|| Ok(1u64)).unwrap());
+    let readers: Vec<_> = (0..4)
+        .map(|_| {
+            let live = live.clone();
+            std::thread::spawn(move || (0..10_000).all(|_| *live.load() >= 1 && **live.load_guard() >= 1))
+        })
+        .collect();
+    for value in 2..100 {
+        live.store(value);
+    }
+    for reader in readers {
+        assert!(reader.join().unwrap());
+    }
+    assert_eq!(*live.load(), 99);
+    assert_eq!(**live.load_guard(), 99);
+}
+
+#[test]
+fn test_live_config_on_change() {
+    let live = LiveConfig::new(|| Ok(1u64)).unwrap();
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let recorder = seen.clone();
+    live.on_change(move |value| recorder.lock().unwrap().push(*value));
+
+    live.store(2);
+    live.reload().unwrap();
+    assert_eq!(*seen.lock().unwrap(), vec![2, 1]);
+}
+
+#[test]
+fn test_live_config_on_change_from_callback() {
+    let live = Arc::new(LiveConfig::new(|| Ok(1u64)).unwrap());
+    let seen = Arc::new(std::sync::Mutex::new(Vec::new()));
+    let (handle, recorder) = (Arc::downgrade(&live), seen.clone());
+    live.on_change(move |value| {
+        // Registering a callback from a callback must not deadlock.
+        if *value == 2 {
+            let recorder = recorder.clone();
+            handle.upgrade().unwrap().on_change(move |value| recorder.lock().unwrap().push(*value));
+        }
+    });
+
+    live.store(2);
+    live.store(3);
+    assert_eq!(*seen.lock().unwrap(), vec![3]);
+}
+
+#[test]
+fn test_live_config_watch_reloads_on_change() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), r#"{"upstream": "a:80"}"#).unwrap();
+    let live = Arc::new(LiveConfig::<Proxy>::from_file(file.path().to_str().unwrap(), None, BTreeMap::new()).unwrap());
+    let _watch = live.watch(std::time::Duration::from_millis(10)).unwrap();
+
+    // Make sure the modification time changes even on coarse-grained filesystems.
+    std::thread::sleep(std::time::Duration::from_millis(20));
+    std::fs::write(file.path(), r#"{"upstream": "b:80"}"#).unwrap();
+    let modified = std::time::SystemTime::now() + std::time::Duration::from_secs(2);
+    std::fs::File::options().write(true).open(file.path()).unwrap().set_modified(modified).unwrap();
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while live.load().upstream != "b:80" && std::time::Instant::now() < deadline {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(live.load().upstream, "b:80");
+}
+
+#[test]
+fn test_live_config_watch_requires_a_file() {
+    let live = Arc::new(LiveConfig::new(|| Ok(1u64)).unwrap());
+    assert!(live.watch(std::time::Duration::from_millis(10)).is_err());
+}
+
+#[test]
+fn test_live_config_from_file_with_resolver() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("proxy.json"), r#"{"upstream": "a:80"}"#).unwrap();
+    let options = cdumay_config::ReadOptions {
+        resolver: Some(Arc::new(cdumay_config::ChrootPathResolver::new(dir.path()))),
+        ..Default::default()
+    };
+    let live = LiveConfig::<Proxy>::from_file_with("/proxy.json", None, BTreeMap::new(), options).unwrap();
+    assert_eq!(live.load().upstream, "a:80");
+    assert_eq!(live.path(), Some(dir.path().join("proxy.json").to_str().unwrap()));
+}
@@ -0,0 +1,41 @@
+use std::collections::BTreeMap;
+
+#[test]
+fn test_location_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let location = cdumay_config::ConfigLocation::new(dir.path().join("myapp").to_str().unwrap());
+    assert_eq!(location.dir(), dir.path().join("myapp"));
+    assert_eq!(location.path("settings.json"), dir.path().join("myapp").join("settings.json"));
+
+    let context = BTreeMap::new();
+    let path = location
+        .write_config(
+            "settings.json",
+            None,
+            serde_json::json!({"endpoint": "https://api.example.com"}),
+            &context,
+        )
+        .unwrap();
+    assert_eq!(path, location.path("settings.json"));
+    let config: serde_json::Value = location.read_config("settings.json", None, &context).unwrap();
+    assert_eq!(config["endpoint"], "https://api.example.com");
+
+    let err = location.read_config::<serde_json::Value>("missing.json", None, &context).unwrap_err();
+    assert_eq!(
+        err.details()["path"],
+        serde_value::Value::String(location.path("missing.json").display().to_string())
+    );
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+#[test]
+fn test_location_for_app() {
+    let base = std::env::var_os("XDG_CONFIG_HOME")
+        .map(std::path::PathBuf::from)
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| std::env::home_dir().map(|home| home.join(".config")));
+    match base {
+        Some(base) => assert_eq!(cdumay_config::ConfigLocation::for_app("myapp").unwrap().dir(), base.join("myapp")),
+        None => assert!(cdumay_config::ConfigLocation::for_app("myapp").is_err()),
+    }
+}
@@ -0,0 +1,67 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use cdumay_config::{FileLock, LockMode, ReadOptions, WriteOptions};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TestConfig {
+    name: String,
+    value: i32,
+}
+
+#[test]
+fn test_locked_write_then_read() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let path = path.to_str().unwrap();
+    let context = BTreeMap::new();
+    let data = TestConfig {
+        name: "locked".to_string(),
+        value: 7,
+    };
+
+    let write_options = WriteOptions {
+        lock: Some(LockMode::Blocking),
+        ..Default::default()
+    };
+    cdumay_config::write_config_with(path, None, &data, &context, &write_options).unwrap();
+    assert!(FileLock::lock_path(path).exists());
+
+    let read_options = ReadOptions {
+        lock: Some(LockMode::Try),
+        ..Default::default()
+    };
+    let read: TestConfig = cdumay_config::read_config_with(path, None, &context, &read_options).unwrap();
+    assert_eq!(read, data);
+}
+
+#[test]
+fn test_shared_locks_are_compatible() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let path = path.to_str().unwrap();
+    let context = BTreeMap::new();
+
+    let _first = FileLock::shared(path, LockMode::Try, &context).unwrap();
+    let second = FileLock::shared(path, LockMode::Try, &context);
+    assert!(second.is_ok());
+}
+
+#[test]
+fn test_exclusive_lock_excludes_others() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let path = path.to_str().unwrap();
+    let context = BTreeMap::new();
+
+    let guard = FileLock::exclusive(path, LockMode::Try, &context).unwrap();
+    assert!(FileLock::shared(path, LockMode::Try, &context).is_err());
+
+    let result = FileLock::exclusive(path, LockMode::Timeout(Duration::from_millis(20)), &context);
+    let err = result.unwrap_err();
+    assert!(format!("{}", err).contains("Failed to lock file within"));
+
+    drop(guard);
+    assert!(FileLock::exclusive(path, LockMode::Try, &context).is_ok());
+}
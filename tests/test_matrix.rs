@@ -0,0 +1,107 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+use serde_value::Value;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Service {
+    url: String,
+    port: u16,
+    tags: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct Template {
+    url: &'static str,
+    port: &'static str,
+    tags: Vec<&'static str>,
+}
+
+fn template() -> Template {
+    Template {
+        url: "https://{env}.example.com/{env}",
+        port: "{port}",
+        tags: vec!["{env}", "{region}-{env}", "{literal"],
+    }
+}
+
+fn environment(env: &str, port: u16) -> BTreeMap<String, Value> {
+    BTreeMap::from([
+        ("env".to_string(), Value::String(env.to_string())),
+        ("port".to_string(), Value::U16(port)),
+        ("region".to_string(), Value::String("eu".to_string())),
+    ])
+}
+
+#[test]
+fn test_matrix_renders_each_environment() {
+    let dir = tempfile::tempdir().unwrap();
+    let pattern = dir.path().join("{env}.json");
+    let paths = cdumay_config::materialize_matrix(
+        &template(),
+        &[environment("staging", 8443), environment("production", 443)],
+        pattern.to_str().unwrap(),
+        None,
+        &Default::default(),
+    )
+    .unwrap();
+    assert_eq!(paths, vec![dir.path().join("staging.json"), dir.path().join("production.json")]);
+    let production: Service = cdumay_config::read_config(paths[1].to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(
+        production,
+        Service {
+            url: "https://production.example.com/production".to_string(),
+            port: 443,
+            tags: vec!["production".to_string(), "eu-production".to_string(), "{literal".to_string()],
+        }
+    );
+}
+
+#[test]
+fn test_matrix_unresolved_placeholder_writes_nothing() {
+    let dir = tempfile::tempdir().unwrap();
+    let pattern = dir.path().join("{env}.json");
+    let mut incomplete = environment("production", 443);
+    incomplete.remove("region");
+    let err = cdumay_config::materialize_matrix(
+        &template(),
+        &[environment("staging", 8443), incomplete],
+        pattern.to_str().unwrap(),
+        None,
+        &Default::default(),
+    )
+    .unwrap_err();
+    assert_eq!(err.message(), "Unresolved placeholder '{region}' in '{region}-{env}'");
+    assert_eq!(err.details()["placeholder"], Value::String("region".to_string()));
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_matrix_duplicate_paths() {
+    let dir = tempfile::tempdir().unwrap();
+    let pattern = dir.path().join("{region}.json");
+    let err = cdumay_config::materialize_matrix(
+        &template(),
+        &[environment("staging", 8443), environment("production", 443)],
+        pattern.to_str().unwrap(),
+        None,
+        &Default::default(),
+    )
+    .unwrap_err();
+    assert!(
+        err.message().starts_with("Several environments render the same file"),
+        "{}",
+        err.message()
+    );
+    assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+fn test_matrix_non_scalar_placeholder() {
+    let dir = tempfile::tempdir().unwrap();
+    let pattern = dir.path().join("{env}.json");
+    let mut environment = environment("staging", 8443);
+    environment.insert("region".to_string(), Value::Seq(vec![]));
+    let err = cdumay_config::materialize_matrix(&template(), &[environment], pattern.to_str().unwrap(), None, &Default::default()).unwrap_err();
+    assert_eq!(err.message(), "Placeholder '{region}' of '{region}-{env}' is not a scalar");
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "msgpack")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use cdumay_config::{ContentFormat, Manager, MsgpackManager};
+use serde_value::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TestConfig {
+    name: String,
+    value: i32,
+}
+
+fn default_context() -> BTreeMap<String, Value> {
+    BTreeMap::new()
+}
+
+#[test]
+fn test_msgpack_manager_new_and_path() {
+    let manager = MsgpackManager::new("app.msgpack");
+    assert_eq!(manager.file_path(), std::path::Path::new("app.msgpack"));
+}
+
+#[test]
+fn test_msgpack_manager_bytes_round_trip() {
+    let data = TestConfig {
+        name: "binary".to_string(),
+        value: -3,
+    };
+    let manager = MsgpackManager::new("app.msgpack");
+    let bytes = manager.write_bytes(&data, &default_context()).unwrap();
+    let result: TestConfig = MsgpackManager::read_bytes(&bytes, &default_context()).unwrap();
+    assert_eq!(result, data);
+
+    // Fields are named, so the payload is readable as a map.
+    let map: BTreeMap<String, Value> = MsgpackManager::read_bytes(&bytes, &default_context()).unwrap();
+    assert!(map.contains_key("name"));
+}
+
+#[test]
+fn test_msgpack_manager_write_str_rejects_binary() {
+    let manager = MsgpackManager::new("app.msgpack");
+    let data = TestConfig {
+        name: "binary".to_string(),
+        value: -3,
+    };
+    let err = manager.write_str(&data, &default_context()).unwrap_err();
+    assert!(err.message().contains("not valid UTF-8"));
+}
+
+#[test]
+fn test_msgpack_manager_read_failure() {
+    let manager = MsgpackManager::new("app.msgpack");
+    let result: cdumay_core::Result<TestConfig> = manager.read(Cursor::new(vec![0xc1]), &default_context());
+    assert!(result.unwrap_err().message().contains("Invalid MessagePack content"));
+}
+
+#[test]
+fn test_msgpack_read_write_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.msgpack");
+    let data = TestConfig {
+        name: "file".to_string(),
+        value: 9,
+    };
+    cdumay_config::write_config(path.to_str().unwrap(), Some(ContentFormat::MSGPACK), &data, &default_context()).unwrap();
+    let read: TestConfig = cdumay_config::read_config(path.to_str().unwrap(), Some(ContentFormat::MSGPACK), &default_context()).unwrap();
+    assert_eq!(read, data);
+}
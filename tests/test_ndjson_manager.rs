@@ -0,0 +1,79 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use cdumay_config::{ContentFormat, Manager, NdjsonManager, VaultSecret, VaultSecrets};
+use serde_value::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Rule {
+    name: String,
+    priority: u32,
+}
+
+fn default_context() -> BTreeMap<String, Value> {
+    BTreeMap::new()
+}
+
+fn rules() -> Vec<Rule> {
+    vec![
+        Rule {
+            name: "allow".to_string(),
+            priority: 1,
+        },
+        Rule {
+            name: "deny".to_string(),
+            priority: 2,
+        },
+    ]
+}
+
+#[test]
+fn test_ndjson_manager_read_str_success() {
+    let content = "{\"name\": \"allow\", \"priority\": 1}\n\n{\"name\": \"deny\", \"priority\": 2}";
+    let result: Vec<Rule> = NdjsonManager::read_str(content, &default_context()).unwrap();
+    assert_eq!(result, rules());
+}
+
+#[test]
+fn test_ndjson_manager_read_str_reports_line() {
+    let content = "{\"name\": \"allow\", \"priority\": 1}\n{\"name\": \"deny\",";
+    let err = NdjsonManager::read_str::<Vec<Rule>>(content, &default_context()).unwrap_err();
+    assert_eq!(err.details().get("line"), Some(&Value::U64(2)));
+}
+
+#[test]
+fn test_ndjson_manager_write_one_record_per_line() {
+    let manager = NdjsonManager::new("rules.ndjson");
+    let mut buffer = Cursor::new(Vec::new());
+    manager.write(&mut buffer, rules(), &default_context()).unwrap();
+    let written = String::from_utf8(buffer.get_ref().clone()).unwrap();
+    assert_eq!(written, "{\"name\":\"allow\",\"priority\":1}\n{\"name\":\"deny\",\"priority\":2}\n");
+
+    buffer.set_position(0);
+    let result: Vec<Rule> = manager.read(buffer, &default_context()).unwrap();
+    assert_eq!(result, rules());
+}
+
+#[test]
+fn test_ndjson_manager_write_requires_sequence() {
+    let manager = NdjsonManager::new("rules.ndjson");
+    let mut buffer = Cursor::new(Vec::new());
+    assert!(manager.write(&mut buffer, &rules()[0], &default_context()).is_err());
+}
+
+#[test]
+fn test_ndjson_read_write_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("rules.jsonl");
+    cdumay_config::write_config(path.to_str().unwrap(), Some(ContentFormat::NDJSON), rules(), &default_context()).unwrap();
+    let read: Vec<Rule> = cdumay_config::read_config(path.to_str().unwrap(), Some(ContentFormat::NDJSON), &default_context()).unwrap();
+    assert_eq!(read, rules());
+}
+
+#[test]
+fn test_ndjson_vault_alias() {
+    let secrets = VaultSecrets::new(vec![VaultSecret::new("rules", "rules_key", "{\"name\": \"allow\", \"priority\": 1}")]);
+    let result: Vec<Rule> = secrets.alias("rules".to_string(), ContentFormat::NDJSON, &default_context()).unwrap();
+    assert_eq!(result.len(), 1);
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "plist")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::Cursor;
+
+use cdumay_config::{ContentFormat, Manager, PlistManager};
+use serde_value::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[serde(rename_all = "PascalCase")]
+struct Agent {
+    label: String,
+    run_at_load: bool,
+    program_arguments: Vec<String>,
+}
+
+fn default_context() -> BTreeMap<String, Value> {
+    BTreeMap::new()
+}
+
+fn sample() -> Agent {
+    Agent {
+        label: "com.example.agent".to_string(),
+        run_at_load: true,
+        program_arguments: vec!["/usr/local/bin/agent".to_string(), "--daemon".to_string()],
+    }
+}
+
+#[test]
+fn test_plist_manager_read_str_success() {
+    let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>Label</key><string>com.example.agent</string>
+    <key>RunAtLoad</key><true/>
+    <key>ProgramArguments</key>
+    <array><string>/usr/local/bin/agent</string><string>--daemon</string></array>
+</dict>
+</plist>"#;
+    let result: Agent = PlistManager::read_str(xml, &default_context()).unwrap();
+    assert_eq!(result, sample());
+}
+
+#[test]
+fn test_plist_manager_read_str_failure() {
+    let result: cdumay_core::Result<Agent> = PlistManager::read_str("<plist><dict>", &default_context());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_plist_manager_xml_write_and_read() {
+    let manager = PlistManager::new("agent.plist");
+    let mut buffer = Cursor::new(Vec::new());
+    manager.write(&mut buffer, sample(), &default_context()).unwrap();
+    assert!(String::from_utf8(buffer.get_ref().clone()).unwrap().contains("<plist"));
+    buffer.set_position(0);
+    let result: Agent = manager.read(buffer, &default_context()).unwrap();
+    assert_eq!(result, sample());
+}
+
+#[test]
+fn test_plist_manager_binary_write_and_read() {
+    let manager = PlistManager::binary("agent.plist");
+    let bytes = manager.write_bytes(sample(), &default_context()).unwrap();
+    assert!(bytes.starts_with(b"bplist00"));
+    let result: Agent = PlistManager::read_bytes(&bytes, &default_context()).unwrap();
+    assert_eq!(result, sample());
+}
+
+#[test]
+fn test_plist_read_binary_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("agent.plist");
+    PlistManager::binary(&path)
+        .write_config(&sample(), &default_context())
+        .unwrap();
+    let read: Agent = cdumay_config::read_config(path.to_str().unwrap(), Some(ContentFormat::PLIST), &default_context()).unwrap();
+    assert_eq!(read, sample());
+}
+
+#[test]
+fn test_plist_read_write_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("agent.plist");
+    cdumay_config::write_config(path.to_str().unwrap(), Some(ContentFormat::PLIST), sample(), &default_context()).unwrap();
+    let read: Agent = cdumay_config::read_config(path.to_str().unwrap(), Some(ContentFormat::PLIST), &default_context()).unwrap();
+    assert_eq!(read, sample());
+}
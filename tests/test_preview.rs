@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Database {
+    url: String,
+    password: String,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Config {
+    database: Database,
+    hosts: Vec<String>,
+}
+
+fn layer(dir: &tempfile::TempDir, name: &str, content: &str) -> String {
+    let path = dir.path().join(name);
+    std::fs::write(&path, content).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_preview_merged_tree_and_provenance() {
+    let dir = tempfile::tempdir().unwrap();
+    let defaults = layer(
+        &dir,
+        "defaults.json",
+        r#"{"database": {"url": "postgres://localhost", "password": "changeme", "pool": {"size": 4}}, "hosts": ["a"]}"#,
+    );
+    let local = layer(
+        &dir,
+        "local.json",
+        r#"{"database": {"password": "s3cr3t", "pool": 8}, "hosts": ["b", "c"]}"#,
+    );
+    let view = cdumay_config::compose_preview::<Config>(
+        &[(&defaults, None), (&local, None)],
+        &BTreeMap::new(),
+        &cdumay_config::Redaction::default(),
+    );
+
+    let merged: serde_json::Value = view.merged.clone().deserialize_into().unwrap();
+    assert_eq!(
+        merged,
+        serde_json::json!({"database": {"url": "postgres://localhost", "password": "***REDACTED***", "pool": 8}, "hosts": ["b", "c"]})
+    );
+    assert_eq!(
+        view.provenance,
+        BTreeMap::from([
+            ("database.url".to_string(), defaults.clone()),
+            ("database.password".to_string(), local.clone()),
+            ("database.pool".to_string(), local.clone()),
+            ("hosts".to_string(), local.clone()),
+        ])
+    );
+    assert_eq!(view.redacted, vec!["database.password".to_string()]);
+    assert_eq!(view.issues.len(), 1);
+    assert_eq!(view.issues[0].key.as_deref(), Some("database.pool"));
+    assert_eq!(view.issues[0].source.as_deref(), Some(local.as_str()));
+    assert!(!view.is_valid());
+}
+
+#[test]
+fn test_preview_collects_failures() {
+    let dir = tempfile::tempdir().unwrap();
+    let defaults = layer(&dir, "defaults.json", r#"{"database": {"url": "postgres://localhost"}}"#);
+    let broken = layer(&dir, "broken.json", "{not json");
+    let missing = dir.path().join("missing.json");
+    let view = cdumay_config::compose_preview::<Config>(
+        &[(&defaults, None), (&broken, None), (missing.to_str().unwrap(), None)],
+        &BTreeMap::new(),
+        &cdumay_config::Redaction::default(),
+    );
+    let sources: Vec<_> = view.issues.iter().map(|issue| issue.source.clone()).collect();
+    assert_eq!(sources, vec![Some(broken), Some(missing.to_str().unwrap().to_string()), None]);
+    assert!(view.issues[2].message.contains("password") || view.issues[2].message.contains("hosts"));
+    assert_eq!(view.provenance.keys().collect::<Vec<_>>(), vec!["database.url"]);
+}
+
+#[test]
+fn test_preview_is_serializable() {
+    let dir = tempfile::tempdir().unwrap();
+    let config = layer(&dir, "config.json", r#"{"database": {"url": "x", "password": "y"}, "hosts": []}"#);
+    let view = cdumay_config::compose_preview::<Config>(&[(&config, None)], &BTreeMap::new(), &cdumay_config::Redaction::default());
+    assert!(view.is_valid());
+    let json = serde_json::to_value(&view).unwrap();
+    assert_eq!(json["merged"]["database"]["password"], "***REDACTED***");
+    assert_eq!(json["provenance"]["database.url"], config.as_str());
+    assert_eq!(json["redacted"], serde_json::json!(["database.password"]));
+}
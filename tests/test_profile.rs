@@ -0,0 +1,63 @@
+use std::collections::BTreeMap;
+
+#[test]
+fn test_profile_path() {
+    let loader = cdumay_config::ProfileLoader::new("config/app.yaml");
+    assert_eq!(loader.profile_path("prod"), "config/app.prod.yaml");
+    assert_eq!(
+        cdumay_config::ProfileLoader::new("app.conf.json").profile_path("dev"),
+        "app.conf.dev.json"
+    );
+    assert_eq!(
+        cdumay_config::ProfileLoader::new("/etc/app.d/config").profile_path("dev"),
+        "/etc/app.d/config.dev"
+    );
+    assert_eq!(cdumay_config::ProfileLoader::new("~/.apprc").profile_path("dev"), "~/.apprc.dev");
+}
+
+#[test]
+fn test_profile_load() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(
+        &path,
+        r#"{"endpoint": "http://localhost", "pool": {"size": 5, "idle": 30}, "debug": true}"#,
+    )
+    .unwrap();
+    std::fs::write(
+        dir.path().join("app.prod.json"),
+        r#"{"endpoint": "https://api.example.com", "pool": {"size": 50}, "debug": false}"#,
+    )
+    .unwrap();
+    let loader = cdumay_config::ProfileLoader::new(path.to_str().unwrap()).with_env_var("CDUMAY_CONFIG_TEST_UNSET_PROFILE");
+    let context = BTreeMap::new();
+
+    assert_eq!(loader.profile(), None);
+    let config: serde_json::Value = loader.load(&context).unwrap();
+    assert_eq!(config["endpoint"], "http://localhost");
+
+    let config: serde_json::Value = loader.clone().with_profile("prod").load(&context).unwrap();
+    assert_eq!(
+        config,
+        serde_json::json!({"endpoint": "https://api.example.com", "pool": {"size": 50, "idle": 30}, "debug": false})
+    );
+
+    let config: serde_json::Value = loader.clone().with_profile("staging").load(&context).unwrap();
+    assert_eq!(config["debug"], true);
+
+    let err = loader.with_profile("../prod").load::<serde_json::Value>(&context).unwrap_err();
+    assert_eq!(err.message(), "Invalid configuration profile: '../prod'");
+}
+
+#[test]
+fn test_profile_env_var() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, r#"{"name": "base"}"#).unwrap();
+    std::fs::write(dir.path().join(format!("app.{}.json", env!("CARGO_PKG_NAME"))), r#"{"name": "profile"}"#).unwrap();
+    // Cargo sets `CARGO_PKG_NAME` when running tests.
+    let loader = cdumay_config::ProfileLoader::new(path.to_str().unwrap()).with_env_var("CARGO_PKG_NAME");
+    assert_eq!(loader.profile().as_deref(), Some(env!("CARGO_PKG_NAME")));
+    let config: serde_json::Value = loader.load(&BTreeMap::new()).unwrap();
+    assert_eq!(config["name"], "profile");
+}
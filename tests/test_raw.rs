@@ -0,0 +1,33 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use cdumay_config::{JsonManager, Manager, RawJson};
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Config {
+    name: String,
+    plugin: Box<RawJson>,
+}
+
+#[test]
+fn test_raw_json_round_trip() {
+    let content = r#"{"name": "app", "plugin": {"z": 1,   "a": [3, 2, 1], "nested": {"keep": "as-is"}}}"#;
+    let config: Config = JsonManager::read_str(content, &BTreeMap::new()).unwrap();
+    assert_eq!(config.plugin.get(), r#"{"z": 1,   "a": [3, 2, 1], "nested": {"keep": "as-is"}}"#);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let manager = JsonManager::new(&path);
+    manager.write_config(&config, &BTreeMap::new()).unwrap();
+
+    let written = std::fs::read_to_string(&path).unwrap();
+    assert!(written.contains(r#"{"z": 1,   "a": [3, 2, 1], "nested": {"keep": "as-is"}}"#));
+    let read: Config = manager.read_config(&BTreeMap::new()).unwrap();
+    assert_eq!(read.plugin.get(), config.plugin.get());
+}
+
+#[test]
+fn test_raw_json_invalid_content() {
+    let result: cdumay_core::Result<Config> = JsonManager::read_str(r#"{"name": "app", "plugin": {"z": }"#, &BTreeMap::new());
+    assert!(result.is_err());
+}
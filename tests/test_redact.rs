@@ -0,0 +1,58 @@
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use cdumay_config::Redaction;
+
+#[derive(Serialize)]
+struct Database {
+    url: String,
+    password: String,
+}
+
+#[derive(Serialize)]
+struct Config {
+    name: String,
+    api_token: Option<String>,
+    database: Database,
+    upstreams: Vec<BTreeMap<String, String>>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "app".to_string(),
+        api_token: Some("t0k3n".to_string()),
+        database: Database {
+            url: "postgres://admin:hunter2@db/app".to_string(),
+            password: "hunter2".to_string(),
+        },
+        upstreams: vec![BTreeMap::from([
+            ("host".to_string(), "10.0.0.1".to_string()),
+            ("Client_Secret".to_string(), "s3cr3t".to_string()),
+        ])],
+    }
+}
+
+#[test]
+fn test_write_config_redacted_masks_default_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("support.json");
+    cdumay_config::write_config_redacted(path.to_str().unwrap(), None, config(), &BTreeMap::new(), &Redaction::default()).unwrap();
+
+    let written: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(written["name"], "app");
+    assert_eq!(written["api_token"], "***REDACTED***");
+    assert_eq!(written["database"]["password"], "***REDACTED***");
+    assert_eq!(written["database"]["url"], "postgres://admin:hunter2@db/app");
+    assert_eq!(written["upstreams"][0]["host"], "10.0.0.1");
+    assert_eq!(written["upstreams"][0]["Client_Secret"], "***REDACTED***");
+}
+
+#[test]
+fn test_redaction_custom_paths_and_placeholder() {
+    let redaction = Redaction::default().with_key("database.url").with_placeholder("<hidden>");
+    let value = redaction.redact(&config(), &BTreeMap::new()).unwrap();
+    let value: serde_json::Value = value.deserialize_into().unwrap();
+    assert_eq!(value["database"]["url"], "<hidden>");
+    assert_eq!(value["database"]["password"], "<hidden>");
+    assert_eq!(value["name"], "app");
+}
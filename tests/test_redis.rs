@@ -0,0 +1,157 @@
+#![cfg(feature = "redis")]
+
+use cdumay_config::{ContentFormat, LiveConfig, RedisSource};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::io::{BufRead, BufReader, Read, Write};
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+/// Reads a command sent as an array of bulk strings.
+fn read_command(reader: &mut BufReader<std::net::TcpStream>) -> Option<Vec<String>> {
+    let mut line = String::new();
+    if reader.read_line(&mut line).ok()? == 0 {
+        return None;
+    }
+    let count: usize = line.trim_end().trim_start_matches('*').parse().ok()?;
+    let mut args = Vec::new();
+    for _ in 0..count {
+        line.clear();
+        reader.read_line(&mut line).ok()?;
+        let size: usize = line.trim_end().trim_start_matches('$').parse().ok()?;
+        let mut arg = vec![0; size + 2];
+        reader.read_exact(&mut arg).ok()?;
+        args.push(String::from_utf8_lossy(&arg[..size]).to_string());
+    }
+    Some(args)
+}
+
+/// Serves one connection answering each command with the next reply, returning the commands.
+fn serve(replies: Vec<&'static str>) -> (String, std::thread::JoinHandle<Vec<Vec<String>>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let handle = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+        let mut commands = Vec::new();
+        for reply in replies {
+            commands.push(read_command(&mut reader).unwrap());
+            writer.write_all(reply.as_bytes()).unwrap();
+        }
+        commands
+    });
+    (address, handle)
+}
+
+#[test]
+fn test_redis_string() {
+    let document = r#"{"host": "db.internal", "port": 5432}"#;
+    let reply: &'static str = Box::leak(format!("${}\r\n{}\r\n", document.len(), document).into_boxed_str());
+    let (address, handle) = serve(vec!["+OK\r\n", "+OK\r\n", "+string\r\n", reply]);
+
+    let config: Config = RedisSource::new(&address, "app:config")
+        .with_credentials("app", "s3cr3t")
+        .with_database(2)
+        .read_config(&BTreeMap::new())
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            host: "db.internal".to_string(),
+            port: 5432
+        }
+    );
+    assert_eq!(
+        handle.join().unwrap(),
+        vec![
+            vec!["AUTH", "app", "s3cr3t"],
+            vec!["SELECT", "2"],
+            vec!["TYPE", "app:config"],
+            vec!["GET", "app:config"],
+        ]
+    );
+}
+
+#[test]
+fn test_redis_hash() {
+    let (address, handle) = serve(vec![
+        "+hash\r\n",
+        "*4\r\n$4\r\nhost\r\n$11\r\ndb.internal\r\n$4\r\nport\r\n$4\r\n5432\r\n",
+    ]);
+
+    let config: Config = RedisSource::new(&address, "app:config")
+        .with_format(ContentFormat::JSON)
+        .read_config(&BTreeMap::new())
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            host: "db.internal".to_string(),
+            port: 5432
+        }
+    );
+    assert_eq!(handle.join().unwrap()[1], vec!["HGETALL", "app:config"]);
+}
+
+#[test]
+fn test_redis_errors() {
+    let (address, _) = serve(vec!["+none\r\n"]);
+    let err = RedisSource::new(&address, "app:config").read_value(&BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["key"], serde_value::Value::String("app:config".to_string()));
+    assert_eq!(err.details()["url"], serde_value::Value::String(format!("redis://{}/0", address)));
+
+    let (address, _) = serve(vec!["-WRONGPASS invalid username-password pair\r\n"]);
+    let err = RedisSource::new(&address, "app:config")
+        .with_password("wrong")
+        .read_value(&BTreeMap::new())
+        .unwrap_err();
+    assert!(err.message().contains("WRONGPASS"));
+
+    let (address, _) = serve(vec!["+list\r\n"]);
+    let err = RedisSource::new(&address, "app:config").read_value(&BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("list"));
+
+    let source = RedisSource::new("127.0.0.1:6379", "app:config").with_password("s3cr3t");
+    assert!(!format!("{:?}", source).contains("s3cr3t"));
+}
+
+#[test]
+fn test_redis_watch() {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    let (notify, notified) = std::sync::mpsc::channel::<()>();
+    let server = std::thread::spawn(move || {
+        let (stream, _) = listener.accept().unwrap();
+        let mut writer = stream.try_clone().unwrap();
+        let mut reader = BufReader::new(stream);
+        let command = read_command(&mut reader).unwrap();
+        writer.write_all(b"*3\r\n$9\r\nsubscribe\r\n$7\r\nupdates\r\n:1\r\n").unwrap();
+        notified.recv().unwrap();
+        writer.write_all(b"*3\r\n$7\r\nmessage\r\n$7\r\nupdates\r\n$7\r\nchanged\r\n").unwrap();
+        // Keep the connection open until the watch stops.
+        let _ = reader.read(&mut [0; 1]);
+        command
+    });
+
+    let counter = std::sync::Arc::new(std::sync::atomic::AtomicU32::new(0));
+    let loads = counter.clone();
+    let live = std::sync::Arc::new(LiveConfig::new(move || Ok(loads.fetch_add(1, std::sync::atomic::Ordering::SeqCst))).unwrap());
+    let source = RedisSource::new(&address, "app:config");
+    assert!(source.watch(&live, &BTreeMap::new()).is_err());
+
+    let watch = source.with_channel("updates").watch(&live, &BTreeMap::new()).unwrap();
+    notify.send(()).unwrap();
+    let start = std::time::Instant::now();
+    while live.generation() == 0 && start.elapsed() < std::time::Duration::from_secs(5) {
+        std::thread::sleep(std::time::Duration::from_millis(10));
+    }
+    assert_eq!(live.generation(), 1);
+    assert_eq!(*live.load(), 1);
+    drop(watch);
+    assert_eq!(server.join().unwrap(), vec!["SUBSCRIBE", "updates"]);
+}
@@ -0,0 +1,129 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::{FormatRegistry, Manager};
+use serde_value::Value;
+
+/// A minimal `key=value` format standing for a downstream proprietary format.
+struct KeyValueManager {
+    path: std::path::PathBuf,
+}
+
+impl Manager for KeyValueManager {
+    fn new<P: AsRef<std::path::Path>>(path: P) -> Self {
+        KeyValueManager { path: path.as_ref().to_path_buf() }
+    }
+
+    fn file_path(&self) -> &std::path::Path {
+        &self.path
+    }
+
+    fn read<R: Read, C: serde::de::DeserializeOwned>(&self, mut reader: R, context: &BTreeMap<String, Value>) -> cdumay_core::Result<C> {
+        let mut content = String::new();
+        reader.read_to_string(&mut content).unwrap();
+        Self::read_str(&content, context)
+    }
+
+    fn write<D: Serialize, W: Write>(&self, mut writer: W, data: D, _: &BTreeMap<String, Value>) -> cdumay_core::Result<()> {
+        let map: BTreeMap<String, String> = serde_value::to_value(data).unwrap().deserialize_into().unwrap();
+        for (key, value) in map {
+            writeln!(writer, "{}={}", key, value).unwrap();
+        }
+        Ok(())
+    }
+
+    fn read_bytes<C: serde::de::DeserializeOwned>(content: &[u8], _: &BTreeMap<String, Value>) -> cdumay_core::Result<C> {
+        let map: BTreeMap<Value, Value> = std::str::from_utf8(content)
+            .unwrap()
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .map(|(key, value)| (Value::String(key.to_string()), Value::String(value.to_string())))
+            .collect();
+        Ok(Value::Map(map).deserialize_into().unwrap())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Settings {
+    host: String,
+    mode: String,
+}
+
+fn settings() -> Settings {
+    Settings {
+        host: "localhost".to_string(),
+        mode: "fast".to_string(),
+    }
+}
+
+#[test]
+fn test_registry_dispatch_by_extension() {
+    let registry = FormatRegistry::new();
+    registry.register::<KeyValueManager>("keyvalue", &[".kv"]);
+    assert_eq!(registry.names(), vec!["keyvalue"]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.kv");
+    registry.write_config(path.to_str().unwrap(), None, settings(), &BTreeMap::new()).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "host=localhost\nmode=fast\n");
+
+    let read: Settings = registry.read_config(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+}
+
+#[test]
+fn test_registry_dispatch_by_name() {
+    let registry = FormatRegistry::new();
+    registry.register::<KeyValueManager>("KeyValue", &[]);
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.conf");
+    std::fs::write(&path, "host=example\nmode=safe\n").unwrap();
+    let read: Settings = registry.read_config(path.to_str().unwrap(), Some("keyvalue"), &BTreeMap::new()).unwrap();
+    assert_eq!(read.host, "example");
+}
+
+#[test]
+fn test_registry_falls_back_to_builtin_formats() {
+    let registry = FormatRegistry::new();
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    registry.write_config(path.to_str().unwrap(), None, settings(), &BTreeMap::new()).unwrap();
+    let read: Settings = registry.read_config(path.to_str().unwrap(), Some("JSON"), &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+
+    let err = registry
+        .read_config::<Settings>(path.to_str().unwrap(), Some("unknown"), &BTreeMap::new())
+        .unwrap_err();
+    assert!(err.message().contains("Unknown configuration format 'unknown'"));
+}
+
+#[test]
+fn test_global_registry() {
+    FormatRegistry::global().register::<KeyValueManager>("global-kv", &["gkv"]);
+    assert!(FormatRegistry::global().names().contains(&"global-kv".to_string()));
+}
+
+#[test]
+fn test_global_registry_plain_read_config() {
+    FormatRegistry::global().register::<KeyValueManager>("plain-kv", &["pkv"]);
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.pkv");
+    cdumay_config::write_config(path.to_str().unwrap(), None, settings(), &BTreeMap::new()).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), "host=localhost\nmode=fast\n");
+
+    let read: Settings = cdumay_config::read_config(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+
+    let path = dir.path().join("app.json");
+    cdumay_config::write_config(
+        path.to_str().unwrap(),
+        Some(cdumay_config::ContentFormat::JSON),
+        settings(),
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    let read: Settings = cdumay_config::read_config(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(read, settings());
+}
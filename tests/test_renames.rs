@@ -0,0 +1,100 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{KeyRenames, ReadOptions};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Storage {
+    url: String,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    storage: Storage,
+    #[serde(default)]
+    timeout: Option<u64>,
+}
+
+fn renames() -> ReadOptions {
+    ReadOptions {
+        renames: Some(KeyRenames::new().rename("database", "storage").rename("storage.timeout", "timeout")),
+        ..Default::default()
+    }
+}
+
+fn read(content: &str, options: &ReadOptions) -> cdumay_core::Result<Config> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, content).unwrap();
+    cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), options)
+}
+
+#[test]
+fn test_renames_accept_both_spellings() {
+    let expected = Config {
+        storage: Storage {
+            url: "postgres://db".to_string(),
+        },
+        timeout: Some(30),
+    };
+    assert_eq!(
+        read(r#"{"database": {"url": "postgres://db", "timeout": 30}}"#, &renames()).unwrap(),
+        expected
+    );
+    assert_eq!(
+        read(r#"{"storage": {"url": "postgres://db"}, "timeout": 30}"#, &renames()).unwrap(),
+        expected
+    );
+    assert!(read(r#"{"database": {"url": "postgres://db"}}"#, &ReadOptions::default()).is_err());
+}
+
+#[test]
+fn test_renames_new_spelling_wins() {
+    let config = read(r#"{"database": {"url": "old"}, "storage": {"url": "new"}}"#, &renames()).unwrap();
+    assert_eq!(config.storage.url, "new");
+}
+
+#[test]
+fn test_renames_apply() {
+    let mut value = serde_value::Value::Map(BTreeMap::from([(
+        serde_value::Value::String("name".to_string()),
+        serde_value::Value::String("api".to_string()),
+    )]));
+    let renames = KeyRenames::new().rename("name", "service.name").rename("missing", "other");
+    let applied = renames.apply(&mut value, &BTreeMap::new()).unwrap();
+    assert_eq!(applied, vec![("name".to_string(), "service.name".to_string())]);
+    let service: BTreeMap<String, BTreeMap<String, String>> = value.deserialize_into().unwrap();
+    assert_eq!(service["service"]["name"], "api");
+}
+
+#[test]
+fn test_renames_parent_not_a_map() {
+    let options = ReadOptions {
+        renames: Some(KeyRenames::new().rename("database", "storage.url.inner")),
+        ..Default::default()
+    };
+    let err = read(r#"{"database": "x", "storage": {"url": "y"}}"#, &options).unwrap_err();
+    assert_eq!(
+        err.message(),
+        "Cannot rename 'database' to 'storage.url.inner': a parent of 'storage.url.inner' is not a map"
+    );
+    assert!(err.details().contains_key("path"));
+}
+
+#[test]
+fn test_renames_before_schema_version() {
+    let options = ReadOptions {
+        renames: Some(KeyRenames::new().rename("version", "schema_version")),
+        schema_version: Some(cdumay_config::SchemaVersion::new(2)),
+        ..Default::default()
+    };
+    #[derive(Debug, Deserialize)]
+    struct Versioned {
+        schema_version: u64,
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"version": 1}"#).unwrap();
+    let versioned: Versioned = cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), &options).unwrap();
+    assert_eq!(versioned.schema_version, 1);
+}
@@ -0,0 +1,72 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{VaultSecret, VaultSecrets};
+
+fn secrets() -> VaultSecrets {
+    VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", r#"{"user": "admin", "password": "hunter2", "port": 5432}"#),
+        VaultSecret::new("db", "db_key", r#"{"user": "root", "password": "prod"}"#).with_env("prod"),
+        VaultSecret::new("token", "token_key", r#""s3cr3t""#),
+    ])
+}
+
+#[test]
+fn test_render_secrets() {
+    let template = "[database]\nuser = {{ secret \"db\" \"user\" }}\npassword = {{secret \"db\" \"password\"}}\nport = {{ secret \"db\" \"port\" }}\ntoken = {{ secret \"token\" }}\n";
+    let rendered = cdumay_config::render_secrets(template, &secrets(), &BTreeMap::new()).unwrap();
+    assert_eq!(
+        rendered.expose(),
+        "[database]\nuser = admin\npassword = hunter2\nport = 5432\ntoken = s3cr3t\n"
+    );
+
+    let context = BTreeMap::from([("env".to_string(), serde_value::Value::String("prod".to_string()))]);
+    let rendered = cdumay_config::render_secrets("{{ secret \"db\" \"user\" }}", &secrets(), &context).unwrap();
+    assert_eq!(rendered.expose(), "root");
+    let rendered = cdumay_config::render_secrets("{{ secret \"db\" }}", &secrets(), &context).unwrap();
+    assert_eq!(rendered.expose(), r#"{"password":"prod","user":"root"}"#);
+    assert_eq!(format!("{:?}", rendered), "***");
+}
+
+#[test]
+fn test_render_secrets_errors() {
+    let context = BTreeMap::new();
+    let err = cdumay_config::render_secrets("a\n  {{ env \"HOME\" }}", &secrets(), &context).unwrap_err();
+    assert!(err.message().starts_with("Unknown placeholder"));
+    assert_eq!(err.details()["line"], serde_value::Value::U64(2));
+    assert_eq!(err.details()["column"], serde_value::Value::U64(3));
+
+    let err = cdumay_config::render_secrets("{{ secret \"db }}", &secrets(), &context).unwrap_err();
+    assert!(err.message().starts_with("Malformed placeholder"));
+    let err = cdumay_config::render_secrets("{{ secret \"db\"", &secrets(), &context).unwrap_err();
+    assert!(err.message().starts_with("Unclosed placeholder"));
+
+    let err = cdumay_config::render_secrets("{{ secret \"missing\" }}", &secrets(), &context).unwrap_err();
+    assert_eq!(err.message(), "Invalid alias: missing");
+    let err = cdumay_config::render_secrets("{{ secret \"db\" \"host\" }}", &secrets(), &context).unwrap_err();
+    assert_eq!(err.message(), "Secret 'db' has no field 'host'");
+    let err = cdumay_config::render_secrets("{{ secret \"token\" \"user\" }}", &secrets(), &context).unwrap_err();
+    assert_eq!(err.details()["alias"], serde_value::Value::String("token".to_string()));
+}
+
+#[test]
+fn test_render_secrets_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let template = dir.path().join("app.ini.tpl");
+    let output = dir.path().join("app.ini");
+    std::fs::write(&template, "password={{ secret \"db\" \"password\" }}\n").unwrap();
+    let context = BTreeMap::new();
+    let path = cdumay_config::render_secrets_file(template.to_str().unwrap(), output.to_str().unwrap(), &secrets(), &context).unwrap();
+    assert_eq!(path, output);
+    assert_eq!(std::fs::read_to_string(&output).unwrap(), "password=hunter2\n");
+    #[cfg(unix)]
+    assert_eq!(
+        std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(&output).unwrap().permissions()) & 0o777,
+        0o600
+    );
+
+    std::fs::write(&template, "password={{ secret \"missing\" }}\n").unwrap();
+    std::fs::remove_file(&output).unwrap();
+    let err = cdumay_config::render_secrets_file(template.to_str().unwrap(), output.to_str().unwrap(), &secrets(), &context).unwrap_err();
+    assert_eq!(err.details()["path"], serde_value::Value::String(template.display().to_string()));
+    assert!(!output.exists());
+}
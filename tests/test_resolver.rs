@@ -0,0 +1,150 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use cdumay_config::{ChrootPathResolver, DefaultPathResolver, JsonManager, LockMode, Manager, PathResolver, ReadOptions, WriteOptions};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Settings {
+    name: String,
+}
+
+/// A resolver refusing every path, standing for a sandbox policy.
+#[derive(Debug)]
+struct DenyAll;
+
+impl PathResolver for DenyAll {
+    fn resolve(&self, path: &Path) -> std::io::Result<PathBuf> {
+        Err(std::io::Error::new(
+            std::io::ErrorKind::PermissionDenied,
+            format!("{} is outside the sandbox", path.display()),
+        ))
+    }
+}
+
+#[test]
+fn test_default_resolver_expands_tilde() {
+    let resolved = DefaultPathResolver.resolve(Path::new("~/app.json")).unwrap();
+    assert!(!resolved.starts_with("~"));
+    assert_eq!(DefaultPathResolver.resolve(Path::new("app.json")).unwrap(), PathBuf::from("app.json"));
+}
+
+#[test]
+fn test_chroot_resolver_confines_paths() {
+    let resolver = ChrootPathResolver::new("/sandbox");
+    assert_eq!(
+        resolver.resolve(Path::new("/etc/app.json")).unwrap(),
+        PathBuf::from("/sandbox/etc/app.json")
+    );
+    assert_eq!(
+        resolver.resolve(Path::new("conf/app.json")).unwrap(),
+        PathBuf::from("/sandbox/conf/app.json")
+    );
+    assert_eq!(
+        resolver.resolve(Path::new("/etc/../../../passwd")).unwrap(),
+        PathBuf::from("/sandbox/passwd")
+    );
+}
+
+#[test]
+fn test_manager_uses_resolver() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir(dir.path().join("etc")).unwrap();
+    let resolver: Arc<dyn PathResolver> = Arc::new(ChrootPathResolver::new(dir.path()));
+    let manager = JsonManager::new("/etc/app.json");
+    let data = Settings {
+        name: "hermetic".to_string(),
+    };
+
+    let write_options = WriteOptions {
+        resolver: Some(resolver.clone()),
+        lock: Some(LockMode::Try),
+        ..Default::default()
+    };
+    let written = manager.write_config_with(&data, &BTreeMap::new(), &write_options).unwrap();
+    assert_eq!(written, dir.path().join("etc/app.json"));
+    assert!(written.exists());
+    assert!(dir.path().join("etc/app.json.lock").exists());
+
+    let read_options = ReadOptions {
+        resolver: Some(resolver),
+        ..Default::default()
+    };
+    let read: Settings = manager.read_config_with(&BTreeMap::new(), &read_options).unwrap();
+    assert_eq!(read, data);
+}
+
+#[test]
+fn test_resolver_error() {
+    let manager = JsonManager::new("/etc/app.json");
+    let options = ReadOptions {
+        resolver: Some(Arc::new(DenyAll)),
+        ..Default::default()
+    };
+    let err = manager.read_config_with::<Settings>(&BTreeMap::new(), &options).unwrap_err();
+    assert!(err.message().contains("Failed to resolve path"));
+    assert_eq!(err.details().get("path"), Some(&serde_value::Value::String("/etc/app.json".to_string())));
+}
+
+#[test]
+fn test_open_error_reports_resolved_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let manager = JsonManager::new("/etc/missing.json");
+    let options = ReadOptions {
+        resolver: Some(Arc::new(ChrootPathResolver::new(dir.path()))),
+        ..Default::default()
+    };
+    let err = manager.read_config_with::<Settings>(&BTreeMap::new(), &options).unwrap_err();
+    assert!(err.message().contains("Failed to open file"));
+    assert_eq!(err.details().get("path"), Some(&serde_value::Value::String("/etc/missing.json".to_string())));
+    assert_eq!(
+        err.details().get("resolved_path"),
+        Some(&serde_value::Value::String(dir.path().join("etc/missing.json").display().to_string()))
+    );
+
+    let options = WriteOptions {
+        resolver: Some(Arc::new(ChrootPathResolver::new(dir.path()))),
+        ..Default::default()
+    };
+    let err = manager.write_config_with(&Settings { name: "app".to_string() }, &BTreeMap::new(), &options).unwrap_err();
+    assert!(err.message().contains("Failed to create file"));
+    assert_eq!(
+        err.details().get("resolved_path"),
+        Some(&serde_value::Value::String(dir.path().join("etc/missing.json").display().to_string()))
+    );
+}
+
+/// A resolver expanding `~` to a given home directory.
+#[derive(Debug)]
+struct HomeIn(PathBuf);
+
+impl PathResolver for HomeIn {
+    fn resolve(&self, path: &Path) -> std::io::Result<PathBuf> {
+        Ok(match path.strip_prefix("~") {
+            Ok(relative) => self.0.join(relative),
+            Err(_) => path.to_path_buf(),
+        })
+    }
+}
+
+#[test]
+fn test_config_functions_pass_tilde_to_resolver() {
+    let dir = tempfile::tempdir().unwrap();
+    let resolver: Arc<dyn PathResolver> = Arc::new(HomeIn(dir.path().to_path_buf()));
+    let data = Settings { name: "home".to_string() };
+
+    let write_options = WriteOptions {
+        resolver: Some(resolver.clone()),
+        ..Default::default()
+    };
+    let written = cdumay_config::write_config_with("~/app.json", None, &data, &BTreeMap::new(), &write_options).unwrap();
+    assert_eq!(written, dir.path().join("app.json"));
+
+    let read_options = ReadOptions {
+        resolver: Some(resolver),
+        ..Default::default()
+    };
+    let read: Settings = cdumay_config::read_config_with("~/app.json", None, &BTreeMap::new(), &read_options).unwrap();
+    assert_eq!(read, data);
+}
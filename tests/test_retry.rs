@@ -0,0 +1,46 @@
+use std::collections::BTreeMap;
+use std::time::{Duration, Instant};
+
+use cdumay_config::{JsonManager, Manager, ReadOptions, RetryPolicy, WriteOptions};
+
+#[test]
+fn test_retry_policy_default_is_bounded() {
+    let policy = RetryPolicy::default();
+    assert_eq!(policy.attempts, 5);
+    assert_eq!(policy.delay, Duration::from_millis(50));
+    assert_eq!(RetryPolicy::none().attempts, 1);
+}
+
+#[test]
+fn test_missing_file_is_not_retried() {
+    let manager = JsonManager::new("/nonexistent/app.json");
+    let options = ReadOptions {
+        retry: RetryPolicy {
+            attempts: 10,
+            delay: Duration::from_secs(1),
+        },
+        ..Default::default()
+    };
+    let start = Instant::now();
+    let result = manager.open_file_with(&BTreeMap::new(), &options);
+    assert!(result.unwrap_err().message().contains("Failed to open file"));
+    assert!(start.elapsed() < Duration::from_secs(1));
+}
+
+#[test]
+fn test_write_and_read_without_retries() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let manager = JsonManager::new(&path);
+    let write_options = WriteOptions {
+        retry: RetryPolicy::none(),
+        ..Default::default()
+    };
+    let read_options = ReadOptions {
+        retry: RetryPolicy::none(),
+        ..Default::default()
+    };
+    manager.write_config_with(&vec![1, 2, 3], &BTreeMap::new(), &write_options).unwrap();
+    let read: Vec<i32> = manager.read_config_with(&BTreeMap::new(), &read_options).unwrap();
+    assert_eq!(read, vec![1, 2, 3]);
+}
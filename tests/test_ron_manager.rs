@@ -0,0 +1,106 @@
+#![cfg(feature = "ron")]
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::io::{Cursor, Write};
+
+use cdumay_config::{ContentFormat, Manager, RonManager, VaultSecret, VaultSecrets};
+use serde_value::Value;
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TestConfig {
+    name: String,
+    value: i32,
+}
+
+fn default_context() -> BTreeMap<String, Value> {
+    BTreeMap::new()
+}
+
+#[test]
+fn test_ron_manager_new_and_path() {
+    let manager = RonManager::new("example.ron");
+    assert_eq!(manager.file_path(), std::path::Path::new("example.ron"));
+}
+
+#[test]
+fn test_ron_manager_read_str_success() {
+    let ron = r#"(name: "alpha", value: 42)"#;
+    let result: TestConfig = RonManager::read_str(ron, &default_context()).unwrap();
+    assert_eq!(result.name, "alpha");
+    assert_eq!(result.value, 42);
+}
+
+#[test]
+fn test_ron_manager_read_str_failure() {
+    let ron = r#"(name: "broken", value: "oops")"#;
+    let result: cdumay_core::Result<TestConfig> = RonManager::read_str(ron, &default_context());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_ron_manager_write_and_read() {
+    let data = TestConfig {
+        name: "gamma".to_string(),
+        value: 7,
+    };
+    let manager = RonManager::new("dummy.ron");
+    let mut buffer = Cursor::new(Vec::new());
+    manager.write(&mut buffer, &data, &default_context()).unwrap();
+
+    buffer.set_position(0);
+    let result: TestConfig = manager.read(buffer, &default_context()).unwrap();
+    assert_eq!(result, data);
+}
+
+#[test]
+fn test_ron_manager_write_failure() {
+    struct FailingWriter;
+
+    impl Write for FailingWriter {
+        fn write(&mut self, _: &[u8]) -> std::io::Result<usize> {
+            Err(std::io::Error::other("write error"))
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let data = TestConfig {
+        name: "fail".to_string(),
+        value: 0,
+    };
+    let manager = RonManager::new("fail.ron");
+    assert!(manager.write(FailingWriter, &data, &default_context()).is_err());
+}
+
+#[test]
+fn test_ron_read_write_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.ron");
+    let data = TestConfig {
+        name: "file".to_string(),
+        value: 3,
+    };
+    cdumay_config::write_config(path.to_str().unwrap(), Some(ContentFormat::RON), &data, &default_context()).unwrap();
+    let read: TestConfig = cdumay_config::read_config(path.to_str().unwrap(), Some(ContentFormat::RON), &default_context()).unwrap();
+    assert_eq!(read, data);
+}
+
+#[test]
+fn test_ron_vault_alias() {
+    let secrets = VaultSecrets::new(vec![VaultSecret::new("game", "game_key", r#"(name: "level", value: 1)"#)]);
+    let result: TestConfig = secrets.alias("game".to_string(), ContentFormat::RON, &default_context()).unwrap();
+    assert_eq!(result.name, "level");
+}
+
+#[test]
+fn test_ron_read_error_details_keep_path() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("broken.ron");
+    std::fs::write(&path, "(name: ").unwrap();
+    let err = cdumay_config::read_config::<TestConfig>(path.to_str().unwrap(), Some(ContentFormat::RON), &default_context()).unwrap_err();
+    assert_eq!(err.details().get("path"), Some(&Value::String(path.to_str().unwrap().to_string())));
+    assert!(err.details().contains_key("origin"));
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Upstream {
+    name: String,
+    port: u16,
+}
+
+fn upstreams() -> Vec<Upstream> {
+    vec![
+        Upstream {
+            name: "primary".to_string(),
+            port: 8080,
+        },
+        Upstream {
+            name: "backup".to_string(),
+            port: 8081,
+        },
+    ]
+}
+
+/// Writes then reads back a root-level sequence and a root-level scalar in the given format.
+fn round_trip(format: cdumay_config::ContentFormat, scalar: bool) {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(format!("upstreams.{}", format));
+    let path = path.to_str().unwrap();
+    cdumay_config::write_config(path, Some(format), upstreams(), &BTreeMap::new()).unwrap();
+    let read: Vec<Upstream> = cdumay_config::read_config(path, Some(format), &BTreeMap::new()).unwrap();
+    assert_eq!(read, upstreams(), "{}", format);
+    if scalar {
+        cdumay_config::write_config(path, Some(format), 42u32, &BTreeMap::new()).unwrap();
+        let read: u32 = cdumay_config::read_config(path, Some(format), &BTreeMap::new()).unwrap();
+        assert_eq!(read, 42, "{}", format);
+    }
+}
+
+#[test]
+fn test_root_values_json() {
+    round_trip(cdumay_config::ContentFormat::JSON, true);
+}
+
+#[test]
+fn test_root_values_ndjson() {
+    round_trip(cdumay_config::ContentFormat::NDJSON, false);
+}
+
+#[cfg(feature = "json5")]
+#[test]
+fn test_root_values_json5() {
+    round_trip(cdumay_config::ContentFormat::JSON5, true);
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_root_values_yaml() {
+    round_trip(cdumay_config::ContentFormat::YAML, true);
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_root_values_ron() {
+    round_trip(cdumay_config::ContentFormat::RON, true);
+}
+
+#[cfg(feature = "kdl")]
+#[test]
+fn test_root_values_kdl() {
+    round_trip(cdumay_config::ContentFormat::KDL, false);
+}
+
+#[cfg(feature = "msgpack")]
+#[test]
+fn test_root_values_msgpack() {
+    round_trip(cdumay_config::ContentFormat::MSGPACK, true);
+}
+
+#[cfg(feature = "plist")]
+#[test]
+fn test_root_values_plist() {
+    round_trip(cdumay_config::ContentFormat::PLIST, true);
+}
+
+#[cfg(feature = "kdl")]
+#[test]
+fn test_root_scalar_kdl_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("value.kdl");
+    let err = cdumay_config::write_config(path.to_str().unwrap(), Some(cdumay_config::ContentFormat::KDL), 42u32, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("root-level scalar"));
+    assert_eq!(err.details().get("root"), Some(&serde_value::Value::String("scalar".to_string())));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_root_sequence_toml_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("upstreams.toml");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "name = \"kept\"\n").unwrap();
+    let err = cdumay_config::write_config(path, Some(cdumay_config::ContentFormat::TOML), upstreams(), &BTreeMap::new()).unwrap_err();
+    assert_eq!(
+        err.message(),
+        "TOML documents cannot hold a root-level sequence, wrap it in a struct or a map"
+    );
+    assert_eq!(err.details().get("root"), Some(&serde_value::Value::String("sequence".to_string())));
+    assert_eq!(err.details().get("path"), Some(&serde_value::Value::String(path.to_string())));
+    assert_eq!(std::fs::read_to_string(path).unwrap(), "name = \"kept\"\n");
+
+    let err = cdumay_config::read_config::<Vec<Upstream>>(path, Some(cdumay_config::ContentFormat::TOML), &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("sequence"));
+}
+
+#[cfg(feature = "xml")]
+#[test]
+fn test_root_values_xml_rejected() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("upstreams.xml");
+    let path = path.to_str().unwrap();
+    let err = cdumay_config::write_config(path, Some(cdumay_config::ContentFormat::XML), upstreams(), &BTreeMap::new()).unwrap_err();
+    assert!(err.message().starts_with("XML documents cannot hold a root-level sequence"));
+    let err = cdumay_config::write_config(path, Some(cdumay_config::ContentFormat::XML), "text", &BTreeMap::new()).unwrap_err();
+    assert!(err.message().starts_with("XML documents cannot hold a root-level scalar"));
+}
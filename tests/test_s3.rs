@@ -0,0 +1,121 @@
+#![cfg(feature = "s3")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::{AwsCredentials, ContentFormat, RetryPolicy, S3Source};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Config {
+    host: String,
+    port: u16,
+}
+
+/// Serves the given responses, one per connection, returning the address of the server and
+/// the requests received (head and body).
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                if let Some(index) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break index + 4;
+                }
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_ascii_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            while request.len() < head_end + length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (address, handle)
+}
+
+#[test]
+fn test_s3_source() {
+    let (address, handle) = serve(vec![
+        ("503 Service Unavailable", ""),
+        ("200 OK", r#"{"host": "db.internal", "port": 5432}"#),
+    ]);
+    let source = S3Source::new("my-bucket", "app/prod v1.json")
+        .with_region("eu-west-3")
+        .with_endpoint(&address)
+        .with_credentials(AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY").with_session_token("session"))
+        .with_retry(RetryPolicy {
+            attempts: 3,
+            delay: std::time::Duration::from_millis(1),
+        });
+    assert_eq!(source.url(), format!("{}/my-bucket/app/prod%20v1.json", address));
+    let config: Config = source
+        .read_config_with(Some(ContentFormat::JSON), &BTreeMap::new(), &Default::default())
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            host: "db.internal".to_string(),
+            port: 5432
+        }
+    );
+
+    let requests = handle.join().unwrap();
+    assert_eq!(requests.len(), 2);
+    let request = requests[1].to_ascii_lowercase();
+    assert!(request.starts_with("get /my-bucket/app/prod%20v1.json http/1.1"));
+    assert!(request.contains("authorization: aws4-hmac-sha256 credential=akidexample/"));
+    assert!(request.contains("/eu-west-3/s3/aws4_request"));
+    assert!(request.contains("signedheaders=host;x-amz-content-sha256;x-amz-date;x-amz-security-token"));
+    assert!(request.contains("x-amz-security-token: session"));
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_s3_format_inference() {
+    let (address, handle) = serve(vec![("200 OK", "host: db.internal\nport: 5432\n")]);
+    let config: Config = S3Source::new("my-bucket", "app/prod.yaml")
+        .with_endpoint(&address)
+        .read_config_with(None, &BTreeMap::new(), &Default::default())
+        .unwrap();
+    assert_eq!(config.host, "db.internal");
+    handle.join().unwrap();
+}
+
+#[test]
+fn test_s3_errors() {
+    let (address, handle) = serve(vec![("404 Not Found", "<Error><Code>NoSuchKey</Code></Error>")]);
+    let err = S3Source::new("my-bucket", "app/missing.json")
+        .with_endpoint(&address)
+        .fetch(&BTreeMap::new())
+        .unwrap_err();
+    assert_eq!(err.details()["status"], serde_value::Value::U16(404));
+    assert_eq!(err.details()["bucket"], serde_value::Value::String("my-bucket".to_string()));
+    assert_eq!(err.details()["key"], serde_value::Value::String("app/missing.json".to_string()));
+    assert!(!handle.join().unwrap()[0].to_ascii_lowercase().contains("authorization:"));
+
+    assert!(S3Source::is_url("S3://my-bucket/app.json"));
+    assert!(!S3Source::is_url("/etc/app.json"));
+    assert!(S3Source::from_env("s3://my-bucket", &BTreeMap::new()).is_err());
+    let err = cdumay_config::read_config::<Config>("s3:///app.json", None, &BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["url"], serde_value::Value::String("s3:///app.json".to_string()));
+}
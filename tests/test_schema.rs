@@ -0,0 +1,40 @@
+#![cfg(feature = "schemars")]
+
+use std::collections::BTreeMap;
+
+#[allow(dead_code)]
+#[derive(serde::Deserialize, schemars::JsonSchema)]
+struct Server {
+    /// Listening port.
+    port: u16,
+    #[serde(default)]
+    hosts: Vec<String>,
+}
+
+#[test]
+fn test_schema_for() {
+    let schema = serde_json::to_value(cdumay_config::schema_for::<Server>()).unwrap();
+    assert_eq!(schema["title"], "Server");
+    assert_eq!(schema["properties"]["port"]["description"], "Listening port.");
+    assert_eq!(schema["required"], serde_json::json!(["port"]));
+}
+
+#[test]
+fn test_write_schema() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("server.schema.json");
+    let written = cdumay_config::write_schema::<Server>(path.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    let schema: serde_json::Value = cdumay_config::read_config(written.to_str().unwrap(), None, &BTreeMap::new()).unwrap();
+    assert_eq!(schema, serde_json::to_value(cdumay_config::schema_for::<Server>()).unwrap());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_write_schema_yaml() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("server.schema.yaml");
+    cdumay_config::write_schema::<Server>(path.to_str().unwrap(), Some(cdumay_config::ContentFormat::YAML), &BTreeMap::new()).unwrap();
+    let schema: serde_json::Value =
+        cdumay_config::read_config(path.to_str().unwrap(), Some(cdumay_config::ContentFormat::YAML), &BTreeMap::new()).unwrap();
+    assert_eq!(schema["properties"]["port"]["description"], "Listening port.");
+}
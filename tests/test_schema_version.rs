@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use cdumay_config::{ReadOptions, SchemaVersion};
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct TestConfig {
+    schema_version: u64,
+    name: String,
+}
+
+fn write_temp(content: &str) -> tempfile::NamedTempFile {
+    let file = tempfile::Builder::new().suffix(".json").tempfile().expect("temp file");
+    std::fs::write(file.path(), content).unwrap();
+    file
+}
+
+fn options(supported: u64) -> ReadOptions {
+    ReadOptions {
+        schema_version: Some(SchemaVersion::new(supported)),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_schema_version_supported() {
+    let file = write_temp(r#"{"schema_version": 2, "name": "app"}"#);
+    let config: TestConfig = cdumay_config::read_config_with(file.path().to_str().unwrap(), None, &BTreeMap::new(), &options(2)).unwrap();
+    assert_eq!(config.name, "app");
+}
+
+#[test]
+fn test_schema_version_newer_is_rejected() {
+    let file = write_temp(r#"{"schema_version": 3, "name": "app"}"#);
+    let result: cdumay_core::Result<TestConfig> =
+        cdumay_config::read_config_with(file.path().to_str().unwrap(), None, &BTreeMap::new(), &options(2));
+    let err = result.unwrap_err();
+    assert!(err.class().contains("SchemaVersionError"));
+    assert!(err.message().contains("newer than the highest supported version 2"));
+    assert_eq!(err.details().get("supported"), Some(&serde_value::Value::U64(2)));
+}
+
+#[test]
+fn test_schema_version_missing_field() {
+    let file = write_temp(r#"{"name": "app"}"#);
+    let result: cdumay_core::Result<serde_value::Value> =
+        cdumay_config::read_config_with(file.path().to_str().unwrap(), None, &BTreeMap::new(), &options(1));
+    assert!(result.unwrap_err().message().contains("Missing required field 'schema_version'"));
+}
+
+#[test]
+fn test_schema_version_custom_field_as_string() {
+    let value = serde_value::to_value(BTreeMap::from([("version", "4")])).unwrap();
+    let check = SchemaVersion::new(5).with_field("version");
+    assert_eq!(check.check(&value, &BTreeMap::new()).unwrap(), 4);
+    assert!(SchemaVersion::new(3).with_field("version").check(&value, &BTreeMap::new()).is_err());
+}
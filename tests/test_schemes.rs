@@ -0,0 +1,89 @@
+use cdumay_config::{ReadOptions, SchemeRegistry, SchemeSource, SecretsSource, VaultConfig, VaultSecret, VaultSecrets, read_config};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Database {
+    host: String,
+    port: u16,
+}
+
+struct Memory;
+
+impl SchemeSource for Memory {
+    fn fetch(&self, uri: &str, _context: &BTreeMap<String, serde_value::Value>, _options: &ReadOptions) -> cdumay_core::Result<Vec<u8>> {
+        Ok(format!(r#"{{"host": "{}", "port": 5432}}"#, uri.trim_start_matches("memory://")).into_bytes())
+    }
+}
+
+#[test]
+fn test_file_scheme() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("db.json");
+    std::fs::write(&path, r#"{"host": "localhost", "port": 5432}"#).unwrap();
+
+    let db: Database = read_config(&format!("file://{}", path.display()), None, &BTreeMap::new()).unwrap();
+    assert_eq!(db.host, "localhost");
+}
+
+#[test]
+fn test_registered_schemes() {
+    SchemeRegistry::global().register("Memory", Memory);
+    let db: Database = read_config("memory://db.internal", None, &BTreeMap::new()).unwrap();
+    assert_eq!(
+        db,
+        Database {
+            host: "db.internal".to_string(),
+            port: 5432
+        }
+    );
+
+    let vault = VaultConfig {
+        secrets: Some(VaultSecrets::new(vec![
+            VaultSecret::new("db", "db", r#"{"host": "dev.internal", "port": 5432}"#),
+            VaultSecret::new("db", "db", r#"{"host": "prod.internal", "port": 6432}"#).with_env("prod"),
+        ])),
+    };
+    SchemeRegistry::global().register("vault", SecretsSource::new(vault));
+    let context = BTreeMap::from([("env".to_string(), serde_value::Value::String("prod".to_string()))]);
+    let db: Database = read_config("vault://db", None, &context).unwrap();
+    assert_eq!(db.port, 6432);
+    assert!(read_config::<Database>("vault://missing", None, &context).is_err());
+    assert!(SchemeRegistry::global().schemes().contains(&"memory".to_string()));
+}
+
+/// A source serving the same YAML document for every URI.
+#[cfg(feature = "yaml")]
+struct YamlMemory;
+
+#[cfg(feature = "yaml")]
+impl SchemeSource for YamlMemory {
+    fn fetch(&self, _uri: &str, _context: &BTreeMap<String, serde_value::Value>, _options: &ReadOptions) -> cdumay_core::Result<Vec<u8>> {
+        Ok(b"host: yaml.internal\nport: 5432\n".to_vec())
+    }
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_scheme_format_inference() {
+    SchemeRegistry::global().register("yaml-memory", YamlMemory);
+    let db: Database = read_config("yaml-memory://bucket/app/prod.yaml?version=2", None, &BTreeMap::new()).unwrap();
+    assert_eq!(db.host, "yaml.internal");
+    let db: Database = read_config("yaml-memory://bucket/app/prod.yml#main", None, &BTreeMap::new()).unwrap();
+    assert_eq!(db.port, 5432);
+    // Without extension, remote documents are decoded as JSON.
+    assert!(read_config::<Database>("yaml-memory://bucket/app/prod", None, &BTreeMap::new()).is_err());
+    assert!(read_config::<Database>("yaml-memory://app.yaml", None, &BTreeMap::new()).is_err());
+}
+
+#[test]
+fn test_scheme_errors() {
+    let err = read_config::<Database>("env://CDUMAY_CONFIG_UNSET_VARIABLE", None, &BTreeMap::new()).unwrap_err();
+    assert_eq!(
+        err.details()["url"],
+        serde_value::Value::String("env://CDUMAY_CONFIG_UNSET_VARIABLE".to_string())
+    );
+
+    let err = read_config::<Database>("ftp://config.internal/db.json", None, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("Unsupported scheme 'ftp'"));
+}
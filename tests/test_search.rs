@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+#[test]
+fn test_read_first_config() {
+    let dir = tempfile::tempdir().unwrap();
+    let local = dir.path().join("app.json");
+    let user = dir.path().join("user.json");
+    let system = dir.path().join("system.json");
+    std::fs::write(&user, r#"{"source": "user"}"#).unwrap();
+    std::fs::write(&system, r#"{"source": "system"}"#).unwrap();
+    let context = BTreeMap::new();
+
+    let config: serde_json::Value = cdumay_config::read_first_config(
+        &[local.to_str().unwrap(), user.to_str().unwrap(), system.to_str().unwrap()],
+        None,
+        &context,
+    )
+    .unwrap();
+    assert_eq!(config["source"], "user");
+
+    std::fs::write(&local, "{").unwrap();
+    assert!(cdumay_config::read_first_config::<serde_json::Value>(&[local.to_str().unwrap(), user.to_str().unwrap()], None, &context).is_err());
+}
+
+#[test]
+fn test_read_first_config_missing() {
+    let dir = tempfile::tempdir().unwrap();
+    let first = dir.path().join("app.json");
+    let second = dir.path().join("etc").join("app.json");
+    let context = BTreeMap::from([("app".to_string(), serde_value::Value::String("demo".to_string()))]);
+
+    let err =
+        cdumay_config::read_first_config::<serde_json::Value>(&[first.to_str().unwrap(), second.to_str().unwrap()], None, &context).unwrap_err();
+    assert_eq!(
+        err.message(),
+        format!("No configuration file found, tried: {}, {}", first.display(), second.display())
+    );
+    assert_eq!(
+        err.details()["tried"],
+        serde_value::Value::Seq(vec![
+            serde_value::Value::String(first.display().to_string()),
+            serde_value::Value::String(second.display().to_string()),
+        ])
+    );
+    assert_eq!(err.details()["app"], serde_value::Value::String("demo".to_string()));
+}
+
+#[test]
+fn test_read_config_dir() {
+    let dir = tempfile::tempdir().unwrap();
+    let conf = dir.path().join("conf.d");
+    std::fs::create_dir_all(conf.join("disabled.json")).unwrap();
+    std::fs::write(conf.join("50-local.json"), r#"{"database": {"host": "db.local"}, "plugins": ["audit"]}"#).unwrap();
+    std::fs::write(
+        conf.join("10-defaults.json"),
+        r#"{"database": {"host": "localhost", "port": 5432}, "plugins": ["base"]}"#,
+    )
+    .unwrap();
+    std::fs::write(conf.join("90-override.json"), r#"{"database": {"port": 6432}}"#).unwrap();
+    std::fs::write(conf.join("README"), "not a configuration file").unwrap();
+    let context = BTreeMap::new();
+
+    let pattern = conf.join("*.json");
+    let config: serde_json::Value = cdumay_config::read_config_dir(pattern.to_str().unwrap(), None, &context).unwrap();
+    assert_eq!(
+        config,
+        serde_json::json!({"database": {"host": "db.local", "port": 6432}, "plugins": ["audit"]})
+    );
+
+    let pattern = dir.path().join("missing.d").join("*.json");
+    let config: serde_json::Value = cdumay_config::read_config_dir(pattern.to_str().unwrap(), None, &context).unwrap();
+    assert_eq!(config, serde_json::json!({}));
+
+    let err = cdumay_config::read_config_dir::<serde_json::Value>("conf.d/[*.json", None, &context).unwrap_err();
+    assert!(err.message().starts_with("Invalid pattern 'conf.d/[*.json'"));
+    assert_eq!(err.details()["pattern"], serde_value::Value::String("conf.d/[*.json".to_string()));
+
+    std::fs::write(conf.join("60-broken.json"), "{").unwrap();
+    let pattern = conf.join("*.json");
+    let err = cdumay_config::read_config_dir::<serde_json::Value>(pattern.to_str().unwrap(), None, &context).unwrap_err();
+    assert_eq!(
+        err.details()["path"],
+        serde_value::Value::String(conf.join("60-broken.json").display().to_string())
+    );
+}
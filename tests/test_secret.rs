@@ -0,0 +1,28 @@
+use cdumay_config::{Secret, SecretString, VaultSecret};
+
+#[derive(serde::Deserialize, Debug)]
+struct Credentials {
+    username: String,
+    password: SecretString,
+}
+
+#[test]
+fn test_secret_masked() {
+    let secret = SecretString::from("hunter2".to_string());
+    assert_eq!(format!("{:?}", secret), "***");
+    assert_eq!(format!("{}", secret), "***");
+    assert_eq!(secret.expose(), "hunter2");
+    assert_eq!(secret.clone().expose(), "hunter2");
+    assert_eq!(Secret::new(vec![1u8, 2, 3]).expose(), &vec![1, 2, 3]);
+
+    let secret = VaultSecret::new("db", "db_key", "hunter2");
+    assert!(!format!("{:?}", secret).contains("hunter2"));
+}
+
+#[test]
+fn test_secret_deserialize() {
+    let credentials: Credentials = serde_json::from_str(r#"{"username": "admin", "password": "hunter2"}"#).unwrap();
+    assert_eq!(credentials.username, "admin");
+    assert_eq!(credentials.password.expose(), "hunter2");
+    assert!(!format!("{:?}", credentials).contains("hunter2"));
+}
@@ -0,0 +1,67 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use cdumay_config::{capture_environment, load_from_snapshot, EnvironmentSnapshot};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct AppConfig {
+    name: String,
+    server: Server,
+}
+
+#[test]
+fn test_capture_and_replay_layers() {
+    let dir = tempfile::tempdir().unwrap();
+    let base = dir.path().join("base.json");
+    let prod = dir.path().join("prod.json");
+    std::fs::write(&base, r#"{"name": "app", "server": {"host": "localhost", "port": 80}}"#).unwrap();
+    std::fs::write(&prod, r#"{"server": {"port": 8080}}"#).unwrap();
+
+    let mut context = BTreeMap::new();
+    context.insert("env".to_string(), serde_value::Value::String("prod".to_string()));
+    let snapshot = capture_environment(&[(base.to_str().unwrap(), None), (prod.to_str().unwrap(), None)], &context).unwrap();
+    assert_eq!(snapshot.crate_version, env!("CARGO_PKG_VERSION"));
+    assert_eq!(snapshot.sources.len(), 2);
+    assert_eq!(snapshot.sources[0].sha256.len(), 64);
+
+    // The snapshot survives a round trip and no longer needs the original files.
+    let serialized = serde_json::to_string(&snapshot).unwrap();
+    drop(dir);
+    let replayed: EnvironmentSnapshot = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(replayed, snapshot);
+
+    let config: AppConfig = load_from_snapshot(&replayed).unwrap();
+    assert_eq!(
+        config,
+        AppConfig {
+            name: "app".to_string(),
+            server: Server {
+                host: "localhost".to_string(),
+                port: 8080
+            }
+        }
+    );
+}
+
+#[test]
+fn test_replay_rejects_tampered_content() {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), r#"{"name": "app"}"#).unwrap();
+
+    let mut snapshot = capture_environment(&[(file.path().to_str().unwrap(), None)], &BTreeMap::new()).unwrap();
+    snapshot.sources[0].content = r#"{"name": "other"}"#.to_string();
+    let result: cdumay_core::Result<serde_value::Value> = load_from_snapshot(&snapshot);
+    assert!(result.unwrap_err().message().contains("does not match its digest"));
+}
+
+#[test]
+fn test_capture_missing_file() {
+    let result = capture_environment(&[("/nonexistent/app.json", None)], &BTreeMap::new());
+    assert!(result.is_err());
+}
@@ -0,0 +1,81 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{Manager, ReadOptions, UnknownKeyPolicy};
+use serde::Deserialize;
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Level {
+    Debug,
+    Info,
+    Warning,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Server {
+    host: String,
+    port: u16,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Deserialize)]
+struct Config {
+    server: Server,
+    level: Option<Level>,
+}
+
+fn suggestion(err: &cdumay_core::Error) -> Option<serde_value::Value> {
+    err.details().get("suggestion").cloned()
+}
+
+fn text(value: &str) -> Option<serde_value::Value> {
+    Some(serde_value::Value::String(value.to_string()))
+}
+
+#[test]
+fn test_suggestions_unknown_field() {
+    let content = r#"{"server": {"host": "localhost", "porrt": 80}}"#;
+    let err = cdumay_config::JsonManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_eq!(suggestion(&err), text("unknown key `porrt`, did you mean `port`?"));
+}
+
+#[test]
+fn test_suggestions_unknown_variant() {
+    let content = r#"{"server": {"host": "localhost", "port": 80}, "level": "warnig"}"#;
+    let err = cdumay_config::JsonManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert_eq!(suggestion(&err), text("unknown value `warnig`, did you mean `warning`?"));
+}
+
+#[test]
+fn test_suggestions_missing_field() {
+    #[allow(dead_code)]
+    #[derive(Debug, Deserialize)]
+    struct Listener {
+        address: String,
+        timeout: u64,
+    }
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.json");
+    std::fs::write(&path, r#"{"listeners": [{"address": "0.0.0.0", "timout": 5}]}"#).unwrap();
+    for policy in [UnknownKeyPolicy::Warn, UnknownKeyPolicy::Deny] {
+        let options = ReadOptions {
+            unknown_keys: policy,
+            ..Default::default()
+        };
+        let err =
+            cdumay_config::read_config_with::<BTreeMap<String, Vec<Listener>>>(path.to_str().unwrap(), None, &BTreeMap::new(), &options).unwrap_err();
+        assert!(err.message().to_lowercase().contains("missing field"), "{}", err.message());
+        assert_eq!(suggestion(&err), text("unknown key `timout`, did you mean `timeout`?"));
+    }
+}
+
+#[test]
+fn test_suggestions_not_set_for_unrelated_names() {
+    let content = r#"{"server": {"host": "localhost", "tls": true}}"#;
+    let err = cdumay_config::JsonManager::read_str::<Config>(content, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("unknown field `tls`"), "{}", err.message());
+    assert_eq!(suggestion(&err), None);
+}
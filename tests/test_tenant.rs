@@ -0,0 +1,90 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use cdumay_config::TenantConfigStore;
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Limits {
+    rps: u32,
+}
+
+fn tenant_dir(root: &std::path::Path, tenant: &str, rps: u32) {
+    std::fs::create_dir_all(root.join(tenant)).unwrap();
+    std::fs::write(root.join(tenant).join("limits.json"), format!(r#"{{"rps": {}}}"#, rps)).unwrap();
+}
+
+#[test]
+fn test_tenant_store_from_directory() {
+    let root = tempfile::tempdir().unwrap();
+    tenant_dir(root.path(), "acme", 10);
+    tenant_dir(root.path(), "globex", 20);
+    let store = TenantConfigStore::<Limits>::from_directory(root.path().to_str().unwrap(), "limits.json", None, BTreeMap::new(), 10);
+    assert!(store.is_empty());
+
+    assert_eq!(store.get("acme").unwrap().rps, 10);
+    assert_eq!(store.get("globex").unwrap().rps, 20);
+    assert_eq!(store.tenants(), vec!["acme", "globex"]);
+
+    tenant_dir(root.path(), "acme", 11);
+    tenant_dir(root.path(), "globex", 21);
+    assert!(store.reload_all().is_empty());
+    assert_eq!(store.get("acme").unwrap().rps, 11);
+    assert_eq!(store.get("globex").unwrap().rps, 21);
+}
+
+#[test]
+fn test_tenant_store_rejects_invalid_ids() {
+    let root = tempfile::tempdir().unwrap();
+    let store = TenantConfigStore::<Limits>::from_directory(root.path().to_str().unwrap(), "limits.json", None, BTreeMap::new(), 10);
+    for tenant in ["", "..", "../etc", "a/b", "."] {
+        assert!(store.get(tenant).is_err(), "tenant id {:?} should be rejected", tenant);
+    }
+    assert!(store.get("missing").is_err());
+    assert!(store.is_empty());
+}
+
+#[test]
+fn test_tenant_store_lru_eviction() {
+    let loads = Arc::new(AtomicUsize::new(0));
+    let counter = loads.clone();
+    let store = TenantConfigStore::new(2, move |tenant: &str| {
+        counter.fetch_add(1, Ordering::SeqCst);
+        Ok(tenant.len())
+    });
+
+    assert_eq!(*store.get("a").unwrap(), 1);
+    assert_eq!(*store.get("bb").unwrap(), 2);
+    assert_eq!(*store.get("a").unwrap(), 1);
+    assert_eq!(loads.load(Ordering::SeqCst), 2);
+
+    // "bb" is the least recently used tenant.
+    assert_eq!(*store.get("ccc").unwrap(), 3);
+    assert_eq!(store.tenants(), vec!["a", "ccc"]);
+    assert_eq!(*store.get("bb").unwrap(), 2);
+    assert_eq!(loads.load(Ordering::SeqCst), 4);
+
+    assert!(store.evict("bb"));
+    assert!(!store.evict("bb"));
+    assert_eq!(store.len(), 1);
+}
+
+#[test]
+fn test_tenant_store_reload_all_reports_failures() {
+    let fail = Arc::new(std::sync::atomic::AtomicBool::new(false));
+    let flag = fail.clone();
+    let store = TenantConfigStore::new(10, move |tenant: &str| {
+        if flag.load(Ordering::SeqCst) && tenant == "broken" {
+            return Err(cdumay_config::ConfigurationFileError::new().with_message("boom".to_string()).into());
+        }
+        Ok(tenant.to_string())
+    });
+    store.get("ok").unwrap();
+    store.get("broken").unwrap();
+
+    fail.store(true, Ordering::SeqCst);
+    let failures = store.reload_all();
+    assert_eq!(failures.keys().collect::<Vec<_>>(), vec!["broken"]);
+    assert_eq!(*store.get("broken").unwrap(), "broken");
+}
@@ -0,0 +1,153 @@
+#![cfg(feature = "toml")]
+
+use std::collections::BTreeMap;
+
+use cdumay_config::TomlDocument;
+use serde::{Deserialize, Serialize};
+
+const CONTENT: &str = r#"# Service configuration
+name = "api" # public name
+
+[server]
+# Listening address
+host = "0.0.0.0"
+port = 8080   # default port
+
+[database]
+url = "postgres://localhost/app"
+"#;
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct Tls {
+    cert: String,
+    key: String,
+}
+
+fn document() -> TomlDocument {
+    TomlDocument::parse("service.toml", CONTENT, &BTreeMap::new()).unwrap()
+}
+
+#[test]
+fn test_toml_document_roundtrip_untouched() {
+    assert_eq!(document().to_string(), CONTENT);
+}
+
+#[test]
+fn test_toml_document_get() {
+    let document = document();
+    assert_eq!(document.get::<u16>("server.port", &BTreeMap::new()).unwrap(), Some(8080));
+    assert_eq!(document.get::<String>("name", &BTreeMap::new()).unwrap(), Some("api".to_string()));
+    assert_eq!(document.get::<u16>("server.missing", &BTreeMap::new()).unwrap(), None);
+    assert_eq!(document.get::<u16>("missing.port", &BTreeMap::new()).unwrap(), None);
+    let table: BTreeMap<String, String> = document.get("database", &BTreeMap::new()).unwrap().unwrap();
+    assert_eq!(table["url"], "postgres://localhost/app");
+    let err = document.get::<u16>("server.host", &BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["key"], serde_value::Value::String("server.host".to_string()));
+}
+
+#[test]
+fn test_toml_document_set_keeps_formatting() {
+    let mut document = document();
+    document.set("server.port", 9090, &BTreeMap::new()).unwrap();
+    document.set("name", "gateway", &BTreeMap::new()).unwrap();
+    assert_eq!(
+        document.to_string(),
+        CONTENT
+            .replace("port = 8080   # default port", "port = 9090   # default port")
+            .replace("\"api\"", "\"gateway\"")
+    );
+}
+
+#[test]
+fn test_toml_document_set_new_keys() {
+    let mut document = document();
+    document.set("server.workers", 4, &BTreeMap::new()).unwrap();
+    document
+        .set(
+            "server.tls",
+            Tls {
+                cert: "cert.pem".to_string(),
+                key: "key.pem".to_string(),
+            },
+            &BTreeMap::new(),
+        )
+        .unwrap();
+    document.set("cache.redis.url", "redis://localhost", &BTreeMap::new()).unwrap();
+    let content = document.to_string();
+    assert!(content.starts_with(CONTENT.split("\n[database]").next().unwrap()), "{}", content);
+    assert!(content.contains("port = 8080   # default port\nworkers = 4\n"), "{}", content);
+    assert!(
+        content.contains("\n[server.tls]\ncert = \"cert.pem\"\nkey = \"key.pem\"\n"),
+        "{}",
+        content
+    );
+    assert!(content.contains("\n[cache.redis]\nurl = \"redis://localhost\"\n"), "{}", content);
+    assert!(!content.contains("[cache]\n"), "{}", content);
+    let tls: Tls = document.get("server.tls", &BTreeMap::new()).unwrap().unwrap();
+    assert_eq!(tls.cert, "cert.pem");
+}
+
+#[test]
+fn test_toml_document_set_table_keeps_comments() {
+    let mut document = document();
+    document
+        .set("server", BTreeMap::from([("host", "127.0.0.1"), ("port", "80")]), &BTreeMap::new())
+        .unwrap();
+    let content = document.to_string();
+    assert!(
+        content.contains("[server]\n# Listening address\nhost = \"127.0.0.1\"\nport = \"80\"   # default port\n"),
+        "{}",
+        content
+    );
+}
+
+#[test]
+fn test_toml_document_set_not_a_table() {
+    let mut document = document();
+    let err = document.set("name.first", "a", &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("'name' is not a table"), "{}", err.message());
+}
+
+#[test]
+fn test_toml_document_remove() {
+    let mut document = document();
+    assert!(document.remove("server.port"));
+    assert!(!document.remove("server.port"));
+    assert!(!document.remove("missing.port"));
+    assert!(!document.to_string().contains("port"));
+}
+
+#[test]
+fn test_toml_document_invalid_content() {
+    let err = TomlDocument::parse("service.toml", "name = ", &BTreeMap::new()).unwrap_err();
+    assert!(err.message().starts_with("Invalid TOML content"), "{}", err.message());
+    assert_eq!(err.details()["path"], serde_value::Value::String("service.toml".to_string()));
+}
+
+#[test]
+fn test_toml_document_open_and_save() {
+    #[derive(Debug, Deserialize)]
+    struct Server {
+        port: u16,
+    }
+    #[derive(Debug, Deserialize)]
+    struct Service {
+        server: Server,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("service.toml");
+    std::fs::write(&path, CONTENT).unwrap();
+    let mut document = TomlDocument::open(&path, &BTreeMap::new()).unwrap();
+    document.set("server.port", 9090, &BTreeMap::new()).unwrap();
+    assert_eq!(document.save(&BTreeMap::new()).unwrap(), path);
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.starts_with("# Service configuration\n"));
+    assert!(content.contains("port = 9090   # default port"));
+    let service: Service = TomlDocument::open(&path, &BTreeMap::new())
+        .unwrap()
+        .deserialize(&BTreeMap::new())
+        .unwrap();
+    assert_eq!(service.server.port, 9090);
+    assert!(TomlDocument::open(dir.path().join("missing.toml"), &BTreeMap::new()).is_err());
+}
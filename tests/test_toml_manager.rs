@@ -1,3 +1,5 @@
+#![cfg(feature = "toml")]
+
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::io::{Cursor, Write};
@@ -15,12 +17,24 @@ fn default_context() -> BTreeMap<String, Value> {
     BTreeMap::new()
 }
 
+// Callers passing a `String` and reading the deprecated `path()` keep compiling.
 #[test]
+#[allow(deprecated, clippy::unnecessary_to_owned)]
 fn test_toml_manager_new_and_path() {
     let manager = TomlManager::new("example.toml".to_string());
     assert_eq!(manager.path(), "example.toml");
 }
 
+#[test]
+fn test_toml_manager_new_from_path() {
+    let manager = TomlManager::new(std::path::Path::new("example.toml"));
+    assert_eq!(manager.file_path(), std::path::Path::new("example.toml"));
+
+    let path = std::path::PathBuf::from("dir").join("example.toml");
+    let manager = TomlManager::new(&path);
+    assert_eq!(manager.file_path(), path.as_path());
+}
+
 #[test]
 fn test_toml_manager_read_str_success() {
     let toml = r#"name = "alpha"
@@ -46,7 +60,7 @@ fn test_toml_manager_read_success() {
 value = 100"#;
     let reader = Cursor::new(toml);
     let context = default_context();
-    let manager = TomlManager::new("reader.toml".to_string());
+    let manager = TomlManager::new("reader.toml");
     let result: TestConfig = manager.read(reader, &context).unwrap();
     assert_eq!(result.name, "reader");
     assert_eq!(result.value, 100);
@@ -58,7 +72,7 @@ fn test_toml_manager_read_invalid_toml() {
 value = 100"#;
     let reader = Cursor::new(toml);
     let context = default_context();
-    let manager = TomlManager::new("broken.toml".to_string());
+    let manager = TomlManager::new("broken.toml");
     let result: cdumay_core::Result<TestConfig> = manager.read(reader, &context);
     assert!(result.is_err());
 }
@@ -71,7 +85,7 @@ fn test_toml_manager_write_success() {
     };
 
     let context = default_context();
-    let manager = TomlManager::new("write.toml".to_string());
+    let manager = TomlManager::new("write.toml");
     let mut buffer = Cursor::new(Vec::new());
 
     let result = manager.write(&mut buffer, &config, &context);
@@ -84,7 +98,7 @@ fn test_toml_manager_write_failure_on_write() {
 
     impl Write for FailingWriter {
         fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-            Err(std::io::Error::new(std::io::ErrorKind::Other, "write error"))
+            Err(std::io::Error::other("write error"))
         }
         fn flush(&mut self) -> std::io::Result<()> {
             Ok(())
@@ -96,8 +110,18 @@ fn test_toml_manager_write_failure_on_write() {
         value: 0,
     };
     let context = default_context();
-    let manager = TomlManager::new("fail.toml".to_string());
+    let manager = TomlManager::new("fail.toml");
 
     let result = manager.write(FailingWriter, &config, &context);
     assert!(result.is_err());
 }
+
+#[test]
+fn test_toml_manager_read_bytes() {
+    let context = BTreeMap::new();
+    let result: TestConfig = TomlManager::read_bytes(b"name = \"bytes\"\nvalue = 5\n", &context).unwrap();
+    assert_eq!(result.name, "bytes");
+
+    let invalid: cdumay_core::Result<TestConfig> = TomlManager::read_bytes(&[0xff, 0xfe], &context);
+    assert!(invalid.unwrap_err().message().contains("Invalid UTF-8 content"));
+}
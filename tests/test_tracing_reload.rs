@@ -0,0 +1,34 @@
+#![cfg(feature = "tracing-reload")]
+
+use serde_json::json;
+use tracing_subscriber::{EnvFilter, Registry, reload};
+
+use cdumay_config::{LiveConfig, apply_log_filter, bind_log_filter};
+
+#[test]
+fn test_apply_log_filter() {
+    let (layer, handle) = reload::Layer::<EnvFilter, Registry>::new(EnvFilter::new("info"));
+    let config = json!({"log": {"filter": "debug,hyper=warn"}});
+    assert!(apply_log_filter(&config, "log.filter", &handle).unwrap());
+    assert_eq!(handle.with_current(|filter| filter.to_string()).unwrap(), "hyper=warn,debug");
+
+    assert!(!apply_log_filter(&json!({"log": {}}), "log.filter", &handle).unwrap());
+    assert!(apply_log_filter(&json!({"log": {"filter": 3}}), "log.filter", &handle).is_err());
+    assert!(apply_log_filter(&json!({"log": {"filter": "=="}}), "log.filter", &handle).is_err());
+    drop(layer);
+}
+
+#[test]
+fn test_bind_log_filter_follows_reloads() {
+    let (_layer, handle) = reload::Layer::<EnvFilter, Registry>::new(EnvFilter::new("info"));
+    let live = LiveConfig::new(|| Ok(json!({"log": {"filter": "warn"}}))).unwrap();
+    bind_log_filter(&live, "log.filter", handle.clone()).unwrap();
+    assert_eq!(handle.with_current(|filter| filter.to_string()).unwrap(), "warn");
+
+    live.store(json!({"log": {"filter": "trace"}}));
+    assert_eq!(handle.with_current(|filter| filter.to_string()).unwrap(), "trace");
+
+    // Invalid filters are ignored on reload.
+    live.store(json!({"log": {"filter": "=="}}));
+    assert_eq!(handle.with_current(|filter| filter.to_string()).unwrap(), "trace");
+}
@@ -0,0 +1,55 @@
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+use cdumay_config::{read_config_tracked, ReadOptions};
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Listener {
+    port: u16,
+}
+
+#[derive(Debug, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    listeners: Vec<Listener>,
+    tls: Option<Listener>,
+}
+
+fn write_temp(content: &str) -> tempfile::NamedTempFile {
+    let file = tempfile::NamedTempFile::new().unwrap();
+    std::fs::write(file.path(), content).unwrap();
+    file
+}
+
+#[test]
+fn test_tracked_reports_unused_keys() {
+    let file = write_temp(
+        r#"{
+            "host": "localhost",
+            "legacy_mode": true,
+            "listeners": [{"port": 80}, {"port": 443, "http2": true}],
+            "tls": {"port": 8443, "cert": "server.pem"}
+        }"#,
+    );
+    let tracked = read_config_tracked::<Server>(file.path().to_str().unwrap(), None, &BTreeMap::new(), &ReadOptions::default()).unwrap();
+    assert_eq!(tracked.config.listeners.len(), 2);
+    assert_eq!(tracked.unused_keys, vec!["legacy_mode", "listeners.1.http2", "tls.cert"]);
+
+    let config = tracked.report("app.json");
+    assert_eq!(config.host, "localhost");
+}
+
+#[test]
+fn test_tracked_without_unused_keys() {
+    let file = write_temp(r#"{"host": "localhost", "listeners": []}"#);
+    let tracked = read_config_tracked::<Server>(file.path().to_str().unwrap(), None, &BTreeMap::new(), &ReadOptions::default()).unwrap();
+    assert!(tracked.unused_keys.is_empty());
+    assert_eq!(tracked.config.tls, None);
+}
+
+#[test]
+fn test_tracked_invalid_content() {
+    let file = write_temp(r#"{"host": 42, "listeners": []}"#);
+    let result = read_config_tracked::<Server>(file.path().to_str().unwrap(), None, &BTreeMap::new(), &ReadOptions::default());
+    assert!(result.is_err());
+}
@@ -0,0 +1,67 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{ReadOptions, UnknownKeyPolicy};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Listener {
+    port: u16,
+}
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Server {
+    host: String,
+    listeners: Vec<Listener>,
+}
+
+const CONTENT: &str = r#"{"host": "localhost", "hots": "typo", "listeners": [{"port": 80}, {"port": 443, "tls": true}]}"#;
+
+fn read(policy: UnknownKeyPolicy) -> cdumay_core::Result<Server> {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("server.json");
+    std::fs::write(&path, CONTENT).unwrap();
+    let options = ReadOptions {
+        unknown_keys: policy,
+        ..Default::default()
+    };
+    cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), &options)
+}
+
+#[test]
+fn test_unknown_keys_ignored_or_warned() {
+    let expected = Server {
+        host: "localhost".to_string(),
+        listeners: vec![Listener { port: 80 }, Listener { port: 443 }],
+    };
+    assert_eq!(read(UnknownKeyPolicy::Ignore).unwrap(), expected);
+    assert_eq!(read(UnknownKeyPolicy::Warn).unwrap(), expected);
+}
+
+#[test]
+fn test_unknown_keys_denied() {
+    let err = read(UnknownKeyPolicy::Deny).unwrap_err();
+    assert!(err.message().starts_with("Unknown configuration keys in '"), "{}", err.message());
+    assert!(err.message().ends_with("': hots, listeners.1.tls"), "{}", err.message());
+    let details = err.details();
+    assert_eq!(
+        details["unknown_keys"],
+        serde_value::Value::Seq(vec![
+            serde_value::Value::String("hots".to_string()),
+            serde_value::Value::String("listeners.1.tls".to_string()),
+        ])
+    );
+    assert!(details.contains_key("path"));
+}
+
+#[test]
+fn test_unknown_keys_denied_without_unknown_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("server.json");
+    std::fs::write(&path, r#"{"host": "localhost", "listeners": []}"#).unwrap();
+    let options = ReadOptions {
+        unknown_keys: UnknownKeyPolicy::Deny,
+        ..Default::default()
+    };
+    let server: Server = cdumay_config::read_config_with(path.to_str().unwrap(), None, &BTreeMap::new(), &options).unwrap();
+    assert!(server.listeners.is_empty());
+}
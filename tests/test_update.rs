@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+
+use serde_value::Value;
+
+const CONTENT: &str = r#"{"server": {"host": "localhost", "port": 8080, "extra": {"debug": true}}, "hosts": ["a", "b", "c"], "owner": "ops"}"#;
+
+fn file(dir: &tempfile::TempDir) -> String {
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, CONTENT).unwrap();
+    path.to_str().unwrap().to_string()
+}
+
+fn read(path: &str) -> serde_json::Value {
+    serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap()
+}
+
+fn remove(value: &mut Option<Value>) -> cdumay_core::Result<()> {
+    *value = None;
+    Ok(())
+}
+
+#[test]
+fn test_update_keeps_unknown_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = file(&dir);
+    cdumay_config::update_config(
+        &path,
+        None,
+        "server.port",
+        |value| {
+            assert_eq!(value.as_ref(), Some(&Value::U64(8080)));
+            *value = Some(Value::U64(9090));
+            Ok(())
+        },
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    assert_eq!(
+        read(&path),
+        serde_json::json!({"server": {"host": "localhost", "port": 9090, "extra": {"debug": true}}, "hosts": ["a", "b", "c"], "owner": "ops"})
+    );
+}
+
+#[test]
+fn test_update_creates_and_removes_keys() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = file(&dir);
+    let set = |value: &mut Option<Value>| {
+        assert!(value.is_none());
+        *value = Some(Value::String("eu".to_string()));
+        Ok(())
+    };
+    cdumay_config::update_config(&path, None, "deploy.region.name", set, &BTreeMap::new()).unwrap();
+    cdumay_config::update_config(&path, None, "owner", remove, &BTreeMap::new()).unwrap();
+    cdumay_config::update_config(&path, None, "hosts.1", remove, &BTreeMap::new()).unwrap();
+    let content = read(&path);
+    assert_eq!(content["deploy"], serde_json::json!({"region": {"name": "eu"}}));
+    assert_eq!(content["hosts"], serde_json::json!(["a", "c"]));
+    assert!(content.get("owner").is_none());
+}
+
+#[test]
+fn test_update_whole_document() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = file(&dir);
+    cdumay_config::update_config(
+        &path,
+        None,
+        "",
+        |value| {
+            if let Some(Value::Map(entries)) = value {
+                entries.remove(&Value::String("server".to_string()));
+            }
+            Ok(())
+        },
+        &BTreeMap::new(),
+    )
+    .unwrap();
+    assert_eq!(read(&path), serde_json::json!({"hosts": ["a", "b", "c"], "owner": "ops"}));
+}
+
+#[test]
+fn test_update_invalid_key() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = file(&dir);
+    let err = cdumay_config::update_config(&path, None, "owner.name", |_| Ok(()), &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("'name' does not address"), "{}", err.message());
+    let err = cdumay_config::update_config(&path, None, "hosts.7.name", |_| Ok(()), &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("'7' does not address"), "{}", err.message());
+    assert_eq!(err.details()["key"], Value::String("hosts.7.name".to_string()));
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), CONTENT);
+}
+
+#[test]
+fn test_update_closure_failure_leaves_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = file(&dir);
+    let err = cdumay_config::update_config(
+        &path,
+        None,
+        "server.port",
+        |_| Err(cdumay_config::ConfigurationFileError::new().with_message("rejected".to_string()).into()),
+        &BTreeMap::new(),
+    )
+    .unwrap_err();
+    assert_eq!(err.message(), "rejected");
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), CONTENT);
+}
+
+#[test]
+fn test_update_with_lock() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = file(&dir);
+    let options = cdumay_config::WriteOptions {
+        lock: Some(cdumay_config::LockMode::Timeout(std::time::Duration::from_secs(1))),
+        ..Default::default()
+    };
+    cdumay_config::update_config_with(
+        &path,
+        None,
+        "owner",
+        |value| {
+            *value = Some(Value::String("dev".to_string()));
+            Ok(())
+        },
+        &BTreeMap::new(),
+        &options,
+    )
+    .unwrap();
+    assert_eq!(read(&path)["owner"], "dev");
+}
@@ -0,0 +1,89 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::ValidationRules;
+use serde::Deserialize;
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Service {
+    name: String,
+    port: u16,
+}
+
+#[derive(Debug, Deserialize)]
+#[allow(dead_code)]
+struct Feature {
+    enabled: bool,
+}
+
+fn tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::create_dir_all(dir.path().join("services/internal")).unwrap();
+    std::fs::write(dir.path().join("services/api.json"), r#"{"name": "api", "port": 8080}"#).unwrap();
+    std::fs::write(dir.path().join("services/web.json"), r#"{"name": "web", "port": 99999}"#).unwrap();
+    std::fs::write(dir.path().join("services/internal/db.json"), r#"{"enabled": "yes"}"#).unwrap();
+    std::fs::write(dir.path().join("services/README.md"), "not a configuration").unwrap();
+    std::fs::write(dir.path().join("broken.json"), "{").unwrap();
+    dir
+}
+
+#[test]
+fn test_validate_tree_reports_all_failures() {
+    let dir = tree();
+    let rules = ValidationRules::new()
+        .with_schema::<Service>("services/*.json")
+        .with_schema::<Feature>("**/*.json");
+    let report = cdumay_config::validate_tree(dir.path().to_str().unwrap(), &rules, &BTreeMap::new()).unwrap();
+
+    assert_eq!(
+        report.checked,
+        vec![
+            dir.path().join("broken.json"),
+            dir.path().join("services/api.json"),
+            dir.path().join("services/internal/db.json"),
+            dir.path().join("services/web.json"),
+        ]
+    );
+    assert!(!report.is_valid());
+    let failed: Vec<_> = report.failures.iter().map(|failure| failure.path.clone()).collect();
+    assert_eq!(
+        failed,
+        vec![
+            dir.path().join("broken.json"),
+            dir.path().join("services/internal/db.json"),
+            dir.path().join("services/web.json"),
+        ]
+    );
+    assert!(report.failures[1].schema.ends_with("Feature"));
+    assert!(report.failures[2].schema.ends_with("Service"));
+    assert_eq!(
+        report.failures[2].error.details().get("path"),
+        Some(&serde_value::Value::String(dir.path().join("services/web.json").display().to_string()))
+    );
+    assert!(report.to_string().ends_with("4 file(s) checked, 3 invalid"));
+}
+
+#[test]
+fn test_validate_tree_valid() {
+    let dir = tree();
+    let rules = ValidationRules::new().with_schema::<Service>("services/api.json");
+    let report = cdumay_config::validate_tree(dir.path().to_str().unwrap(), &rules, &BTreeMap::new()).unwrap();
+    assert!(report.is_valid());
+    assert_eq!(report.checked, vec![dir.path().join("services/api.json")]);
+}
+
+#[test]
+fn test_validate_tree_invalid_pattern() {
+    let dir = tree();
+    let rules = ValidationRules::new().with_schema::<Service>("services/[.json");
+    let err = cdumay_config::validate_tree(dir.path().to_str().unwrap(), &rules, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("Invalid validation pattern"));
+}
+
+#[test]
+fn test_validate_tree_missing_root() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+    let result = cdumay_config::validate_tree(missing.to_str().unwrap(), &ValidationRules::new(), &BTreeMap::new());
+    assert!(result.is_err());
+}
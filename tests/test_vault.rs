@@ -106,3 +106,826 @@ fn test_vault_config_secrets_none_error() {
     let err = result.unwrap_err();
     assert!(format!("{}", err).contains("Failed to read vault data"));
 }
+
+fn env_context(env: &str) -> BTreeMap<String, serde_value::Value> {
+    let mut ctx = BTreeMap::new();
+    ctx.insert("env".to_string(), serde_value::Value::String(env.to_string()));
+    ctx
+}
+
+#[test]
+fn test_secret_alias_per_environment() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_shared", "\"shared\""),
+        VaultSecret::new("db", "db_prod", "\"prod\"").with_env("prod"),
+        VaultSecret::new("db", "db_dev", "\"dev\"").with_env("dev"),
+        VaultSecret::new("api@staging", "api_staging", "\"api-staging\""),
+        VaultSecret::new("api", "api_shared", "\"api-shared\""),
+    ]);
+    let alias = |name: &str, context: &BTreeMap<String, serde_value::Value>| -> String {
+        secrets.alias(name.to_string(), ContentFormat::JSON, context).unwrap()
+    };
+
+    assert_eq!(alias("db", &env_context("prod")), "prod");
+    assert_eq!(alias("db", &env_context("dev")), "dev");
+    assert_eq!(alias("db", &env_context("staging")), "shared");
+    assert_eq!(alias("db", &BTreeMap::new()), "shared");
+    assert_eq!(alias("api", &env_context("staging")), "api-staging");
+    assert_eq!(alias("api", &env_context("prod")), "api-shared");
+}
+
+#[test]
+fn test_secret_alias_other_environment_only() {
+    let secrets = VaultSecrets::new(vec![VaultSecret::new("db", "db_prod", "\"prod\"").with_env("prod")]);
+    let result: cdumay_core::Result<String> = secrets.alias("db".to_string(), ContentFormat::JSON, &env_context("dev"));
+    assert!(result.unwrap_err().message().contains("Invalid alias"));
+    let result: cdumay_core::Result<String> = secrets.alias("db".to_string(), ContentFormat::JSON, &BTreeMap::new());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_vault_config_env_field() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        r#"[
+            {"alias": "db", "key": "db_shared", "value": "\"shared\""},
+            {"alias": "db", "key": "db_prod", "value": "\"prod\"", "env": "prod"}
+        ]"#,
+    )
+    .unwrap();
+    let config = VaultConfig::init(path.to_str().unwrap(), &BTreeMap::new()).unwrap();
+    let secrets = config.secrets(&BTreeMap::new()).unwrap();
+    let value: String = secrets.alias("db".to_string(), ContentFormat::JSON, &env_context("prod")).unwrap();
+    assert_eq!(value, "prod");
+}
+
+#[test]
+fn test_vault_config_shared() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    let path = path.to_str().unwrap().to_string();
+    let context = BTreeMap::new();
+    assert!(VaultConfig::shared(&path, &context).is_err());
+
+    std::fs::write(&path, r#"[{"alias": "token", "key": "token", "value": "\"v1\""}]"#).unwrap();
+    let handles: Vec<_> = (0..8)
+        .map(|_| {
+            let path = path.clone();
+            std::thread::spawn(move || VaultConfig::shared(&path, &BTreeMap::new()).unwrap())
+        })
+        .collect();
+    let vaults: Vec<_> = handles.into_iter().map(|handle| handle.join().unwrap()).collect();
+    assert!(vaults.iter().all(|vault| std::sync::Arc::ptr_eq(vault, &vaults[0])));
+    assert_eq!(vaults[0].generation(), 0);
+
+    let token = |vault: &cdumay_config::LiveConfig<VaultConfig>| -> String {
+        vault.load().secrets(&context).unwrap().alias("token".to_string(), ContentFormat::JSON, &context).unwrap()
+    };
+    assert_eq!(token(&vaults[0]), "v1");
+
+    std::fs::write(&path, r#"[{"alias": "token", "key": "token", "value": "\"v2\""}]"#).unwrap();
+    let vault = VaultConfig::shared(&path, &context).unwrap();
+    assert_eq!(token(&vault), "v1");
+    let dotted = dir.path().join(".").join("vault.json");
+    let same = VaultConfig::shared(dotted.to_str().unwrap(), &context).unwrap();
+    assert!(std::sync::Arc::ptr_eq(&same, &vault));
+    #[cfg(unix)]
+    {
+        let alias = dir.path().join("alias.json");
+        std::os::unix::fs::symlink(&path, &alias).unwrap();
+        let same = VaultConfig::shared(alias.to_str().unwrap(), &context).unwrap();
+        assert!(std::sync::Arc::ptr_eq(&same, &vault));
+    }
+    vault.reload().unwrap();
+    assert_eq!(token(&vaults[0]), "v2");
+
+    std::fs::write(&path, "not json").unwrap();
+    assert!(vault.reload().is_err());
+    assert_eq!(token(&vault), "v2");
+}
+
+#[test]
+fn test_secret_version() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", "\"shared\"").with_version("3"),
+        VaultSecret::new("db", "db_key", "\"dev\"").with_env("dev").with_version("5"),
+        VaultSecret::new("api", "api_key", "\"abc\""),
+    ]);
+    assert_eq!(secrets.version("db", &sample_context()), Some("5"));
+    assert_eq!(secrets.version("db", &BTreeMap::new()), Some("3"));
+    assert_eq!(secrets.version("api", &BTreeMap::new()), None);
+    assert_eq!(secrets.version("missing", &BTreeMap::new()), None);
+}
+
+/// A toy cipher storing the content as hexadecimal text.
+#[derive(Debug)]
+struct HexCipher;
+
+impl cdumay_config::Cipher for HexCipher {
+    fn encrypt(&self, plaintext: &[u8]) -> std::io::Result<Vec<u8>> {
+        Ok(hex::encode(plaintext).into_bytes())
+    }
+
+    fn decrypt(&self, ciphertext: &[u8]) -> std::io::Result<Vec<u8>> {
+        hex::decode(ciphertext).map_err(|err| std::io::Error::new(std::io::ErrorKind::InvalidData, err))
+    }
+}
+
+#[test]
+fn test_vault_config_encrypted_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        format!(
+            r#"[
+                {{"alias": "db", "key": "db_key", "value": "{}", "encrypted": true}},
+                {{"alias": "api", "key": "api_key", "value": "\"clear\""}},
+                {{"alias": "broken", "key": "broken_key", "value": "not hex", "encrypted": true}}
+            ]"#,
+            hex::encode(r#"{"username": "admin", "password": "1234"}"#)
+        ),
+    )
+    .unwrap();
+    let context = BTreeMap::new();
+    let secrets = VaultConfig::init(path.to_str().unwrap(), &context).unwrap().secrets(&context).unwrap();
+
+    let err = secrets.alias::<DummySecret>("db".to_string(), ContentFormat::JSON, &context).unwrap_err();
+    assert!(err.message().contains("no key provider"));
+    assert_eq!(err.details()["alias"], serde_value::Value::String("db".to_string()));
+
+    let cipher: std::sync::Arc<dyn cdumay_config::Cipher> = std::sync::Arc::new(HexCipher);
+    let secrets = secrets.with_key_provider(cipher.clone());
+    let secret: DummySecret = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(secret.password, "1234");
+    let api: String = secrets.alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(api, "clear");
+
+    let err = secrets.alias::<String>("broken".to_string(), ContentFormat::JSON, &context).unwrap_err();
+    assert!(err.message().starts_with("Failed to decrypt secret 'broken'"));
+    let binary = VaultSecrets::new(vec![VaultSecret::new("binary", "binary_key", "c3ff").with_encrypted(true)]).with_key_provider(cipher);
+    let err = binary.alias::<String>("binary".to_string(), ContentFormat::JSON, &context).unwrap_err();
+    assert_eq!(err.message(), "Decrypted secret 'binary' is not valid UTF-8");
+    assert!(VaultSecret::new("db", "db_key", "00").with_encrypted(true).is_encrypted());
+}
+
+#[test]
+fn test_secret_lookup_by_key() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "database/password", "\"shared\""),
+        VaultSecret::new("db", "database/password", "\"dev\"").with_env("dev"),
+        VaultSecret::new("api", "db", "\"api\""),
+    ]);
+    let value: String = secrets.key("database/password", ContentFormat::JSON, &sample_context()).unwrap();
+    assert_eq!(value, "dev");
+    let value: String = secrets.key("database/password", ContentFormat::JSON, &BTreeMap::new()).unwrap();
+    assert_eq!(value, "shared");
+    let err = secrets.key::<String>("missing", ContentFormat::JSON, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("Invalid key: missing"));
+
+    // Aliases take precedence over keys.
+    let value: String = secrets.lookup("db", ContentFormat::JSON, &BTreeMap::new()).unwrap();
+    assert_eq!(value, "shared");
+    let value: String = secrets.lookup("database/password", ContentFormat::JSON, &BTreeMap::new()).unwrap();
+    assert_eq!(value, "shared");
+    let err = secrets.lookup::<String>("missing", ContentFormat::JSON, &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("Invalid alias or key: missing"));
+}
+
+#[test]
+fn test_secret_accessors() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", "\"dev\"").with_env("dev").with_version("2"),
+        VaultSecret::new("api", "api_key", "\"1234\""),
+        VaultSecret::new("db", "db_key", "\"shared\""),
+    ]);
+    assert_eq!(secrets.aliases(), vec!["api", "db"]);
+    assert_eq!(secrets.secrets().len(), 3);
+
+    let secret = &secrets.secrets()[0];
+    assert_eq!(secret.alias(), "db");
+    assert_eq!(secret.key(), "db_key");
+    assert_eq!(secret.value().expose(), "\"dev\"");
+    assert_eq!(format!("{:?}", secret.value()), "***");
+    assert_eq!(secret.env(), Some("dev"));
+    assert_eq!(secret.version(), Some("2"));
+    assert_eq!(secrets.secrets()[1].env(), None);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_vault_config_save_replaces_file_atomically() {
+    use std::io::Read;
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    let original = r#"[{"alias": "db", "key": "db_key", "value": "\"old\""}]"#;
+    std::fs::write(&path, original).unwrap();
+    let context = BTreeMap::new();
+    let mut config = VaultConfig::init(path.to_str().unwrap(), &context).unwrap();
+    config.secrets.as_mut().unwrap().update("db", None, "\"new\"", &context).unwrap();
+
+    let mut reader = std::fs::File::open(&path).unwrap();
+    let inode = std::fs::metadata(&path).unwrap().ino();
+    config.save(path.to_str().unwrap(), None, &context).unwrap();
+
+    // A reader of the previous file still sees it whole, and the new file is in place.
+    let mut content = String::new();
+    reader.read_to_string(&mut content).unwrap();
+    assert_eq!(content, original);
+    let metadata = std::fs::metadata(&path).unwrap();
+    assert_ne!(metadata.ino(), inode);
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+    let names: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(names, vec![std::ffi::OsString::from("vault.json")]);
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+    assert_eq!(saved[0]["value"], "\"new\"");
+}
+
+#[test]
+fn test_vault_config_rotate_and_save() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(
+        path,
+        r#"[
+            {"alias": "db", "key": "db_key", "value": "\"old\"", "version": "1"},
+            {"alias": "db", "key": "db_key", "value": "\"prod\"", "env": "prod"},
+            {"alias": "api", "key": "api_key", "value": "\"1234\""}
+        ]"#,
+    )
+    .unwrap();
+    let context = BTreeMap::new();
+    let mut config = VaultConfig::init(path, &context).unwrap();
+    let secrets = config.secrets.as_mut().unwrap();
+    secrets.update("db", None, "\"new\"", &context).unwrap();
+    assert!(secrets.update("db", Some("dev"), "\"dev\"", &context).is_err());
+    assert_eq!(secrets.remove("api", None).unwrap().key(), "api_key");
+    assert!(secrets.remove("api", None).is_none());
+    assert!(secrets.insert(VaultSecret::new("token", "token_key", "\"abc\"")).is_none());
+    let replaced = secrets.insert(VaultSecret::new("db", "db_key", "\"prod2\"").with_env("prod")).unwrap();
+    assert_eq!(replaced.value().expose(), "\"prod\"");
+    config.save(path, None, &context).unwrap();
+    #[cfg(unix)]
+    assert_eq!(
+        std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(path).unwrap().permissions()) & 0o777,
+        0o600
+    );
+    let copy = dir.path().join("copy.json");
+    config.save(copy.to_str().unwrap(), None, &context).unwrap();
+    #[cfg(unix)]
+    assert_eq!(
+        std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(&copy).unwrap().permissions()) & 0o777,
+        0o600
+    );
+
+    let saved: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+    assert_eq!(
+        saved,
+        serde_json::json!([
+            {"alias": "db", "key": "db_key", "value": "\"new\""},
+            {"alias": "db", "key": "db_key", "value": "\"prod2\"", "env": "prod"},
+            {"alias": "token", "key": "token_key", "value": "\"abc\""}
+        ])
+    );
+    let secrets = VaultConfig::init(path, &context).unwrap().secrets(&context).unwrap();
+    assert_eq!(secrets.aliases(), vec!["db", "token"]);
+    let value: String = secrets.alias("db".to_string(), ContentFormat::JSON, &env_context("prod")).unwrap();
+    assert_eq!(value, "prod2");
+
+    assert!(VaultConfig { secrets: None }.save(path, None, &context).is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_vault_config_init_yaml() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.yaml");
+    std::fs::write(&path, "- alias: db\n  key: db_key\n  value: '\"yaml\"'\n").unwrap();
+    let context = BTreeMap::new();
+    let config = VaultConfig::init_with_format(path.to_str().unwrap(), Some(ContentFormat::YAML), &context).unwrap();
+    let value: String = config.secrets(&context).unwrap().alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "yaml");
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_vault_config_init_toml() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.toml");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, "[[secrets]]\nalias = \"db\"\nkey = \"db_key\"\nvalue = '\"toml\"'\nenv = \"prod\"\n").unwrap();
+    let context = BTreeMap::new();
+    let config = VaultConfig::init_with_format(path, Some(ContentFormat::TOML), &context).unwrap();
+    let value: String = config.secrets(&context).unwrap().alias("db".to_string(), ContentFormat::JSON, &env_context("prod")).unwrap();
+    assert_eq!(value, "toml");
+
+    config.save(path, Some(ContentFormat::TOML), &context).unwrap();
+    let config = VaultConfig::init_with_format(path, Some(ContentFormat::TOML), &context).unwrap();
+    assert_eq!(config.secrets(&context).unwrap().secrets()[0].env(), Some("prod"));
+}
+
+#[test]
+fn test_secret_declared_format() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        r#"[
+            {"alias": "db", "key": "db_key", "value": "{\"username\": \"admin\", \"password\": \"1234\"}", "format": "json"},
+            {"alias": "hosts", "key": "hosts_key", "value": "[\"a\"]\n[\"b\"]", "format": "ndjson"},
+            {"alias": "token", "key": "token_key", "value": "\"abc\""}
+        ]"#,
+    )
+    .unwrap();
+    let context = BTreeMap::new();
+    let secrets = VaultConfig::init(path.to_str().unwrap(), &context).unwrap().secrets(&context).unwrap();
+    let secret: DummySecret = secrets.get("db", &context).unwrap();
+    assert_eq!(secret.username, "admin");
+    let hosts: Vec<Vec<String>> = secrets.get("hosts", &context).unwrap();
+    assert_eq!(hosts, vec![vec!["a".to_string()], vec!["b".to_string()]]);
+    let token: String = secrets.get("token", &context).unwrap();
+    assert_eq!(token, "abc");
+    assert!(secrets.get::<String>("missing", &context).unwrap_err().message().contains("Invalid alias"));
+    assert_eq!(secrets.secrets()[1].format(), Some(ContentFormat::NDJSON));
+
+    std::fs::write(&path, r#"[{"alias": "db", "key": "db_key", "value": "1", "format": "unknown"}]"#).unwrap();
+    assert!(VaultConfig::init(path.to_str().unwrap(), &context).is_err());
+}
+
+#[cfg(feature = "yaml")]
+#[test]
+fn test_secret_declared_format_yaml() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", "username: admin\npassword: '1234'").with_format(ContentFormat::YAML),
+    ]);
+    let secret: DummySecret = secrets.get("db", &BTreeMap::new()).unwrap();
+    assert_eq!(secret.password, "1234");
+}
+
+#[test]
+fn test_secrets_iteration_and_decode_all() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("tenant_a", "a", r#"{"username": "a", "password": "1"}"#),
+        VaultSecret::new("tenant_b", "b", r#"{"username": "b", "password": "2"}"#),
+        VaultSecret::new("tenant_b", "b", r#"{"username": "b", "password": "dev"}"#).with_env("dev"),
+        VaultSecret::new("tenant_c", "c", r#"{"username": "c", "password": "3"}"#).with_env("prod"),
+    ]);
+    assert_eq!(secrets.len(), 4);
+    assert!(!secrets.is_empty());
+    assert!(VaultSecrets::new(vec![]).is_empty());
+    assert_eq!(secrets.iter().filter(|secret| secret.env().is_none()).count(), 2);
+    assert_eq!((&secrets).into_iter().map(VaultSecret::key).collect::<Vec<_>>(), vec!["a", "b", "b", "c"]);
+
+    let all: BTreeMap<String, DummySecret> = secrets.decode_all(Some(ContentFormat::JSON), &sample_context()).unwrap();
+    assert_eq!(all.keys().collect::<Vec<_>>(), vec!["tenant_a", "tenant_b"]);
+    assert_eq!(all["tenant_b"].password, "dev");
+
+    assert!(secrets.decode_all::<u32>(None, &BTreeMap::new()).is_err());
+}
+
+#[test]
+fn test_vault_duplicate_secrets() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        r#"[
+            {"alias": "db", "key": "db_key", "value": "\"first\""},
+            {"alias": "db", "key": "db_key", "value": "\"prod\"", "env": "prod"},
+            {"alias": "db", "key": "other_key", "value": "\"second\""},
+            {"alias": "api", "key": "db_key", "value": "\"api\"", "env": "prod"}
+        ]"#,
+    )
+    .unwrap();
+    let err = VaultConfig::init(path.to_str().unwrap(), &BTreeMap::new()).unwrap_err();
+    assert!(err.message().starts_with("Duplicate vault secrets"));
+    assert_eq!(
+        err.details()["duplicate_aliases"],
+        serde_value::Value::Seq(vec![serde_value::Value::String("db".to_string())])
+    );
+    assert_eq!(
+        err.details()["duplicate_keys"],
+        serde_value::Value::Seq(vec![serde_value::Value::String("db_key@prod".to_string())])
+    );
+
+    // Secrets built in code are only warned about, and the last one wins.
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", "\"first\""),
+        VaultSecret::new("db", "db_key", "\"second\""),
+    ]);
+    assert!(secrets.validate(&BTreeMap::new()).is_err());
+    let value: String = secrets.alias("db".to_string(), ContentFormat::JSON, &BTreeMap::new()).unwrap();
+    assert_eq!(value, "second");
+    assert!(VaultSecrets::new(vec![VaultSecret::new("db", "db_key", "1")]).validate(&BTreeMap::new()).is_ok());
+}
+
+#[test]
+fn test_secret_metadata() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        r#"[
+            {"alias": "db", "key": "db_key", "value": "\"1234\"", "tags": ["db", "payments"],
+             "description": "Payments database", "created_at": "2025-01-31T12:00:00Z", "expires_at": "2099-01-01T00:00:00Z"},
+            {"alias": "cache", "key": "cache_key", "value": "\"5678\"", "tags": ["db"]},
+            {"alias": "api", "key": "api_key", "value": "\"abcd\""}
+        ]"#,
+    )
+    .unwrap();
+    let context = BTreeMap::new();
+    let secrets = VaultConfig::init(path.to_str().unwrap(), &context).unwrap().secrets(&context).unwrap();
+    let db = &secrets.secrets()[0];
+    assert_eq!(db.tags(), ["db", "payments"]);
+    assert_eq!(db.description(), Some("Payments database"));
+    assert_eq!(db.created_at(), Some("2025-01-31T12:00:00Z"));
+    assert_eq!(db.expires_at(), Some("2099-01-01T00:00:00Z"));
+    assert_eq!(secrets.secrets()[2].description(), None);
+
+    assert_eq!(secrets.with_tag("db").aliases(), vec!["cache", "db"]);
+    assert!(secrets.with_tag("unknown").is_empty());
+    let expiring = secrets.filter(|secret| secret.expires_at().is_some());
+    let value: String = expiring.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "1234");
+
+    let secret = VaultSecret::new("token", "token_key", "\"t\"")
+        .with_tag("api")
+        .with_description("API token")
+        .with_created_at("2025-02-01T00:00:00Z");
+    assert!(secret.has_tag("api"));
+    assert!(!secret.has_tag("db"));
+}
+
+#[test]
+fn test_secret_expiration_and_refresh() {
+    let mut secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", "\"old\"").with_expires_at("2020-01-01T00:00:00Z"),
+        VaultSecret::new("api", "api_key", "\"old\"").with_expires_at("2020-01-01T01:00:00.250+01:00"),
+        VaultSecret::new("cache", "cache_key", "\"old\"").with_expires_at("not a timestamp"),
+        VaultSecret::new("token", "token_key", "\"valid\"").with_expires_at("2999-12-31"),
+        VaultSecret::new("queue", "queue_key", "\"valid\"").with_expires_at("2999-12-31T23:59:59-05:00"),
+    ]);
+    let context = BTreeMap::new();
+    let err = secrets.alias::<String>("db".to_string(), ContentFormat::JSON, &context).unwrap_err();
+    assert_eq!(err.class(), cdumay_config::ExpiredSecretError::new().class());
+    assert_eq!(err.details()["expires_at"], serde_value::Value::String("2020-01-01T00:00:00Z".to_string()));
+    assert!(secrets.secrets()[1].is_expired());
+    assert!(secrets.secrets()[2].is_expired());
+    let token: String = secrets.alias("token".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(token, "valid");
+    assert!(!secrets.secrets()[4].is_expired());
+    assert!(secrets.decode_all::<String>(None, &context).is_err());
+
+    let source = VaultConfig {
+        secrets: Some(VaultSecrets::new(vec![
+            VaultSecret::new("db", "db_key", "\"new\"").with_expires_at("2999-01-01T00:00:00Z"),
+            VaultSecret::new("api", "api_key", "\"new\""),
+        ])),
+    };
+    assert_eq!(secrets.refresh(&source, &context).unwrap(), 2);
+    let db: String = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(db, "new");
+    let api: String = secrets.alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(api, "new");
+    assert!(secrets.secrets()[2].is_expired());
+    assert_eq!(secrets.refresh(&source, &context).unwrap(), 0);
+}
+
+#[test]
+fn test_secret_version_history() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        r#"[
+            {"alias": "db", "key": "db_key", "value": "\"v1\"", "version": "1"},
+            {"alias": "db", "key": "db_key", "value": "\"v2\"", "version": "2"},
+            {"alias": "db", "key": "db_key", "value": "\"prod\"", "version": "1", "env": "prod"}
+        ]"#,
+    )
+    .unwrap();
+    let context = BTreeMap::new();
+    let mut secrets = VaultConfig::init(path.to_str().unwrap(), &context).unwrap().secrets(&context).unwrap();
+    assert_eq!(secrets.versions("db", &context), vec!["1", "2"]);
+    assert_eq!(secrets.versions("db", &env_context("prod")), vec!["1"]);
+    assert!(secrets.versions("missing", &context).is_empty());
+    assert_eq!(secrets.latest("db", &context).unwrap().value().expose(), "\"v2\"");
+    let latest: String = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(latest, "v2");
+    let previous: String = secrets.alias_version("db", "1", ContentFormat::JSON, &context).unwrap();
+    assert_eq!(previous, "v1");
+    let prod: String = secrets.alias_version("db", "1", ContentFormat::JSON, &env_context("prod")).unwrap();
+    assert_eq!(prod, "prod");
+    let err = secrets.alias_version::<String>("db", "3", ContentFormat::JSON, &context).unwrap_err();
+    assert!(err.message().contains("Invalid alias: db (version 3)"));
+
+    // Rotation adds the new version, keeping the previous ones.
+    assert!(secrets.insert(VaultSecret::new("db", "db_key", "\"v3\"").with_version("3")).is_none());
+    assert_eq!(secrets.versions("db", &context), vec!["1", "2", "3"]);
+    assert!(secrets.validate(&context).is_ok());
+    secrets.insert(VaultSecret::new("db", "db_key", "\"v2 fixed\"").with_version("2"));
+    let fixed: String = secrets.alias_version("db", "2", ContentFormat::JSON, &context).unwrap();
+    assert_eq!(fixed, "v2 fixed");
+}
+
+#[test]
+fn test_secret_namespaces() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(
+        &path,
+        r#"[
+            {"alias": "db", "key": "db_key", "value": "\"payments\"", "namespace": "payments"},
+            {"alias": "db", "key": "db_key", "value": "\"billing\"", "namespace": "billing"},
+            {"alias": "db", "key": "db_key", "value": "\"shared\""},
+            {"alias": "api", "key": "api_key", "value": "\"api\"", "namespace": "payments"}
+        ]"#,
+    )
+    .unwrap();
+    let context = BTreeMap::new();
+    let mut secrets = VaultConfig::init(path.to_str().unwrap(), &context).unwrap().secrets(&context).unwrap();
+    let payments = secrets.scope("payments");
+    assert_eq!(payments.namespace(), Some("payments"));
+    assert_eq!(payments.aliases(), vec!["api", "db"]);
+    let value: String = payments.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "payments");
+    let value: String = secrets.scope("billing").alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "billing");
+
+    // Unscoped lookups only see the secrets without namespace.
+    assert_eq!(secrets.aliases(), vec!["db"]);
+    let value: String = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "shared");
+    assert!(secrets.alias::<String>("api".to_string(), ContentFormat::JSON, &context).is_err());
+
+    let err = secrets.scope("billing").alias::<String>("api".to_string(), ContentFormat::JSON, &context).unwrap_err();
+    assert_eq!(err.details()["namespace"], serde_value::Value::String("billing".to_string()));
+
+    assert!(secrets.remove("api", None).is_none());
+    secrets.update("db", None, "\"updated\"", &context).unwrap();
+    let value: String = secrets.scope("payments").alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "payments");
+    assert_eq!(VaultSecret::new("db", "db_key", "1").with_namespace("payments").namespace(), Some("payments"));
+}
+
+#[test]
+fn test_secret_env_export() {
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db-password", "db_key", r#""p@ss \"$word\"\nline""#),
+        VaultSecret::new("db-password", "db_key", r#""prod""#).with_env("prod"),
+        VaultSecret::new("db.port", "db_port", "5432"),
+        VaultSecret::new("tls", "tls_key", r#"{"cert": "a"}"#),
+        VaultSecret::new("token", "token_key", "raw-token"),
+        VaultSecret::new("staging", "staging_key", r#""staging""#).with_env("staging"),
+    ]);
+    let vars = secrets.env_vars("APP_", &sample_context()).unwrap();
+    assert_eq!(
+        vars.keys().collect::<Vec<_>>(),
+        vec!["APP_DB_PASSWORD", "APP_DB_PORT", "APP_TLS", "APP_TOKEN"]
+    );
+    assert_eq!(vars["APP_DB_PASSWORD"].expose(), "p@ss \"$word\"\nline");
+    assert_eq!(vars["APP_DB_PORT"].expose(), "5432");
+    assert_eq!(vars["APP_TLS"].expose(), r#"{"cert":"a"}"#);
+    assert_eq!(vars["APP_TOKEN"].expose(), "raw-token");
+    let vars = secrets.env_vars("", &env_context("prod")).unwrap();
+    assert_eq!(vars["DB_PASSWORD"].expose(), "prod");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join(".env");
+    let written = secrets.write_env_file(path.to_str().unwrap(), "APP_", &sample_context()).unwrap();
+    assert_eq!(written, path);
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        "APP_DB_PASSWORD=\"p@ss \\\"\\$word\\\"\\nline\"\nAPP_DB_PORT=\"5432\"\nAPP_TLS=\"{\\\"cert\\\":\\\"a\\\"}\"\nAPP_TOKEN=\"raw-token\"\n"
+    );
+    #[cfg(unix)]
+    assert_eq!(std::os::unix::fs::PermissionsExt::mode(&std::fs::metadata(&path).unwrap().permissions()) & 0o777, 0o600);
+
+    let expired = VaultSecrets::new(vec![VaultSecret::new("old", "old_key", "\"x\"").with_expires_at("2000-01-01T00:00:00Z")]);
+    assert!(expired.write_env_file(dir.path().join("expired.env").to_str().unwrap(), "", &sample_context()).is_err());
+    assert!(!dir.path().join("expired.env").exists());
+}
+
+#[derive(Debug, Default)]
+struct AuditRecorder(std::sync::Mutex<Vec<cdumay_config::SecretAccess>>);
+
+impl cdumay_config::AuditSink for AuditRecorder {
+    fn record(&self, access: &cdumay_config::SecretAccess) {
+        self.0.lock().unwrap().push(access.clone());
+    }
+}
+
+#[test]
+fn test_secret_audit() {
+    let recorder = std::sync::Arc::new(AuditRecorder::default());
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", r#""hunter2""#),
+        VaultSecret::new("port", "port_key", r#""hunter2""#),
+        VaultSecret::new("api", "api_key", r#""abcd""#).with_namespace("payments"),
+    ])
+    .with_audit_sink(recorder.clone());
+    let context = sample_context();
+    let before = std::time::SystemTime::now();
+    let _: String = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert!(secrets.alias::<String>("missing".to_string(), ContentFormat::JSON, &context).is_err());
+    assert!(secrets.get::<u16>("port", &context).is_err());
+    let _: String = secrets.key("db_key", ContentFormat::JSON, &context).unwrap();
+    let _: String = secrets.scope("payments").alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    // Lookups without value retrieval are not recorded.
+    assert_eq!(secrets.version("db", &context), None);
+
+    let accesses = recorder.0.lock().unwrap();
+    assert_eq!(
+        accesses.iter().map(|access| (access.alias.as_str(), access.is_success())).collect::<Vec<_>>(),
+        vec![("db", true), ("missing", false), ("port", false), ("db_key", true), ("api", true)]
+    );
+    assert!(accesses.iter().all(|access| access.timestamp >= before && access.context == context));
+    assert_eq!(accesses[4].namespace.as_deref(), Some("payments"));
+    assert!(accesses[1].error.is_some());
+    assert!(accesses.iter().all(|access| !format!("{:?}", access).contains("hunter2")));
+}
+
+/// A secret store answering with the alias in upper case, counting its lookups.
+#[derive(Debug, Default)]
+struct UpperFallback(std::sync::atomic::AtomicUsize);
+
+impl cdumay_config::SecretProvider for UpperFallback {
+    fn get(&self, alias: &str, context: &BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<cdumay_config::SecretString> {
+        self.0.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        match alias.starts_with("remote") {
+            true => Ok(format!("\"{}\"", alias.to_uppercase()).into()),
+            false => Err(cdumay_config::VaultSecretError::new()
+                .with_message(format!("Unknown remote alias: {}", alias))
+                .with_details(context.clone())
+                .into()),
+        }
+    }
+}
+
+#[test]
+fn test_secret_fallback() {
+    use cdumay_config::SecretProvider;
+
+    let fallback = std::sync::Arc::new(UpperFallback::default());
+    let secrets = VaultSecrets::new(vec![VaultSecret::new("db", "db_key", r#""local""#)]).with_fallback(fallback.clone());
+    let context = sample_context();
+
+    // Held secrets do not query the fallback.
+    let value: String = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "local");
+    assert_eq!(fallback.0.load(std::sync::atomic::Ordering::Relaxed), 0);
+
+    let value: String = secrets.alias("remote_db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "REMOTE_DB");
+    let value: String = secrets.get("remote_api", &context).unwrap();
+    assert_eq!(value, "REMOTE_API");
+    let value: String = secrets.lookup("remote_x", ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "REMOTE_X");
+    let err = secrets.alias::<String>("other".to_string(), ContentFormat::JSON, &context).unwrap_err();
+    assert_eq!(err.message(), "Unknown remote alias: other");
+    assert_eq!(fallback.0.load(std::sync::atomic::Ordering::Relaxed), 4);
+
+    // Lookups by key do not fall back.
+    let err = secrets.key::<String>("remote_key", ContentFormat::JSON, &context).unwrap_err();
+    assert_eq!(err.message(), "Invalid key: remote_key");
+    assert_eq!(secrets.aliases(), vec!["db"]);
+
+    // Loaded secrets are the default fallback, and can be chained.
+    assert_eq!(SecretProvider::get(&secrets, "db", &context).unwrap().expose(), r#""local""#);
+    assert_eq!(SecretProvider::get(&secrets, "remote_db", &context).unwrap().expose(), r#""REMOTE_DB""#);
+    let chained = VaultSecrets::new(vec![]).with_fallback(secrets);
+    let value: String = chained.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "local");
+    let err = SecretProvider::get(&VaultSecrets::new(vec![]), "db", &context).unwrap_err();
+    assert_eq!(err.message(), "Invalid alias: db");
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("vault.json");
+    std::fs::write(&path, r#"[{"alias": "db", "key": "db_key", "value": "\"file\""}]"#).unwrap();
+    let config = VaultConfig::init(path.to_str().unwrap(), &context).unwrap();
+    assert_eq!(SecretProvider::get(&config, "db", &context).unwrap().expose(), r#""file""#);
+    assert_eq!(SecretProvider::secrets(&config, &context).unwrap().aliases(), vec!["db"]);
+
+    // Stores queried alias by alias cannot list their secrets.
+    let err = SecretProvider::secrets(&UpperFallback::default(), &context).unwrap_err();
+    assert!(err.message().contains("cannot list its secrets"));
+}
+
+/// A store only able to list its secrets.
+#[derive(Debug)]
+struct ListedSecrets;
+
+impl cdumay_config::SecretProvider for ListedSecrets {
+    fn secrets(&self, _: &BTreeMap<String, serde_value::Value>) -> cdumay_core::Result<VaultSecrets> {
+        Ok(VaultSecrets::new(vec![VaultSecret::new("api", "api_key", r#""listed""#)]))
+    }
+}
+
+#[test]
+fn test_secret_provider_get_from_listed_secrets() {
+    let context = sample_context();
+    let provider: &dyn cdumay_config::SecretProvider = &ListedSecrets;
+    assert_eq!(provider.get("api", &context).unwrap().expose(), r#""listed""#);
+    assert_eq!(provider.get("db", &context).unwrap_err().message(), "Invalid alias: db");
+
+    let secrets = VaultSecrets::new(vec![]).with_fallback(ListedSecrets);
+    let value: String = secrets.alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(value, "listed");
+}
+
+#[test]
+fn test_secret_references() {
+    #[derive(Deserialize, Debug, PartialEq)]
+    struct Database {
+        url: String,
+        password: String,
+        port: u16,
+        replicas: Vec<String>,
+        note: String,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(
+        path,
+        r#"{
+            "url": "postgres://db.example.com",
+            "password": "vault://db/password",
+            "port": "vault://db/port",
+            "replicas": ["vault://replica"],
+            "note": "see vault://db"
+        }"#,
+    )
+    .unwrap();
+    let secrets = VaultSecrets::new(vec![
+        VaultSecret::new("db", "db_key", r#"{"password": "hunter2", "port": 5432}"#),
+        VaultSecret::new("db", "db_key", r#"{"password": "prod", "port": 6432}"#).with_env("prod"),
+        VaultSecret::new("replica", "replica_key", "replica.example.com"),
+    ]);
+    let options = cdumay_config::ReadOptions {
+        secrets: Some(secrets.clone()),
+        ..Default::default()
+    };
+    let config: Database = cdumay_config::read_config_with(path, None, &sample_context(), &options).unwrap();
+    assert_eq!(
+        config,
+        Database {
+            url: "postgres://db.example.com".to_string(),
+            password: "hunter2".to_string(),
+            port: 5432,
+            replicas: vec!["replica.example.com".to_string()],
+            note: "see vault://db".to_string(),
+        }
+    );
+    let config: Database = cdumay_config::read_config_with(path, None, &env_context("prod"), &options).unwrap();
+    assert_eq!((config.password.as_str(), config.port), ("prod", 6432));
+
+    // Without secrets, references are plain strings.
+    let config: BTreeMap<String, serde_json::Value> = cdumay_config::read_config(path, None, &sample_context()).unwrap();
+    assert_eq!(config["password"], "vault://db/password");
+
+    std::fs::write(path, r#"{"database": {"replicas": ["vault://replica", "vault://db/host"]}}"#).unwrap();
+    let err = cdumay_config::read_config_with::<serde_json::Value>(path, None, &sample_context(), &options).unwrap_err();
+    assert_eq!(err.message(), "database.replicas[1]: Secret 'db' has no field 'host'");
+    assert_eq!(err.details()["key_path"], serde_value::Value::String("database.replicas[1]".to_string()));
+    assert_eq!(err.details()["path"], serde_value::Value::String(path.to_string()));
+    assert!(!format!("{:?}", err).contains("hunter2"));
+}
+
+#[test]
+fn test_secret_references_masked_in_errors() {
+    #[derive(Deserialize, Debug)]
+    #[allow(dead_code)]
+    struct Database {
+        port: u16,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let path = path.to_str().unwrap();
+    std::fs::write(path, r#"{"port": "vault://db/password"}"#).unwrap();
+    let options = cdumay_config::ReadOptions {
+        secrets: Some(VaultSecrets::new(vec![VaultSecret::new("db", "db_key", r#"{"password": "hunter2"}"#)])),
+        ..Default::default()
+    };
+    let err = cdumay_config::read_config_with::<Database>(path, None, &sample_context(), &options).unwrap_err();
+    assert!(err.message().contains("Invalid type string \"***\""), "{}", err.message());
+    assert!(!format!("{:?}", err).contains("hunter2"));
+    assert_eq!(
+        err.details()["masked_references"],
+        serde_value::Value::Seq(vec![serde_value::Value::String("port".to_string())])
+    );
+
+    let options = cdumay_config::ReadOptions {
+        unknown_keys: cdumay_config::UnknownKeyPolicy::Warn,
+        ..options
+    };
+    let err = cdumay_config::read_config_with::<Database>(path, None, &sample_context(), &options).unwrap_err();
+    assert!(!format!("{:?}", err).contains("hunter2"));
+}
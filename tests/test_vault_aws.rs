@@ -0,0 +1,114 @@
+#![cfg(feature = "aws")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::{AwsCredentials, AwsSecretsManager, ContentFormat, SecretProvider};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Database {
+    username: String,
+    password: String,
+}
+
+/// Serves the given responses, one per connection, returning the address of the server and
+/// the requests received (head and body).
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                if let Some(index) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break index + 4;
+                }
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_ascii_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            while request.len() < head_end + length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (address, handle)
+}
+
+#[test]
+fn test_aws_secrets() {
+    let (address, handle) = serve(vec![
+        (
+            "200 OK",
+            r#"{"Name": "app/db", "SecretString": "{\"username\": \"admin\", \"password\": \"s3cr3t\"}"}"#,
+        ),
+        ("200 OK", r#"{"Name": "app/api", "SecretBinary": "ImFiYyI="}"#),
+    ]);
+    let credentials = AwsCredentials::new("AKIDEXAMPLE", "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY").with_session_token("session");
+    assert!(!format!("{:?}", credentials).contains("EXAMPLEKEY"));
+    let provider = AwsSecretsManager::new("eu-west-1", credentials)
+        .with_endpoint(&address)
+        .with_secret("db", "app/db")
+        .with_env_secret("api", "app/api", "prod");
+    let provider: &dyn SecretProvider = &provider;
+    let context = BTreeMap::from([("env".to_string(), serde_value::Value::String("prod".to_string()))]);
+    let secrets = provider.secrets(&context).unwrap();
+    let database: Database = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(
+        database,
+        Database {
+            username: "admin".to_string(),
+            password: "s3cr3t".to_string()
+        }
+    );
+    let api: String = secrets.alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(api, "abc");
+    assert!(secrets.alias::<String>("api".to_string(), ContentFormat::JSON, &BTreeMap::new()).is_err());
+
+    let requests = handle.join().unwrap();
+    let request = requests[0].to_ascii_lowercase();
+    assert!(request.starts_with("post / "));
+    assert!(request.contains("x-amz-target: secretsmanager.getsecretvalue"));
+    assert!(request.contains("x-amz-security-token: session"));
+    assert!(request.contains("authorization: aws4-hmac-sha256 credential=akidexample/"));
+    assert!(request.contains(
+        "/eu-west-1/secretsmanager/aws4_request, signedheaders=content-type;host;x-amz-date;x-amz-security-token;x-amz-target, signature="
+    ));
+    assert!(requests[0].ends_with(r#"{"SecretId":"app/db"}"#));
+}
+
+#[test]
+fn test_aws_errors() {
+    let (address, handle) = serve(vec![(
+        "400 Bad Request",
+        r#"{"__type": "ResourceNotFoundException", "message": "Secrets Manager can't find the specified secret."}"#,
+    )]);
+    let err = AwsSecretsManager::new("eu-west-1", AwsCredentials::new("AKID", "secret"))
+        .with_endpoint(&address)
+        .with_secret("db", "app/missing")
+        .config(&BTreeMap::new())
+        .unwrap_err();
+    handle.join().unwrap();
+    assert!(err.message().contains("app/missing"));
+    assert_eq!(err.details()["status"], serde_value::Value::U16(400));
+    assert_eq!(err.details()["url"], serde_value::Value::String(address));
+    assert!(matches!(&err.details()["origin"], serde_value::Value::String(origin) if origin.contains("ResourceNotFoundException")));
+}
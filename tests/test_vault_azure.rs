@@ -0,0 +1,126 @@
+#![cfg(feature = "azure")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::{AzureAuth, AzureKeyVault, ContentFormat, SecretProvider};
+
+/// Serves the given responses, one per connection, returning the address of the server and
+/// the requests received (head and body).
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                if let Some(index) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break index + 4;
+                }
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_ascii_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            while request.len() < head_end + length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (address, handle)
+}
+
+#[test]
+fn test_azure_managed_identity() {
+    let (address, handle) = serve(vec![
+        ("200 OK", r#"{"access_token": "identity-token", "token_type": "Bearer"}"#),
+        ("200 OK", r#"{"value": "\"abc\"", "id": "https://vault/secrets/api-key/1"}"#),
+        ("200 OK", r#"{"value": "{\"user\": \"admin\"}", "id": "https://vault/secrets/db/v2"}"#),
+    ]);
+    let provider = AzureKeyVault::new(
+        &format!("{}/", address),
+        AzureAuth::ManagedIdentity {
+            client_id: Some("uai".to_string()),
+        },
+    )
+    .with_identity_endpoint(&format!("{}/metadata/identity/oauth2/token", address))
+    .with_secret("api", "api-key")
+    .with_secret_version("db", "db", "v2");
+    let provider: &dyn SecretProvider = &provider;
+    let context = BTreeMap::new();
+    let secrets = provider.secrets(&context).unwrap();
+    let api: String = secrets.alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(api, "abc");
+    let db: BTreeMap<String, String> = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(db["user"], "admin");
+
+    let requests = handle.join().unwrap();
+    assert!(
+        requests[0].starts_with("GET /metadata/identity/oauth2/token?api-version=2018-02-01&resource=https%3A%2F%2Fvault.azure.net&client_id=uai ")
+    );
+    assert!(requests[0].to_ascii_lowercase().contains("metadata: true"));
+    assert!(requests[1].starts_with("GET /secrets/api-key?api-version=7.4 "));
+    assert!(requests[1].to_ascii_lowercase().contains("authorization: bearer identity-token"));
+    assert!(requests[2].starts_with("GET /secrets/db/v2?api-version=7.4 "));
+}
+
+#[test]
+fn test_azure_client_credentials() {
+    let (address, handle) = serve(vec![
+        ("200 OK", r#"{"access_token": "app-token"}"#),
+        ("200 OK", r#"{"value": "\"s3cr3t\""}"#),
+    ]);
+    let auth = AzureAuth::ClientCredentials {
+        tenant_id: "tenant".to_string(),
+        client_id: "app".to_string(),
+        client_secret: "p&ss".to_string(),
+    };
+    assert!(!format!("{:?}", auth).contains("p&ss"));
+    let config = AzureKeyVault::new(&address, auth)
+        .with_authority(&address)
+        .with_env_secret("db", "db-password", "prod")
+        .config(&BTreeMap::new())
+        .unwrap();
+    let prod = BTreeMap::from([("env".to_string(), serde_value::Value::String("prod".to_string()))]);
+    let password: String = config
+        .secrets(&prod)
+        .unwrap()
+        .alias("db".to_string(), ContentFormat::JSON, &prod)
+        .unwrap();
+    assert_eq!(password, "s3cr3t");
+
+    let requests = handle.join().unwrap();
+    assert!(requests[0].starts_with("POST /tenant/oauth2/v2.0/token "));
+    assert!(requests[0].ends_with("grant_type=client_credentials&client_id=app&client_secret=p%26ss&scope=https%3A%2F%2Fvault.azure.net%2F.default"));
+    assert!(requests[1].to_ascii_lowercase().contains("authorization: bearer app-token"));
+}
+
+#[test]
+fn test_azure_errors() {
+    let (address, handle) = serve(vec![("404 Not Found", r#"{"error": {"code": "SecretNotFound"}}"#)]);
+    let err = AzureKeyVault::new(&address, AzureAuth::Token("token".to_string()))
+        .with_secret("api", "missing")
+        .secrets(&BTreeMap::new())
+        .unwrap_err();
+    handle.join().unwrap();
+    assert!(err.message().contains("404"));
+    assert_eq!(err.details()["status"], serde_value::Value::U16(404));
+    assert_eq!(err.details()["url"], serde_value::Value::String(format!("{}/secrets/missing", address)));
+    assert!(matches!(&err.details()["origin"], serde_value::Value::String(origin) if origin.contains("SecretNotFound")));
+}
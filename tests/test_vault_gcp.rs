@@ -0,0 +1,121 @@
+#![cfg(feature = "gcp")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::{ContentFormat, GcpAuth, GcpSecretManager, SecretProvider};
+
+/// Serves the given responses, one per connection, returning the address of the server and
+/// the requests received (head and body).
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                if let Some(index) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break index + 4;
+                }
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_ascii_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            while request.len() < head_end + length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (address, handle)
+}
+
+#[test]
+fn test_gcp_metadata_server() {
+    let (address, handle) = serve(vec![
+        ("200 OK", r#"{"access_token": "sa-token", "expires_in": 3599, "token_type": "Bearer"}"#),
+        (
+            "200 OK",
+            r#"{"name": "projects/123/secrets/api-key/versions/7", "payload": {"data": "ImFiYyI="}}"#,
+        ),
+        (
+            "200 OK",
+            r#"{"name": "projects/123/secrets/db/versions/2", "payload": {"data": "eyJ1c2VyIjogImFkbWluIn0="}}"#,
+        ),
+    ]);
+    let provider = GcpSecretManager::new("my-project", GcpAuth::MetadataServer)
+        .with_endpoint(&address)
+        .with_metadata_endpoint(&format!("{}/computeMetadata/v1/instance/service-accounts/default/token", address))
+        .with_secret("api", "api-key")
+        .with_secret_version("db", "db", "2");
+    let provider: &dyn SecretProvider = &provider;
+    let context = BTreeMap::new();
+    let secrets = provider.secrets(&context).unwrap();
+    let api: String = secrets.alias("api".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(api, "abc");
+    assert_eq!(secrets.version("api", &context), Some("7"));
+    let db: BTreeMap<String, String> = secrets.alias("db".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(db["user"], "admin");
+    assert_eq!(secrets.version("db", &context), Some("2"));
+    assert_eq!(secrets.version("missing", &context), None);
+
+    let requests = handle.join().unwrap();
+    assert!(requests[0].starts_with("GET /computeMetadata/v1/instance/service-accounts/default/token "));
+    assert!(requests[0].to_ascii_lowercase().contains("metadata-flavor: google"));
+    assert!(requests[1].starts_with("GET /v1/projects/my-project/secrets/api-key/versions/latest:access "));
+    assert!(requests[1].to_ascii_lowercase().contains("authorization: bearer sa-token"));
+    assert!(requests[2].starts_with("GET /v1/projects/my-project/secrets/db/versions/2:access "));
+}
+
+#[test]
+fn test_gcp_token_and_errors() {
+    let (address, handle) = serve(vec![
+        (
+            "200 OK",
+            r#"{"name": "projects/123/secrets/db/versions/4", "payload": {"data": "InByb2Qi"}}"#,
+        ),
+        ("403 Forbidden", r#"{"error": {"status": "PERMISSION_DENIED"}}"#),
+    ]);
+    let auth = GcpAuth::Token("ya29.token".to_string());
+    assert!(!format!("{:?}", auth).contains("ya29"));
+    let prod = BTreeMap::from([("env".to_string(), serde_value::Value::String("prod".to_string()))]);
+    let config = GcpSecretManager::new("my-project", auth.clone())
+        .with_endpoint(&address)
+        .with_env_secret("db", "db", "prod")
+        .config(&prod)
+        .unwrap();
+    let secrets = config.secrets(&prod).unwrap();
+    let db: String = secrets.alias("db".to_string(), ContentFormat::JSON, &prod).unwrap();
+    assert_eq!(db, "prod");
+    assert_eq!(secrets.version("db", &prod), Some("4"));
+
+    let err = GcpSecretManager::new("my-project", auth)
+        .with_endpoint(&address)
+        .with_secret("api", "api-key")
+        .secrets(&BTreeMap::new())
+        .unwrap_err();
+    let requests = handle.join().unwrap();
+    assert!(requests[0].to_ascii_lowercase().contains("authorization: bearer ya29.token"));
+    assert_eq!(err.details()["status"], serde_value::Value::U16(403));
+    assert_eq!(
+        err.details()["url"],
+        serde_value::Value::String(format!("{}/v1/projects/my-project/secrets/api-key/versions/latest:access", address))
+    );
+    assert!(matches!(&err.details()["origin"], serde_value::Value::String(origin) if origin.contains("PERMISSION_DENIED")));
+}
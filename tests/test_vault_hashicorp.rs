@@ -0,0 +1,145 @@
+#![cfg(feature = "vault-hashicorp")]
+
+use std::collections::BTreeMap;
+use std::io::{Read, Write};
+
+use cdumay_config::{ContentFormat, HashicorpAuth, HashicorpVault};
+use serde::Deserialize;
+
+#[derive(Debug, PartialEq, Deserialize)]
+struct Database {
+    username: String,
+    password: String,
+}
+
+/// Serves the given responses, one per connection, returning the address of the server and
+/// the requests received (head and body).
+fn serve(responses: Vec<(&'static str, &'static str)>) -> (String, std::thread::JoinHandle<Vec<String>>) {
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let address = format!("http://{}", listener.local_addr().unwrap());
+    let handle = std::thread::spawn(move || {
+        let mut requests = Vec::new();
+        for (status, body) in responses {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buffer = [0; 1024];
+            let head_end = loop {
+                if let Some(index) = request.windows(4).position(|window| window == b"\r\n\r\n") {
+                    break index + 4;
+                }
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            };
+            let head = String::from_utf8_lossy(&request[..head_end]).to_ascii_lowercase();
+            let length: usize = head
+                .lines()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .map_or(0, |length| length.trim().parse().unwrap());
+            while request.len() < head_end + length {
+                let read = stream.read(&mut buffer).unwrap();
+                request.extend_from_slice(&buffer[..read]);
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+            requests.push(String::from_utf8(request).unwrap());
+        }
+        requests
+    });
+    (address, handle)
+}
+
+const SECRET: &str = r#"{"data": {"data": {"username": "admin", "password": "s3cr3t"}, "metadata": {"version": 3}}}"#;
+
+#[test]
+fn test_hashicorp_token() {
+    let (address, handle) = serve(vec![("200 OK", SECRET)]);
+    let context = BTreeMap::new();
+    let config = HashicorpVault::new(&address, HashicorpAuth::Token("s.token".to_string()))
+        .with_mount("kv")
+        .with_namespace("team")
+        .with_secret("db", "app/database")
+        .config(&context)
+        .unwrap();
+    let database: Database = config
+        .secrets(&context)
+        .unwrap()
+        .alias("db".to_string(), ContentFormat::JSON, &context)
+        .unwrap();
+    assert_eq!(
+        database,
+        Database {
+            username: "admin".to_string(),
+            password: "s3cr3t".to_string()
+        }
+    );
+
+    let requests = handle.join().unwrap();
+    let request = requests[0].to_ascii_lowercase();
+    assert!(request.starts_with("get /v1/kv/data/app/database "));
+    assert!(request.contains("x-vault-token: s.token"));
+    assert!(request.contains("x-vault-namespace: team"));
+}
+
+#[test]
+fn test_hashicorp_approle_and_environments() {
+    let login = r#"{"auth": {"client_token": "s.approle"}}"#;
+    let (address, handle) = serve(vec![
+        ("200 OK", login),
+        ("200 OK", SECRET),
+        ("200 OK", r#"{"data": {"data": {"username": "prod", "password": "p"}}}"#),
+    ]);
+    let auth = HashicorpAuth::AppRole {
+        role_id: "role".to_string(),
+        secret_id: "secret".to_string(),
+        mount: None,
+    };
+    assert!(!format!("{:?}", auth).contains("\"secret\""));
+    let secrets = HashicorpVault::new(&format!("{}/", address), auth)
+        .with_secret("db", "app/database")
+        .with_env_secret("db", "prod/app/database", "prod")
+        .secrets(&BTreeMap::new())
+        .unwrap();
+    let prod = BTreeMap::from([("env".to_string(), serde_value::Value::String("prod".to_string()))]);
+    let database: Database = secrets.alias("db".to_string(), ContentFormat::JSON, &prod).unwrap();
+    assert_eq!(database.username, "prod");
+    let database: Database = secrets.alias("db".to_string(), ContentFormat::JSON, &BTreeMap::new()).unwrap();
+    assert_eq!(database.username, "admin");
+
+    let requests = handle.join().unwrap();
+    assert!(requests[0].starts_with("POST /v1/auth/approle/login "));
+    assert!(requests[0].ends_with(r#"{"role_id":"role","secret_id":"secret"}"#));
+    assert!(requests[1].to_ascii_lowercase().contains("x-vault-token: s.approle"));
+}
+
+#[test]
+fn test_hashicorp_errors() {
+    let (address, handle) = serve(vec![("403 Forbidden", r#"{"errors": ["permission denied"]}"#)]);
+    let err = HashicorpVault::new(&address, HashicorpAuth::Token("s.token".to_string()))
+        .with_secret("db", "app/database")
+        .secrets(&BTreeMap::new())
+        .unwrap_err();
+    handle.join().unwrap();
+    assert!(err.message().contains("403"));
+    assert_eq!(err.details()["status"], serde_value::Value::U16(403));
+    assert_eq!(
+        err.details()["url"],
+        serde_value::Value::String(format!("{}/v1/secret/data/app/database", address))
+    );
+    assert_eq!(
+        err.details()["origin"],
+        serde_value::Value::String(r#"["permission denied"]"#.to_string())
+    );
+
+    let (address, handle) = serve(vec![("200 OK", r#"{"data": null}"#)]);
+    let err = HashicorpVault::new(&address, HashicorpAuth::Token("s.token".to_string()))
+        .with_secret("db", "app/database")
+        .secrets(&BTreeMap::new())
+        .unwrap_err();
+    handle.join().unwrap();
+    assert!(err.message().contains("no data"));
+}
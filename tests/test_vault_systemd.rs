@@ -0,0 +1,46 @@
+use cdumay_config::{ContentFormat, SecretProvider, SystemdCredentials};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+
+#[derive(Deserialize, PartialEq, Debug)]
+struct Database {
+    user: String,
+    password: String,
+}
+
+#[test]
+fn test_systemd_credentials() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(dir.path().join("db.json"), r#"{"user": "app", "password": "s3cr3t"}"#).unwrap();
+    std::fs::write(dir.path().join("token"), "\"abc\"\n").unwrap();
+    std::fs::create_dir(dir.path().join("nested")).unwrap();
+
+    let context = BTreeMap::new();
+    let provider = SystemdCredentials::new(dir.path());
+    assert_eq!(provider.directory(), dir.path());
+    let secrets = SecretProvider::secrets(&provider, &context).unwrap();
+
+    let db: Database = secrets.alias("db.json".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(
+        db,
+        Database {
+            user: "app".to_string(),
+            password: "s3cr3t".to_string()
+        }
+    );
+    let token: String = secrets.alias("token".to_string(), ContentFormat::JSON, &context).unwrap();
+    assert_eq!(token, "abc");
+    assert!(secrets.alias::<String>("nested".to_string(), ContentFormat::JSON, &context).is_err());
+
+    let config = provider.config(&context).unwrap();
+    assert!(config.secrets.is_some());
+}
+
+#[test]
+fn test_systemd_credentials_missing_directory() {
+    let dir = tempfile::tempdir().unwrap();
+    let missing = dir.path().join("missing");
+    let err = SystemdCredentials::new(&missing).secrets(&BTreeMap::new()).unwrap_err();
+    assert_eq!(err.details()["path"], serde_value::Value::String(missing.display().to_string()));
+    assert!(err.details().contains_key("origin"));
+}
@@ -0,0 +1,91 @@
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex};
+
+use cdumay_config::{HookFailurePolicy, WriteHook, WriteOptions};
+
+fn failing(name: &str) -> WriteHook {
+    WriteHook::new(name, |_| {
+        Err(cdumay_config::WriteHookError::new().with_message("boom".to_string()).into())
+    })
+}
+
+#[test]
+fn test_hooks_run_in_order_after_write() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let hook = |name: &'static str| {
+        let seen = seen.clone();
+        WriteHook::new(name, move |written| {
+            seen.lock().unwrap().push((name, std::fs::read_to_string(written).unwrap()));
+            Ok(())
+        })
+    };
+    let options = WriteOptions {
+        hooks: vec![hook("first"), hook("second")],
+        ..Default::default()
+    };
+    cdumay_config::write_config_with(path.to_str().unwrap(), None, BTreeMap::from([("a", 1)]), &BTreeMap::new(), &options).unwrap();
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.iter().map(|(name, _)| *name).collect::<Vec<_>>(), vec!["first", "second"]);
+    assert!(seen[0].1.contains("\"a\""));
+}
+
+#[test]
+fn test_hook_failure_policies() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let ran = Arc::new(Mutex::new(false));
+    let flag = ran.clone();
+    let last = WriteHook::new("last", move |_| {
+        *flag.lock().unwrap() = true;
+        Ok(())
+    });
+
+    let mut options = WriteOptions {
+        hooks: vec![failing("broken"), last],
+        ..Default::default()
+    };
+    let err = cdumay_config::write_config_with(path.to_str().unwrap(), None, 1, &BTreeMap::new(), &options).unwrap_err();
+    assert_eq!(err.message(), "boom");
+    assert!(path.exists());
+    assert!(!*ran.lock().unwrap());
+
+    options.hook_failure = HookFailurePolicy::Warn;
+    cdumay_config::write_config_with(path.to_str().unwrap(), None, 1, &BTreeMap::new(), &options).unwrap();
+    assert!(*ran.lock().unwrap());
+}
+
+#[test]
+fn test_hooks_skipped_when_write_fails() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("missing").join("app.json");
+    let options = WriteOptions {
+        hooks: vec![WriteHook::new("unreachable", |_| panic!("hook must not run"))],
+        ..Default::default()
+    };
+    assert!(cdumay_config::write_config_with(path.to_str().unwrap(), None, 1, &BTreeMap::new(), &options).is_err());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_command_hook() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    let copy = dir.path().join("copy.json");
+    let options = WriteOptions {
+        hooks: vec![WriteHook::command("cp", &["{path}", copy.to_str().unwrap()])],
+        ..Default::default()
+    };
+    assert_eq!(options.hooks[0].name(), format!("cp {{path}} {}", copy.display()));
+    cdumay_config::write_config_with(path.to_str().unwrap(), None, 42, &BTreeMap::new(), &options).unwrap();
+    assert_eq!(std::fs::read_to_string(&copy).unwrap(), "42");
+
+    let options = WriteOptions {
+        hooks: vec![WriteHook::command("false", &[])],
+        ..Default::default()
+    };
+    let err = cdumay_config::write_config_with(path.to_str().unwrap(), None, 42, &BTreeMap::new(), &options).unwrap_err();
+    assert!(err.message().contains("'false' failed"));
+}
@@ -0,0 +1,92 @@
+use std::collections::BTreeMap;
+
+use cdumay_config::{JsonManager, Manager, WriteOptions};
+
+fn secret_options() -> WriteOptions {
+    WriteOptions {
+        mode: Some(0o600),
+        ..Default::default()
+    }
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_config_with_mode_on_new_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.json");
+    let data = BTreeMap::from([("password", "hunter2")]);
+    cdumay_config::write_config_with(path.to_str().unwrap(), None, &data, &BTreeMap::new(), &secret_options()).unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_config_with_mode_on_existing_file() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("secrets.json");
+    std::fs::write(&path, "{}").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+
+    let manager = JsonManager::new(&path);
+    manager
+        .write_config_with(&BTreeMap::from([("token", "abc")]), &BTreeMap::new(), &secret_options())
+        .unwrap();
+
+    let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+    assert_eq!(mode & 0o777, 0o600);
+    let read: BTreeMap<String, String> = manager.read_config(&BTreeMap::new()).unwrap();
+    assert_eq!(read.get("token").map(String::as_str), Some("abc"));
+}
+
+#[test]
+fn test_create_file_with_default_options() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("plain.json");
+    let manager = JsonManager::new(&path);
+    assert!(manager.create_file_with(&BTreeMap::new(), &WriteOptions::default()).is_ok());
+    assert!(path.exists());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_config_replaces_file_atomically() {
+    use std::os::unix::fs::{MetadataExt, PermissionsExt};
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("app.json");
+    std::fs::write(&path, "{}").unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o640)).unwrap();
+    let inode = std::fs::metadata(&path).unwrap().ino();
+
+    let manager = JsonManager::new(&path);
+    manager.write_config(&BTreeMap::from([("name", "app")]), &BTreeMap::new()).unwrap();
+
+    let metadata = std::fs::metadata(&path).unwrap();
+    assert_ne!(metadata.ino(), inode);
+    assert_eq!(metadata.permissions().mode() & 0o777, 0o640);
+    let names: Vec<_> = std::fs::read_dir(dir.path()).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+    assert_eq!(names, vec![std::ffi::OsString::from("app.json")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_write_config_follows_symlink() {
+    let dir = tempfile::tempdir().unwrap();
+    let target = dir.path().join("target.json");
+    let link = dir.path().join("link.json");
+    std::fs::write(&target, "{}").unwrap();
+    std::os::unix::fs::symlink(&target, &link).unwrap();
+
+    JsonManager::new(&link)
+        .write_config(&BTreeMap::from([("name", "app")]), &BTreeMap::new())
+        .unwrap();
+    assert!(link.is_symlink());
+    let read: BTreeMap<String, String> = JsonManager::new(&target).read_config(&BTreeMap::new()).unwrap();
+    assert_eq!(read.get("name").map(String::as_str), Some("app"));
+}
@@ -0,0 +1,107 @@
+use std::collections::{BTreeMap, HashMap};
+
+use cdumay_config::{ContentFormat, JsonManager, Manager, WriteOptions, WriteStyle};
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct Server {
+    name: String,
+    port: u16,
+    labels: HashMap<String, String>,
+}
+
+fn server() -> Server {
+    Server {
+        name: "api".to_string(),
+        port: 8080,
+        labels: HashMap::from([("zone".to_string(), "b".to_string()), ("env".to_string(), "prod".to_string())]),
+    }
+}
+
+fn styled(style: WriteStyle) -> WriteOptions {
+    WriteOptions { style, ..Default::default() }
+}
+
+#[test]
+fn test_json_compact_sorted() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("server.json");
+    let style = WriteStyle {
+        pretty: false,
+        sort_keys: true,
+        ..Default::default()
+    };
+    cdumay_config::write_config_with(
+        path.to_str().unwrap(),
+        Some(ContentFormat::JSON),
+        server(),
+        &BTreeMap::new(),
+        &styled(style),
+    )
+    .unwrap();
+    assert_eq!(
+        std::fs::read_to_string(&path).unwrap(),
+        r#"{"labels":{"env":"prod","zone":"b"},"name":"api","port":8080}"#
+    );
+}
+
+#[test]
+fn test_json_indent() {
+    let style = WriteStyle {
+        indent: Some(4),
+        ..Default::default()
+    };
+    let mut buffer = Vec::new();
+    JsonManager::new("server.json")
+        .write_styled(&mut buffer, BTreeMap::from([("port", 8080)]), &style, &BTreeMap::new())
+        .unwrap();
+    assert_eq!(String::from_utf8(buffer).unwrap(), "{\n    \"port\": 8080\n}");
+}
+
+#[test]
+fn test_default_style_is_unchanged() {
+    let mut styled = Vec::new();
+    let mut plain = Vec::new();
+    let manager = JsonManager::new("server.json");
+    manager
+        .write_styled(&mut styled, BTreeMap::from([("port", 8080)]), &WriteStyle::default(), &BTreeMap::new())
+        .unwrap();
+    manager.write(&mut plain, BTreeMap::from([("port", 8080)]), &BTreeMap::new()).unwrap();
+    assert_eq!(styled, plain);
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_toml_compact_sorted() {
+    let style = WriteStyle {
+        pretty: false,
+        sort_keys: true,
+        ..Default::default()
+    };
+    let content = render(&cdumay_config::TomlManager::new("server.toml"), server(), &style);
+    assert_eq!(content, "name = \"api\"\nport = 8080\n\n[labels]\nenv = \"prod\"\nzone = \"b\"\n");
+}
+
+#[cfg(feature = "ron")]
+#[test]
+fn test_ron_compact_and_indent() {
+    let manager = cdumay_config::RonManager::new("server.ron");
+    let compact = WriteStyle {
+        pretty: false,
+        ..Default::default()
+    };
+    assert_eq!(render(&manager, BTreeMap::from([("port", 8080)]), &compact), "{\"port\":8080}");
+    let indented = WriteStyle {
+        indent: Some(2),
+        ..Default::default()
+    };
+    assert!(render(&manager, BTreeMap::from([("port", 8080)]), &indented).contains("\n  \"port\": 8080"));
+}
+
+/// Writes data with the given style into a string.
+#[cfg(any(feature = "toml", feature = "ron"))]
+fn render<M: Manager, D: Serialize>(manager: &M, data: D, style: &WriteStyle) -> String {
+    let mut buffer = Vec::new();
+    manager.write_styled(&mut buffer, data, style, &BTreeMap::new()).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "xml")]
+
 use std::collections::BTreeMap;
 use std::io::{Cursor, Seek, SeekFrom, Write};
 
@@ -15,12 +17,24 @@ fn default_context() -> BTreeMap<String, Value> {
     BTreeMap::new()
 }
 
+// Callers passing a `String` and reading the deprecated `path()` keep compiling.
 #[test]
+#[allow(deprecated, clippy::unnecessary_to_owned)]
 fn test_xml_manager_new_and_path() {
     let manager = XmlManager::new("test.xml".to_string());
     assert_eq!(manager.path(), "test.xml");
 }
 
+#[test]
+fn test_xml_manager_new_from_path() {
+    let manager = XmlManager::new(std::path::Path::new("test.xml"));
+    assert_eq!(manager.file_path(), std::path::Path::new("test.xml"));
+
+    let path = std::path::PathBuf::from("dir").join("test.xml");
+    let manager = XmlManager::new(&path);
+    assert_eq!(manager.file_path(), path.as_path());
+}
+
 #[test]
 fn test_xml_manager_read_str_success() {
     let xml = r#"<TestXmlConfig><name>config</name><count>5</count></TestXmlConfig>"#;
@@ -43,7 +57,7 @@ fn test_xml_manager_read_success() {
     let xml = r#"<TestXmlConfig><name>read</name><count>42</count></TestXmlConfig>"#;
     let reader = Cursor::new(xml);
     let context = default_context();
-    let manager = XmlManager::new("read.xml".to_string());
+    let manager = XmlManager::new("read.xml");
     let result: TestXmlConfig = manager.read(reader, &context).unwrap();
     assert_eq!(result.name, "read");
     assert_eq!(result.count, 42);
@@ -54,7 +68,7 @@ fn test_xml_manager_read_invalid() {
     let xml = r#"<TestXmlConfig><name>bad<name><count>5</count></TestXmlConfig>"#;
     let reader = Cursor::new(xml);
     let context = default_context();
-    let manager = XmlManager::new("fail_read.xml".to_string());
+    let manager = XmlManager::new("fail_read.xml");
     let result: cdumay_core::Result<TestXmlConfig> = manager.read(reader, &context);
     assert!(result.is_err());
 }
@@ -66,7 +80,7 @@ fn test_xml_manager_write_success() {
         count: 7,
     };
     let context = default_context();
-    let manager = XmlManager::new("write.xml".to_string());
+    let manager = XmlManager::new("write.xml");
     let mut buffer = Cursor::new(Vec::new());
 
     manager.write(&mut buffer, &config, &context).unwrap();
@@ -82,7 +96,7 @@ fn test_xml_manager_write_failure_on_write() {
 
     impl Write for FailingWriter {
         fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-            Err(std::io::Error::new(std::io::ErrorKind::Other, "Simulated write error"))
+            Err(std::io::Error::other("Simulated write error"))
         }
         fn flush(&mut self) -> std::io::Result<()> {
             Ok(())
@@ -90,7 +104,7 @@ fn test_xml_manager_write_failure_on_write() {
     }
 
     let context = default_context();
-    let manager = XmlManager::new("failing.xml".to_string());
+    let manager = XmlManager::new("failing.xml");
     let config = TestXmlConfig {
         name: "fail".to_string(),
         count: 0,
@@ -99,3 +113,229 @@ fn test_xml_manager_write_failure_on_write() {
     let result = manager.write(FailingWriter, &config, &context);
     assert!(result.is_err());
 }
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Server {
+    host: String,
+    port: u16,
+    aliases: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    comment: Option<String>,
+}
+
+fn server() -> Server {
+    Server {
+        host: "db&co".to_string(),
+        port: 5432,
+        aliases: vec!["primary".to_string(), "main".to_string()],
+        comment: None,
+    }
+}
+
+fn write_with(options: cdumay_config::XmlOptions, data: &Server) -> String {
+    let manager = XmlManager::with_options("server.xml", options);
+    let mut buffer = Vec::new();
+    manager.write(&mut buffer, data, &default_context()).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+#[test]
+fn test_xml_manager_default_options() {
+    let manager = XmlManager::new("server.xml");
+    assert_eq!(manager.options(), &cdumay_config::XmlOptions::default());
+}
+
+#[test]
+fn test_xml_manager_root_name() {
+    let options = cdumay_config::XmlOptions {
+        root: Some("server".to_string()),
+        ..Default::default()
+    };
+    let content = write_with(options, &server());
+    assert_eq!(
+        content,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<server>\n  <host>db&amp;co</host>\n  <port>5432</port>\n  <aliases>primary</aliases>\n  <aliases>main</aliases>\n</server>\n"
+    );
+    let read: Server = XmlManager::read_str(&content, &default_context()).unwrap();
+    assert_eq!(read, server());
+}
+
+#[test]
+fn test_xml_manager_scalar_attributes() {
+    let options = cdumay_config::XmlOptions {
+        scalar_attributes: true,
+        ..Default::default()
+    };
+    let content = write_with(options, &server());
+    assert!(content.contains("<Server host=\"db&amp;co\" port=\"5432\">\n  <aliases>primary</aliases>\n"));
+}
+
+#[test]
+fn test_xml_manager_list_wrappers() {
+    let options = cdumay_config::XmlOptions {
+        root: Some("server".to_string()),
+        scalar_attributes: true,
+        list_items: Some("alias".to_string()),
+        ..Default::default()
+    };
+    let content = write_with(options, &server());
+    assert_eq!(
+        content,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<server host=\"db&amp;co\" port=\"5432\">\n  <aliases>\n    <alias>primary</alias>\n    <alias>main</alias>\n  </aliases>\n</server>\n"
+    );
+}
+
+#[test]
+fn test_xml_manager_write_config_with_options() {
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Entry {
+        #[serde(rename = "@id")]
+        id: u32,
+        #[cfg_attr(feature = "quick-xml", serde(rename = "$text"))]
+        #[cfg_attr(not(feature = "quick-xml"), serde(rename = "#text"))]
+        value: String,
+    }
+    #[derive(Debug, Serialize, Deserialize, PartialEq)]
+    struct Entries {
+        entry: Vec<Entry>,
+    }
+
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("entries.xml");
+    let options = cdumay_config::XmlOptions {
+        root: Some("entries".to_string()),
+        ..Default::default()
+    };
+    let data = Entries {
+        entry: vec![
+            Entry {
+                id: 1,
+                value: "one".to_string(),
+            },
+            Entry {
+                id: 2,
+                value: "two".to_string(),
+            },
+        ],
+    };
+    XmlManager::with_options(&path, options).write_config(&data, &default_context()).unwrap();
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert!(content.contains("<entry id=\"1\">one</entry>\n  <entry id=\"2\">two</entry>"));
+    let read: Entries = XmlManager::new(&path).read_config(&default_context()).unwrap();
+    assert_eq!(read, data);
+}
+
+#[test]
+fn test_xml_manager_options_unsupported_root() {
+    let options = cdumay_config::XmlOptions {
+        scalar_attributes: true,
+        ..Default::default()
+    };
+    let manager = XmlManager::with_options("list.xml", options);
+    let err = manager.write(Vec::new(), vec![1, 2], &default_context()).unwrap_err();
+    assert!(err.message().contains("Failed to write XML file"));
+    assert_eq!(err.details().get("path"), Some(&Value::String("list.xml".to_string())));
+}
+
+#[test]
+fn test_xml_manager_write_declaration() {
+    let manager = XmlManager::new("declaration.xml");
+    let mut buffer = Vec::new();
+    let data = TestXmlConfig {
+        name: "a<b".to_string(),
+        count: 1,
+    };
+    manager.write(&mut buffer, &data, &default_context()).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    assert!(content.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>"));
+    let read: TestXmlConfig = XmlManager::read_str(&content, &default_context()).unwrap();
+    assert_eq!(read, data);
+}
+
+fn namespaced_options() -> cdumay_config::XmlOptions {
+    cdumay_config::XmlOptions {
+        root: Some("server".to_string()),
+        namespaces: BTreeMap::from([("cfg".to_string(), "urn:example:cfg".to_string())]),
+        prefix: Some("cfg".to_string()),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn test_xml_manager_write_namespaces() {
+    let data = TestXmlConfig {
+        name: "api".to_string(),
+        count: 2,
+    };
+    let manager = XmlManager::with_options("server.xml", namespaced_options());
+    let mut buffer = Vec::new();
+    manager.write(&mut buffer, &data, &default_context()).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    assert_eq!(
+        content,
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<cfg:server xmlns:cfg=\"urn:example:cfg\">\n  <cfg:name>api</cfg:name>\n  <cfg:count>2</cfg:count>\n</cfg:server>\n"
+    );
+    let read: TestXmlConfig = manager.read(content.as_bytes(), &default_context()).unwrap();
+    assert_eq!(read, data);
+}
+
+#[test]
+fn test_xml_manager_read_namespaces_by_uri() {
+    let xml = r#"<c:TestXmlConfig xmlns:c="urn:example:cfg" xmlns:ext="urn:example:ext">
+        <c:name>a &amp; b</c:name>
+        <ext:name>ignored</ext:name>
+        <c:count><![CDATA[7]]></c:count>
+    </c:TestXmlConfig>"#;
+    let manager = XmlManager::with_options("server.xml", namespaced_options());
+    let read: TestXmlConfig = manager.read(xml.as_bytes(), &default_context()).unwrap();
+    assert_eq!(
+        read,
+        TestXmlConfig {
+            name: "a & b".to_string(),
+            count: 7,
+        }
+    );
+}
+
+#[test]
+fn test_xml_manager_default_namespace() {
+    let options = cdumay_config::XmlOptions {
+        namespaces: BTreeMap::from([(String::new(), "urn:example:cfg".to_string())]),
+        ..Default::default()
+    };
+    let data = TestXmlConfig {
+        name: "api".to_string(),
+        count: 3,
+    };
+    let manager = XmlManager::with_options("server.xml", options);
+    let mut buffer = Vec::new();
+    manager.write(&mut buffer, &data, &default_context()).unwrap();
+    let content = String::from_utf8(buffer).unwrap();
+    assert!(content.contains("<TestXmlConfig xmlns=\"urn:example:cfg\">"));
+    let read: TestXmlConfig = manager.read(content.as_bytes(), &default_context()).unwrap();
+    assert_eq!(read, data);
+}
+
+#[test]
+fn test_xml_manager_undeclared_prefix() {
+    let options = cdumay_config::XmlOptions {
+        prefix: Some("cfg".to_string()),
+        ..Default::default()
+    };
+    let manager = XmlManager::with_options("server.xml", options);
+    let data = TestXmlConfig {
+        name: "api".to_string(),
+        count: 3,
+    };
+    let err = manager.write(Vec::new(), &data, &default_context()).unwrap_err();
+    assert!(err.message().contains("namespace prefix 'cfg' is not declared"));
+}
+
+#[test]
+fn test_xml_manager_read_namespaces_invalid() {
+    let manager = XmlManager::with_options("server.xml", namespaced_options());
+    let result: cdumay_core::Result<TestXmlConfig> = manager.read("<c:a xmlns:c=\"urn:example:cfg\"></c:b>".as_bytes(), &default_context());
+    let err = result.unwrap_err();
+    assert!(err.message().starts_with("Invalid XML file content"));
+    assert_eq!(err.details().get("path"), Some(&Value::String("server.xml".to_string())));
+}
@@ -0,0 +1,217 @@
+#![cfg(feature = "yaml")]
+//! Behaviors every YAML backend must share, so that switching backends (`serde_norway` by
+//! default, `serde_yaml` with its feature) does not break existing configurations. Run with
+//! each backend.
+
+use std::collections::BTreeMap;
+
+use cdumay_config::{Manager, YamlManager};
+use serde::{Deserialize, Serialize};
+use serde_value::Value;
+
+fn read(content: &str) -> cdumay_core::Result<Value> {
+    YamlManager::read_str(content, &BTreeMap::new())
+}
+
+fn write<D: Serialize>(data: D) -> String {
+    let mut buffer = Vec::new();
+    YamlManager::new("conformance.yaml").write(&mut buffer, data, &BTreeMap::new()).unwrap();
+    String::from_utf8(buffer).unwrap()
+}
+
+fn map(entries: Vec<(&str, Value)>) -> Value {
+    Value::Map(entries.into_iter().map(|(key, value)| (Value::String(key.to_string()), value)).collect())
+}
+
+#[test]
+fn test_conformance_scalars() {
+    let value = read("int: 42\nneg: -7\nhex: 0x1F\nfloat: 1.5\nbool: true\nnothing: ~\nstring: hello\nquoted: '42'\n").unwrap();
+    assert_eq!(
+        value,
+        map(vec![
+            ("int", Value::U64(42)),
+            ("neg", Value::I64(-7)),
+            ("hex", Value::U64(31)),
+            ("float", Value::F64(1.5)),
+            ("bool", Value::Bool(true)),
+            ("nothing", Value::Unit),
+            ("string", Value::String("hello".to_string())),
+            ("quoted", Value::String("42".to_string())),
+        ])
+    );
+}
+
+#[test]
+fn test_conformance_yaml_1_2_booleans() {
+    // YAML 1.1 booleans (yes/no/on/off) are plain strings.
+    let value = read("a: yes\nb: off\n").unwrap();
+    assert_eq!(
+        value,
+        map(vec![("a", Value::String("yes".to_string())), ("b", Value::String("off".to_string()))])
+    );
+}
+
+#[test]
+fn test_conformance_block_scalars() {
+    let value = read("literal: |\n  line 1\n  line 2\nfolded: >\n  line 1\n  line 2\n").unwrap();
+    assert_eq!(
+        value,
+        map(vec![
+            ("literal", Value::String("line 1\nline 2\n".to_string())),
+            ("folded", Value::String("line 1 line 2\n".to_string())),
+        ])
+    );
+}
+
+#[test]
+fn test_conformance_anchors() {
+    let value = read("base: &base\n  port: 80\ncopy: *base\n").unwrap();
+    assert_eq!(
+        value,
+        map(vec![
+            ("base", map(vec![("port", Value::U64(80))])),
+            ("copy", map(vec![("port", Value::U64(80))]))
+        ])
+    );
+}
+
+#[test]
+fn test_conformance_tagged_enums() {
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    enum Backend {
+        Memory,
+        Disk { path: String },
+    }
+
+    let backends: Vec<Backend> = YamlManager::read_str("- Memory\n- !Disk\n  path: /data\n", &BTreeMap::new()).unwrap();
+    assert_eq!(backends, vec![Backend::Memory, Backend::Disk { path: "/data".to_string() }]);
+    assert_eq!(write(&backends), "- Memory\n- !Disk\n  path: /data\n");
+}
+
+#[test]
+fn test_conformance_write_quoting() {
+    #[derive(Serialize)]
+    struct Quoted {
+        number: String,
+        boolean: String,
+        tilde: String,
+        plain: String,
+        multiline: String,
+    }
+
+    let content = write(Quoted {
+        number: "42".to_string(),
+        boolean: "yes".to_string(),
+        tilde: "~".to_string(),
+        plain: "hello world".to_string(),
+        multiline: "a\nb\n".to_string(),
+    });
+    // YAML 1.1 booleans are written unquoted, as YAML 1.2 reads them back as strings.
+    assert_eq!(
+        content,
+        "number: '42'\nboolean: yes\ntilde: '~'\nplain: hello world\nmultiline: |\n  a\n  b\n"
+    );
+}
+
+#[test]
+fn test_conformance_duplicate_keys() {
+    #[derive(Debug, Deserialize)]
+    struct Single {
+        #[allow(dead_code)]
+        key: u32,
+    }
+
+    // Untyped maps keep the last entry, structs reject duplicated fields.
+    assert_eq!(read("key: 1\nkey: 2\n").unwrap(), map(vec![("key", Value::U64(2))]));
+    let err = YamlManager::read_str::<Single>("key: 1\nkey: 2\n", &BTreeMap::new()).unwrap_err();
+    assert!(err.message().contains("duplicate field"), "{}", err.message());
+}
+
+#[test]
+fn test_conformance_error_conversion() {
+    let err = read("key: [unclosed\n").unwrap_err();
+    assert_eq!(err.class(), "Client::YamlData::DataError");
+    assert!(err.message().contains("line 2"), "{}", err.message());
+}
+
+#[test]
+fn test_conformance_octal_and_special_floats() {
+    // Only the YAML 1.2 `0o` prefix denotes octal, a leading zero makes a plain string.
+    let value = read("octal: 0o17\nleading: 017\ninf: .inf\nneg_inf: -.inf\n").unwrap();
+    assert_eq!(
+        value,
+        map(vec![
+            ("octal", Value::U64(15)),
+            ("leading", Value::String("017".to_string())),
+            ("inf", Value::F64(f64::INFINITY)),
+            ("neg_inf", Value::F64(f64::NEG_INFINITY)),
+        ])
+    );
+    assert!(matches!(read("nan: .nan\n").unwrap(), Value::Map(entries) if matches!(entries.values().next(), Some(Value::F64(nan)) if nan.is_nan())));
+}
+
+#[test]
+fn test_conformance_write_special_strings() {
+    #[derive(Serialize)]
+    struct Special {
+        null: String,
+        exponent: String,
+        hex: String,
+        octal: String,
+        infinity: String,
+        empty: String,
+    }
+
+    let content = write(Special {
+        null: "null".to_string(),
+        exponent: "1e3".to_string(),
+        hex: "0x1F".to_string(),
+        octal: "012".to_string(),
+        infinity: ".inf".to_string(),
+        empty: String::new(),
+    });
+    assert_eq!(
+        content,
+        "'null': 'null'\nexponent: '1e3'\nhex: '0x1F'\noctal: '012'\ninfinity: '.inf'\nempty: ''\n"
+    );
+    let value = read(&content).unwrap();
+    assert_eq!(
+        value,
+        map(vec![
+            ("null", Value::String("null".to_string())),
+            ("exponent", Value::String("1e3".to_string())),
+            ("hex", Value::String("0x1F".to_string())),
+            ("octal", Value::String("012".to_string())),
+            ("infinity", Value::String(".inf".to_string())),
+            ("empty", Value::String(String::new())),
+        ])
+    );
+}
+
+#[test]
+fn test_conformance_crlf() {
+    let value = read("name: api\r\nport: 8080\r\n").unwrap();
+    assert_eq!(value, map(vec![("name", Value::String("api".to_string())), ("port", Value::U64(8080))]));
+}
+
+#[test]
+fn test_conformance_document_markers() {
+    assert_eq!(
+        read("---\nname: api\n...\n").unwrap(),
+        map(vec![("name", Value::String("api".to_string()))])
+    );
+    let documents: Vec<Value> = YamlManager::read_all_str("name: a\n...\n---\nname: b\n", &BTreeMap::new()).unwrap();
+    assert_eq!(
+        documents,
+        vec![
+            map(vec![("name", Value::String("a".to_string()))]),
+            map(vec![("name", Value::String("b".to_string()))])
+        ]
+    );
+}
+
+#[test]
+fn test_conformance_long_lines_are_not_folded() {
+    let line = "word ".repeat(40).trim_end().to_string();
+    assert_eq!(write(BTreeMap::from([("text", line.clone())])), format!("text: {}\n", line));
+}
@@ -1,3 +1,5 @@
+#![cfg(feature = "yaml")]
+
 use std::collections::BTreeMap;
 use std::io::{Cursor, Seek, SeekFrom, Write};
 
@@ -15,12 +17,24 @@ fn default_context() -> BTreeMap<String, Value> {
     BTreeMap::new()
 }
 
+// Callers passing a `String` and reading the deprecated `path()` keep compiling.
 #[test]
+#[allow(deprecated, clippy::unnecessary_to_owned)]
 fn test_yaml_manager_new_and_path() {
     let manager = YamlManager::new("test.yaml".to_string());
     assert_eq!(manager.path(), "test.yaml");
 }
 
+#[test]
+fn test_yaml_manager_new_from_path() {
+    let manager = YamlManager::new(std::path::Path::new("test.yaml"));
+    assert_eq!(manager.file_path(), std::path::Path::new("test.yaml"));
+
+    let path = std::path::PathBuf::from("dir").join("test.yaml");
+    let manager = YamlManager::new(&path);
+    assert_eq!(manager.file_path(), path.as_path());
+}
+
 #[test]
 fn test_yaml_manager_read_str_success() {
     let yaml = r#"
@@ -49,7 +63,7 @@ fn test_yaml_manager_read_success() {
     let yaml = b"project: read_app\nversion: 3\n";
     let reader = Cursor::new(yaml);
     let context = default_context();
-    let manager = YamlManager::new("read.yaml".to_string());
+    let manager = YamlManager::new("read.yaml");
     let config: TestYamlConfig = manager.read(reader, &context).unwrap();
     assert_eq!(config.project, "read_app");
     assert_eq!(config.version, 3);
@@ -60,7 +74,7 @@ fn test_yaml_manager_read_failure() {
     let yaml = b"project: read_app\nversion: [oops]\n";
     let reader = Cursor::new(yaml);
     let context = default_context();
-    let manager = YamlManager::new("fail_read.yaml".to_string());
+    let manager = YamlManager::new("fail_read.yaml");
     let result: cdumay_core::Result<TestYamlConfig> = manager.read(reader, &context);
     assert!(result.is_err());
 }
@@ -72,13 +86,13 @@ fn test_yaml_manager_write_success() {
         version: 7,
     };
     let context = default_context();
-    let manager = YamlManager::new("write.yaml".to_string());
+    let manager = YamlManager::new("write.yaml");
     let mut buffer = Cursor::new(Vec::new());
 
     manager.write(&mut buffer, &config, &context).unwrap();
     buffer.seek(SeekFrom::Start(0)).unwrap();
 
-    let deserialized: TestYamlConfig = serde_yaml::from_reader(buffer).unwrap();
+    let deserialized: TestYamlConfig = manager.read(buffer, &context).unwrap();
     assert_eq!(deserialized, config);
 }
 
@@ -88,7 +102,7 @@ fn test_yaml_manager_write_failure_on_writer() {
 
     impl Write for FailingWriter {
         fn write(&mut self, _buf: &[u8]) -> std::io::Result<usize> {
-            Err(std::io::Error::new(std::io::ErrorKind::Other, "Simulated write failure"))
+            Err(std::io::Error::other("Simulated write failure"))
         }
         fn flush(&mut self) -> std::io::Result<()> {
             Ok(())
@@ -100,8 +114,135 @@ fn test_yaml_manager_write_failure_on_writer() {
         project: "fail_writer".to_string(),
         version: 99,
     };
-    let manager = YamlManager::new("fail.yaml".to_string());
+    let manager = YamlManager::new("fail.yaml");
 
     let result = manager.write(FailingWriter, &config, &context);
     assert!(result.is_err());
 }
+
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+struct Resource {
+    kind: String,
+    name: String,
+}
+
+fn resources() -> Vec<Resource> {
+    vec![
+        Resource {
+            kind: "Deployment".to_string(),
+            name: "api".to_string(),
+        },
+        Resource {
+            kind: "Service".to_string(),
+            name: "api".to_string(),
+        },
+    ]
+}
+
+#[test]
+fn test_yaml_manager_read_all_str() {
+    let content = "kind: Deployment\nname: api\n---\nkind: Service\nname: api\n";
+    let read: Vec<Resource> = YamlManager::read_all_str(content, &default_context()).unwrap();
+    assert_eq!(read, resources());
+    let single: Vec<Resource> = YamlManager::read_all_str("kind: Deployment\nname: api\n", &default_context()).unwrap();
+    assert_eq!(single.len(), 1);
+}
+
+#[test]
+fn test_yaml_manager_write_all_read_all() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bundle.yaml");
+    let manager = YamlManager::new(&path);
+    assert_eq!(manager.write_all(&resources(), &default_context()).unwrap(), path);
+    let content = std::fs::read_to_string(&path).unwrap();
+    assert_eq!(content, "kind: Deployment\nname: api\n---\nkind: Service\nname: api\n");
+    let read: Vec<Resource> = manager.read_all(&default_context()).unwrap();
+    assert_eq!(read, resources());
+}
+
+#[test]
+fn test_yaml_manager_read_all_invalid_document() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("bundle.yaml");
+    std::fs::write(&path, "kind: Deployment\nname: api\n---\nkind: [Service\n").unwrap();
+    let result: cdumay_core::Result<Vec<Resource>> = YamlManager::new(&path).read_all(&default_context());
+    let err = result.unwrap_err();
+    assert_eq!(err.details().get("path"), Some(&Value::String(path.display().to_string())));
+}
+
+#[test]
+fn test_yaml_manager_merge_keys() {
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Service {
+        image: String,
+        replicas: u32,
+        version: String,
+    }
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Services {
+        api: Service,
+        worker: Service,
+    }
+
+    let content = "defaults: &defaults\n  image: app\n  replicas: 2\n  version: 1.0\napi:\n  <<: *defaults\n  replicas: 4\nworker:\n  <<: [*defaults]\n";
+    let read: Services = YamlManager::read_str(content, &default_context()).unwrap();
+    assert_eq!(
+        read.api,
+        Service {
+            image: "app".to_string(),
+            replicas: 4,
+            version: "1.0".to_string(),
+        }
+    );
+    assert_eq!(read.worker.replicas, 2);
+
+    let documents: Vec<Value> = YamlManager::read_all_str("a: 1\n---\nbase: &b {x: 1}\nc:\n  <<: *b\n", &default_context()).unwrap();
+    assert_eq!(documents.len(), 2);
+}
+
+#[test]
+fn test_yaml_manager_invalid_merge_key() {
+    let result: cdumay_core::Result<Value> = YamlManager::read_str("a:\n  <<: scalar\n", &default_context());
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_yaml_manager_billion_laughs() {
+    let mut content = String::from("l0: &l0 [lol, lol, lol, lol, lol, lol, lol, lol, lol, lol]\n");
+    for level in 1..9 {
+        let previous = format!("*l{}", level - 1);
+        content.push_str(&format!("l{}: &l{} [{}]\n", level, level, [previous.as_str(); 10].join(", ")));
+    }
+    let result: cdumay_core::Result<Value> = YamlManager::read_str(&content, &default_context());
+    let err = result.unwrap_err();
+    // Rejected either by the node limit or by the alias repetition limit of the backend.
+    assert!(
+        err.message().contains("nodes once aliases are expanded") || err.message().contains("repetition limit"),
+        "{}",
+        err.message()
+    );
+
+    let limits = cdumay_config::YamlLimits {
+        max_nodes: 20,
+        ..Default::default()
+    };
+    let manager = YamlManager::with_limits("small.yaml", limits);
+    let result: cdumay_core::Result<Value> = manager.read("l0: &l0 [a, b, c, d, e]\nl1: [*l0, *l0, *l0]\n".as_bytes(), &default_context());
+    let err = result.unwrap_err();
+    assert!(err.message().contains("limit of 20 nodes"), "{}", err.message());
+    assert_eq!(err.details().get("path"), Some(&Value::String("small.yaml".to_string())));
+}
+
+#[test]
+fn test_yaml_manager_max_depth() {
+    let limits = cdumay_config::YamlLimits {
+        max_depth: 3,
+        ..Default::default()
+    };
+    let manager = YamlManager::with_limits("deep.yaml", limits);
+    assert_eq!(manager.limits(), &limits);
+    let read: cdumay_core::Result<Value> = manager.read("a: {b: {c: 1}}\n".as_bytes(), &default_context());
+    assert!(read.is_ok());
+    let result: cdumay_core::Result<Value> = manager.read("a: {b: {c: {d: 1}}}\n".as_bytes(), &default_context());
+    assert!(result.unwrap_err().message().contains("maximum depth of 3"));
+}